@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::DownloadStatus;
+
+/// How important a `DownloadEvent::Message` is; the backend drops messages
+/// below the user's `event_verbosity` before they reach the UI or the log.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageLevel {
+    Error,
+    /// Stage changes and results ("Launching downloader", "Saved (video)").
+    #[default]
+    Milestone,
+    /// Raw tool output and internal detail.
+    Debug,
+}
+
+/// Download manager event streamed to every subscribed page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum DownloadEvent {
+    StatusChanged {
+        id: i64,
+        status: DownloadStatus,
+    },
+    BulkStatusChanged {
+        ids: Vec<i64>,
+        status: DownloadStatus,
+    },
+    Progress {
+        id: i64,
+        progress: f32,
+        downloaded_bytes: u64,
+        total_bytes: Option<u64>,
+    },
+    Message {
+        id: i64,
+        #[serde(default)]
+        level: MessageLevel,
+        message: String,
+    },
+    /// Metadata found for a row that has not been downloaded yet.
+    MetadataUpdated {
+        id: i64,
+        title: Option<String>,
+        duration_secs: Option<u32>,
+        thumbnail_url: Option<String>,
+        estimated_bytes: Option<u64>,
+    },
+    /// A removed row's item was found at another link.
+    MirrorFound {
+        id: i64,
+        url: String,
+        source: String,
+    },
+}
@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+
+/// Broad cause of a failed download, read from its stored error message so
+/// failures can be retried selectively (e.g. only network hiccups).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    Network,
+    RateLimited,
+    /// Needs cookies or a login the browser didn't provide.
+    Login,
+    /// Removed, private or never existed; retrying rarely helps.
+    Unavailable,
+    Other,
+}
+
+// Status codes are read from "HTTP Error 503" (yt-dlp) or "HTTP 503" (direct
+// downloads) only; bare digits also turn up inside video ids and URLs.
+const RATE_LIMITED: &[&str] = &["too many requests", "rate limit", "rate-limit"];
+const LOGIN: &[&str] = &[
+    "login",
+    "log in",
+    "sign in",
+    "cookies",
+    "authenticat",
+    "age-restricted",
+];
+const UNAVAILABLE: &[&str] = &[
+    "not found",
+    "unavailable",
+    "removed",
+    "deleted",
+    "private",
+    "does not exist",
+];
+const NETWORK: &[&str] = &[
+    "timed out",
+    "timeout",
+    "connection",
+    "network",
+    "name resolution",
+    "getaddrinfo",
+    "ssl",
+    "reset by peer",
+    "unable to download webpage",
+    "drive is not connected",
+];
+
+/// First HTTP status in `lower` written as "http error NNN" or "http NNN".
+fn http_status(lower: &str) -> Option<u16> {
+    ["http error ", "http "].iter().find_map(|prefix| {
+        lower.match_indices(prefix).find_map(|(at, _)| {
+            let rest = &lower[at + prefix.len()..];
+            let digits = rest.get(..3)?;
+            let next_is_digit = rest[3..].starts_with(|c: char| c.is_ascii_digit());
+            if next_is_digit || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            digits.parse().ok()
+        })
+    })
+}
+
+impl FailureKind {
+    /// Best guess from a row's `last_error`; `None` counts as `Other`.
+    pub fn classify(message: Option<&str>) -> Self {
+        let lower = message.unwrap_or_default().to_lowercase();
+        match http_status(&lower) {
+            Some(500..=599) => return FailureKind::Network,
+            Some(429) => return FailureKind::RateLimited,
+            Some(404 | 410) => return FailureKind::Unavailable,
+            _ => {}
+        }
+        let has = |needles: &[&str]| needles.iter().any(|n| lower.contains(n));
+        if has(RATE_LIMITED) {
+            FailureKind::RateLimited
+        } else if has(LOGIN) {
+            FailureKind::Login
+        } else if has(UNAVAILABLE) {
+            FailureKind::Unavailable
+        } else if has(NETWORK) {
+            FailureKind::Network
+        } else {
+            FailureKind::Other
+        }
+    }
+
+    /// Worth retrying on its own after a wait: network trouble, 5xx and rate limits.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, FailureKind::Network | FailureKind::RateLimited)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FailureKind::Network => "Network",
+            FailureKind::RateLimited => "Rate limited",
+            FailureKind::Login => "Login needed",
+            FailureKind::Unavailable => "Unavailable",
+            FailureKind::Other => "Other",
+        }
+    }
+}
+
+/// A platform whose dispatching stopped because too many of its recent
+/// downloads failed; other platforms keep going.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlatformPause {
+    /// Platform id ("youtube", ...)
+    pub platform: String,
+    pub failures: u32,
+    /// Recent downloads looked at
+    pub window: u32,
+    /// Most common cause among the failures
+    pub dominant: FailureKind,
+    /// RFC 3339
+    pub paused_at: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(message: &str) -> FailureKind {
+        FailureKind::classify(Some(message))
+    }
+
+    #[test]
+    fn server_errors_are_network() {
+        assert_eq!(
+            classify("ERROR: [youtube] abc: Unable to download webpage: HTTP Error 503: Service Unavailable"),
+            FailureKind::Network
+        );
+        assert_eq!(
+            classify("HTTP 503 Service Unavailable for https://cdn.example.com/a.mp4"),
+            FailureKind::Network
+        );
+        assert_eq!(
+            classify("ERROR: unable to download video data: HTTP Error 502: Bad Gateway"),
+            FailureKind::Network
+        );
+    }
+
+    #[test]
+    fn status_codes_are_read_from_http_tokens() {
+        assert_eq!(
+            classify("ERROR: [twitter] 1404: HTTP Error 404: Not Found"),
+            FailureKind::Unavailable
+        );
+        assert_eq!(
+            classify("HTTP 404 Not Found for https://cdn.example.com/v/429.mp4"),
+            FailureKind::Unavailable
+        );
+        assert_eq!(
+            classify("ERROR: [youtube] x: HTTP Error 429: Too Many Requests"),
+            FailureKind::RateLimited
+        );
+        assert_eq!(
+            classify("HTTP 410 Gone for https://cdn.example.com/a.mp4"),
+            FailureKind::Unavailable
+        );
+    }
+
+    #[test]
+    fn digits_inside_ids_are_ignored() {
+        assert_eq!(
+            classify("connection error: https://x.com/a/status/1404290 timed out"),
+            FailureKind::Network
+        );
+        assert_eq!(
+            classify("ERROR: [instagram] C429xyz: oops"),
+            FailureKind::Other
+        );
+    }
+
+    #[test]
+    fn text_needles_still_apply() {
+        assert_eq!(
+            classify("ERROR: [youtube] abc: Private video. Sign in if you've been granted access"),
+            FailureKind::Login
+        );
+        assert_eq!(
+            classify("ERROR: [youtube] abc: Video unavailable. This video has been removed"),
+            FailureKind::Unavailable
+        );
+        assert_eq!(
+            classify("network: incomplete download, got 10 of 20 bytes (will resume)"),
+            FailureKind::Network
+        );
+        assert_eq!(FailureKind::classify(None), FailureKind::Other);
+    }
+}
@@ -0,0 +1,22 @@
+//! Models and URL helpers shared by the Yew frontend and the Tauri backend.
+//!
+//! Anything that crosses the IPC boundary lives here so both sides
+//! serialize the same tokens.
+
+pub mod events;
+pub mod failure;
+pub mod models;
+pub mod platform;
+pub mod url;
+
+pub use events::{DownloadEvent, MessageLevel};
+pub use failure::{FailureKind, PlatformPause};
+pub use models::{
+    ClipRow, CollectionFilter, ContentType, CrashRecovery, CsvMapping, CsvPreview, DateRange,
+    DownloadAttempt, DownloadStatus, ImportRecord, ImportSummary, LibraryCollectionNode,
+    LibraryPlatformNode, LibraryStats, MaintenanceTask, MediaKind, Platform, PlatformCapabilities,
+    RecoveredDownload, RowFilter, RowPage, RowSort, ScheduleStatus, SessionReport, StorageLevel,
+    StorageStatus, TagCount, TaskStatus, ToolAdvisory, ToolReport, UrlBatchReport, VolumeStatus,
+    WatchedCollection,
+};
+pub use platform::PlatformInfo;
@@ -0,0 +1,730 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+    Tiktok,
+    Instagram,
+    Youtube,
+    Pinterest,
+    Twitter,
+    Reddit,
+    #[serde(other)]
+    Other,
+}
+
+impl Platform {
+    /// Token stored in the DB and used as the site folder name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Platform::Tiktok => "tiktok",
+            Platform::Instagram => "instagram",
+            Platform::Youtube => "youtube",
+            Platform::Pinterest => "pinterest",
+            Platform::Twitter => "twitter",
+            Platform::Reddit => "reddit",
+            Platform::Other => "other",
+        }
+    }
+}
+
+impl From<&str> for Platform {
+    fn from(s: &str) -> Self {
+        match s.trim().to_lowercase().as_str() {
+            "tiktok" => Platform::Tiktok,
+            "instagram" => Platform::Instagram,
+            "youtube" => Platform::Youtube,
+            "pinterest" => Platform::Pinterest,
+            "twitter" | "x" => Platform::Twitter,
+            "reddit" => Platform::Reddit,
+            _ => Platform::Other,
+        }
+    }
+}
+
+/// Where a row came from (the DB `origin` column, the CSV `Type` column).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentType {
+    Liked,
+    Reposts,
+    Profile,
+    Bookmarks,
+    Playlist,
+    Recommendation,
+    Manual,
+    Pinboard,
+    /// Proposed from browser history
+    History,
+    #[serde(other)]
+    Other,
+}
+
+impl ContentType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentType::Liked => "liked",
+            ContentType::Reposts => "reposts",
+            ContentType::Profile => "profile",
+            ContentType::Bookmarks => "bookmarks",
+            ContentType::Playlist => "playlist",
+            ContentType::Recommendation => "recommendation",
+            ContentType::Manual => "manual",
+            ContentType::Pinboard => "pinboard",
+            ContentType::History => "history",
+            ContentType::Other => "other",
+        }
+    }
+}
+
+impl From<&str> for ContentType {
+    fn from(s: &str) -> Self {
+        match s.trim().to_lowercase().as_str() {
+            "liked" => ContentType::Liked,
+            "reposts" => ContentType::Reposts,
+            "profile" => ContentType::Profile,
+            "bookmarks" => ContentType::Bookmarks,
+            "playlist" => ContentType::Playlist,
+            "recommendation" => ContentType::Recommendation,
+            "manual" => ContentType::Manual,
+            "pinboard" => ContentType::Pinboard,
+            "history" => ContentType::History,
+            _ => ContentType::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    /// Older CSV exports and UI builds used "pictures".
+    #[serde(
+        alias = "pictures",
+        alias = "images",
+        alias = "Image",
+        alias = "Pictures"
+    )]
+    Image,
+    #[serde(alias = "videos", alias = "Video")]
+    Video,
+}
+
+impl MediaKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MediaKind::Image => "image",
+            MediaKind::Video => "video",
+        }
+    }
+}
+
+impl From<&str> for MediaKind {
+    fn from(s: &str) -> Self {
+        match s.trim().to_lowercase().as_str() {
+            "image" | "images" | "pictures" => MediaKind::Image,
+            _ => MediaKind::Video,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DownloadStatus {
+    /// Imported for review; not yet in the backlog
+    Pending,
+    #[default]
+    Backlog,
+    Queued,
+    Downloading,
+    Done,
+    Error,
+    Canceled,
+    /// The link answered 404/410 when it was imported
+    Invalid,
+    /// Stopped by the user mid-download; partial files are kept for resuming
+    Paused,
+    /// Left out by its collection's length/size filter; `last_error` says why
+    Skipped,
+    /// Was downloaded, but the library check found the file gone or changed
+    Missing,
+    /// In the trash; restoring it brings back the status it had before
+    Deleted,
+}
+
+impl DownloadStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DownloadStatus::Pending => "pending",
+            DownloadStatus::Backlog => "backlog",
+            DownloadStatus::Queued => "queued",
+            DownloadStatus::Downloading => "downloading",
+            DownloadStatus::Done => "done",
+            DownloadStatus::Error => "error",
+            DownloadStatus::Canceled => "canceled",
+            DownloadStatus::Invalid => "invalid",
+            DownloadStatus::Paused => "paused",
+            DownloadStatus::Skipped => "skipped",
+            DownloadStatus::Missing => "missing",
+            DownloadStatus::Deleted => "deleted",
+        }
+    }
+
+    /// Lenient parse of a stored status; legacy "queue" maps to `Queued`.
+    pub fn from_db<S: AsRef<str>>(raw: S) -> Self {
+        match raw.as_ref().to_lowercase().as_str() {
+            "queue" | "queued" => DownloadStatus::Queued,
+            "pending" => DownloadStatus::Pending,
+            "backlog" => DownloadStatus::Backlog,
+            "downloading" => DownloadStatus::Downloading,
+            "done" => DownloadStatus::Done,
+            "error" => DownloadStatus::Error,
+            "canceled" => DownloadStatus::Canceled,
+            "invalid" => DownloadStatus::Invalid,
+            "paused" => DownloadStatus::Paused,
+            "skipped" => DownloadStatus::Skipped,
+            "missing" => DownloadStatus::Missing,
+            "deleted" => DownloadStatus::Deleted,
+            _ => DownloadStatus::Backlog,
+        }
+    }
+}
+
+/// One download row as shown in the UI and as read from an import CSV
+/// (header `Platform,Type,Handle,Media,link`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClipRow {
+    #[serde(default)]
+    pub id: i64,
+    #[serde(rename = "Platform")]
+    pub platform: Platform,
+    #[serde(rename = "Type")]
+    pub content_type: ContentType,
+    #[serde(rename = "Handle")]
+    pub handle: String,
+    #[serde(rename = "Media")]
+    pub media: MediaKind,
+    #[serde(rename = "link")]
+    pub link: String,
+    /// Comes from DB; optional when deserializing CSV.
+    #[serde(default)]
+    pub name: String,
+    /// Optional per-row preferred output; "audio" or "video" (or omitted/default)
+    #[serde(default)]
+    pub output_format: Option<String>,
+    #[serde(default)]
+    pub status: DownloadStatus,
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// Queue order: higher starts sooner; ties go first in, first out.
+    #[serde(default)]
+    pub priority: i64,
+    /// User tags; only filled by the Library queries.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Media metadata from the site, once known
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub uploader: Option<String>,
+    /// YYYY-MM-DD
+    #[serde(default)]
+    pub upload_date: Option<String>,
+    #[serde(default)]
+    pub duration_secs: Option<u32>,
+    #[serde(default)]
+    pub thumbnail_url: Option<String>,
+    /// Size of the default format as the site reports it, before downloading
+    #[serde(default)]
+    pub estimated_bytes: Option<u64>,
+    /// Subtitle file saved with the video; with embedding on it may have been
+    /// merged into the video and removed
+    #[serde(default)]
+    pub subtitle_path: Option<String>,
+    /// One-off folder chosen at enqueue time, used instead of the collection folder
+    #[serde(default)]
+    pub destination_dir: Option<String>,
+    /// Cached preview image for the Library grid
+    #[serde(default)]
+    pub thumbnail_path: Option<String>,
+    /// From the metadata sidecar, when those are written
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub view_count: Option<u64>,
+    /// Audio format pinned for this row ("mp3", "flac", ...)
+    #[serde(default)]
+    pub audio_format: Option<String>,
+    /// Browser whose cookies this row tries first
+    #[serde(default)]
+    pub cookie_browser: Option<String>,
+    /// Another link to the same item, found after this one was removed
+    #[serde(default)]
+    pub mirror_url: Option<String>,
+    /// Where `mirror_url` was found ("Wayback Machine", "YouTube search")
+    #[serde(default)]
+    pub mirror_source: Option<String>,
+    /// Size on disk once downloaded (the whole folder for galleries)
+    #[serde(default)]
+    pub file_bytes: Option<u64>,
+}
+
+/// One run of a download tool for a row, kept for diagnosing failures.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DownloadAttempt {
+    pub id: i64,
+    pub download_id: i64,
+    /// RFC 3339
+    pub attempted_at: String,
+    /// "yt-dlp", "gallery-dl", "direct", ...
+    pub tool: String,
+    /// Browser whose cookies were used, if any
+    pub browser: Option<String>,
+    /// `None` when the tool didn't exit on its own (timeout, spawn failure)
+    pub exit_code: Option<i32>,
+    pub ok: bool,
+    /// Last lines of the tool's output, or the error when it didn't run
+    pub output_tail: String,
+}
+
+/// A user tag and how many distinct links carry it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagCount {
+    pub name: String,
+    pub count: u64,
+}
+
+/// One collection (handle + origin) inside a platform of the Library tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LibraryCollectionNode {
+    pub handle: String,
+    /// Raw origin token as stored in the DB ("profile" | "playlist" | "manual" | ...)
+    pub origin: String,
+    pub item_count: u64,
+}
+
+/// Top level of the Library tree: platform → collections → item counts.
+/// Items themselves are fetched per collection on demand.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LibraryPlatformNode {
+    pub platform: String,
+    pub item_count: u64,
+    pub collections: Vec<LibraryCollectionNode>,
+}
+
+/// Summary of one queue run, from the first download starting until the queue drained.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionReport {
+    pub id: i64,
+    /// RFC 3339 timestamps
+    pub started_at: String,
+    pub ended_at: String,
+    pub elapsed_secs: u64,
+    pub downloaded: u32,
+    pub failed: u32,
+    pub bytes: u64,
+    /// (failure category, count), most frequent first
+    pub failures: Vec<(String, u32)>,
+    /// (collection label, items downloaded), largest first
+    pub top_collections: Vec<(String, u32)>,
+    #[serde(default)]
+    pub dismissed: bool,
+}
+
+/// Publish-date window for expanding collections and queueing rows; either
+/// end may be left open.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateRange {
+    /// YYYY-MM-DD, inclusive
+    #[serde(default)]
+    pub after: Option<String>,
+    /// YYYY-MM-DD, inclusive
+    #[serde(default)]
+    pub before: Option<String>,
+}
+
+impl DateRange {
+    /// "2024-05-01" or "20240501" as "20240501"; `None` for anything else.
+    pub fn compact(date: &str) -> Option<String> {
+        let digits: String = date.chars().filter(|c| c.is_ascii_digit()).collect();
+        (digits.len() == 8).then_some(digits)
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.after.as_deref().and_then(Self::compact).is_none()
+            && self.before.as_deref().and_then(Self::compact).is_none()
+    }
+
+    /// Whether something published on `date` falls inside. Unknown dates are
+    /// kept, since flat listings often leave them out.
+    pub fn contains(&self, date: Option<&str>) -> bool {
+        let Some(date) = date.and_then(Self::compact) else {
+            return true;
+        };
+        let after = self.after.as_deref().and_then(Self::compact);
+        let before = self.before.as_deref().and_then(Self::compact);
+        after.is_none_or(|a| date >= a) && before.is_none_or(|b| date <= b)
+    }
+}
+
+/// Length, size and keyword limits for one collection's downloads; items
+/// outside them are marked Skipped instead of downloaded. Unset bounds don't apply.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionFilter {
+    #[serde(default)]
+    pub min_duration_secs: Option<u32>,
+    #[serde(default)]
+    pub max_duration_secs: Option<u32>,
+    #[serde(default)]
+    pub max_bytes: Option<u64>,
+    /// Regex (case-insensitive) the title or description must match
+    #[serde(default)]
+    pub include: Option<String>,
+    /// Regex (case-insensitive); matching titles or descriptions are skipped
+    #[serde(default)]
+    pub exclude: Option<String>,
+}
+
+impl CollectionFilter {
+    pub fn is_empty(&self) -> bool {
+        self.min_duration_secs.is_none()
+            && self.max_duration_secs.is_none()
+            && self.max_bytes.is_none()
+            && !self.has_keywords()
+    }
+
+    pub fn has_keywords(&self) -> bool {
+        self.include.is_some() || self.exclude.is_some()
+    }
+
+    /// Whether checking an item needs the length, size or title that isn't known yet.
+    pub fn needs_probe(
+        &self,
+        duration_secs: Option<u32>,
+        bytes: Option<u64>,
+        title: Option<&str>,
+    ) -> bool {
+        let checks_duration = self.min_duration_secs.is_some() || self.max_duration_secs.is_some();
+        (checks_duration && duration_secs.is_none())
+            || (self.max_bytes.is_some() && bytes.is_none())
+            || (self.has_keywords() && title.is_none())
+    }
+
+    /// Why an item this long and this large is left out; unknown values pass.
+    pub fn skip_reason(&self, duration_secs: Option<u32>, bytes: Option<u64>) -> Option<String> {
+        if let (Some(min), Some(d)) = (self.min_duration_secs, duration_secs) {
+            if d < min {
+                return Some(format!("Skipped: shorter than {min}s ({d}s)"));
+            }
+        }
+        if let (Some(max), Some(d)) = (self.max_duration_secs, duration_secs) {
+            if d > max {
+                return Some(format!(
+                    "Skipped: longer than {} min ({} min)",
+                    max / 60,
+                    d.div_ceil(60)
+                ));
+            }
+        }
+        if let (Some(max), Some(b)) = (self.max_bytes, bytes) {
+            if b > max {
+                const MB: u64 = 1024 * 1024;
+                return Some(format!(
+                    "Skipped: larger than {} MB ({} MB)",
+                    max / MB,
+                    b.div_ceil(MB)
+                ));
+            }
+        }
+        None
+    }
+}
+
+/// What the previous run left behind when it ended without shutting down cleanly.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrashRecovery {
+    /// RFC 3339; last heartbeat of the run that crashed
+    pub last_alive: Option<String>,
+    /// Downloads that were in flight, now back in the queue
+    pub recovered: Vec<RecoveredDownload>,
+    /// Rows waiting in the queue, recovered ones included
+    pub queued: usize,
+    /// Unfinished files (`.part`) under the download folder
+    pub partial_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveredDownload {
+    pub id: i64,
+    /// Title when known, else the link
+    pub label: String,
+}
+
+/// Whether queued downloads may start right now under the user's download windows.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleStatus {
+    /// At least one window is configured; otherwise downloads may always start
+    pub scheduled: bool,
+    pub open: bool,
+    /// Local start of the next window while closed: "01:00", or "Tue 01:00" on a later day
+    pub starts_at: Option<String>,
+}
+
+/// Whether the drive holding the download folder is connected. Only removable
+/// and network volumes can go offline; a folder on the system disk never does.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VolumeStatus {
+    pub offline: bool,
+    /// Where the volume mounts ("/Volumes/Backup", "E:\\"), when it is a removable one
+    pub mount_point: Option<String>,
+}
+
+/// How close the download folder's drive is to running out of space.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StorageLevel {
+    #[default]
+    Ok,
+    Low,
+    Critical,
+    /// The folder cannot be reached (drive unplugged, share down)
+    Unreachable,
+}
+
+/// Free space of the download folder's drive, for the sidebar badge.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageStatus {
+    pub level: StorageLevel,
+    pub path: String,
+    pub free_bytes: u64,
+    pub total_bytes: u64,
+}
+
+/// One list import. `created_ids` are exactly the rows it added, so a
+/// rollback deletes those and nothing else.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportRecord {
+    pub id: i64,
+    /// File name, or "Pasted list" when the CSV came without a file
+    pub source_name: String,
+    /// Full path of the file; re-running an import reads it again
+    pub source_path: Option<String>,
+    /// RFC 3339 timestamps
+    pub imported_at: String,
+    pub row_count: u32,
+    /// Rows whose link was dead (404/410) at import time; stored as `Invalid`
+    #[serde(default)]
+    pub invalid_count: u32,
+    #[serde(default)]
+    pub created_ids: Vec<i64>,
+    /// Rows went to Pending review instead of the backlog
+    pub review: bool,
+    pub rolled_back_at: Option<String>,
+    /// Links that already had a row and were left out
+    #[serde(default)]
+    pub skipped_count: u32,
+    /// Existing rows moved into the imported collection instead of duplicated
+    #[serde(default)]
+    pub updated_count: u32,
+}
+
+/// A profile, channel or playlist checked for new items every
+/// `interval_hours`; new links are added to the backlog, and queued when
+/// `auto_queue` is on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchedCollection {
+    pub id: i64,
+    pub url: String,
+    pub platform: Platform,
+    pub handle: String,
+    pub origin: ContentType,
+    pub interval_hours: u32,
+    pub auto_queue: bool,
+    /// RFC 3339; `None` until the first check
+    #[serde(default)]
+    pub last_checked_at: Option<String>,
+    /// New items found by the last check
+    #[serde(default)]
+    pub last_added: u32,
+    /// Why the last check failed
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+/// What one import did with its rows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub inserted: u64,
+    /// Existing rows moved into the imported collection
+    pub updated: u64,
+    /// Links left out because they already had a row
+    pub skipped: u64,
+}
+
+/// Which CSV column, by index, holds each field of an imported row. Fields
+/// left unmapped are worked out from the link.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CsvMapping {
+    /// The first line names the columns rather than holding a row
+    #[serde(default)]
+    pub header_row: bool,
+    #[serde(default)]
+    pub platform: Option<usize>,
+    /// The `Type` column
+    #[serde(default)]
+    pub origin: Option<usize>,
+    #[serde(default)]
+    pub handle: Option<usize>,
+    #[serde(default)]
+    pub media: Option<usize>,
+    #[serde(default)]
+    pub link: Option<usize>,
+}
+
+/// A CSV file read before importing it, so its columns can be mapped.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CsvPreview {
+    pub path: String,
+    /// Column names from the header row, or "Column N" without one
+    pub headers: Vec<String>,
+    /// The mapping guessed from the header names
+    pub mapping: CsvMapping,
+    /// The first few data rows
+    pub sample: Vec<Vec<String>>,
+}
+
+/// Aggregates over downloaded rows for the Stats page.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LibraryStats {
+    /// Downloaded rows in all
+    pub total: u64,
+    /// (platform, rows), largest first
+    pub by_platform: Vec<(String, u64)>,
+    /// (origin, rows), largest first
+    pub by_origin: Vec<(String, u64)>,
+    /// (media kind, rows), largest first
+    pub by_media: Vec<(String, u64)>,
+    /// Size of the downloaded files that could be read; unplugged drives count 0
+    pub bytes_on_disk: u64,
+    /// ("YYYY-MM-DD", downloads) for each of the last 90 days, oldest first
+    pub per_day: Vec<(String, u64)>,
+    /// Seconds per finished download over all recorded sessions
+    pub avg_download_secs: Option<f64>,
+}
+
+/// Order of a paged row listing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RowSort {
+    /// By platform, handle, origin, then name, like the full listing
+    #[default]
+    Collection,
+    /// By title, or the name when there is none
+    Title,
+    Newest,
+    Oldest,
+    /// Biggest files first
+    Largest,
+    /// Longest media first
+    Longest,
+}
+
+/// Narrows a paged row listing; unset fields match every row.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RowFilter {
+    #[serde(default)]
+    pub platform: Option<String>,
+    #[serde(default)]
+    pub handle: Option<String>,
+    #[serde(default)]
+    pub origin: Option<String>,
+    /// Part of the title, name or link
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// One page of a row listing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RowPage {
+    pub rows: Vec<ClipRow>,
+    /// Rows matching the filter across all pages
+    pub total: u64,
+}
+
+/// What `download_urls` did with a pasted batch of links.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UrlBatchReport {
+    /// Ids of the rows created for new links
+    pub added: Vec<i64>,
+    /// Links that already had a row
+    pub known: u64,
+    /// Rows handed to the download queue, new and known ones
+    pub queued: u64,
+    /// Lines that aren't a link from a supported site
+    pub invalid: Vec<String>,
+}
+
+/// Installed extractor versions, probed at startup and from diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ToolReport {
+    /// `None` when the tool is missing or did not answer `--version`
+    pub yt_dlp: Option<String>,
+    pub gallery_dl: Option<String>,
+    /// Bundled known-bad version ranges the installed tools fall into
+    #[serde(default)]
+    pub advisories: Vec<ToolAdvisory>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolAdvisory {
+    /// "yt-dlp" | "gallery-dl"
+    pub tool: String,
+    pub version: String,
+    /// Platform id the advisory is about
+    pub platform: String,
+    pub message: String,
+}
+
+/// What can be downloaded from a platform with the installed tools.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlatformCapabilities {
+    pub platform: Platform,
+    pub label: String,
+    /// "yt-dlp" | "gallery-dl" | "direct"
+    pub tool: String,
+    pub video: bool,
+    pub images: bool,
+    pub stories: bool,
+    pub playlists: bool,
+    pub audio_only: bool,
+    pub subtitles: bool,
+    /// Why something is off: missing tool, advisory for the installed version
+    #[serde(default)]
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskStatus {
+    Running,
+    Completed,
+    Failed,
+    Canceled,
+}
+
+/// One background maintenance job (rescan, re-organize, verify, prune, ...).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceTask {
+    pub id: u32,
+    /// Stable job type, e.g. "rename_collection_folders"
+    pub kind: String,
+    pub label: String,
+    pub status: TaskStatus,
+    pub done: u64,
+    pub total: Option<u64>,
+    /// Latest progress line while running, summary or error once finished
+    pub message: String,
+    /// Jobs that would leave things half-done when stopped can't be canceled.
+    pub cancelable: bool,
+    /// RFC 3339 timestamps
+    pub started_at: String,
+    pub finished_at: Option<String>,
+}
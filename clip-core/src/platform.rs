@@ -0,0 +1,187 @@
+use crate::models::{MediaKind, Platform};
+use crate::url::{host, url_extension};
+
+/// External downloader used for a URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    YtDlp,
+    GalleryDl,
+    /// In-process HTTP fetch for direct media links.
+    DirectHttp,
+}
+
+/// What both sides know about a platform: how to recognise its links, how to
+/// label and draw them and which tool fetches them.
+pub struct PlatformInfo {
+    pub platform: Platform,
+    pub label: &'static str,
+    /// Brand icon among the frontend's assets; empty for none.
+    pub icon: &'static str,
+    /// Tool for regular (video) links.
+    pub preferred_tool: Tool,
+    /// Tool for links matching `image_patterns`.
+    pub image_tool: Tool,
+    /// Hosts of the site ("youtu.be", "pin.it", ...); subdomains match too.
+    pub domains: &'static [&'static str],
+    /// File extensions (lowercase, no dot) that identify a direct media link.
+    pub extensions: &'static [&'static str],
+    /// Path fragments that mark an image post (e.g. TikTok "/photo/").
+    pub image_patterns: &'static [&'static str],
+    pub default_media: MediaKind,
+    /// Query params that are part of what was saved (video id, start time,
+    /// playlist); every other param is tracking noise and gets dropped.
+    pub keep_params: &'static [&'static str],
+}
+
+impl PlatformInfo {
+    /// Token stored in the DB and used as the site folder name.
+    pub fn id(&self) -> &'static str {
+        self.platform.as_str()
+    }
+
+    pub fn matches(&self, url: &str) -> bool {
+        let lower = url.to_lowercase();
+        if let Some(host) = host(&lower) {
+            let on = |d: &&str| {
+                host.strip_suffix(*d)
+                    .is_some_and(|sub| sub.is_empty() || sub.ends_with('.'))
+            };
+            if self.domains.iter().any(on) {
+                return true;
+            }
+        }
+        let is_http = lower.starts_with("http://") || lower.starts_with("https://");
+        is_http && url_extension(&lower).is_some_and(|ext| self.extensions.contains(&ext.as_str()))
+    }
+
+    pub fn is_image_url(&self, url: &str) -> bool {
+        let path = url.split(['?', '#']).next().unwrap_or(url);
+        self.image_patterns.iter().any(|p| path.contains(p))
+    }
+
+    pub fn tool_for(&self, url: &str) -> Tool {
+        if self.is_image_url(url) {
+            self.image_tool
+        } else {
+            self.preferred_tool
+        }
+    }
+
+    pub fn media_for(&self, url: &str) -> MediaKind {
+        if self.is_image_url(url) {
+            MediaKind::Image
+        } else {
+            self.default_media
+        }
+    }
+}
+
+pub static PLATFORMS: &[PlatformInfo] = &[
+    PlatformInfo {
+        platform: Platform::Instagram,
+        label: "Instagram",
+        icon: "public/instagram.webp",
+        preferred_tool: Tool::YtDlp,
+        image_tool: Tool::YtDlp,
+        domains: &["instagram.com"],
+        extensions: &[],
+        image_patterns: &["/p/"],
+        default_media: MediaKind::Video,
+        keep_params: &["img_index"],
+    },
+    PlatformInfo {
+        platform: Platform::Tiktok,
+        label: "TikTok",
+        icon: "public/tiktok.webp",
+        preferred_tool: Tool::YtDlp,
+        image_tool: Tool::GalleryDl,
+        domains: &["tiktok.com"],
+        extensions: &[],
+        image_patterns: &["/photo/"],
+        default_media: MediaKind::Video,
+        keep_params: &[],
+    },
+    PlatformInfo {
+        platform: Platform::Youtube,
+        label: "YouTube",
+        icon: "public/youtube.webp",
+        preferred_tool: Tool::YtDlp,
+        image_tool: Tool::YtDlp,
+        domains: &["youtube.com", "youtu.be"],
+        extensions: &[],
+        image_patterns: &[],
+        default_media: MediaKind::Video,
+        keep_params: &["v", "t", "start", "list", "index"],
+    },
+    PlatformInfo {
+        platform: Platform::Pinterest,
+        label: "Pinterest",
+        icon: "public/pinterest.png",
+        preferred_tool: Tool::GalleryDl,
+        image_tool: Tool::GalleryDl,
+        domains: &["pinterest.com", "pin.it"],
+        extensions: &[],
+        image_patterns: &[],
+        default_media: MediaKind::Image,
+        keep_params: &[],
+    },
+    PlatformInfo {
+        platform: Platform::Twitter,
+        label: "X (Twitter)",
+        icon: "public/x.com.png",
+        preferred_tool: Tool::YtDlp,
+        image_tool: Tool::GalleryDl,
+        domains: &["twitter.com", "x.com"],
+        extensions: &[],
+        image_patterns: &["/photo/"],
+        default_media: MediaKind::Video,
+        keep_params: &[],
+    },
+    PlatformInfo {
+        platform: Platform::Reddit,
+        label: "Reddit",
+        icon: "public/reddit.svg",
+        preferred_tool: Tool::YtDlp,
+        image_tool: Tool::GalleryDl,
+        // "redd.it" also covers the v.redd.it / i.redd.it media hosts.
+        domains: &["reddit.com", "redd.it"],
+        extensions: &[],
+        image_patterns: &["i.redd.it/", "/gallery/"],
+        default_media: MediaKind::Video,
+        keep_params: &[],
+    },
+    // Catch-all for direct file links; must stay last so site domains win.
+    PlatformInfo {
+        platform: Platform::Other,
+        label: "Other",
+        icon: "",
+        preferred_tool: Tool::DirectHttp,
+        image_tool: Tool::DirectHttp,
+        domains: &[],
+        extensions: &[
+            "mp4", "webm", "mov", "m4v", "jpg", "jpeg", "png", "gif", "webp",
+        ],
+        image_patterns: &[".jpg", ".jpeg", ".png", ".gif", ".webp"],
+        default_media: MediaKind::Video,
+        keep_params: &[],
+    },
+];
+
+/// Platform whose domains (or, for direct links, file extension) match `url`.
+pub fn detect(url: &str) -> Option<&'static PlatformInfo> {
+    PLATFORMS.iter().find(|p| p.matches(url))
+}
+
+/// Info for a DB token such as "tiktok".
+pub fn by_id(id: &str) -> Option<&'static PlatformInfo> {
+    PLATFORMS
+        .iter()
+        .find(|p| p.id().eq_ignore_ascii_case(id.trim()))
+}
+
+pub fn info(platform: Platform) -> &'static PlatformInfo {
+    PLATFORMS
+        .iter()
+        .find(|p| p.platform == platform)
+        .expect("every Platform has an entry")
+}
@@ -0,0 +1,378 @@
+use crate::models::{ContentType, Platform};
+
+/// Extract Instagram (handle, id) from /reel/… or /p/…
+pub fn ig_handle_and_id(url: &str) -> (Option<String>, Option<String>) {
+    if let Some(pos) = url.find("instagram.com/") {
+        let rest = &url[pos + "instagram.com/".len()..];
+        let parts: Vec<&str> = rest.trim_matches('/').split('/').collect();
+        if parts.len() >= 3 {
+            let handle = parts[0].to_string();
+            let typ = parts[1];
+            let id = parts[2].to_string();
+            if typ == "reel" || typ == "p" {
+                return (Some(handle), Some(id));
+            }
+        }
+    }
+    (None, None)
+}
+
+/// Extract TikTok id token after /video/ or /photo/
+pub fn tiktok_id_from_url(url: &str) -> Option<String> {
+    for key in ["/video/", "/photo/"] {
+        if let Some(idx) = url.find(key) {
+            let tail = &url[idx + key.len()..];
+            let id = tail.split(['/', '?', '&']).next().unwrap_or("").to_string();
+            if !id.is_empty() {
+                return Some(id);
+            }
+        }
+    }
+    None
+}
+
+/// Extract Twitter/X (handle, status id) from /{handle}/status/{id}
+pub fn twitter_handle_and_id(url: &str) -> (Option<String>, Option<String>) {
+    let lower = url.to_lowercase();
+    let Some(pos) = ["twitter.com/", "x.com/"].iter().find_map(|d| {
+        lower
+            .find(d)
+            .filter(|&i| i == 0 || matches!(lower.as_bytes()[i - 1], b'/' | b'.'))
+            .map(|i| i + d.len())
+    }) else {
+        return (None, None);
+    };
+    let rest = url
+        .get(pos..)
+        .unwrap_or("")
+        .split(['?', '#'])
+        .next()
+        .unwrap_or("");
+    let parts: Vec<&str> = rest.trim_matches('/').split('/').collect();
+    if parts.len() >= 3 && parts[1] == "status" && !parts[2].is_empty() {
+        let handle = (parts[0] != "i").then(|| parts[0].to_string());
+        return (handle, Some(parts[2].to_string()));
+    }
+    (None, None)
+}
+
+/// Extract Reddit (handle, post id). The handle is the subreddit, or "u_{name}"
+/// for user posts; short links (redd.it, v.redd.it, i.redd.it) carry no handle.
+pub fn reddit_handle_and_id(url: &str) -> (Option<String>, Option<String>) {
+    let Some(h) = host(url) else {
+        return (None, None);
+    };
+    let tail = url_after_domain(url.trim());
+    let tail = tail.split(['?', '#']).next().unwrap_or("");
+    let parts: Vec<&str> = tail.split('/').filter(|s| !s.is_empty()).collect();
+    if h.ends_with("redd.it") {
+        let id = parts.first().map(|s| match h.as_str() {
+            // i.redd.it/{id}.jpg
+            "i.redd.it" => s.split('.').next().unwrap_or(s).to_string(),
+            _ => s.to_string(),
+        });
+        return (None, id.filter(|s| !s.is_empty()));
+    }
+    if !h.ends_with("reddit.com") {
+        return (None, None);
+    }
+    match parts.as_slice() {
+        ["r", sub, "comments", id, ..] => (Some(sub.to_string()), Some(id.to_string())),
+        ["u" | "user", name, "comments", id, ..] => {
+            (Some(format!("u_{name}")), Some(id.to_string()))
+        }
+        ["comments" | "gallery", id, ..] => (None, Some(id.to_string())),
+        _ => (None, None),
+    }
+}
+
+/// Extract YouTube video id from v=… or /shorts/…
+pub fn youtube_id_from_url(url: &str) -> Option<String> {
+    if let Some(qidx) = url.find('?') {
+        for pair in url[qidx + 1..].split('&') {
+            if let Some((k, v)) = pair.split_once('=') {
+                if k == "v" && !v.is_empty() {
+                    return Some(v.to_string());
+                }
+            }
+        }
+    }
+    if let Some(idx) = url.find("/shorts/") {
+        let tail = &url[idx + "/shorts/".len()..];
+        let id = tail.split(['/', '?', '&']).next().unwrap_or("").to_string();
+        if !id.is_empty() {
+            return Some(id);
+        }
+    }
+    None
+}
+
+/// `url` with only the query params named in `keep`, in their original order.
+/// The fragment is dropped unless it carries a `t=` start time.
+pub fn retain_query(url: &str, keep: &[&str]) -> String {
+    let (rest, fragment) = url.split_once('#').unwrap_or((url, ""));
+    let (base, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or("");
+            !key.is_empty() && keep.contains(&key)
+        })
+        .collect();
+    let mut out = base.to_string();
+    if !kept.is_empty() {
+        out.push('?');
+        out.push_str(&kept.join("&"));
+    }
+    if fragment.starts_with("t=") && keep.contains(&"t") {
+        out.push('#');
+        out.push_str(fragment);
+    }
+    out
+}
+
+/// Start offset saved in a link (`t=120`, `t=1m30s`, `start=90`, `#t=2h`), in seconds.
+pub fn start_offset_secs(url: &str) -> Option<u32> {
+    let (rest, fragment) = url.split_once('#').unwrap_or((url, ""));
+    let query = rest.split_once('?').map_or("", |(_, q)| q);
+    query
+        .split('&')
+        .chain(std::iter::once(fragment))
+        .filter_map(|pair| pair.split_once('='))
+        .filter(|(k, _)| *k == "t" || *k == "start")
+        .find_map(|(_, v)| parse_timestamp(v))
+        .filter(|secs| *secs > 0)
+}
+
+/// "90", "90s", "1m30s" or "1h2m3s" as seconds.
+fn parse_timestamp(v: &str) -> Option<u32> {
+    if let Ok(secs) = v.parse::<u32>() {
+        return Some(secs);
+    }
+    let mut total = 0u32;
+    let mut digits = String::new();
+    for c in v.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let n: u32 = digits.parse().ok()?;
+        digits.clear();
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        total = total.checked_add(n.checked_mul(unit)?)?;
+    }
+    digits.is_empty().then_some(total)
+}
+
+/// Stable per-item id for a link, used for file and row names.
+pub fn item_id(platform: Platform, url: &str) -> Option<String> {
+    match platform {
+        Platform::Instagram => ig_handle_and_id(url).1,
+        Platform::Tiktok => tiktok_id_from_url(url),
+        Platform::Youtube => youtube_id_from_url(url),
+        Platform::Twitter => twitter_handle_and_id(url).1,
+        Platform::Reddit => reddit_handle_and_id(url).1,
+        Platform::Pinterest | Platform::Other => None,
+    }
+}
+
+/// Groups the files of a multi-image post; only Reddit gallery links are
+/// recognisable as such from the URL alone.
+pub fn image_set_id(platform: Platform, url: &str) -> Option<String> {
+    match platform {
+        Platform::Reddit if url.contains("/gallery/") => {
+            reddit_handle_and_id(url).1.map(|id| format!("reddit_{id}"))
+        }
+        _ => None,
+    }
+}
+
+/// Origin implied by the shape of a link, for CSVs whose Type column is empty
+/// or "other". Single-post links say nothing about where they came from.
+pub fn infer_origin(platform: Platform, url: &str) -> Option<ContentType> {
+    let path = url_after_domain(url.trim());
+    let (path, query) = path.split_once('?').unwrap_or((&path, ""));
+    let path = path.split('#').next().unwrap_or("");
+    let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let has_param = |key: &str| {
+        query.split('&').any(|pair| {
+            pair.split_once('=')
+                .is_some_and(|(k, v)| k == key && !v.is_empty())
+        })
+    };
+    match platform {
+        Platform::Instagram => match parts.as_slice() {
+            [_, "saved", ..] => Some(ContentType::Bookmarks),
+            [_, "reels"] | [_]
+                if !matches!(parts[0], "p" | "reel" | "reels" | "explore" | "stories") =>
+            {
+                Some(ContentType::Profile)
+            }
+            _ => None,
+        },
+        Platform::Tiktok => match parts.as_slice() {
+            [user] if user.starts_with('@') => Some(ContentType::Profile),
+            _ => None,
+        },
+        Platform::Youtube => {
+            // A video opened from a playlist or Mix keeps `list`, but is one video.
+            let video = has_param("v") || host(url).is_some_and(|h| h == "youtu.be");
+            if parts.first() == Some(&"playlist") || (has_param("list") && !video) {
+                return Some(ContentType::Playlist);
+            }
+            match parts.as_slice() {
+                [handle, ..] if handle.starts_with('@') => Some(ContentType::Profile),
+                ["channel" | "c" | "user", _, ..] => Some(ContentType::Profile),
+                _ => None,
+            }
+        }
+        // pin.it short links are single pins, whatever their shape.
+        Platform::Pinterest if host(url).is_some_and(|h| h == "pin.it") => None,
+        Platform::Pinterest => match parts.as_slice() {
+            ["pin", ..] => None,
+            [_, _] => Some(ContentType::Pinboard),
+            [_] => Some(ContentType::Profile),
+            _ => None,
+        },
+        Platform::Twitter => match parts.as_slice() {
+            ["i", "bookmarks", ..] => Some(ContentType::Bookmarks),
+            [_, "likes"] => Some(ContentType::Liked),
+            [handle] if *handle != "i" => Some(ContentType::Profile),
+            _ => None,
+        },
+        Platform::Reddit => match parts.as_slice() {
+            ["u" | "user", _, "saved", ..] => Some(ContentType::Bookmarks),
+            ["u" | "user", _, "upvoted", ..] => Some(ContentType::Liked),
+            ["u" | "user", _] | ["u" | "user", _, "submitted", ..] => Some(ContentType::Profile),
+            _ => None,
+        },
+        Platform::Other => None,
+    }
+}
+
+/// Whether `url` lists many items (a profile, channel, board or playlist) that
+/// can be expanded into one row per item.
+pub fn is_collection_url(platform: Platform, url: &str) -> bool {
+    matches!(
+        infer_origin(platform, url),
+        Some(ContentType::Profile | ContentType::Playlist | ContentType::Pinboard)
+    )
+}
+
+/// Owner handle of a collection link (without `@`), or the playlist id for
+/// YouTube playlists; `None` for single-item links.
+pub fn collection_handle(platform: Platform, url: &str) -> Option<String> {
+    if !is_collection_url(platform, url) {
+        return None;
+    }
+    let path = url_after_domain(url.trim());
+    let (path, query) = path.split_once('?').unwrap_or((&path, ""));
+    let parts: Vec<&str> = path
+        .split('#')
+        .next()
+        .unwrap_or("")
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+    let handle = match (platform, parts.as_slice()) {
+        (Platform::Youtube, [first, ..]) if first.starts_with('@') => *first,
+        (Platform::Youtube, ["channel" | "c" | "user", name, ..]) => *name,
+        (Platform::Youtube, _) => query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("list="))
+            .filter(|v| !v.is_empty())?,
+        (Platform::Reddit, ["u" | "user", name, ..]) => *name,
+        (_, [first, ..]) => *first,
+        _ => return None,
+    };
+    Some(handle.trim_start_matches('@').to_string())
+}
+
+/// Fallback last path segment without trailing slash/query
+pub fn last_segment(url: &str) -> Option<String> {
+    let base = url.split('?').next().unwrap_or(url).trim_end_matches('/');
+    base.rsplit('/')
+        .next()
+        .map(|s| s.to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Lowercased extension of the URL's last path segment, ignoring query and fragment.
+pub fn url_extension(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next()?;
+    let path = path.split_once("://").map_or(path, |(_, rest)| rest);
+    let (_, tail) = path.split_once('/')?;
+    let (_, ext) = tail.rsplit('/').next()?.rsplit_once('.')?;
+    (!ext.is_empty()).then(|| ext.to_lowercase())
+}
+
+/// Lowercased host of a link without "www." or port; accepts a bare domain too.
+pub fn host(url: &str) -> Option<String> {
+    let rest = url.trim().split_once("://").map_or(url.trim(), |(_, r)| r);
+    let host = rest.split(['/', '?', '#']).next()?;
+    let host = host.rsplit('@').next()?.split(':').next()?.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    (!host.is_empty()).then(|| host.to_string())
+}
+
+fn url_after_domain(url: &str) -> String {
+    let no_scheme = url.split("//").nth(1).unwrap_or(url);
+    match no_scheme.find('/') {
+        Some(i) => no_scheme[i + 1..].to_string(),
+        None => String::new(),
+    }
+}
+
+fn last_two_path_segments(url: &str) -> String {
+    let tail = url_after_domain(url);
+    let parts: Vec<&str> = tail.split('/').filter(|s| !s.is_empty()).collect();
+    match parts.len() {
+        0 => tail,
+        1 => parts[0].to_string(),
+        _ => format!("{}/{}", parts[parts.len() - 2], parts[parts.len() - 1]),
+    }
+}
+
+/// Short display label for a link: "p/{id}", "video/{id}", or the last two path segments.
+pub fn item_label(platform: Platform, url: &str) -> String {
+    let link = url.trim();
+    match platform {
+        Platform::Instagram => {
+            let tail = url_after_domain(link);
+            let mut parts = tail.split('/').filter(|s| !s.is_empty());
+            let _maybe_user = parts.next().unwrap_or_default();
+            let b = parts.next().unwrap_or_default(); // "p" or "reel"
+            let c = parts.next().unwrap_or_default(); // id
+            if (b == "p" || b == "reel") && !c.is_empty() {
+                format!("{b}/{c}")
+            } else {
+                last_two_path_segments(link)
+            }
+        }
+        Platform::Tiktok => {
+            let tail = url_after_domain(link);
+            let pieces: Vec<&str> = tail.split('/').filter(|s| !s.is_empty()).collect();
+            match pieces.iter().position(|p| *p == "photo" || *p == "video") {
+                Some(pos) if pos + 1 < pieces.len() => {
+                    format!("{}/{}", pieces[pos], pieces[pos + 1])
+                }
+                _ => last_two_path_segments(link),
+            }
+        }
+        Platform::Twitter => match twitter_handle_and_id(link).1 {
+            Some(id) => format!("status/{id}"),
+            None => last_two_path_segments(link),
+        },
+        Platform::Reddit => match reddit_handle_and_id(link) {
+            (_, Some(id)) if link.contains("/gallery/") => format!("gallery/{id}"),
+            (_, Some(id)) => format!("comments/{id}"),
+            _ => last_two_path_segments(link),
+        },
+        _ => last_two_path_segments(link),
+    }
+}
@@ -0,0 +1,169 @@
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use rusqlite::{Connection, OpenFlags};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::database::Settings;
+
+const DB_ENTRY: &str = "downloads.db";
+const SETTINGS_ENTRY: &str = "settings.json";
+/// Suffix of files a restore stages next to the live ones; swapped in on next launch.
+const STAGED_SUFFIX: &str = ".restore";
+/// Suffix of the files a restore replaced, kept in case it was a mistake.
+const REPLACED_SUFFIX: &str = ".before-restore";
+
+fn live_path(entry: &str) -> PathBuf {
+    crate::libraries::active_dir().join(entry)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut s = path.as_os_str().to_owned();
+    s.push(suffix);
+    PathBuf::from(s)
+}
+
+/// Write `clip-downloader-backup-<timestamp>.zip` with downloads.db and
+/// settings.json of the active library into `dest_dir`.
+pub fn create_backup(dest_dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(dest_dir).map_err(|e| e.to_string())?;
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let zip_path = dest_dir.join(format!("clip-downloader-backup-{stamp}.zip"));
+
+    // VACUUM INTO gives a consistent copy even while downloads write to the live file.
+    let scratch = tempfile::tempdir().map_err(|e| e.to_string())?;
+    let snapshot = scratch.path().join(DB_ENTRY);
+    let conn = Connection::open(live_path(DB_ENTRY)).map_err(|e| e.to_string())?;
+    conn.execute("VACUUM INTO ?1", [snapshot.to_string_lossy()])
+        .map_err(|e| format!("could not snapshot the database: {e}"))?;
+    drop(conn);
+
+    let entries = [
+        (DB_ENTRY, snapshot),
+        (SETTINGS_ENTRY, live_path(SETTINGS_ENTRY)),
+    ];
+    if let Err(e) = write_zip(&zip_path, &entries) {
+        let _ = fs::remove_file(&zip_path);
+        return Err(e);
+    }
+    Ok(zip_path)
+}
+
+fn write_zip(zip_path: &Path, entries: &[(&str, PathBuf)]) -> Result<(), String> {
+    let file = File::create(zip_path).map_err(|e| e.to_string())?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, path) in entries {
+        if !path.is_file() {
+            continue;
+        }
+        zip.start_file(*name, options).map_err(|e| e.to_string())?;
+        let mut src = File::open(path).map_err(|e| e.to_string())?;
+        io::copy(&mut src, &mut zip).map_err(|e| e.to_string())?;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// The database must pass SQLite's own integrity check, look like ours and
+/// not come from a newer build.
+fn verify_database(path: &Path) -> Result<(), String> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("backup database cannot be opened: {e}"))?;
+    let check: String = conn
+        .query_row("PRAGMA integrity_check", [], |r| r.get(0))
+        .map_err(|e| format!("backup database cannot be read: {e}"))?;
+    if check != "ok" {
+        return Err(format!("backup database is damaged: {check}"));
+    }
+    let has_downloads: bool = conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'downloads')",
+            [],
+            |r| r.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    if !has_downloads {
+        return Err("backup database has no downloads table".into());
+    }
+    if !crate::database::schema_is_supported(&conn).map_err(|e| e.to_string())? {
+        return Err("backup was made by a newer version; update the app first".into());
+    }
+    Ok(())
+}
+
+/// Check a backup and stage its files to replace the live ones on the next
+/// launch. Nothing live is touched, and nothing stays staged, if a check fails.
+pub fn stage_restore(zip_path: &Path) -> Result<(), String> {
+    let file = File::open(zip_path).map_err(|e| e.to_string())?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("not a backup archive: {e}"))?;
+
+    let staged_db = with_suffix(&live_path(DB_ENTRY), STAGED_SUFFIX);
+    let staged_settings = with_suffix(&live_path(SETTINGS_ENTRY), STAGED_SUFFIX);
+    let result = (|| {
+        let mut entry = archive
+            .by_name(DB_ENTRY)
+            .map_err(|_| "backup has no downloads.db".to_string())?;
+        let mut out = File::create(&staged_db).map_err(|e| e.to_string())?;
+        io::copy(&mut entry, &mut out).map_err(|e| e.to_string())?;
+        drop(entry);
+        drop(out);
+        verify_database(&staged_db)?;
+
+        if let Ok(mut entry) = archive.by_name(SETTINGS_ENTRY) {
+            let mut text = String::new();
+            entry.read_to_string(&mut text).map_err(|e| e.to_string())?;
+            serde_json::from_str::<Settings>(&text)
+                .map_err(|e| format!("backup settings.json is unreadable: {e}"))?;
+            fs::write(&staged_settings, text).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&staged_db);
+        let _ = fs::remove_file(&staged_settings);
+    }
+    result
+}
+
+/// Swap in files staged by `stage_restore`. Runs at startup, before anything
+/// opens the database, so no connection ever sees the file change under it.
+pub fn apply_pending_restore() {
+    let live_db = live_path(DB_ENTRY);
+    let staged_db = with_suffix(&live_db, STAGED_SUFFIX);
+    if !staged_db.is_file() {
+        return;
+    }
+    let replaced_db = with_suffix(&live_db, REPLACED_SUFFIX);
+    let _ = fs::remove_file(&replaced_db);
+    if live_db.exists() {
+        if let Err(e) = fs::rename(&live_db, &replaced_db) {
+            eprintln!("[BACKEND] restore postponed, cannot move current database: {e}");
+            return;
+        }
+    }
+    // The old write-ahead log belongs to the replaced file.
+    for suffix in ["-wal", "-shm", "-journal"] {
+        let _ = fs::remove_file(with_suffix(&live_db, suffix));
+    }
+    if let Err(e) = fs::rename(&staged_db, &live_db) {
+        eprintln!("[BACKEND] restore failed, keeping current database: {e}");
+        let _ = fs::rename(&replaced_db, &live_db);
+        return;
+    }
+
+    let live_settings = live_path(SETTINGS_ENTRY);
+    let staged_settings = with_suffix(&live_settings, STAGED_SUFFIX);
+    if staged_settings.is_file() {
+        let _ = fs::copy(&live_settings, with_suffix(&live_settings, REPLACED_SUFFIX));
+        if let Err(e) = fs::rename(&staged_settings, &live_settings) {
+            eprintln!("[BACKEND] restored database but not settings.json: {e}");
+        }
+    }
+    println!(
+        "[BACKEND] restored database from backup; previous copy kept as {}",
+        replaced_db.display()
+    );
+}
@@ -0,0 +1,158 @@
+//! Visited links from local browser history databases. Browsers keep these
+//! locked while running, so each one is copied and the copy opened read-only.
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OpenFlags};
+
+use crate::database::Platform;
+use crate::utils::os::home;
+
+/// Microseconds between 1601-01-01 (Chromium's epoch) and the Unix epoch.
+const CHROMIUM_EPOCH_OFFSET_US: i64 = 11_644_473_600_000_000;
+/// Seconds between the Unix epoch and 2001-01-01 (Safari's epoch).
+const SAFARI_EPOCH_OFFSET_S: i64 = 978_307_200;
+
+#[derive(Clone, Copy)]
+enum Engine {
+    Chromium,
+    Firefox,
+    Safari,
+}
+
+/// (label, history database, engine) for every profile found (macOS locations).
+fn history_databases() -> Vec<(String, PathBuf, Engine)> {
+    let h = home();
+    let mut out = Vec::new();
+    for (label, root) in [
+        (
+            "brave",
+            "Library/Application Support/BraveSoftware/Brave-Browser",
+        ),
+        ("chrome", "Library/Application Support/Google/Chrome"),
+    ] {
+        for entry in fs::read_dir(h.join(root)).into_iter().flatten().flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let db = entry.path().join("History");
+            if (name == "Default" || name.starts_with("Profile ")) && db.is_file() {
+                out.push((format!("{label} ({name})"), db, Engine::Chromium));
+            }
+        }
+    }
+    for (label, root) in [
+        ("firefox", "Library/Application Support/Firefox/Profiles"),
+        (
+            "librewolf",
+            "Library/Application Support/librewolf/Profiles",
+        ),
+    ] {
+        for entry in fs::read_dir(h.join(root)).into_iter().flatten().flatten() {
+            let db = entry.path().join("places.sqlite");
+            if db.is_file() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                out.push((format!("{label} ({name})"), db, Engine::Firefox));
+            }
+        }
+    }
+    let safari = h.join("Library/Safari/History.db");
+    if safari.is_file() {
+        out.push(("safari".into(), safari, Engine::Safari));
+    }
+    out
+}
+
+/// Open a throwaway copy of `db` (plus its WAL, which holds recent visits).
+fn open_copy(db: &Path, tmp_dir: &Path) -> Result<Connection, String> {
+    fs::create_dir_all(tmp_dir).map_err(|e| e.to_string())?;
+    let copy = tmp_dir.join(uuid::Uuid::new_v4().to_string());
+    fs::copy(db, &copy).map_err(|e| format!("{}: {e}", db.display()))?;
+    let mut wal = db.as_os_str().to_owned();
+    wal.push("-wal");
+    let mut copy_wal = copy.as_os_str().to_owned();
+    copy_wal.push("-wal");
+    let _ = fs::copy(PathBuf::from(wal), PathBuf::from(copy_wal));
+    Connection::open_with_flags(&copy, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(|e| e.to_string())
+}
+
+fn visited_urls(
+    conn: &Connection,
+    engine: Engine,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> rusqlite::Result<Vec<String>> {
+    let (sql, lo, hi) = match engine {
+        Engine::Chromium => (
+            "SELECT DISTINCT u.url FROM visits v JOIN urls u ON u.id = v.url
+              WHERE v.visit_time BETWEEN ?1 AND ?2",
+            from.timestamp_micros() + CHROMIUM_EPOCH_OFFSET_US,
+            to.timestamp_micros() + CHROMIUM_EPOCH_OFFSET_US,
+        ),
+        Engine::Firefox => (
+            "SELECT DISTINCT p.url FROM moz_historyvisits v JOIN moz_places p ON p.id = v.place_id
+              WHERE v.visit_date BETWEEN ?1 AND ?2",
+            from.timestamp_micros(),
+            to.timestamp_micros(),
+        ),
+        Engine::Safari => (
+            "SELECT DISTINCT i.url FROM history_visits v JOIN history_items i ON i.id = v.history_item
+              WHERE v.visit_time BETWEEN ?1 AND ?2",
+            from.timestamp() - SAFARI_EPOCH_OFFSET_S,
+            to.timestamp() - SAFARI_EPOCH_OFFSET_S,
+        ),
+    };
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([lo, hi], |r| r.get(0))?;
+    rows.collect()
+}
+
+/// A single video/post page of a supported site, not a feed or profile.
+fn is_item_link(url: &str) -> bool {
+    match crate::platforms::detect(url) {
+        Some(d) if d.platform == Platform::Pinterest => url.contains("/pin/"),
+        Some(d) => d.item_id(url).is_some(),
+        None => false,
+    }
+}
+
+/// Links to supported items visited between `from` and `to` in any browser,
+/// without repeats. Unreadable databases are skipped and logged.
+pub fn supported_visits(from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<String> {
+    let tmp_dir = std::env::temp_dir().join("clip-downloader-history");
+    let mut seen = HashSet::new();
+    let mut links = Vec::new();
+    for (label, db, engine) in history_databases() {
+        let urls = open_copy(&db, &tmp_dir)
+            .and_then(|conn| visited_urls(&conn, engine, from, to).map_err(|e| e.to_string()));
+        match urls {
+            Ok(urls) => {
+                println!(
+                    "[BACKEND] [history] {label}: {} visits in range",
+                    urls.len()
+                );
+                for url in urls {
+                    if is_item_link(&url) && seen.insert(url.clone()) {
+                        links.push(url);
+                    }
+                }
+            }
+            Err(e) => eprintln!("[BACKEND] [history] {label} unreadable: {e}"),
+        }
+    }
+    let _ = fs::remove_dir_all(&tmp_dir);
+    links
+}
+
+/// "youtube.com" for grouping proposed rows by site.
+pub fn domain_of(url: &str) -> String {
+    url.split("://")
+        .nth(1)
+        .unwrap_or(url)
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("www.")
+        .trim_start_matches("m.")
+        .to_lowercase()
+}
@@ -0,0 +1,226 @@
+//! Headless subcommands for cron jobs and scripts. Each prints one JSON
+//! object to stdout and exits with:
+//!
+//! - `0` success
+//! - `2` partial failure (some of the given items were not handled)
+//! - `3` configuration or usage error (unreadable settings, bad arguments)
+//! - `1` anything else
+//!
+//! Downloads themselves still run in the app; rows added with `--queue` start
+//! the next time it opens. Windows release builds have no console window, so
+//! redirect stdout there (`> result.json`) to capture the output.
+use std::path::PathBuf;
+
+use serde_json::{json, Value};
+
+use crate::commands::downloader::manual_download;
+use crate::database::{Database, DownloadStatus, OutputFormat};
+
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_FAILURE: i32 = 1;
+pub const EXIT_PARTIAL: i32 = 2;
+pub const EXIT_CONFIG: i32 = 3;
+
+const USAGE: &str = "usage: clip-downloader <status | add [--queue] <url>... | list [--status <status>] | export-stats <dir> [--format csv|json] | backup <dir>>";
+
+/// Outcome of a subcommand: the JSON body and the exit code.
+type Outcome = (Value, i32);
+
+fn fail(code: i32, message: impl Into<String>) -> Outcome {
+    (json!({ "ok": false, "error": message.into() }), code)
+}
+
+fn db() -> Result<Database, Outcome> {
+    Database::new().map_err(|e| fail(EXIT_CONFIG, format!("cannot open the database: {e}")))
+}
+
+/// Run the subcommand named by `args[0]`. `None` when it isn't one, so the
+/// app starts normally (launchers may pass their own arguments).
+pub fn run(args: &[String]) -> Option<i32> {
+    let (cmd, rest) = args.split_first()?;
+    let run: fn(&[String]) -> Outcome = match cmd.as_str() {
+        "status" => status,
+        "add" => add,
+        "list" => list,
+        "export-stats" => export_stats,
+        "backup" => backup,
+        "help" | "--help" | "-h" => |_| (json!({ "ok": true, "usage": USAGE }), EXIT_OK),
+        _ => return None,
+    };
+    let (body, code) = match crate::settings::read_settings_strict() {
+        Ok(_) => run(rest),
+        Err(e) => fail(EXIT_CONFIG, e),
+    };
+    println!("{body}");
+    Some(code)
+}
+
+/// Value of `--name <value>` and the remaining positional arguments.
+fn split_flag(args: &[String], name: &str) -> Result<(Option<String>, Vec<String>), Outcome> {
+    let mut value = None;
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == name {
+            let v = iter
+                .next()
+                .ok_or_else(|| fail(EXIT_CONFIG, format!("{name} needs a value")))?;
+            value = Some(v.clone());
+        } else if arg.starts_with("--") {
+            return Err(fail(EXIT_CONFIG, format!("unknown option {arg}; {USAGE}")));
+        } else {
+            positional.push(arg.clone());
+        }
+    }
+    Ok((value, positional))
+}
+
+fn one_dir(positional: Vec<String>) -> Result<PathBuf, Outcome> {
+    match positional.as_slice() {
+        [dir] => Ok(PathBuf::from(dir)),
+        _ => Err(fail(EXIT_CONFIG, USAGE)),
+    }
+}
+
+fn status(_args: &[String]) -> Outcome {
+    let db = match db() {
+        Ok(db) => db,
+        Err(out) => return out,
+    };
+    match db.count_by_status() {
+        Ok(counts) => (
+            json!({
+                "ok": true,
+                "counts": counts.into_iter().collect::<std::collections::BTreeMap<_, _>>(),
+            }),
+            EXIT_OK,
+        ),
+        Err(e) => fail(EXIT_FAILURE, e.to_string()),
+    }
+}
+
+fn add(args: &[String]) -> Outcome {
+    let queue = args.iter().any(|a| a == "--queue");
+    let urls: Vec<&String> = args.iter().filter(|a| *a != "--queue").collect();
+    if urls.is_empty() {
+        return fail(EXIT_CONFIG, USAGE);
+    }
+    if let Some(bad) = urls.iter().find(|u| u.starts_with("--")) {
+        return fail(EXIT_CONFIG, format!("unknown option {bad}; {USAGE}"));
+    }
+    if let Err(e) = crate::settings::ensure_writable() {
+        return fail(EXIT_CONFIG, e);
+    }
+    let db = match db() {
+        Ok(db) => db,
+        Err(out) => return out,
+    };
+    let status = if queue {
+        DownloadStatus::Queued
+    } else {
+        DownloadStatus::Backlog
+    };
+    let mut added = Vec::new();
+    let mut already_known = Vec::new();
+    let mut failed = Vec::new();
+    for url in urls {
+        let url = url.trim();
+        if crate::platforms::detect(url).is_none() {
+            failed.push(json!({ "url": url, "error": "unsupported site" }));
+            continue;
+        }
+        match db.collection_for_link(url) {
+            Ok(Some(_)) => {
+                already_known.push(url.to_string());
+                continue;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                failed.push(json!({ "url": url, "error": e.to_string() }));
+                continue;
+            }
+        }
+        let row = manual_download(url, OutputFormat::Default, status);
+        match db.insert_download(&row) {
+            Ok(id) => added.push(json!({ "id": id, "url": url })),
+            Err(e) => failed.push(json!({ "url": url, "error": e.to_string() })),
+        }
+    }
+    let code = if failed.is_empty() {
+        EXIT_OK
+    } else {
+        EXIT_PARTIAL
+    };
+    (
+        json!({
+            "ok": failed.is_empty(),
+            "status": status.as_str(),
+            "added": added,
+            "already_known": already_known,
+            "failed": failed,
+        }),
+        code,
+    )
+}
+
+fn list(args: &[String]) -> Outcome {
+    let (wanted, positional) = match split_flag(args, "--status") {
+        Ok(split) => split,
+        Err(out) => return out,
+    };
+    if !positional.is_empty() {
+        return fail(EXIT_CONFIG, USAGE);
+    }
+    let db = match db() {
+        Ok(db) => db,
+        Err(out) => return out,
+    };
+    match db.list_all_ui() {
+        Ok(rows) => {
+            let rows: Vec<_> = rows
+                .into_iter()
+                .filter(|r| {
+                    wanted
+                        .as_deref()
+                        .map_or(true, |s| r.status.as_str().eq_ignore_ascii_case(s))
+                })
+                .collect();
+            (
+                json!({ "ok": true, "count": rows.len(), "items": rows }),
+                EXIT_OK,
+            )
+        }
+        Err(e) => fail(EXIT_FAILURE, e.to_string()),
+    }
+}
+
+fn export_stats(args: &[String]) -> Outcome {
+    let (format, positional) = match split_flag(args, "--format") {
+        Ok(split) => split,
+        Err(out) => return out,
+    };
+    let format = match format.as_deref().map(str::to_ascii_lowercase).as_deref() {
+        None | Some("csv") => crate::stats::ExportFormat::Csv,
+        Some("json") => crate::stats::ExportFormat::Json,
+        Some(other) => return fail(EXIT_CONFIG, format!("unknown format {other}")),
+    };
+    let dir = match one_dir(positional) {
+        Ok(dir) => dir,
+        Err(out) => return out,
+    };
+    match crate::stats::export(&dir, format) {
+        Ok(files) => (json!({ "ok": true, "files": files }), EXIT_OK),
+        Err(e) => fail(EXIT_FAILURE, e),
+    }
+}
+
+fn backup(args: &[String]) -> Outcome {
+    let dir = match one_dir(args.to_vec()) {
+        Ok(dir) => dir,
+        Err(out) => return out,
+    };
+    match crate::backup::create_backup(&dir) {
+        Ok(path) => (json!({ "ok": true, "file": path }), EXIT_OK),
+        Err(e) => fail(EXIT_FAILURE, e),
+    }
+}
@@ -0,0 +1,146 @@
+//! Optional clipboard watcher. Polls the system clipboard and hands links from
+//! supported sites to the backlog or to the UI, per the `clipboard_watch` setting.
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::downloader::manual_download;
+use crate::database::{ClipboardWatch, Database, DownloadStatus, OutputFormat};
+
+/// How often the clipboard is read while watching.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Copying a whole page of links shouldn't flood the backlog.
+const MAX_LINKS: usize = 50;
+
+static MODE: AtomicU8 = AtomicU8::new(ClipboardWatch::Off as u8);
+
+pub fn set_mode(mode: ClipboardWatch) {
+    MODE.store(mode as u8, Ordering::Relaxed);
+}
+
+fn mode() -> ClipboardWatch {
+    match MODE.load(Ordering::Relaxed) {
+        m if m == ClipboardWatch::Ask as u8 => ClipboardWatch::Ask,
+        m if m == ClipboardWatch::Backlog as u8 => ClipboardWatch::Backlog,
+        _ => ClipboardWatch::Off,
+    }
+}
+
+/// Links from supported sites in `text`, in order and without repeats.
+fn supported_links(text: &str) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        let word =
+            word.trim_matches(|c: char| matches!(c, '<' | '>' | '"' | '\'' | '(' | ')' | ','));
+        if !(word.starts_with("https://") || word.starts_with("http://")) {
+            continue;
+        }
+        if crate::platforms::detect(word).is_none() || links.iter().any(|l| l == word) {
+            continue;
+        }
+        links.push(word.to_string());
+        if links.len() == MAX_LINKS {
+            break;
+        }
+    }
+    links
+}
+
+/// The links that have no row yet, in any status.
+pub fn unknown_links(links: Vec<String>) -> Result<Vec<String>, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let mut fresh = Vec::new();
+    for link in links {
+        if db
+            .collection_for_link(&link)
+            .map_err(|e| e.to_string())?
+            .is_none()
+        {
+            fresh.push(link);
+        }
+    }
+    Ok(fresh)
+}
+
+/// Insert `links` as manual backlog rows; returns their ids.
+pub fn add_to_backlog(links: &[String]) -> Result<Vec<i64>, String> {
+    crate::settings::ensure_writable()?;
+    let db = Database::new().map_err(|e| e.to_string())?;
+    links
+        .iter()
+        .map(|link| {
+            let row = manual_download(link, OutputFormat::Default, DownloadStatus::Backlog);
+            db.insert_download(&row).map_err(|e| e.to_string())
+        })
+        .collect()
+}
+
+/// New links go straight to the backlog, or to the UI as an "Add to queue?" prompt.
+fn handle_links(app: &AppHandle, mode: ClipboardWatch, links: Vec<String>) -> Result<(), String> {
+    let fresh = unknown_links(links)?;
+    if fresh.is_empty() {
+        return Ok(());
+    }
+    match mode {
+        ClipboardWatch::Off => {}
+        ClipboardWatch::Ask => {
+            let _ = app.emit("clipboard_links", fresh);
+        }
+        ClipboardWatch::Backlog => {
+            let ids = add_to_backlog(&fresh)?;
+            println!("[BACKEND] added {} copied links to the backlog", ids.len());
+            let _ = app.emit("import_completed", ids.len());
+        }
+    }
+    Ok(())
+}
+
+/// Add the new supported links currently on the clipboard to the backlog,
+/// whatever the watch mode. Returns their ids.
+pub fn add_clipboard_links(app: &AppHandle) -> Result<Vec<i64>, String> {
+    let text = app
+        .state::<tauri_plugin_clipboard::Clipboard>()
+        .read_text()
+        .map_err(|e| e.to_string())?;
+    let fresh = unknown_links(supported_links(&text))?;
+    if fresh.is_empty() {
+        return Ok(Vec::new());
+    }
+    let ids = add_to_backlog(&fresh)?;
+    println!("[BACKEND] added {} pasted links to the backlog", ids.len());
+    let _ = app.emit("import_completed", ids.len());
+    Ok(ids)
+}
+
+/// Poll the clipboard for the lifetime of the app. Whatever is on the
+/// clipboard when watching starts counts as already seen.
+pub async fn run_watcher(app: AppHandle) {
+    let mut last: Option<String> = None;
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        let mode = mode();
+        if mode == ClipboardWatch::Off {
+            last = None;
+            continue;
+        }
+        let Ok(text) = app.state::<tauri_plugin_clipboard::Clipboard>().read_text() else {
+            continue;
+        };
+        match last.replace(text.clone()) {
+            Some(prev) if prev != text => {}
+            _ => continue,
+        }
+        let links = supported_links(&text);
+        if links.is_empty() {
+            continue;
+        }
+        let app = app.clone();
+        let result =
+            tauri::async_runtime::spawn_blocking(move || handle_links(&app, mode, links)).await;
+        if let Ok(Err(e)) = result {
+            eprintln!("[BACKEND] clipboard links not added: {e}");
+        }
+    }
+}
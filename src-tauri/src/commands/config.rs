@@ -0,0 +1,169 @@
+use crate::database::Settings;
+use crate::download::manager::{DownloadCommand, DownloadManager};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tauri::State;
+
+/// Bumped when the bundle layout changes incompatibly.
+const BUNDLE_FORMAT: u32 = 1;
+
+/// Portable app configuration: everything needed to reproduce a setup on another
+/// machine, without media or library rows. Secrets are left out on export.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub format: u32,
+    #[serde(default)]
+    pub app_version: String,
+    #[serde(default)]
+    pub exported_at: String,
+    pub settings: Settings,
+}
+
+/// Save the current configuration to a JSON file the user picks.
+/// Returns the written path, or `None` when the dialog was dismissed.
+#[tauri::command]
+pub async fn export_config(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::{DialogExt, FilePath};
+
+    let mut settings = crate::settings::load_settings();
+    settings.smtp.password.clear();
+    settings.http_api.token.clear();
+    let bundle = ConfigBundle {
+        format: BUNDLE_FORMAT,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        settings,
+    };
+    let json = serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())?;
+
+    let picked = app
+        .dialog()
+        .file()
+        .set_file_name("clip-downloader-config.json")
+        .add_filter("Config", &["json"])
+        .blocking_save_file();
+    let path = match picked {
+        Some(FilePath::Path(p)) => p,
+        Some(FilePath::Url(url)) => return Err(format!("Unsupported URL selection: {url}")),
+        None => return Ok(None),
+    };
+    fs::write(&path, json).map_err(|e| e.to_string())?;
+    println!("[BACKEND] config exported to {}", path.display());
+    Ok(Some(path.to_string_lossy().to_string()))
+}
+
+/// Load a bundle picked by the user, save it as the current settings and apply it.
+/// Machine-specific values (a download directory that doesn't exist here, a blank
+/// SMTP password or HTTP API token) keep their current local value. Returns the applied settings.
+#[tauri::command]
+pub async fn import_config(
+    app: tauri::AppHandle,
+    manager: State<'_, DownloadManager>,
+) -> Result<Option<Settings>, String> {
+    crate::settings::ensure_writable()?;
+    use tauri_plugin_dialog::{DialogExt, FilePath};
+
+    let picked = app
+        .dialog()
+        .file()
+        .add_filter("Config", &["json"])
+        .blocking_pick_file();
+    let path = match picked {
+        Some(FilePath::Path(p)) => p,
+        Some(FilePath::Url(url)) => return Err(format!("Unsupported URL selection: {url}")),
+        None => return Ok(None),
+    };
+    let text = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let bundle: ConfigBundle =
+        serde_json::from_str(&text).map_err(|e| format!("not a config bundle: {e}"))?;
+    if bundle.format > BUNDLE_FORMAT {
+        return Err(format!(
+            "config bundle was made by a newer version ({}); update the app first",
+            bundle.app_version
+        ));
+    }
+
+    let current = crate::settings::load_settings();
+    let mut settings = bundle.settings;
+    settings.id = current.id;
+    if !Path::new(&settings.download_directory).is_dir() {
+        settings.download_directory = current.download_directory;
+    }
+    if settings.smtp.password.is_empty() {
+        settings.smtp.password = current.smtp.password;
+    }
+    if settings.http_api.token.is_empty() {
+        settings.http_api.token = current.http_api.token;
+    }
+
+    crate::settings::save_settings(&settings)?;
+    crate::logging::set_file_logging_enabled(settings.debug_logs);
+    crate::logging::set_redaction_enabled(settings.redact_logs);
+    crate::download::bandwidth::set_limit_kbps(settings.max_download_rate_kbps);
+    crate::clipboard::set_mode(settings.clipboard_watch);
+    crate::http_api::apply(&app, &crate::settings::load_settings().http_api);
+    manager
+        .send(DownloadCommand::RefreshSettings)
+        .await
+        .map_err(|e| e.to_string())?;
+    println!("[BACKEND] config imported from {}", path.display());
+    Ok(Some(crate::settings::load_settings()))
+}
+
+/// Zip the database and settings into a folder the user picks.
+/// Returns the written archive, or `None` when the dialog was dismissed.
+#[tauri::command]
+pub async fn backup_database(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::{DialogExt, FilePath};
+
+    let picked = app.dialog().file().blocking_pick_folder();
+    let dir = match picked {
+        Some(FilePath::Path(p)) => p,
+        Some(FilePath::Url(url)) => return Err(format!("Unsupported URL folder: {url}")),
+        None => return Ok(None),
+    };
+    let zip = tauri::async_runtime::spawn_blocking(move || crate::backup::create_backup(&dir))
+        .await
+        .map_err(|e| format!("Join error: {e}"))??;
+    println!("[BACKEND] backup written to {}", zip.display());
+    Ok(Some(zip.to_string_lossy().to_string()))
+}
+
+/// Restore a backup the user picks: it is checked first, then swapped in by
+/// restarting the app. Refused while downloads run. Returns `false` when the
+/// dialog was dismissed.
+#[tauri::command]
+pub async fn restore_database(app: tauri::AppHandle) -> Result<bool, String> {
+    crate::settings::ensure_writable()?;
+    use tauri_plugin_dialog::{DialogExt, FilePath};
+
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    if !db
+        .list_downloading_ids()
+        .map_err(|e| e.to_string())?
+        .is_empty()
+    {
+        return Err("wait for active downloads to finish before restoring a backup".into());
+    }
+    let picked = app
+        .dialog()
+        .file()
+        .add_filter("Backup", &["zip"])
+        .blocking_pick_file();
+    let path = match picked {
+        Some(FilePath::Path(p)) => p,
+        Some(FilePath::Url(url)) => return Err(format!("Unsupported URL selection: {url}")),
+        None => return Ok(false),
+    };
+    let staged = path.clone();
+    tauri::async_runtime::spawn_blocking(move || crate::backup::stage_restore(&staged))
+        .await
+        .map_err(|e| format!("Join error: {e}"))??;
+    println!(
+        "[BACKEND] backup {} verified; restarting to restore it",
+        path.display()
+    );
+    crate::session::complete_session();
+    app.restart();
+}
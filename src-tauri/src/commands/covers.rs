@@ -0,0 +1,146 @@
+use crate::database::Database;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::icons::{data_url, mime_for};
+
+const IMAGE_EXTS: &[&str] = &["jpg", "jpeg", "png", "webp", "gif"];
+/// Covers are inlined as data URLs; skip anything unreasonably large.
+const MAX_COVER_BYTES: u64 = 8 * 1024 * 1024;
+/// How many recent downloads to look through when auto-picking.
+const AUTO_PICK_SCAN: usize = 20;
+
+fn covers_cache_dir() -> PathBuf {
+    crate::libraries::active_dir().join("covers")
+}
+
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| IMAGE_EXTS.contains(&e.to_lowercase().as_str()))
+}
+
+/// An image download itself, or a thumbnail saved next to a video (same stem).
+fn thumbnail_for(path: &Path) -> Option<PathBuf> {
+    if !path.is_file() {
+        return None;
+    }
+    if is_image(path) {
+        return Some(path.to_path_buf());
+    }
+    IMAGE_EXTS
+        .iter()
+        .map(|ext| path.with_extension(ext))
+        .find(|p| p.is_file())
+}
+
+fn read_cover(path: &Path) -> Option<String> {
+    let len = fs::metadata(path).ok()?.len();
+    if len == 0 || len > MAX_COVER_BYTES {
+        return None;
+    }
+    let bytes = fs::read(path).ok()?;
+    Some(data_url(&bytes, mime_for(&bytes)))
+}
+
+/// Cover for a collection as a `data:` URL: the user's pick if set and still
+/// on disk, otherwise the newest downloaded image or video thumbnail.
+#[tauri::command]
+pub async fn get_collection_cover(
+    platform: String,
+    handle: String,
+    origin: String,
+) -> Result<Option<String>, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    if let Some(custom) = db
+        .collection_cover(&platform, &handle, &origin)
+        .map_err(|e| e.to_string())?
+    {
+        if let Some(url) = read_cover(Path::new(&custom)) {
+            return Ok(Some(url));
+        }
+    }
+    let paths = db
+        .newest_done_paths_in_collection(&platform, &handle, &origin, AUTO_PICK_SCAN)
+        .map_err(|e| e.to_string())?;
+    Ok(paths
+        .iter()
+        .filter_map(|p| thumbnail_for(Path::new(p)))
+        .find_map(|p| read_cover(&p)))
+}
+
+/// Cached preview of one download as a `data:` URL, for the Library grid.
+#[tauri::command]
+pub async fn get_download_thumbnail(id: i64) -> Result<Option<String>, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    Ok(db
+        .thumbnail_path(id)
+        .map_err(|e| e.to_string())?
+        .and_then(|p| read_cover(Path::new(&p))))
+}
+
+/// Let the user pick an image, copy it into the covers cache and use it for the collection.
+/// Returns the new cover, or `None` when the dialog was dismissed.
+#[tauri::command]
+pub async fn pick_collection_cover(
+    app: tauri::AppHandle,
+    platform: String,
+    handle: String,
+    origin: String,
+) -> Result<Option<String>, String> {
+    crate::settings::ensure_writable()?;
+    use tauri_plugin_dialog::{DialogExt, FilePath};
+
+    let picked = app
+        .dialog()
+        .file()
+        .add_filter("Images", IMAGE_EXTS)
+        .blocking_pick_file();
+    let src = match picked {
+        Some(FilePath::Path(p)) => p,
+        Some(FilePath::Url(url)) => return Err(format!("Unsupported URL selection: {url}")),
+        None => return Ok(None),
+    };
+
+    let dir = covers_cache_dir();
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let ext = src
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("jpg")
+        .to_lowercase();
+    let dest = dir.join(format!("{}.{ext}", uuid::Uuid::new_v4()));
+    fs::copy(&src, &dest).map_err(|e| format!("failed to copy cover: {e}"))?;
+
+    let db = Database::new().map_err(|e| e.to_string())?;
+    if let Some(old) = db
+        .collection_cover(&platform, &handle, &origin)
+        .map_err(|e| e.to_string())?
+    {
+        let _ = fs::remove_file(old);
+    }
+    db.set_collection_cover(&platform, &handle, &origin, &dest.to_string_lossy())
+        .map_err(|e| e.to_string())?;
+    println!("[BACKEND] cover set for {platform}/{handle}/{origin}");
+    Ok(read_cover(&dest))
+}
+
+/// Drop the user's cover so the collection falls back to auto-pick.
+#[tauri::command]
+pub async fn clear_collection_cover(
+    platform: String,
+    handle: String,
+    origin: String,
+) -> Result<(), String> {
+    crate::settings::ensure_writable()?;
+    let db = Database::new().map_err(|e| e.to_string())?;
+    if let Some(old) = db
+        .collection_cover(&platform, &handle, &origin)
+        .map_err(|e| e.to_string())?
+    {
+        let _ = fs::remove_file(old);
+    }
+    db.clear_collection_cover(&platform, &handle, &origin)
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -1,32 +1,36 @@
 use crate::database::{
-    Database, Download, DownloadStatus, MediaKind, Origin, OutputFormat, Platform,
+    ContentType, Database, DateRange, Download, DownloadAttempt, DownloadStatus, FailureKind,
+    MediaKind, OutputFormat, Platform, ScheduleStatus, StorageStatus, UrlBatchReport, VolumeStatus,
 };
-use crate::download::manager::{DownloadCommand, DownloadManager, DownloadOverrides};
+use crate::download::events::EventHub;
+use crate::download::manager::{
+    DownloadCommand, DownloadEvent, DownloadManager, DownloadOverrides,
+};
+use crate::maintenance::{MaintenanceTasks, TaskHandle};
 use chrono::Utc;
+use tauri::ipc::Channel;
 use tauri::State;
 
+/// Home page entry point: find or insert the row for `url`, then hand it to the
+/// download manager like any other queued row.
 #[tauri::command]
 pub async fn download_url(
     manager: State<'_, DownloadManager>,
     url: String,
     output_format: Option<String>,
-    outputFormat: Option<String>,
     flat_destination: Option<bool>,
-    flatDestination: Option<bool>,
 ) -> Result<i64, String> {
-    let force_audio =
-        output_format
-            .or(outputFormat)
-            .and_then(|fmt| match fmt.to_lowercase().as_str() {
-                "audio" => Some(true),
-                "video" => Some(false),
-                _ => None,
-            });
-    let flat = flat_destination.or(flatDestination).unwrap_or(false);
-
-    let cleaned_url = sanitize_url(&url);
+    crate::settings::ensure_writable()?;
+    let force_audio = output_format.and_then(|fmt| match fmt.to_lowercase().as_str() {
+        "audio" => Some(true),
+        "video" => Some(false),
+        _ => None,
+    });
+    let flat = flat_destination.unwrap_or(false);
+
+    let cleaned_url = url.trim().to_string();
     let lookup_url = cleaned_url.clone();
-    let force_audio_clone = force_audio.clone();
+    let force_audio_clone = force_audio;
     let (row_id, created) = tauri::async_runtime::spawn_blocking(move || {
         ensure_row_for_url(&lookup_url, force_audio_clone)
     })
@@ -46,6 +50,8 @@ pub async fn download_url(
             overrides: Some(DownloadOverrides {
                 force_audio,
                 flat_destination: flat,
+                resume: false,
+                overwrite: false,
             }),
         })
         .await?;
@@ -53,23 +59,513 @@ pub async fn download_url(
     Ok(row_id)
 }
 
+/// Home page entry point for profile, channel and playlist links: list their
+/// items in the background and add each one to the backlog under that
+/// collection. Returns the maintenance task id so the page can follow progress.
+/// `date_range` limits it to items published in that window.
+#[tauri::command]
+pub async fn expand_collection_url(
+    app: tauri::AppHandle,
+    tasks: State<'_, MaintenanceTasks>,
+    url: String,
+    date_range: Option<DateRange>,
+) -> Result<u32, String> {
+    let range = date_range.unwrap_or_default();
+    crate::settings::ensure_writable()?;
+    let url = url.trim().to_string();
+    let platform = infer_platform(&url);
+    let Some(handle) = clip_core::url::collection_handle(platform, &url) else {
+        return Err("not a profile, channel or playlist link".into());
+    };
+    let origin = clip_core::url::infer_origin(platform, &url).unwrap_or(ContentType::Profile);
+    let task_app = app.clone();
+    tasks.spawn(
+        &app,
+        "expand_collection",
+        format!("List {handle} ({})", origin.as_str()),
+        true,
+        move |task| expand_collection(&task_app, task, &url, &range, platform, origin, &handle),
+    )
+}
+
+fn expand_collection(
+    app: &tauri::AppHandle,
+    task: &TaskHandle,
+    url: &str,
+    range: &DateRange,
+    platform: Platform,
+    origin: ContentType,
+    handle: &str,
+) -> Result<String, String> {
+    use tauri::Emitter;
+    let settings = crate::settings::load_settings();
+    let browsers = crate::settings::cookie_sources(&settings, url, None);
+    let Some((_, cookie_arg)) = browsers.first() else {
+        return Err("No logged-in browsers detected for cookies.".into());
+    };
+    task.progress(0, None, format!("Listing {url}"));
+    let links = tauri::async_runtime::block_on(crate::download::video::list_collection_entries(
+        app,
+        cookie_arg,
+        url,
+        range,
+        || !task.is_canceled(),
+        |found| task.progress(found as u64, None, format!("Found {found} items")),
+    ))?;
+
+    let added = add_collection_rows(task, &links, platform, origin, handle)?;
+    if added > 0 {
+        let _ = app.emit("import_completed", added);
+    }
+    println!(
+        "[BACKEND] expanded {url}: {added} new of {} items",
+        links.len()
+    );
+    Ok(format!(
+        "Added {added} of {} items from {handle} to the backlog",
+        links.len()
+    ))
+}
+
+/// List a playlist link's videos in the background and add one backlog row
+/// per video, with origin `Playlist` and the playlist title as the handle.
+/// A backlog row for the playlist link itself is removed once expanded.
+#[tauri::command]
+pub async fn expand_playlist(
+    app: tauri::AppHandle,
+    tasks: State<'_, MaintenanceTasks>,
+    url: String,
+    date_range: Option<DateRange>,
+) -> Result<u32, String> {
+    let range = date_range.unwrap_or_default();
+    crate::settings::ensure_writable()?;
+    let url = url.trim().to_string();
+    let platform = infer_platform(&url);
+    if clip_core::url::infer_origin(platform, &url) != Some(ContentType::Playlist) {
+        return Err("not a playlist link".into());
+    }
+    let task_app = app.clone();
+    tasks.spawn(
+        &app,
+        "expand_playlist",
+        format!("Expand playlist {url}"),
+        true,
+        move |task| expand_playlist_rows(&task_app, task, &url, &range, platform),
+    )
+}
+
+fn expand_playlist_rows(
+    app: &tauri::AppHandle,
+    task: &TaskHandle,
+    url: &str,
+    range: &DateRange,
+    platform: Platform,
+) -> Result<String, String> {
+    use tauri::Emitter;
+    let settings = crate::settings::load_settings();
+    let browsers = crate::settings::cookie_sources(&settings, url, None);
+    let Some((_, cookie_arg)) = browsers.first() else {
+        return Err("No logged-in browsers detected for cookies.".into());
+    };
+    task.progress(0, None, format!("Listing {url}"));
+    let (title, links) = tauri::async_runtime::block_on(crate::download::video::list_playlist(
+        app, cookie_arg, url, range,
+    ))?;
+    if task.is_canceled() {
+        return Ok("Canceled before adding any videos".into());
+    }
+    let handle = title
+        .or_else(|| clip_core::url::collection_handle(platform, url))
+        .unwrap_or_else(|| "Unknown".into());
+    let added = add_collection_rows(task, &links, platform, ContentType::Playlist, &handle)?;
+    if !task.is_canceled() {
+        let db = Database::new().map_err(|e| e.to_string())?;
+        db.delete_backlog_link(url).map_err(|e| e.to_string())?;
+    }
+    let _ = app.emit("import_completed", added);
+    println!(
+        "[BACKEND] expanded playlist {url}: {added} new of {} videos",
+        links.len()
+    );
+    Ok(format!(
+        "Added {added} of {} videos from \"{handle}\" to the backlog",
+        links.len()
+    ))
+}
+
+/// Add `links` to the backlog as one collection; links that already have a row
+/// are skipped. Returns how many rows were added.
+fn add_collection_rows(
+    task: &TaskHandle,
+    links: &[String],
+    platform: Platform,
+    origin: ContentType,
+    handle: &str,
+) -> Result<usize, String> {
+    let total = links.len() as u64;
+    let added = insert_collection_links(links, platform, origin, handle, |i, link| {
+        if task.is_canceled() {
+            return false;
+        }
+        task.progress(i as u64, Some(total), format!("Adding {link}"));
+        true
+    })?;
+    Ok(added.len())
+}
+
+/// Insert backlog rows for the links that don't have a row yet (compared
+/// normalized, trashed rows aside) and return their ids. `keep_going` sees
+/// each link before it is handled and stops the loop by returning `false`.
+pub(crate) fn insert_collection_links(
+    links: &[String],
+    platform: Platform,
+    origin: ContentType,
+    handle: &str,
+    mut keep_going: impl FnMut(usize, &str) -> bool,
+) -> Result<Vec<i64>, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let mut added = Vec::new();
+    for (i, link) in links.iter().enumerate() {
+        if !keep_going(i, link) {
+            break;
+        }
+        if db
+            .find_id_by_normalized_link(link)
+            .map_err(|e| e.to_string())?
+            .is_some()
+        {
+            continue;
+        }
+        let download = Download {
+            platform,
+            user: handle.to_string(),
+            origin,
+            media: infer_media(link),
+            image_set_id: clip_core::url::image_set_id(platform, link),
+            ..manual_download(link, OutputFormat::Default, DownloadStatus::Backlog)
+        };
+        added.push(db.insert_download(&download).map_err(|e| e.to_string())?);
+    }
+    Ok(added)
+}
+
 #[tauri::command]
 pub async fn cancel_download(manager: State<'_, DownloadManager>, id: i64) -> Result<(), String> {
+    crate::settings::ensure_writable()?;
     manager
         .send(DownloadCommand::Cancel { id })
         .await
         .map_err(|e| e.to_string())
 }
 
+/// Queue rows by id. Rows already queued or downloading are left alone;
+/// returns the ids that were actually queued.
 #[tauri::command]
 pub async fn enqueue_downloads(
     manager: State<'_, DownloadManager>,
     ids: Vec<i64>,
-) -> Result<(), String> {
+) -> Result<Vec<i64>, String> {
+    crate::settings::ensure_writable()?;
+    let (reply, rx) = tokio::sync::oneshot::channel();
     manager
-        .send(DownloadCommand::Enqueue { ids })
+        .send(DownloadCommand::Enqueue {
+            ids,
+            reply: Some(reply),
+        })
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    rx.await.map_err(|e| e.to_string())
+}
+
+/// Queue every failed row of a collection again, optionally only those whose
+/// error falls into one of `kinds`. Returns the ids that were queued.
+#[tauri::command]
+pub async fn retry_failed_collection(
+    manager: State<'_, DownloadManager>,
+    platform: String,
+    handle: String,
+    origin: String,
+    kinds: Option<Vec<FailureKind>>,
+) -> Result<Vec<i64>, String> {
+    crate::settings::ensure_writable()?;
+    let failed = tauri::async_runtime::spawn_blocking(move || {
+        let db = Database::new().map_err(|e| e.to_string())?;
+        db.list_collection_errors(&platform, &handle, &origin)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("Join error: {e}"))??;
+    let ids: Vec<i64> = failed
+        .into_iter()
+        .filter(|(_, err)| {
+            kinds.as_ref().map_or(true, |kinds| {
+                kinds.contains(&FailureKind::classify(err.as_deref()))
+            })
+        })
+        .map(|(id, _)| id)
+        .collect();
+    if ids.is_empty() {
+        return Ok(ids);
+    }
+    let (reply, rx) = tokio::sync::oneshot::channel();
+    manager
+        .send(DownloadCommand::Enqueue {
+            ids,
+            reply: Some(reply),
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    rx.await.map_err(|e| e.to_string())
+}
+
+/// Queue the copied links the user accepted from the clipboard prompt; links
+/// that gained a row in the meantime are skipped. Returns the queued ids.
+#[tauri::command]
+pub async fn queue_copied_links(
+    app: tauri::AppHandle,
+    manager: State<'_, DownloadManager>,
+    links: Vec<String>,
+) -> Result<Vec<i64>, String> {
+    use tauri::Emitter;
+    crate::settings::ensure_writable()?;
+    let ids = tauri::async_runtime::spawn_blocking(move || {
+        crate::clipboard::add_to_backlog(&crate::clipboard::unknown_links(links)?)
+    })
+    .await
+    .map_err(|e| format!("Join error: {e}"))??;
+    if ids.is_empty() {
+        return Ok(ids);
+    }
+    let _ = app.emit("import_completed", ids.len());
+    let (reply, rx) = tokio::sync::oneshot::channel();
+    manager
+        .send(DownloadCommand::Enqueue {
+            ids,
+            reply: Some(reply),
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    rx.await.map_err(|e| e.to_string())
+}
+
+/// Home page batch box: one link per line (blank lines and `#` comments are
+/// ignored). New links get a manual row; with `queue` they, and known links
+/// still waiting in the backlog, go straight to the download queue.
+#[tauri::command]
+pub async fn download_urls(
+    app: tauri::AppHandle,
+    manager: State<'_, DownloadManager>,
+    text: String,
+    queue: bool,
+) -> Result<UrlBatchReport, String> {
+    add_and_queue_urls(&app, &manager, text, queue).await
+}
+
+/// Shared by the paste box and dropped link lists: see `download_urls`.
+pub(crate) async fn add_and_queue_urls(
+    app: &tauri::AppHandle,
+    manager: &DownloadManager,
+    text: String,
+    queue: bool,
+) -> Result<UrlBatchReport, String> {
+    use tauri::Emitter;
+    crate::settings::ensure_writable()?;
+    let (mut report, waiting) =
+        tauri::async_runtime::spawn_blocking(move || add_url_batch(&text, queue))
+            .await
+            .map_err(|e| format!("Join error: {e}"))??;
+    if !report.added.is_empty() {
+        let _ = app.emit("import_completed", report.added.len());
+    }
+    if queue && !waiting.is_empty() {
+        let (reply, rx) = tokio::sync::oneshot::channel();
+        manager
+            .send(DownloadCommand::Enqueue {
+                ids: waiting,
+                reply: Some(reply),
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+        report.queued = rx.await.map_err(|e| e.to_string())?.len() as u64;
+    }
+    println!(
+        "[BACKEND] url batch: {} added, {} known, {} queued, {} invalid",
+        report.added.len(),
+        report.known,
+        report.queued,
+        report.invalid.len()
+    );
+    Ok(report)
+}
+
+/// Insert the new links of a batch; also returns the ids to queue.
+fn add_url_batch(text: &str, queue: bool) -> Result<(UrlBatchReport, Vec<i64>), String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let mut report = UrlBatchReport::default();
+    let mut waiting = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let valid = (line.starts_with("https://") || line.starts_with("http://"))
+            && !line.contains(char::is_whitespace)
+            && crate::platforms::detect(line).is_some();
+        if !valid {
+            report.invalid.push(line.to_string());
+            continue;
+        }
+        if !seen.insert(crate::database::normalize_link(line.to_string())) {
+            continue;
+        }
+        if let Some(id) = db
+            .find_id_by_normalized_link(line)
+            .map_err(|e| e.to_string())?
+        {
+            report.known += 1;
+            if !queue {
+                continue;
+            }
+            let in_backlog = db
+                .find_download_by_id(id)
+                .map_err(|e| e.to_string())?
+                .is_some_and(|row| row.status == DownloadStatus::Backlog);
+            if in_backlog {
+                waiting.push(id);
+            }
+            continue;
+        }
+        let row = manual_download(line, OutputFormat::Default, DownloadStatus::Backlog);
+        let id = db.insert_download(&row).map_err(|e| e.to_string())?;
+        report.added.push(id);
+        waiting.push(id);
+    }
+    Ok((report, waiting))
+}
+
+/// Switch a removed row to the mirror found for it and queue it again.
+#[tauri::command]
+pub async fn use_mirror(
+    app: tauri::AppHandle,
+    manager: State<'_, DownloadManager>,
+    id: i64,
+) -> Result<Vec<i64>, String> {
+    use tauri::Emitter;
+    crate::settings::ensure_writable()?;
+    let switched = tauri::async_runtime::spawn_blocking(move || {
+        let db = Database::new().map_err(|e| e.to_string())?;
+        db.use_mirror(id).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("Join error: {e}"))??;
+    if !switched {
+        return Err("No mirror found for this download".into());
+    }
+    let _ = app.emit("import_completed", 1);
+    let (reply, rx) = tokio::sync::oneshot::channel();
+    manager
+        .send(DownloadCommand::Enqueue {
+            ids: vec![id],
+            reply: Some(reply),
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    rx.await.map_err(|e| e.to_string())
+}
+
+/// Queue the finished or failed row for `link` to be downloaded again, e.g.
+/// to replace a corrupted or low-quality file. `overwrite` replaces the old
+/// file for this one job instead of following the duplicate setting. Returns
+/// the queued id, or `None` when it was already queued or running.
+#[tauri::command]
+pub async fn requeue_link(
+    manager: State<'_, DownloadManager>,
+    link: String,
+    overwrite: Option<bool>,
+) -> Result<Option<i64>, String> {
+    crate::settings::ensure_writable()?;
+    let lookup = link.clone();
+    let id = tauri::async_runtime::spawn_blocking(move || {
+        let db = Database::new().map_err(|e| e.to_string())?;
+        db.find_redownload_row(&lookup).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("Join error: {e}"))??
+    .ok_or_else(|| format!("Nothing to download again for {link}"))?;
+    let (reply, rx) = tokio::sync::oneshot::channel();
+    manager
+        .send(DownloadCommand::Redownload {
+            id,
+            overwrite: overwrite.unwrap_or(false),
+            reply: Some(reply),
+        })
+        .await?;
+    let queued = rx.await.map_err(|e| e.to_string())?;
+    println!("[BACKEND] requeued {link} (row {id})");
+    Ok(queued.first().copied())
+}
+
+/// Queue `ids` into a folder the user picks, bypassing the collection folder
+/// template for just these items. Returns the folder, or `None` when the
+/// dialog was dismissed.
+#[tauri::command]
+pub async fn enqueue_to_folder(
+    app: tauri::AppHandle,
+    manager: State<'_, DownloadManager>,
+    ids: Vec<i64>,
+) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::{DialogExt, FilePath};
+
+    crate::settings::ensure_writable()?;
+    let dir = match app.dialog().file().blocking_pick_folder() {
+        Some(FilePath::Path(p)) => p.to_string_lossy().to_string(),
+        Some(FilePath::Url(url)) => return Err(format!("Unsupported URL folder: {url}")),
+        None => return Ok(None),
+    };
+    let (set_ids, set_dir) = (ids.clone(), dir.clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = Database::new().map_err(|e| e.to_string())?;
+        db.set_destination_dir(&set_ids, Some(&set_dir))
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("Join error: {e}"))??;
+    println!("[BACKEND] queueing {} rows into {dir}", ids.len());
+    manager
+        .send(DownloadCommand::Enqueue { ids, reply: None })
+        .await?;
+    Ok(Some(dir))
+}
+
+/// Queue every backlog row added after `session` (RFC 3339), defaulting to the
+/// end of the last completed session. Returns how many rows were queued.
+#[tauri::command]
+pub async fn enqueue_new_since(
+    manager: State<'_, DownloadManager>,
+    last: State<'_, crate::session::LastSession>,
+    session: Option<String>,
+) -> Result<u64, String> {
+    crate::settings::ensure_writable()?;
+    let since = session
+        .map(|s| {
+            chrono::DateTime::parse_from_rfc3339(&s)
+                .map(|d| d.with_timezone(&Utc))
+                .map_err(|e| format!("invalid session timestamp: {e}"))
+        })
+        .transpose()?;
+    let ids = crate::session::backlog_ids_added_since(&last, since)?;
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let (reply, rx) = tokio::sync::oneshot::channel();
+    manager
+        .send(DownloadCommand::Enqueue {
+            ids,
+            reply: Some(reply),
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rx.await.map_err(|e| e.to_string())?.len() as u64)
 }
 
 #[tauri::command]
@@ -77,12 +573,117 @@ pub async fn move_downloads_to_backlog(
     manager: State<'_, DownloadManager>,
     ids: Vec<i64>,
 ) -> Result<(), String> {
+    crate::settings::ensure_writable()?;
+    // A one-off destination only applies to the queueing it was picked for.
+    let clear_ids = ids.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = Database::new().map_err(|e| e.to_string())?;
+        db.set_destination_dir(&clear_ids, None)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("Join error: {e}"))??;
     manager
         .send(DownloadCommand::MoveToBacklog { ids })
         .await
         .map_err(|e| e.to_string())
 }
 
+/// Change the download rate cap (kbit/s, 0 = unlimited) until the next settings
+/// save or restart. Direct downloads pick it up immediately, yt-dlp and
+/// gallery-dl from their next download.
+#[tauri::command]
+pub async fn set_bandwidth_limit(kbps: u32) -> Result<(), String> {
+    crate::download::bandwidth::set_limit_kbps(kbps);
+    println!("[BACKEND] bandwidth limit set to {kbps} kbit/s");
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_bandwidth_limit() -> Result<u32, String> {
+    Ok(crate::download::bandwidth::limit_kbps())
+}
+
+/// Current state of the download windows, for the "Scheduled: starts at …" hint.
+#[tauri::command]
+pub async fn download_schedule_status() -> Result<ScheduleStatus, String> {
+    let schedule =
+        crate::download::schedule::Schedule::from_settings(&crate::settings::load_settings());
+    Ok(schedule.status(chrono::Local::now()))
+}
+
+/// Whether the drive holding the download folder is connected.
+#[tauri::command]
+pub async fn download_volume_status() -> Result<VolumeStatus, String> {
+    let settings = crate::settings::load_settings();
+    Ok(crate::download::volume::status(std::path::Path::new(
+        &settings.download_directory,
+    )))
+}
+
+/// Free space and reachability of the download folder's drive.
+#[tauri::command]
+pub async fn get_storage_status() -> Result<StorageStatus, String> {
+    let settings = crate::settings::load_settings();
+    let root = std::path::PathBuf::from(settings.download_directory);
+    tauri::async_runtime::spawn_blocking(move || crate::download::volume::storage_status(&root))
+        .await
+        .map_err(|e| format!("Join error: {e}"))
+}
+
+/// Explicit queue priority for a row (higher starts sooner; 0 = default).
+#[tauri::command]
+pub async fn set_download_priority(
+    manager: State<'_, DownloadManager>,
+    id: i64,
+    priority: i64,
+) -> Result<(), String> {
+    crate::settings::ensure_writable()?;
+    manager
+        .send(DownloadCommand::SetPriority { id, priority })
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Move a queued row just before (or after, with `after`) another queued row.
+#[tauri::command]
+pub async fn move_queued_download(
+    manager: State<'_, DownloadManager>,
+    id: i64,
+    anchor: i64,
+    after: bool,
+) -> Result<(), String> {
+    crate::settings::ensure_writable()?;
+    let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
+    manager
+        .send(DownloadCommand::ReorderQueue {
+            id,
+            anchor,
+            after,
+            reply: reply_tx,
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    reply_rx
+        .await
+        .map_err(|e| format!("reorder channel closed: {e}"))?
+}
+
+/// Attach a per-page channel that receives every `DownloadEvent`; returns the subscription id.
+#[tauri::command]
+pub async fn subscribe_download_events(
+    hub: State<'_, EventHub>,
+    channel: Channel<DownloadEvent>,
+) -> Result<u32, String> {
+    Ok(hub.subscribe(channel))
+}
+
+#[tauri::command]
+pub async fn unsubscribe_download_events(hub: State<'_, EventHub>, id: u32) -> Result<(), String> {
+    hub.unsubscribe(id);
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn set_download_paused(
     manager: State<'_, DownloadManager>,
@@ -94,6 +695,34 @@ pub async fn set_download_paused(
         .map_err(|e| e.to_string())
 }
 
+/// Recorded tool runs of one download, newest first.
+#[tauri::command]
+pub async fn get_download_history(id: i64) -> Result<Vec<DownloadAttempt>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = Database::new().map_err(|e| e.to_string())?;
+        db.list_download_attempts(id).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("Join error: {e}"))?
+}
+
+/// Pause one download; its partial files stay for `resume_download`.
+#[tauri::command]
+pub async fn pause_download(manager: State<'_, DownloadManager>, id: i64) -> Result<(), String> {
+    manager
+        .send(DownloadCommand::PauseDownload { id })
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn resume_download(manager: State<'_, DownloadManager>, id: i64) -> Result<(), String> {
+    manager
+        .send(DownloadCommand::ResumeDownload { id })
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn refresh_download_settings(manager: State<'_, DownloadManager>) -> Result<(), String> {
     manager
@@ -110,10 +739,32 @@ pub async fn reconcile_downloads(manager: State<'_, DownloadManager>) -> Result<
         .map_err(|e| e.to_string())
 }
 
+/// Platforms currently held back because most of their recent downloads failed.
+#[tauri::command]
+pub async fn paused_platforms(
+    manager: State<'_, DownloadManager>,
+) -> Result<Vec<crate::database::PlatformPause>, String> {
+    let (reply, rx) = tokio::sync::oneshot::channel();
+    manager
+        .send(DownloadCommand::PausedPlatforms { reply })
+        .await?;
+    rx.await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn resume_platform(
+    manager: State<'_, DownloadManager>,
+    platform: String,
+) -> Result<(), String> {
+    manager
+        .send(DownloadCommand::ResumePlatform { platform })
+        .await
+}
+
 #[tauri::command]
 pub async fn refresh_downloads_snapshot(
     manager: State<'_, DownloadManager>,
-) -> Result<Vec<crate::database::UiBacklogRow>, String> {
+) -> Result<Vec<crate::database::ClipRow>, String> {
     let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
     manager
         .send(DownloadCommand::RefreshSnapshot { reply: reply_tx })
@@ -124,62 +775,54 @@ pub async fn refresh_downloads_snapshot(
         .map_err(|e| format!("snapshot channel closed: {e}"))?
 }
 
-fn sanitize_url(raw: &str) -> String {
-    raw.trim()
-        .replace("#__audio_only__", "")
-        .replace("#__flat__", "")
-}
-
 fn ensure_row_for_url(url: &str, force_audio: Option<bool>) -> Result<(i64, bool), String> {
     let db = Database::new().map_err(|e| e.to_string())?;
-    if let Some(id) = db.find_id_by_link(url).map_err(|e| e.to_string())? {
+    if let Some(id) = db
+        .find_id_by_normalized_link(url)
+        .map_err(|e| e.to_string())?
+    {
         return Ok((id, false));
     }
 
-    let platform = infer_platform(url);
-    let media_kind = infer_media(url);
     let output = match force_audio {
         Some(true) => OutputFormat::Audio,
         Some(false) => OutputFormat::Video,
         None => OutputFormat::Default,
     };
+    let download = manual_download(url, output, DownloadStatus::Queued);
+    let id = db.insert_download(&download).map_err(|e| e.to_string())?;
+    Ok((id, true))
+}
 
-    let download = Download {
+/// A row for a link added by hand rather than from an import.
+pub(crate) fn manual_download(url: &str, output: OutputFormat, status: DownloadStatus) -> Download {
+    let platform = infer_platform(url);
+    Download {
         id: None,
         platform,
         name: url.to_string(),
-        media: media_kind,
+        media: infer_media(url),
         user: "Unknown".into(),
-        origin: Origin::Manual,
+        origin: ContentType::Manual,
         link: url.to_string(),
         output_format: output,
-        status: DownloadStatus::Queued,
+        status,
         path: "unknown_path".into(),
-        image_set_id: None,
+        image_set_id: clip_core::url::image_set_id(platform, url),
         last_error: None,
         date_added: Utc::now(),
         date_downloaded: None,
-    };
-    let id = db.insert_download(&download).map_err(|e| e.to_string())?;
-    Ok((id, true))
+    }
 }
 
 fn infer_platform(url: &str) -> Platform {
-    if url.contains("instagram.com") {
-        Platform::Instagram
-    } else if url.contains("tiktok.com") {
-        Platform::Tiktok
-    } else if url.contains("pinterest.com") || url.contains("pin.it") {
-        Platform::Pinterest
-    } else {
-        Platform::Youtube
-    }
+    crate::platforms::detect(url)
+        .map(|d| d.platform)
+        .unwrap_or(Platform::Youtube)
 }
 
 fn infer_media(url: &str) -> MediaKind {
-    if url.contains("/photo/") || url.contains("pinterest.com") {
-        MediaKind::Image
-    } else {
-        MediaKind::Video
-    }
+    crate::platforms::detect(url)
+        .map(|d| d.media_for(url))
+        .unwrap_or(MediaKind::Video)
 }
@@ -1,7 +1,12 @@
 use std::fs as std_fs;
 
+use crate::database::CsvMapping;
+
 #[tauri::command]
-pub async fn pick_csv_and_read(app: tauri::AppHandle) -> Result<String, String> {
+pub async fn pick_csv_and_read(
+    app: tauri::AppHandle,
+    review: Option<bool>,
+) -> Result<String, String> {
     use tauri::Emitter;
     println!("[BACKEND] [commands/files.rs] [pick_csv_and_read]");
     use tauri_plugin_dialog::{DialogExt, FilePath};
@@ -21,10 +26,16 @@ pub async fn pick_csv_and_read(app: tauri::AppHandle) -> Result<String, String>
 
     match file_path {
         FilePath::Path(path_buf) => {
-            let csv_text = std_fs::read_to_string(path_buf).map_err(|e| e.to_string())?;
+            let csv_text = std_fs::read_to_string(&path_buf).map_err(|e| e.to_string())?;
 
-            let result = super::import::import_csv_text(csv_text.clone()).await;
-            let n = result.as_ref().copied().unwrap_or(0);
+            let result = super::import::import_csv_text(
+                csv_text.clone(),
+                review.unwrap_or(false),
+                Some(&path_buf),
+                None,
+            )
+            .await;
+            let n = result.as_ref().map_or(0, |s| s.inserted);
             println!("[BACKEND] [files] imported {n} rows (picker)");
             let _ = app.emit("import_completed", n);
             if let Err(e) = result {
@@ -38,7 +49,12 @@ pub async fn pick_csv_and_read(app: tauri::AppHandle) -> Result<String, String>
 }
 
 #[tauri::command]
-pub async fn read_csv_from_path(app: tauri::AppHandle, path: String) -> Result<String, String> {
+pub async fn read_csv_from_path(
+    app: tauri::AppHandle,
+    path: String,
+    review: Option<bool>,
+    mapping: Option<CsvMapping>,
+) -> Result<String, String> {
     use tauri::Emitter;
     println!(
         "[BACKEND] [commands/files.rs] [read_csv_from_path] {}",
@@ -47,8 +63,14 @@ pub async fn read_csv_from_path(app: tauri::AppHandle, path: String) -> Result<S
 
     let csv_text = std_fs::read_to_string(&path).map_err(|e| e.to_string())?;
 
-    let result = super::import::import_csv_text(csv_text.clone()).await;
-    let n = result.as_ref().copied().unwrap_or(0);
+    let result = super::import::import_csv_text(
+        csv_text.clone(),
+        review.unwrap_or(false),
+        Some(std::path::Path::new(&path)),
+        mapping,
+    )
+    .await;
+    let n = result.as_ref().map_or(0, |s| s.inserted);
     println!("[BACKEND] [files] imported {n} rows (drag-drop) from {path}");
     let _ = app.emit("import_completed", n);
     if let Err(e) = result {
@@ -57,6 +79,18 @@ pub async fn read_csv_from_path(app: tauri::AppHandle, path: String) -> Result<S
     Ok(csv_text)
 }
 
+/// A dropped `.txt` list, one link per line, sent to the download queue.
+#[tauri::command]
+pub async fn read_links_from_path(
+    app: tauri::AppHandle,
+    manager: tauri::State<'_, crate::download::manager::DownloadManager>,
+    path: String,
+) -> Result<crate::database::UrlBatchReport, String> {
+    println!("[BACKEND] [files] link list dropped: {path}");
+    let text = std_fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    super::downloader::add_and_queue_urls(&app, &manager, text, true).await
+}
+
 #[tauri::command]
 pub async fn pick_directory(app: tauri::AppHandle) -> Result<String, String> {
     use tauri_plugin_dialog::{DialogExt, FilePath}; // ← bring FilePath into scope
@@ -0,0 +1,98 @@
+use base64::Engine;
+use reqwest::header::CONTENT_TYPE;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Largest favicon we keep; anything bigger is not an icon.
+const MAX_ICON_BYTES: usize = 256 * 1024;
+
+fn favicon_cache_dir() -> PathBuf {
+    crate::settings::app_support_dir().join("favicons")
+}
+
+pub(crate) fn data_url(bytes: &[u8], mime: &str) -> String {
+    format!(
+        "data:{mime};base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    )
+}
+
+/// Sniff the image formats we serve back as data URLs (favicons, covers).
+pub(crate) fn mime_for(bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(b"\x89PNG") {
+        "image/png"
+    } else if bytes.starts_with(b"GIF8") {
+        "image/gif"
+    } else if bytes.starts_with(b"\xFF\xD8") {
+        "image/jpeg"
+    } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        "image/webp"
+    } else if bytes.starts_with(b"<svg") || bytes.starts_with(b"<?xml") {
+        "image/svg+xml"
+    } else {
+        "image/x-icon"
+    }
+}
+
+async fn fetch_favicon(domain: &str) -> Result<Option<Vec<u8>>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let resp = client
+        .get(format!("https://{domain}/favicon.ico"))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Ok(None);
+    }
+    let is_image = resp
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map_or(true, |ct| ct.starts_with("image/"));
+    if !is_image {
+        return Ok(None);
+    }
+    let bytes = resp.bytes().await.map_err(|e| e.to_string())?;
+    if bytes.is_empty() || bytes.len() > MAX_ICON_BYTES {
+        return Ok(None);
+    }
+    Ok(Some(bytes.to_vec()))
+}
+
+/// Favicon for a site as a `data:` URL, fetched once and cached under the app
+/// config dir. An empty cache file records "no icon" so the site isn't retried.
+#[tauri::command]
+pub async fn get_platform_icon(domain: String) -> Result<Option<String>, String> {
+    let Some(domain) = clip_core::url::host(&domain) else {
+        return Ok(None);
+    };
+    if !domain
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+    {
+        return Ok(None);
+    }
+
+    let dir = favicon_cache_dir();
+    let cached = dir.join(format!("{domain}.ico"));
+    if let Ok(bytes) = fs::read(&cached) {
+        return Ok((!bytes.is_empty()).then(|| data_url(&bytes, mime_for(&bytes))));
+    }
+
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let fetched = match fetch_favicon(&domain).await {
+        Ok(v) => v,
+        Err(e) => {
+            // Network trouble is not cached; try again next launch.
+            println!("[BACKEND] favicon fetch failed for {domain}: {e}");
+            return Ok(None);
+        }
+    };
+    let bytes = fetched.unwrap_or_default();
+    fs::write(&cached, &bytes).map_err(|e| e.to_string())?;
+    Ok((!bytes.is_empty()).then(|| data_url(&bytes, mime_for(&bytes))))
+}
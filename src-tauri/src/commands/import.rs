@@ -1,42 +1,197 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::database::{
+    CsvMapping, CsvPreview, Database, ImportDuplicate, ImportRecord, ImportSummary,
+};
+use crate::download::manager::{DownloadCommand, DownloadManager};
+
 /// Import a CSV (as text) and add all rows into the DB with status=Backlog.
 /// This is the core function that processes CSV files imported via "Import list" button or drag-and-drop.
-/// Expected CSV header format: Platform,Type,Handle,Media,link
+/// Columns follow `mapping`, or are guessed from the header names when it is
+/// `None`; the classic layout is Platform,Type,Handle,Media,link:
 /// - Platform: "instagram", "tiktok", or "youtube"
 /// - Type: content source like "recommendation", "playlist", "profile", "bookmarks", etc.
 /// - Handle: username or channel name
 /// - Media: "Pictures" or "Video"
 /// - link: the URL to download from
 ///
-/// All imported items are stored in the database with status "Backlog" for later downloading.
-/// Returns the number of successfully imported rows.
+/// All imported items are stored in the database with status "Backlog" for later downloading,
+/// or "Pending" when `review` is set so they can be approved per collection first.
+/// With `check_links_on_import`, links answering 404/410 are stored as "Invalid" instead.
+/// Links that already have a row are handled per `on_import_duplicate`.
+/// Every call is recorded in the `imports` table so it can be re-run or rolled back.
+/// Returns how many rows were inserted, moved or skipped.
 
 #[tauri::command]
 pub async fn import_csv_to_db(
     csv_text: Option<String>,
     csvText: Option<String>,
-) -> Result<u64, String> {
+    review: Option<bool>,
+    mapping: Option<CsvMapping>,
+) -> Result<ImportSummary, String> {
     // Accept both snake_case and camelCase keys from JS.
     let csv_text = csv_text
         .or(csvText)
         .ok_or_else(|| "missing argument: csv_text/csvText".to_string())?;
 
-    import_csv_text(csv_text).await
+    import_csv_text(csv_text, review.unwrap_or(false), None, mapping).await
 }
 
-pub async fn import_csv_text(csv_text: String) -> Result<u64, String> {
-    println!("[BACKEND] [commands/import.rs] [import_csv_to_db]");
+/// Rows shown in a CSV preview.
+const PREVIEW_ROWS: usize = 5;
 
-    let mut rdr = csv::ReaderBuilder::new()
+/// Header names accepted for each field, lowercase.
+const PLATFORM_HEADERS: &[&str] = &["platform", "site", "service"];
+const ORIGIN_HEADERS: &[&str] = &["type", "origin", "source", "collection"];
+const HANDLE_HEADERS: &[&str] = &["handle", "user", "username", "channel", "author", "creator"];
+const MEDIA_HEADERS: &[&str] = &["media", "kind", "media type"];
+const LINK_HEADERS: &[&str] = &["link", "url", "href", "address"];
+
+fn csv_reader(csv_text: &str) -> csv::Reader<&[u8]> {
+    csv::ReaderBuilder::new()
         .trim(csv::Trim::All)
-        .has_headers(true)
-        .from_reader(csv_text.as_bytes());
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(csv_text.as_bytes())
+}
+
+fn looks_like_link(cell: &str) -> bool {
+    cell.contains("://") || cell.starts_with("www.")
+}
 
-    // Initialize database connection
-    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
-    let mut inserted: u64 = 0;
+/// Guess the mapping from the first line: a line without any link is a
+/// header row, matched by name; unknown names are ignored. Without names,
+/// a five-column file is read in the classic order and anything else only
+/// for its link column.
+fn detect_mapping(first: &csv::StringRecord) -> CsvMapping {
+    let cells: Vec<String> = first.iter().map(|c| c.trim().to_lowercase()).collect();
+    let classic = CsvMapping {
+        header_row: false,
+        platform: Some(0),
+        origin: Some(1),
+        handle: Some(2),
+        media: Some(3),
+        link: Some(4),
+    };
+    if cells.iter().any(|c| looks_like_link(c)) {
+        let link = cells.iter().position(|c| looks_like_link(c));
+        return if cells.len() >= 5 && link == Some(4) {
+            classic
+        } else {
+            CsvMapping {
+                link,
+                ..CsvMapping::default()
+            }
+        };
+    }
+    let find = |names: &[&str]| cells.iter().position(|c| names.contains(&c.as_str()));
+    let mapping = CsvMapping {
+        header_row: true,
+        platform: find(PLATFORM_HEADERS),
+        origin: find(ORIGIN_HEADERS),
+        handle: find(HANDLE_HEADERS),
+        media: find(MEDIA_HEADERS),
+        link: find(LINK_HEADERS),
+    };
+    if mapping.link.is_none() && cells.len() >= 5 {
+        // unfamiliar names: fall back to the classic order
+        return CsvMapping {
+            header_row: true,
+            ..classic
+        };
+    }
+    mapping
+}
+
+/// Read the CSV at `path` (or one picked in a dialog) without importing it:
+/// its columns, the guessed mapping and a few rows.
+#[tauri::command]
+pub async fn preview_csv(
+    app: tauri::AppHandle,
+    path: Option<String>,
+) -> Result<CsvPreview, String> {
+    use tauri_plugin_dialog::{DialogExt, FilePath};
+    let path = match path {
+        Some(path) => path,
+        None => {
+            let mut builder = app.dialog().file();
+            if let Some(home) = dirs::home_dir() {
+                builder = builder.set_directory(home);
+            }
+            match builder.add_filter("CSV", &["csv"]).blocking_pick_file() {
+                Some(FilePath::Path(p)) => p.display().to_string(),
+                Some(FilePath::Url(url)) => {
+                    return Err(format!("Unsupported URL selection: {url}"))
+                }
+                None => return Err("No file selected".into()),
+            }
+        }
+    };
+    let csv_text = std::fs::read_to_string(&path).map_err(|e| format!("{path}: {e}"))?;
+    let mut records = csv_reader(&csv_text).into_records().filter_map(Result::ok);
+    let first = records.next().ok_or_else(|| format!("{path} is empty"))?;
+    let mapping = detect_mapping(&first);
+    let mut sample: Vec<Vec<String>> = Vec::new();
+    if !mapping.header_row {
+        sample.push(first.iter().map(str::to_string).collect());
+    }
+    sample.extend(
+        records
+            .take(PREVIEW_ROWS - sample.len())
+            .map(|r| r.iter().map(str::to_string).collect()),
+    );
+    let width = sample
+        .iter()
+        .map(Vec::len)
+        .chain([first.len()])
+        .max()
+        .unwrap_or(0);
+    let headers = (0..width)
+        .map(|i| match first.get(i) {
+            Some(name) if mapping.header_row && !name.is_empty() => name.to_string(),
+            _ => format!("Column {}", i + 1),
+        })
+        .collect();
+    println!("[BACKEND] [import] previewed {path}: {mapping:?}");
+    Ok(CsvPreview {
+        path,
+        headers,
+        mapping,
+        sample,
+    })
+}
+
+/// `source` is the file the CSV was read from, if any.
+pub async fn import_csv_text(
+    csv_text: String,
+    review: bool,
+    source: Option<&Path>,
+    mapping: Option<CsvMapping>,
+) -> Result<ImportSummary, String> {
+    crate::settings::ensure_writable()?;
+    println!("[BACKEND] [commands/import.rs] [import_csv_to_db]");
+
+    let mut records = csv_reader(&csv_text).into_records().peekable();
+    let mapping = match mapping {
+        Some(mapping) => mapping,
+        None => match records.peek() {
+            Some(Ok(first)) => detect_mapping(first),
+            _ => CsvMapping::default(),
+        },
+    };
+    if mapping.link.is_none() {
+        return Err("no column is mapped to the link".into());
+    }
+    if mapping.header_row {
+        records.next();
+    }
+
+    let mut candidates: Vec<crate::database::Download> = Vec::new();
+    let status = import_status(review);
 
     // Process each row
-    for rec in rdr.records() {
+    for rec in records {
         let rec = match rec {
             Ok(r) => r,
             Err(e) => {
@@ -45,65 +200,81 @@ pub async fn import_csv_text(csv_text: String) -> Result<u64, String> {
             }
         };
 
-        // Extract CSV columns (Platform,Type,Handle,Media,link)
-        let platform_s = rec.get(0).unwrap_or("").to_string();
-        let typ_s = rec.get(1).unwrap_or("").to_lowercase();
-        let mut handle = rec.get(2).unwrap_or("Unknown").to_string();
-        let media_s = rec.get(3).unwrap_or("").to_string();
-        let link = rec.get(4).unwrap_or("").to_string();
+        // Extract the mapped columns; extra ones are ignored
+        let cell = |i: Option<usize>| i.and_then(|i| rec.get(i)).unwrap_or("");
+        let platform_s = cell(mapping.platform).to_string();
+        let typ_s = cell(mapping.origin).to_lowercase();
+        let mut handle = cell(mapping.handle).to_string();
+        let media_s = cell(mapping.media).to_string();
+        let link = cell(mapping.link).to_string();
         if link.is_empty() {
             continue;
         }
 
-        let platform = crate::database::Platform::from(platform_s.clone());
-        let media = crate::database::MediaKind::from(media_s);
+        let detected = crate::platforms::detect(&link);
+        let platform = match crate::database::Platform::from(platform_s.as_str()) {
+            crate::database::Platform::Other if platform_s.is_empty() => detected
+                .map(|d| d.platform)
+                .unwrap_or(crate::database::Platform::Other),
+            platform => platform,
+        };
+        let media = match (media_s.is_empty(), detected) {
+            (true, Some(d)) => d.media_for(&link),
+            _ => crate::database::MediaKind::from(media_s.as_str()),
+        };
 
-        // Determine origin; special-case Pinterest "{user} - {something}" to pinboard
-        let origin = if platform_s.eq_ignore_ascii_case("pinterest") {
-            let is_pinboard = handle.contains(" - ");
+        // Determine origin; special-case Pinterest "{user} - {something}" to pinboard.
+        // A missing/"other" Type falls back to what the URL itself says.
+        let inferred = clip_core::url::infer_origin(platform, &link);
+        let origin = if platform == crate::database::Platform::Pinterest {
+            let is_pinboard =
+                handle.contains(" - ") || inferred == Some(crate::database::ContentType::Pinboard);
             if is_pinboard {
-                crate::database::Origin::Pinboard
+                crate::database::ContentType::Pinboard
             } else {
-                crate::database::Origin::Profile
+                crate::database::ContentType::Profile
             }
         } else {
             match typ_s.as_str() {
-                "recommendation" => crate::database::Origin::Recommendation,
-                "playlist" => crate::database::Origin::Playlist,
-                "profile" => crate::database::Origin::Profile,
-                "bookmarks" => crate::database::Origin::Bookmarks,
-                "liked" | "reposts" => crate::database::Origin::Other,
-                _ => crate::database::Origin::Other,
+                "recommendation" => crate::database::ContentType::Recommendation,
+                "playlist" => crate::database::ContentType::Playlist,
+                "profile" => crate::database::ContentType::Profile,
+                "bookmarks" => crate::database::ContentType::Bookmarks,
+                "liked" | "reposts" => crate::database::ContentType::Other,
+                _ => inferred.unwrap_or(crate::database::ContentType::Other),
             }
         };
 
         // Derive a sensible name from the URL per platform
-        let name = if link.contains("instagram.com/") {
-            if let (_, Some(id)) = super::parse::ig_handle_and_id(&link) {
-                id
-            } else {
-                super::parse::last_segment(&link).unwrap_or_else(|| "Unknown".into())
-            }
-        } else if link.contains("tiktok.com/") {
-            super::parse::tiktok_id_from_url(&link)
-                .or_else(|| super::parse::last_segment(&link))
-                .unwrap_or_else(|| "Unknown".into())
-        } else if link.contains("youtube.com/") || link.contains("youtu.be/") {
-            super::parse::youtube_id_from_url(&link)
-                .or_else(|| super::parse::last_segment(&link))
-                .unwrap_or_else(|| "Unknown".into())
-        } else if link.contains("pinterest.com/") || link.contains("pin.it/") {
-            super::parse::last_segment(&link).unwrap_or_else(|| "Unknown".into())
-        } else {
-            super::parse::last_segment(&link).unwrap_or_else(|| "Unknown".into())
-        };
+        let name = crate::platforms::detect(&link)
+            .and_then(|d| d.item_id(&link))
+            .or_else(|| clip_core::url::last_segment(&link))
+            .unwrap_or_else(|| "Unknown".into());
 
-        // Fill in IG handle if missing
-        if (handle.is_empty() || handle == "Unknown") && link.contains("instagram.com/") {
-            if let (Some(h), _) = super::parse::ig_handle_and_id(&link) {
+        // Fill in IG / X / Reddit handle if missing
+        if (handle.is_empty() || handle == "Unknown")
+            && crate::platforms::detect(&link)
+                .is_some_and(|d| d.platform == crate::database::Platform::Instagram)
+        {
+            if let (Some(h), _) = clip_core::url::ig_handle_and_id(&link) {
                 handle = h;
             }
         }
+        if (handle.is_empty() || handle == "Unknown")
+            && platform == crate::database::Platform::Twitter
+        {
+            if let (Some(h), _) = clip_core::url::twitter_handle_and_id(&link) {
+                handle = h;
+            }
+        }
+        if (handle.is_empty() || handle == "Unknown")
+            && platform == crate::database::Platform::Reddit
+        {
+            if let (Some(h), _) = clip_core::url::reddit_handle_and_id(&link) {
+                handle = h;
+            }
+        }
+        let image_set_id = clip_core::url::image_set_id(platform, &link);
 
         // Normalize empty handles to "Unknown" so the UI queue callback
         // (which displays "Unknown") matches the stored value.
@@ -111,16 +282,7 @@ pub async fn import_csv_text(csv_text: String) -> Result<u64, String> {
             handle = "Unknown".into();
         }
 
-        let platform_token = format!("{:?}", platform).to_lowercase();
-        let origin_token = format!("{:?}", origin).to_lowercase();
-        if db
-            .link_exists_in_collection(&link, &platform_token, &handle, &origin_token)
-            .unwrap_or(false)
-        {
-            continue;
-        }
-
-        let download = crate::database::Download {
+        candidates.push(crate::database::Download {
             id: None,
             platform,
             name,
@@ -129,18 +291,365 @@ pub async fn import_csv_text(csv_text: String) -> Result<u64, String> {
             origin,
             link,
             output_format: crate::database::OutputFormat::Default,
-            status: crate::database::DownloadStatus::Backlog,
+            status,
             path: String::new(),
-            image_set_id: None,
+            image_set_id,
             last_error: None,
             date_added: chrono::Utc::now(),
             date_downloaded: None,
-        };
+        });
+    }
+
+    import_downloads(candidates, review, source).await
+}
+
+/// Status of freshly imported rows.
+fn import_status(review: bool) -> crate::database::DownloadStatus {
+    if review {
+        crate::database::DownloadStatus::Pending
+    } else {
+        crate::database::DownloadStatus::Backlog
+    }
+}
+
+/// Store parsed import rows: duplicates are handled per `on_import_duplicate`,
+/// dead links flagged when `check_links_on_import` is on, and the import is
+/// recorded in the history.
+async fn import_downloads(
+    candidates: Vec<crate::database::Download>,
+    review: bool,
+    source: Option<&Path>,
+) -> Result<ImportSummary, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let mut created_ids: Vec<i64> = Vec::new();
+    let mut rows: Vec<crate::database::Download> = Vec::new();
+    let mut seen: HashSet<(String, String, String, String)> = HashSet::new();
+    // Normalized link -> existing rows; links from this file join with no ids.
+    let mut known = db.link_index().map_err(|e| e.to_string())?;
+    let policy = crate::settings::load_settings().on_import_duplicate;
+    let mut summary = ImportSummary::default();
+
+    for download in candidates {
+        let link = download.link.clone();
+        let handle = download.user.clone();
+        let platform_token = download.platform.as_str().to_string();
+        let origin_token = download.origin.as_str().to_string();
+        if db
+            .link_exists_in_collection(&link, &platform_token, &handle, &origin_token)
+            .unwrap_or(false)
+            || !seen.insert((
+                link.clone(),
+                platform_token.clone(),
+                handle.clone(),
+                origin_token.clone(),
+            ))
+        {
+            summary.skipped += 1;
+            continue;
+        }
+        let key = crate::database::normalize_link(link.clone());
+        if let Some(existing) = known.get(&key) {
+            match policy {
+                ImportDuplicate::Skip => {
+                    summary.skipped += 1;
+                    continue;
+                }
+                ImportDuplicate::UpdateCollection => {
+                    let moved = db
+                        .move_rows_to_collection(existing, &platform_token, &handle, &origin_token)
+                        .map_err(|e| e.to_string())?;
+                    if moved > 0 {
+                        summary.updated += moved as u64;
+                    } else {
+                        summary.skipped += 1;
+                    }
+                    continue;
+                }
+                ImportDuplicate::InsertAnyway => {}
+            }
+        }
+        known.entry(key).or_default();
 
-        if db.insert_download(&download).is_ok() {
-            inserted += 1;
+        rows.push(download);
+    }
+
+    // Optional link health check: dead links are kept (so the import can be
+    // rolled back as a whole) but stored as Invalid instead of backlog rows.
+    if crate::settings::load_settings().check_links_on_import && !rows.is_empty() {
+        let links = rows.iter().map(|d| d.link.clone()).collect();
+        let dead = crate::download::http::dead_links(links).await;
+        for download in rows.iter_mut() {
+            if let Some(code) = dead.get(&download.link) {
+                download.status = crate::database::DownloadStatus::Invalid;
+                download.last_error = Some(format!("Link returned HTTP {code} on import"));
+            }
+        }
+    }
+
+    let mut invalid_count = 0u32;
+    for download in &rows {
+        if let Ok(id) = db.insert_download(download) {
+            created_ids.push(id);
+            if download.status == crate::database::DownloadStatus::Invalid {
+                invalid_count += 1;
+            }
         }
     }
+    if invalid_count > 0 {
+        println!("[BACKEND] [commands/import.rs] {invalid_count} dead link(s) flagged invalid");
+    }
+
+    summary.inserted = created_ids.len() as u64;
+    if summary.skipped + summary.updated > 0 {
+        println!(
+            "[BACKEND] [commands/import.rs] duplicates: {} skipped, {} moved",
+            summary.skipped, summary.updated
+        );
+    }
+    let record = ImportRecord {
+        id: 0,
+        source_name: source
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Pasted list".into()),
+        source_path: source.map(|p| p.to_string_lossy().to_string()),
+        imported_at: chrono::Utc::now().to_rfc3339(),
+        row_count: created_ids.len() as u32,
+        invalid_count,
+        created_ids,
+        review,
+        rolled_back_at: None,
+        skipped_count: summary.skipped as u32,
+        updated_count: summary.updated as u32,
+    };
+    if let Err(e) = db.insert_import(&record) {
+        eprintln!("[import] failed to record import history: {e}");
+    }
+
+    Ok(summary)
+}
+
+/* --------------------------------- takeout -------------------------------- */
+
+/// Import a platform data export (YouTube Takeout history or playlist CSV,
+/// TikTok data JSON) from `path`, or one picked in a dialog. Liked lists get
+/// the `liked` origin, Watch later and TikTok favorites `bookmarks`.
+#[tauri::command]
+pub async fn import_takeout(
+    app: tauri::AppHandle,
+    path: Option<String>,
+    review: Option<bool>,
+) -> Result<ImportSummary, String> {
+    use tauri::Emitter;
+    use tauri_plugin_dialog::{DialogExt, FilePath};
+    crate::settings::ensure_writable()?;
+    let path = match path {
+        Some(path) => path,
+        None => {
+            let mut builder = app.dialog().file();
+            if let Some(home) = dirs::home_dir() {
+                builder = builder.set_directory(home);
+            }
+            match builder
+                .add_filter("Takeout", &["json", "csv"])
+                .blocking_pick_file()
+            {
+                Some(FilePath::Path(p)) => p.display().to_string(),
+                Some(FilePath::Url(url)) => {
+                    return Err(format!("Unsupported URL selection: {url}"))
+                }
+                None => return Err("No file selected".into()),
+            }
+        }
+    };
+    let text = std::fs::read_to_string(&path).map_err(|e| format!("{path}: {e}"))?;
+    let entries = crate::takeout::parse(Path::new(&path), &text)
+        .ok_or_else(|| "not a YouTube Takeout or TikTok export file".to_string())??;
+    let summary =
+        import_takeout_entries(entries, review.unwrap_or(false), Path::new(&path)).await?;
+    println!(
+        "[BACKEND] [import] takeout {path}: {} new, {} skipped",
+        summary.inserted, summary.skipped
+    );
+    let _ = app.emit("import_completed", summary.inserted);
+    Ok(summary)
+}
+
+async fn import_takeout_entries(
+    entries: Vec<crate::takeout::TakeoutEntry>,
+    review: bool,
+    source: &Path,
+) -> Result<ImportSummary, String> {
+    let status = import_status(review);
+    let candidates = entries
+        .into_iter()
+        .map(|e| crate::database::Download {
+            platform: e.platform,
+            user: e.handle,
+            origin: e.origin,
+            ..super::downloader::manual_download(
+                &e.link,
+                crate::database::OutputFormat::Default,
+                status,
+            )
+        })
+        .collect();
+    import_downloads(candidates, review, Some(source)).await
+}
+
+/* ----------------------------- browser history ---------------------------- */
+
+/// Propose the supported links visited between `from` and `to` (YYYY-MM-DD,
+/// both inclusive) in any local browser as Pending rows, one collection per
+/// site, so they can be reviewed before joining the backlog. Returns how many
+/// rows were proposed.
+#[tauri::command]
+pub async fn import_browser_history(
+    app: tauri::AppHandle,
+    from: String,
+    to: String,
+) -> Result<u64, String> {
+    use crate::database::{ContentType, DownloadStatus, OutputFormat};
+    use tauri::Emitter;
+    crate::settings::ensure_writable()?;
+
+    let parse = |s: &str| {
+        chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").map_err(|e| format!("{s}: {e}"))
+    };
+    let start = parse(&from)?
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc();
+    let end = parse(&to)?
+        .and_hms_opt(23, 59, 59)
+        .unwrap_or_default()
+        .and_utc();
+    if end < start {
+        return Err("the end date is before the start date".into());
+    }
+
+    let created_ids = tauri::async_runtime::spawn_blocking(move || {
+        let links = crate::browser_history::supported_visits(start, end);
+        let db = Database::new().map_err(|e| e.to_string())?;
+        let mut ids = Vec::new();
+        for link in &links {
+            if db
+                .find_id_by_normalized_link(link)
+                .map_err(|e| e.to_string())?
+                .is_some()
+            {
+                continue;
+            }
+            let download = crate::database::Download {
+                user: crate::browser_history::domain_of(link),
+                origin: ContentType::History,
+                ..super::downloader::manual_download(
+                    link,
+                    OutputFormat::Default,
+                    DownloadStatus::Pending,
+                )
+            };
+            ids.push(db.insert_download(&download).map_err(|e| e.to_string())?);
+        }
+        let record = ImportRecord {
+            id: 0,
+            source_name: format!("Browser history {from} – {to}"),
+            source_path: None,
+            imported_at: chrono::Utc::now().to_rfc3339(),
+            row_count: ids.len() as u32,
+            invalid_count: 0,
+            created_ids: ids.clone(),
+            review: true,
+            rolled_back_at: None,
+            skipped_count: 0,
+            updated_count: 0,
+        };
+        if let Err(e) = db.insert_import(&record) {
+            eprintln!("[import] failed to record import history: {e}");
+        }
+        Ok::<_, String>(ids)
+    })
+    .await
+    .map_err(|e| format!("Join error: {e}"))??;
+
+    let n = created_ids.len() as u64;
+    println!("[BACKEND] [import] proposed {n} links from browser history");
+    let _ = app.emit("import_completed", n);
+    Ok(n)
+}
 
-    Ok(inserted)
+/* ----------------------------- import history ----------------------------- */
+
+#[tauri::command]
+pub async fn list_imports(limit: Option<usize>) -> Result<Vec<ImportRecord>, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    db.list_imports(limit.unwrap_or(20))
+        .map_err(|e| e.to_string())
+}
+
+/// Read an import's source file again; rows that still exist are skipped as usual.
+#[tauri::command]
+pub async fn rerun_import(app: tauri::AppHandle, id: i64) -> Result<ImportSummary, String> {
+    use tauri::Emitter;
+    crate::settings::ensure_writable()?;
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let record = db
+        .find_import(id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("import {id} not found"))?;
+    let path = record
+        .source_path
+        .ok_or_else(|| "this import has no source file to re-read".to_string())?;
+    let text = std::fs::read_to_string(&path).map_err(|e| format!("{path}: {e}"))?;
+    let summary = match crate::takeout::parse(Path::new(&path), &text) {
+        Some(entries) => import_takeout_entries(entries?, record.review, Path::new(&path)).await?,
+        None => import_csv_text(text, record.review, Some(Path::new(&path)), None).await?,
+    };
+    println!(
+        "[BACKEND] [import] re-ran import {id}: {} new rows from {path}",
+        summary.inserted
+    );
+    let _ = app.emit("import_completed", summary.inserted);
+    Ok(summary)
+}
+
+/// Delete exactly the rows an import created (files on disk are left alone).
+#[tauri::command]
+pub async fn rollback_import(
+    app: tauri::AppHandle,
+    manager: tauri::State<'_, DownloadManager>,
+    id: i64,
+) -> Result<u64, String> {
+    use tauri::Emitter;
+    crate::settings::ensure_writable()?;
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let record = db
+        .find_import(id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("import {id} not found"))?;
+    if record.rolled_back_at.is_some() {
+        return Err("this import was already rolled back".into());
+    }
+    for row_id in &record.created_ids {
+        if let Some(row) = db.find_download_by_id(*row_id).map_err(|e| e.to_string())? {
+            if row.status == crate::database::DownloadStatus::Downloading {
+                return Err("some rows from this import are downloading; cancel them first".into());
+            }
+        }
+    }
+    // Take them out of the manager's queue before the rows disappear.
+    manager
+        .send(DownloadCommand::MoveToBacklog {
+            ids: record.created_ids.clone(),
+        })
+        .await?;
+    let mut deleted: u64 = 0;
+    for row_id in &record.created_ids {
+        deleted += db.delete_row_by_id(*row_id).map_err(|e| e.to_string())? as u64;
+    }
+    db.mark_import_rolled_back(id, chrono::Utc::now())
+        .map_err(|e| e.to_string())?;
+    println!("[BACKEND] [import] rolled back import {id}: {deleted} rows deleted");
+    let _ = app.emit("import_completed", 0u64);
+    Ok(deleted)
 }
@@ -0,0 +1,37 @@
+use crate::database::Database;
+use crate::libraries::{LibraryEntry, LibraryIndex};
+
+#[tauri::command]
+pub async fn list_libraries() -> Result<LibraryIndex, String> {
+    Ok(crate::libraries::load_index())
+}
+
+#[tauri::command]
+pub async fn create_library(
+    name: String,
+    download_directory: Option<String>,
+) -> Result<LibraryEntry, String> {
+    crate::settings::ensure_writable()?;
+    crate::libraries::create_library(&name, download_directory)
+}
+
+/// Switch to another library by restarting the app on it; refused while downloads run.
+#[tauri::command]
+pub async fn switch_library(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    crate::settings::ensure_writable()?;
+    if crate::libraries::load_index().active == id {
+        return Ok(());
+    }
+    let db = Database::new().map_err(|e| e.to_string())?;
+    if !db
+        .list_downloading_ids()
+        .map_err(|e| e.to_string())?
+        .is_empty()
+    {
+        return Err("wait for active downloads to finish before switching libraries".into());
+    }
+    crate::libraries::set_active(&id)?;
+    println!("[BACKEND] switching to library {id}; restarting");
+    crate::session::complete_session();
+    app.restart();
+}
@@ -1,7 +1,13 @@
-use std::path::PathBuf;
+use crate::database::{CollectionInfo, Database, FilesystemKind, VolumeStatus};
+use crate::maintenance::{MaintenanceTasks, TaskHandle};
+use crate::utils::delete::remove_media_file;
+use crate::utils::filenames::FilenameRules;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 fn open_with_default_app(path: &str) -> Result<(), String> {
+    let path = &crate::utils::paths::plain(std::path::Path::new(path));
     #[cfg(target_os = "windows")]
     {
         // Use PowerShell to avoid `start` quoting quirks
@@ -30,7 +36,8 @@ fn open_with_default_app(path: &str) -> Result<(), String> {
 fn open_folder(path: &str) -> Result<(), String> {
     let p = PathBuf::from(path);
     let dir = p.parent().ok_or_else(|| "no parent folder".to_string())?;
-    let dir_str = dir.to_string_lossy().to_string();
+    // Explorer does not understand `\\?\` paths; UNC shares open fine as `\\server\share`.
+    let dir_str = crate::utils::paths::plain(dir);
 
     #[cfg(target_os = "windows")]
     {
@@ -57,8 +64,21 @@ fn open_folder(path: &str) -> Result<(), String> {
 }
 
 fn path_exists_ok(path: &str) -> bool {
-    let p = PathBuf::from(path);
-    p.exists() && p.is_file()
+    crate::utils::paths::extended(Path::new(path)).is_file()
+}
+
+/// A file that cannot be found because its drive is unplugged is offline, not gone.
+fn ensure_online(path: &str) -> Result<(), String> {
+    match crate::download::volume::status(Path::new(path)) {
+        VolumeStatus {
+            offline: true,
+            mount_point,
+        } => Err(format!(
+            "This item is offline: connect the drive {} to open it.",
+            mount_point.unwrap_or_default()
+        )),
+        _ => Ok(()),
+    }
 }
 
 #[tauri::command]
@@ -68,7 +88,7 @@ pub async fn open_file_for_link(link: String) -> Result<(), String> {
         return Ok(());
     };
     if !path_exists_ok(&path) {
-        return Ok(());
+        return ensure_online(&path);
     }
     open_with_default_app(&path)
 }
@@ -80,12 +100,13 @@ pub async fn open_folder_for_link(link: String) -> Result<(), String> {
         return Ok(());
     };
     let p = PathBuf::from(&path);
-    let dir = if p.exists() {
+    let exists = |d: &Path| crate::utils::paths::extended(d).exists();
+    let dir = if exists(&p) {
         path.clone()
-    } else if let Some(parent) = p.parent().filter(|d| d.exists()) {
+    } else if let Some(parent) = p.parent().filter(|d| exists(d)) {
         parent.to_string_lossy().to_string()
     } else {
-        return Ok(());
+        return ensure_online(&path);
     };
     open_folder(&dir)
 }
@@ -108,9 +129,14 @@ pub async fn open_collection_folder(
     content_type: String,
 ) -> Result<(), String> {
     let s = crate::settings::load_settings();
-    let base = std::path::PathBuf::from(s.download_directory);
-    let label = crate::database::Database::collection_folder_label(&content_type, &handle);
-    let p = base.join(platform).join(label);
+    let p = crate::settings::collection_dir(
+        &s,
+        &s.folder_template,
+        &s.collection_folder_template,
+        &platform,
+        &content_type,
+        &handle,
+    );
     if !p.exists() {
         return Ok(());
     }
@@ -119,28 +145,492 @@ pub async fn open_collection_folder(
 
 #[tauri::command]
 pub async fn delete_library_item(link: String) -> Result<(), String> {
-    use std::fs;
-
+    crate::settings::ensure_writable()?;
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
     let some = db.find_done_row_by_link(&link).map_err(|e| e.to_string())?;
 
     let (id, path) = some.ok_or_else(|| "no library item found for link".to_string())?;
 
-    // Respect delete mode
-    let mode = crate::settings::load_settings().delete_mode;
-    if matches!(mode, crate::database::DeleteMode::Hard) {
-        // Best-effort file delete first
-        if !path.is_empty() && path != "unknown_path" {
-            if let Err(e) = fs::remove_file(&path) {
-                // If it doesn't exist, that's fine; otherwise surface error
-                if std::io::ErrorKind::NotFound != e.kind() {
-                    return Err(format!("failed to delete file: {e}"));
-                }
-            }
-        }
+    // Respect delete mode: Soft only moves the row to the trash
+    let settings = crate::settings::load_settings();
+    if matches!(settings.delete_mode, crate::database::DeleteMode::Soft) {
+        db.trash_rows(&[id]).map_err(|e| e.to_string())?;
+        return Ok(());
     }
+    remove_media_file(&path, settings.hard_delete_to_trash)
+        .map_err(|e| format!("failed to delete file: {e}"))?;
 
     // Remove DB row
     let _ = db.delete_row_by_id(id).map_err(|e| e.to_string())?;
     Ok(())
 }
+
+/* ------------------------- collection folder naming ------------------------- */
+
+#[derive(Debug, Serialize)]
+pub struct CollectionFolderPreview {
+    pub platform: String,
+    pub handle: String,
+    pub origin: String,
+    /// Folder under the current templates, relative to the download directory
+    pub current: String,
+    /// Folder under the candidate templates
+    pub preview: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct CollectionRenameReport {
+    pub renamed: u32,
+    pub skipped: u32,
+    pub errors: Vec<String>,
+}
+
+/// "instagram/liked - bob" for a collection under the given templates.
+fn relative_collection_dir(
+    settings: &crate::database::Settings,
+    folder_template: &str,
+    collection_template: &str,
+    c: &CollectionInfo,
+) -> String {
+    let parts = crate::settings::collection_dir_names(
+        settings,
+        folder_template,
+        collection_template,
+        &c.platform,
+        &c.origin,
+        &c.user_handle,
+    );
+    if parts.is_empty() {
+        ".".into()
+    } else {
+        parts.join("/")
+    }
+}
+
+/// Folders of a handful of real collections under the current and candidate
+/// templates. `folder_template` defaults to the saved layout.
+#[tauri::command]
+pub async fn preview_collection_folders(
+    template: String,
+    folder_template: Option<String>,
+) -> Result<Vec<CollectionFolderPreview>, String> {
+    let settings = crate::settings::load_settings();
+    let layout = folder_template.unwrap_or_else(|| settings.folder_template.clone());
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let collections = db.list_collections(Some(8)).map_err(|e| e.to_string())?;
+    Ok(collections
+        .into_iter()
+        .map(|c| CollectionFolderPreview {
+            current: relative_collection_dir(
+                &settings,
+                &settings.folder_template,
+                &settings.collection_folder_template,
+                &c,
+            ),
+            preview: relative_collection_dir(&settings, &layout, &template, &c),
+            platform: c.platform,
+            handle: c.user_handle,
+            origin: c.origin,
+        })
+        .collect())
+}
+
+/// Move existing collection folders from the current templates to `template`
+/// (and `folder_template`, when given), rewrite stored paths, then persist the
+/// new templates. Runs as a maintenance task and returns its id; it can't be
+/// canceled midway since the templates only change once every folder has moved.
+#[tauri::command]
+pub async fn apply_collection_folder_template(
+    app: tauri::AppHandle,
+    tasks: tauri::State<'_, MaintenanceTasks>,
+    template: String,
+    folder_template: Option<String>,
+) -> Result<u32, String> {
+    crate::settings::ensure_writable()?;
+    let db = Database::new().map_err(|e| e.to_string())?;
+    if !db
+        .list_downloading_ids()
+        .map_err(|e| e.to_string())?
+        .is_empty()
+    {
+        return Err("wait for active downloads to finish before renaming folders".into());
+    }
+    tasks.spawn(
+        &app,
+        "rename_collection_folders",
+        "Rename collection folders",
+        false,
+        move |task| {
+            let report = rename_collection_folders(task, template, folder_template)?;
+            for err in &report.errors {
+                println!("[BACKEND] collection rename failed: {err}");
+            }
+            Ok(format!(
+                "Renamed {}, skipped {}, failed {}",
+                report.renamed,
+                report.skipped,
+                report.errors.len()
+            ))
+        },
+    )
+}
+
+/// Fill title, uploader and upload date of done rows that have none, by asking
+/// the site again; media files are not touched. `ids` limits it to a selection
+/// (empty = the whole library). Runs as a cancelable maintenance task and
+/// returns its id.
+#[tauri::command]
+pub async fn refresh_metadata(
+    app: tauri::AppHandle,
+    tasks: tauri::State<'_, MaintenanceTasks>,
+    ids: Vec<i64>,
+) -> Result<u32, String> {
+    crate::settings::ensure_writable()?;
+    let probe_app = app.clone();
+    tasks.spawn(
+        &app,
+        "refresh_metadata",
+        "Refresh metadata",
+        true,
+        move |task| refresh_metadata_rows(&probe_app, task, &ids),
+    )
+}
+
+fn refresh_metadata_rows(
+    app: &tauri::AppHandle,
+    task: &TaskHandle,
+    ids: &[i64],
+) -> Result<String, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let rows = db
+        .list_done_missing_metadata(ids)
+        .map_err(|e| e.to_string())?;
+    let settings = crate::settings::load_settings();
+    let total = rows.len() as u64;
+    let (mut filled, mut failed) = (0, 0);
+    for (i, (id, link)) in rows.into_iter().enumerate() {
+        if task.is_canceled() {
+            break;
+        }
+        task.progress(i as u64, Some(total), link.clone());
+        // Same order as the pipeline: cookies.txt, then the preferred browser.
+        let mut probed = Err("No logged-in browsers detected for cookies.".to_string());
+        for (_, cookie_arg) in crate::settings::cookie_sources(&settings, &link, None) {
+            probed = tauri::async_runtime::block_on(crate::download::video::probe_metadata(
+                app,
+                &cookie_arg,
+                &link,
+            ));
+            if probed.is_ok() {
+                break;
+            }
+        }
+        match probed {
+            Ok(meta) => {
+                db.set_media_metadata(id, &meta)
+                    .map_err(|e| e.to_string())?;
+                filled += 1;
+            }
+            Err(e) => {
+                println!("[BACKEND] metadata refresh failed for {link}: {e}");
+                failed += 1;
+            }
+        }
+    }
+    Ok(format!("Updated {filled} of {total}, failed {failed}"))
+}
+
+/// Check that every finished download is still on disk: the file (or gallery
+/// folder) exists and isn't empty, and matches its stored checksum when there
+/// is one. Rows that fail become `Missing` and show up with the failed
+/// downloads, where they can be repaired. Files on an unplugged drive are left
+/// alone. Runs as a cancelable maintenance task and returns its id.
+#[tauri::command]
+pub async fn verify_library(
+    app: tauri::AppHandle,
+    tasks: tauri::State<'_, MaintenanceTasks>,
+) -> Result<u32, String> {
+    crate::settings::ensure_writable()?;
+    let emit_app = app.clone();
+    tasks.spawn(&app, "verify_library", "Verify files", true, move |task| {
+        let summary = verify_library_rows(task)?;
+        use tauri::Emitter;
+        let _ = emit_app.emit("import_completed", 0u64);
+        Ok(summary)
+    })
+}
+
+/// Why the download at `path` no longer counts as present, or `None` if it does.
+fn missing_reason(path: &str, hash: Option<&str>) -> Option<String> {
+    let p = crate::utils::paths::extended(Path::new(path));
+    let Ok(meta) = std::fs::metadata(&p) else {
+        return Some("File missing from disk".into());
+    };
+    if meta.is_dir() {
+        let empty = std::fs::read_dir(&p).map_or(true, |mut d| d.next().is_none());
+        return empty.then(|| "Folder is empty".into());
+    }
+    if meta.len() == 0 {
+        return Some("File is empty".into());
+    }
+    let expected = hash?;
+    match crate::utils::checksum::sha256_file(&p) {
+        Ok(actual) if actual == expected => None,
+        Ok(_) => Some("File changed since it was downloaded".into()),
+        Err(e) => Some(format!("File can't be read: {e}")),
+    }
+}
+
+fn verify_library_rows(task: &TaskHandle) -> Result<String, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let rows = db.list_done_files().map_err(|e| e.to_string())?;
+    let total = rows.len() as u64;
+    let (mut missing, mut offline, mut unknown) = (0, 0, 0);
+    for (i, (id, path, hash)) in rows.into_iter().enumerate() {
+        if task.is_canceled() {
+            break;
+        }
+        task.progress(i as u64, Some(total), path.clone());
+        if path.is_empty() || path == "unknown_path" {
+            unknown += 1;
+            continue;
+        }
+        if crate::download::volume::is_offline(Path::new(&path)) {
+            offline += 1;
+            continue;
+        }
+        if let Some(reason) = missing_reason(&path, hash.as_deref()) {
+            println!("[BACKEND] verify: {path}: {reason}");
+            missing += db.mark_missing(id, &reason).map_err(|e| e.to_string())?;
+        }
+    }
+    let mut summary = format!("Checked {total}, {missing} missing");
+    if offline > 0 {
+        summary.push_str(&format!(", {offline} on an unplugged drive"));
+    }
+    if unknown > 0 {
+        summary.push_str(&format!(", {unknown} without a path"));
+    }
+    Ok(summary)
+}
+
+/// Queue every `Missing` row to be downloaded again. Returns the queued ids.
+#[tauri::command]
+pub async fn repair_missing(
+    manager: tauri::State<'_, crate::download::manager::DownloadManager>,
+) -> Result<Vec<i64>, String> {
+    crate::settings::ensure_writable()?;
+    let ids = Database::new()
+        .and_then(|db| db.list_missing_ids())
+        .map_err(|e| e.to_string())?;
+    if ids.is_empty() {
+        return Ok(ids);
+    }
+    let (reply, rx) = tokio::sync::oneshot::channel();
+    manager
+        .send(crate::download::manager::DownloadCommand::Enqueue {
+            ids,
+            reply: Some(reply),
+        })
+        .await?;
+    rx.await.map_err(|e| e.to_string())
+}
+
+/// Folders whose target already exists are left alone and reported as skipped,
+/// as are collections whose files sit directly in the download folder (flat
+/// layout) on either side, since those can't be moved as one folder.
+fn rename_collection_folders(
+    task: &TaskHandle,
+    template: String,
+    folder_template: Option<String>,
+) -> Result<CollectionRenameReport, String> {
+    let mut settings = crate::settings::load_settings();
+    let layout = folder_template.unwrap_or_else(|| settings.folder_template.clone());
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let collection_dir = |folder_template: &str, collection_template: &str, c: &CollectionInfo| {
+        crate::settings::collection_dir(
+            &settings,
+            folder_template,
+            collection_template,
+            &c.platform,
+            &c.origin,
+            &c.user_handle,
+        )
+    };
+    let mut report = CollectionRenameReport::default();
+    let collections = db.list_collections(None).map_err(|e| e.to_string())?;
+    let total = collections.len() as u64;
+    for (i, c) in collections.into_iter().enumerate() {
+        task.progress(
+            i as u64,
+            Some(total),
+            format!("{}/{}", c.platform, c.user_handle),
+        );
+        let old_dir = collection_dir(
+            &settings.folder_template,
+            &settings.collection_folder_template,
+            &c,
+        );
+        let new_dir = collection_dir(&layout, &template, &c);
+        let base = crate::settings::platform_root(&settings, &c.platform);
+        if old_dir == new_dir || !old_dir.is_dir() {
+            continue;
+        }
+        if old_dir == base || new_dir == base || new_dir.starts_with(&old_dir) {
+            println!(
+                "[BACKEND] collection rename skipped, can't move {} to {}",
+                old_dir.display(),
+                new_dir.display()
+            );
+            report.skipped += 1;
+            continue;
+        }
+        if new_dir.exists() {
+            println!(
+                "[BACKEND] collection rename skipped, target exists: {}",
+                new_dir.display()
+            );
+            report.skipped += 1;
+            continue;
+        }
+        if let Some(parent) = new_dir.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                report.errors.push(format!("{}: {e}", parent.display()));
+                continue;
+            }
+        }
+        if let Err(e) = std::fs::rename(&old_dir, &new_dir) {
+            report.errors.push(format!("{}: {e}", old_dir.display()));
+            continue;
+        }
+        // Drop the emptied intermediate folders the old layout left behind.
+        for dir in old_dir.ancestors().skip(1) {
+            if dir == base || std::fs::remove_dir(dir).is_err() {
+                break;
+            }
+        }
+        // Trailing separator so "liked - a" does not also match "liked - ab".
+        let sep = std::path::MAIN_SEPARATOR;
+        let old_prefix = format!("{}{sep}", old_dir.to_string_lossy());
+        let new_prefix = format!("{}{sep}", new_dir.to_string_lossy());
+        db.rewrite_path_prefix(&old_prefix, &new_prefix)
+            .map_err(|e| e.to_string())?;
+        println!("[BACKEND] renamed collection folder {old_prefix} -> {new_prefix}");
+        report.renamed += 1;
+    }
+    task.progress(total, Some(total), "Saving template");
+
+    settings.collection_folder_template = template;
+    settings.folder_template = layout;
+    crate::settings::save_settings(&settings)?;
+    Ok(report)
+}
+
+/* ------------------------------ collection export ----------------------------- */
+
+/// Ask where to save, then zip a collection's downloaded files there as a
+/// cancelable maintenance task. Returns the task id, or `None` when the dialog
+/// was dismissed.
+#[tauri::command]
+pub async fn export_collection_zip(
+    app: tauri::AppHandle,
+    tasks: tauri::State<'_, MaintenanceTasks>,
+    platform: String,
+    handle: String,
+    content_type: String,
+) -> Result<Option<u32>, String> {
+    use tauri_plugin_dialog::{DialogExt, FilePath};
+
+    // Strictest rules, since the dialog may point at any drive.
+    let rules = FilenameRules::for_filesystem(FilesystemKind::Ntfs);
+    let suggested = rules.clean(&format!("{platform}-{handle}-{content_type}.zip"));
+    let picked = app
+        .dialog()
+        .file()
+        .set_file_name(suggested)
+        .add_filter("ZIP", &["zip"])
+        .blocking_save_file();
+    let dest = match picked {
+        Some(FilePath::Path(p)) => p,
+        Some(FilePath::Url(url)) => return Err(format!("Unsupported URL selection: {url}")),
+        None => return Ok(None),
+    };
+    let label = format!("Zip {handle} ({content_type})");
+    tasks
+        .spawn(&app, "export_collection_zip", label, true, move |task| {
+            let db = Database::new().map_err(|e| e.to_string())?;
+            let paths = db
+                .list_done_paths_by_collection(&platform, &handle, &content_type)
+                .map_err(|e| e.to_string())?;
+            let result = write_collection_zip(task, &dest, &paths);
+            if result.is_err() || task.is_canceled() {
+                let _ = std::fs::remove_file(&dest);
+            }
+            result
+        })
+        .map(Some)
+}
+
+/// Files of every row (image sets are folders) go into the archive under their
+/// own names; clashes get a numeric prefix.
+fn write_collection_zip(
+    task: &TaskHandle,
+    dest: &Path,
+    paths: &[String],
+) -> Result<String, String> {
+    use std::collections::HashSet;
+    use std::fs::File;
+    use zip::write::SimpleFileOptions;
+
+    let files: Vec<PathBuf> = paths
+        .iter()
+        .map(PathBuf::from)
+        .flat_map(|p| {
+            if p.is_dir() {
+                walkdir::WalkDir::new(&p)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.file_type().is_file())
+                    .map(|e| e.into_path())
+                    .collect()
+            } else if p.is_file() {
+                vec![p]
+            } else {
+                Vec::new()
+            }
+        })
+        .collect();
+    if files.is_empty() {
+        return Err("no downloaded files found for this collection".into());
+    }
+
+    let out = File::create(dest).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipWriter::new(out);
+    // Media is already compressed; deflating it again only costs time.
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    let total = files.len() as u64;
+    let mut names = HashSet::new();
+    for (i, file) in files.iter().enumerate() {
+        if task.is_canceled() {
+            return Ok("Canceled".into());
+        }
+        let base = file
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| format!("file-{i}"));
+        let name = if names.insert(base.clone()) {
+            base
+        } else {
+            let unique = format!("{i}-{base}");
+            names.insert(unique.clone());
+            unique
+        };
+        task.progress(i as u64, Some(total), name.clone());
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        let mut src = File::open(file).map_err(|e| e.to_string())?;
+        std::io::copy(&mut src, &mut zip).map_err(|e| e.to_string())?;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    println!("[BACKEND] collection zip written to {}", dest.display());
+    Ok(format!("Zipped {total} files into {}", dest.display()))
+}
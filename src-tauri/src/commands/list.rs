@@ -1,23 +1,33 @@
+use crate::utils::delete::remove_media_file;
 use tauri::command;
 
 /// Return all backlog rows, already normalized for the UI.
 #[command]
-pub async fn list_backlog() -> Result<Vec<crate::database::UiBacklogRow>, String> {
+pub async fn list_backlog() -> Result<Vec<crate::database::ClipRow>, String> {
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
     db.list_backlog_ui().map_err(|e| e.to_string())
 }
 
 /// Return all queue rows, normalized for the UI.
 #[command]
-pub async fn list_queue() -> Result<Vec<crate::database::UiBacklogRow>, String> {
+pub async fn list_queue() -> Result<Vec<crate::database::ClipRow>, String> {
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
     db.list_queue_ui().map_err(|e| e.to_string())
 }
 
+/// Return every row that ended without a file (failed, canceled, dead link,
+/// skipped or missing), with its last error.
+#[command]
+pub async fn list_errors() -> Result<Vec<crate::database::ClipRow>, String> {
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    db.list_errors_ui().map_err(|e| e.to_string())
+}
+
 /* ---- mutations: move → queue ---- */
 
 #[command]
 pub async fn move_link_to_queue(link: String) -> Result<u64, String> {
+    crate::settings::ensure_writable()?;
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
     let n = db.move_link_to_queue(&link).map_err(|e| e.to_string())?;
     Ok(n as u64)
@@ -29,6 +39,7 @@ pub async fn move_collection_to_queue(
     handle: String,
     content_type: String,
 ) -> Result<u64, String> {
+    crate::settings::ensure_writable()?;
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
     // DB stores origin in lowercase already; UI provides lowercase tokens too.
     let n = db
@@ -39,6 +50,7 @@ pub async fn move_collection_to_queue(
 
 #[command]
 pub async fn move_platform_to_queue(platform: String) -> Result<u64, String> {
+    crate::settings::ensure_writable()?;
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
     let n = db
         .move_platform_to_queue(&platform)
@@ -49,6 +61,7 @@ pub async fn move_platform_to_queue(platform: String) -> Result<u64, String> {
 
 #[command]
 pub async fn move_link_to_backlog(link: String) -> Result<u64, String> {
+    crate::settings::ensure_writable()?;
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
     let n = db.move_link_to_backlog(&link).map_err(|e| e.to_string())?;
     Ok(n as u64)
@@ -60,6 +73,7 @@ pub async fn move_collection_to_backlog(
     handle: String,
     content_type: String,
 ) -> Result<u64, String> {
+    crate::settings::ensure_writable()?;
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
     let n = db
         .move_collection_to_backlog(&platform, &handle, &content_type)
@@ -69,6 +83,7 @@ pub async fn move_collection_to_backlog(
 
 #[command]
 pub async fn move_platform_to_backlog(platform: String) -> Result<u64, String> {
+    crate::settings::ensure_writable()?;
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
     let n = db
         .move_platform_to_backlog(&platform)
@@ -78,6 +93,7 @@ pub async fn move_platform_to_backlog(platform: String) -> Result<u64, String> {
 
 #[tauri::command]
 pub async fn toggle_output_format(link: String) -> Result<(), String> {
+    crate::settings::ensure_writable()?;
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
     db.toggle_output_format_for_link(&link)
         .map_err(|e| e.to_string())?;
@@ -86,97 +102,397 @@ pub async fn toggle_output_format(link: String) -> Result<(), String> {
 
 #[tauri::command]
 pub async fn set_output_format(link: String, format: String) -> Result<(), String> {
+    crate::settings::ensure_writable()?;
+    // "mp3", "flac", ... mean audio in that specific format
+    let audio = crate::database::AudioFormat::parse(&format);
     let fmt = match format.to_lowercase().as_str() {
         "audio" => crate::database::OutputFormat::Audio,
         "video" => crate::database::OutputFormat::Video,
+        _ if audio.is_some() => crate::database::OutputFormat::Audio,
         _ => crate::database::OutputFormat::Default,
     };
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
-    db.set_output_format_for_link(&link, fmt)
+    db.set_output_format_for_link(&link, fmt, audio)
         .map_err(|e| e.to_string())?;
     Ok(())
 }
 
+/// Browser tried first for one row; empty goes back to the settings' choice.
 #[tauri::command]
-pub async fn list_done() -> Result<Vec<crate::database::UiBacklogRow>, String> {
+pub async fn set_cookie_browser(link: String, browser: String) -> Result<(), String> {
+    crate::settings::ensure_writable()?;
+    let browser = browser.trim();
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    db.set_cookie_browser_for_link(&link, (!browser.is_empty()).then_some(browser))
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_collection_filter(
+    platform: String,
+    handle: String,
+    origin: String,
+) -> Result<crate::database::CollectionFilter, String> {
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    db.collection_filter(&platform, &handle, &origin)
+        .map_err(|e| e.to_string())
+}
+
+/// Length/size/keyword limits for a collection's downloads, checked as each
+/// one starts. Blank patterns are dropped; ones that don't compile are refused.
+#[tauri::command]
+pub async fn set_collection_filter(
+    platform: String,
+    handle: String,
+    origin: String,
+    mut filter: crate::database::CollectionFilter,
+) -> Result<(), String> {
+    crate::settings::ensure_writable()?;
+    for pattern in [&mut filter.include, &mut filter.exclude] {
+        *pattern = pattern
+            .take()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty());
+    }
+    crate::download::filters::validate(&filter)?;
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    db.set_collection_filter(&platform, &handle, &origin, &filter)
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_done() -> Result<Vec<crate::database::ClipRow>, String> {
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
     db.list_done_ui().map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn list_downloads() -> Result<Vec<crate::database::UiBacklogRow>, String> {
+pub async fn list_library_tree() -> Result<Vec<crate::database::LibraryPlatformNode>, String> {
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
-    db.list_all_ui().map_err(|e| e.to_string())
+    db.list_library_tree().map_err(|e| e.to_string())
 }
 
-/* ---- deletions: honor delete_mode ---- */
+#[tauri::command]
+pub async fn list_library_collection(
+    platform: String,
+    handle: String,
+    origin: String,
+) -> Result<Vec<crate::database::ClipRow>, String> {
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    db.list_library_collection(&platform, &handle, &origin)
+        .map_err(|e| e.to_string())
+}
+
+/// Largest page `list_done_paged` hands out.
+const MAX_PAGE: u64 = 1000;
 
+/// One page of done rows plus the total matching `filter`, so large
+/// libraries are never sent to the webview in one piece.
 #[tauri::command]
-pub async fn delete_rows_by_platform(platform: String) -> Result<u64, String> {
-    use std::fs;
+pub async fn list_done_paged(
+    offset: u64,
+    limit: u64,
+    sort: Option<crate::database::RowSort>,
+    filter: Option<crate::database::RowFilter>,
+) -> Result<crate::database::RowPage, String> {
+    let filter = filter.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = crate::database::Database::new()?;
+        Ok(crate::database::RowPage {
+            rows: db.list_done_paged(
+                offset,
+                limit.min(MAX_PAGE),
+                sort.unwrap_or_default(),
+                &filter,
+            )?,
+            total: db.count_done(&filter)?,
+        })
+    })
+    .await
+    .map_err(|e| format!("Join error: {e}"))?
+    .map_err(|e: rusqlite::Error| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_downloads() -> Result<Vec<crate::database::ClipRow>, String> {
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
-    let mode = crate::settings::load_settings().delete_mode;
-    let pairs = db
-        .list_ids_and_paths_by_platform(&platform)
+    db.list_all_ui().map_err(|e| e.to_string())
+}
+
+/// Full-text search over name, handle, link and origin. `status_filter`
+/// limits hits to one status ("done" for the Library); capped at 500 rows.
+#[tauri::command]
+pub async fn search_downloads(
+    query: String,
+    status_filter: Option<String>,
+) -> Result<Vec<crate::database::ClipRow>, String> {
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    db.search_downloads(&query, status_filter.as_deref(), 500)
+        .map_err(|e| e.to_string())
+}
+
+/* ---- pending review: approve → backlog, reject → drop ---- */
+
+#[command]
+pub async fn approve_pending_collection(
+    platform: String,
+    handle: String,
+    content_type: String,
+) -> Result<u64, String> {
+    crate::settings::ensure_writable()?;
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    let n = db
+        .approve_pending_collection(&platform, &handle, &content_type)
         .map_err(|e| e.to_string())?;
-    let (ids, paths): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
-    if matches!(mode, crate::database::DeleteMode::Hard) {
-        for p in paths.into_iter() {
-            if !p.is_empty() && p != "unknown_path" {
-                let _ = fs::remove_file(p);
-            }
-        }
+    Ok(n as u64)
+}
+
+#[command]
+pub async fn reject_pending_collection(
+    platform: String,
+    handle: String,
+    content_type: String,
+) -> Result<u64, String> {
+    crate::settings::ensure_writable()?;
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    let n = db
+        .reject_pending_collection(&platform, &handle, &content_type)
+        .map_err(|e| e.to_string())?;
+    Ok(n as u64)
+}
+
+/* ---- deletions: honor delete_mode ---- */
+
+/// Delete `rows` the way `delete_mode` says: Soft moves them to the trash and
+/// leaves their files until it is emptied, Hard removes files and rows at
+/// once. Queued and running rows are taken out of the manager first.
+async fn discard_rows(
+    manager: &crate::download::manager::DownloadManager,
+    db: &crate::database::Database,
+    rows: Vec<(i64, String)>,
+) -> Result<u64, String> {
+    let settings = crate::settings::load_settings();
+    let mut in_manager = db.list_queued_ids().map_err(|e| e.to_string())?;
+    in_manager.extend(db.list_downloading_ids().map_err(|e| e.to_string())?);
+    let held: Vec<i64> = rows
+        .iter()
+        .map(|(id, _)| *id)
+        .filter(|id| in_manager.contains(id))
+        .collect();
+    if !held.is_empty() {
+        manager
+            .send(crate::download::manager::DownloadCommand::MoveToBacklog { ids: held })
+            .await?;
+    }
+    if matches!(settings.delete_mode, crate::database::DeleteMode::Soft) {
+        let ids: Vec<i64> = rows.into_iter().map(|(id, _)| id).collect();
+        return Ok(db.trash_rows(&ids).map_err(|e| e.to_string())? as u64);
     }
     let mut deleted: u64 = 0;
-    for id in ids.into_iter() {
+    for (id, path) in rows {
+        let _ = remove_media_file(&path, settings.hard_delete_to_trash);
         deleted += db.delete_row_by_id(id).map_err(|e| e.to_string())? as u64;
     }
     Ok(deleted)
 }
 
+#[tauri::command]
+pub async fn delete_rows_by_platform(
+    manager: tauri::State<'_, crate::download::manager::DownloadManager>,
+    platform: String,
+) -> Result<u64, String> {
+    crate::settings::ensure_writable()?;
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    let pairs = db
+        .list_ids_and_paths_by_platform(&platform)
+        .map_err(|e| e.to_string())?;
+    discard_rows(&manager, &db, pairs).await
+}
+
 #[tauri::command]
 pub async fn delete_rows_by_collection(
+    manager: tauri::State<'_, crate::download::manager::DownloadManager>,
     platform: String,
     handle: String,
     origin: String,
 ) -> Result<u64, String> {
-    use std::fs;
+    crate::settings::ensure_writable()?;
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
-    let mode = crate::settings::load_settings().delete_mode;
     let pairs = db
         .list_ids_and_paths_by_collection(&platform, &handle, &origin)
         .map_err(|e| e.to_string())?;
-    let (ids, paths): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
-    if matches!(mode, crate::database::DeleteMode::Hard) {
-        for p in paths.into_iter() {
-            if !p.is_empty() && p != "unknown_path" {
-                let _ = fs::remove_file(p);
-            }
-        }
-    }
-    let mut deleted: u64 = 0;
-    for id in ids.into_iter() {
-        deleted += db.delete_row_by_id(id).map_err(|e| e.to_string())? as u64;
-    }
-    Ok(deleted)
+    discard_rows(&manager, &db, pairs).await
 }
 
 #[tauri::command]
-pub async fn delete_rows_by_link(link: String) -> Result<u64, String> {
-    use std::fs;
+pub async fn delete_rows_by_link(
+    manager: tauri::State<'_, crate::download::manager::DownloadManager>,
+    link: String,
+) -> Result<u64, String> {
+    crate::settings::ensure_writable()?;
     let db = crate::database::Database::new().map_err(|e| e.to_string())?;
-    let mode = crate::settings::load_settings().delete_mode;
     let pairs = db
         .list_ids_and_paths_by_link(&link)
         .map_err(|e| e.to_string())?;
+    discard_rows(&manager, &db, pairs).await
+}
+
+/* ---- trash ---- */
+
+/// Rows deleted while `delete_mode` was Soft, most recent first.
+#[command]
+pub async fn list_trash() -> Result<Vec<crate::database::ClipRow>, String> {
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    db.list_trash_ui().map_err(|e| e.to_string())
+}
+
+/// Take `ids` out of the trash with the status they had (all of it when
+/// `ids` is empty). Returns how many came back.
+#[command]
+pub async fn restore_from_trash(app: tauri::AppHandle, ids: Vec<i64>) -> Result<u64, String> {
+    use tauri::Emitter;
+    crate::settings::ensure_writable()?;
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    let ids = if ids.is_empty() {
+        db.list_trash_paths()
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect()
+    } else {
+        ids
+    };
+    let restored = db.restore_rows(&ids).map_err(|e| e.to_string())? as u64;
+    let _ = app.emit("import_completed", 0u64);
+    Ok(restored)
+}
+
+/// Delete everything in the trash for good, files of finished downloads
+/// included (to the system trash when `hard_delete_to_trash` is on). Returns
+/// how many rows went.
+#[command]
+pub async fn empty_trash() -> Result<u64, String> {
+    crate::settings::ensure_writable()?;
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    let settings = crate::settings::load_settings();
     let mut deleted: u64 = 0;
-    for (id, path) in pairs.into_iter() {
-        if matches!(mode, crate::database::DeleteMode::Hard) {
-            if !path.is_empty() && path != "unknown_path" {
-                let _ = fs::remove_file(&path);
-            }
+    for (id, path) in db.list_trash_paths().map_err(|e| e.to_string())? {
+        // A duplicate sent here by dedupe may share its file with the row kept.
+        let in_use = !path.is_empty() && db.path_in_use(&path).map_err(|e| e.to_string())?;
+        if in_use {
+            println!("[BACKEND] [trash] keeping {path}, another row still uses it");
+        } else if let Err(e) = remove_media_file(&path, settings.hard_delete_to_trash) {
+            eprintln!("[BACKEND] [trash] could not remove {path}: {e}");
+            continue;
         }
         deleted += db.delete_row_by_id(id).map_err(|e| e.to_string())? as u64;
     }
+    println!("[BACKEND] [trash] emptied {deleted} rows");
+    Ok(deleted)
+}
+
+/// Where a row stands when choosing which copy of a link to keep.
+fn keep_rank(status: &crate::database::DownloadStatus) -> u8 {
+    use crate::database::DownloadStatus;
+    match status {
+        DownloadStatus::Done => 0,
+        DownloadStatus::Downloading => 1,
+        DownloadStatus::Queued => 2,
+        DownloadStatus::Backlog => 3,
+        DownloadStatus::Pending => 4,
+        _ => 5,
+    }
+}
+
+/// Collapse rows sharing a normalized link into one: a finished download wins
+/// over a queued one, which wins over the backlog; ties keep the oldest row.
+/// Rows that are downloading are left alone. Returns how many rows went.
+#[command]
+pub async fn dedupe_downloads(
+    app: tauri::AppHandle,
+    manager: tauri::State<'_, crate::download::manager::DownloadManager>,
+) -> Result<u64, String> {
+    use crate::database::DownloadStatus;
+    use tauri::Emitter;
+    crate::settings::ensure_writable()?;
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    let mut doomed: Vec<(i64, String)> = Vec::new();
+    for group in db.duplicate_link_groups().map_err(|e| e.to_string())? {
+        let Some((keep_id, _, keep_path)) = group
+            .iter()
+            .min_by_key(|(id, status, _)| (keep_rank(status), *id))
+            .cloned()
+        else {
+            continue;
+        };
+        for (id, status, path) in group {
+            if id == keep_id || status == DownloadStatus::Downloading {
+                continue;
+            }
+            // Another row may point at the file that is being kept.
+            let path = if path == keep_path {
+                String::new()
+            } else {
+                path
+            };
+            doomed.push((id, path));
+        }
+    }
+    if doomed.is_empty() {
+        return Ok(0);
+    }
+    let deleted = discard_rows(&manager, &db, doomed).await?;
+    println!("[BACKEND] [list] removed {deleted} duplicate rows");
+    let _ = app.emit("import_completed", 0u64);
+    Ok(deleted)
+}
+
+/// Queue every row listed by `list_errors` again. Returns the queued ids.
+#[command]
+pub async fn retry_all_errors(
+    manager: tauri::State<'_, crate::download::manager::DownloadManager>,
+) -> Result<Vec<i64>, String> {
+    crate::settings::ensure_writable()?;
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    let ids: Vec<i64> = db
+        .list_errors_ui()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+    if ids.is_empty() {
+        return Ok(ids);
+    }
+    let (reply, rx) = tokio::sync::oneshot::channel();
+    manager
+        .send(crate::download::manager::DownloadCommand::Enqueue {
+            ids,
+            reply: Some(reply),
+        })
+        .await?;
+    rx.await.map_err(|e| e.to_string())
+}
+
+/// Delete every row listed by `list_errors` the way `delete_mode` says; they
+/// have no finished file, so only the rows go. Returns how many were removed.
+#[command]
+pub async fn clear_errors(
+    app: tauri::AppHandle,
+    manager: tauri::State<'_, crate::download::manager::DownloadManager>,
+) -> Result<u64, String> {
+    use tauri::Emitter;
+    crate::settings::ensure_writable()?;
+    let db = crate::database::Database::new().map_err(|e| e.to_string())?;
+    let rows = db
+        .list_errors_ui()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|row| (row.id, String::new()))
+        .collect();
+    let deleted = discard_rows(&manager, &db, rows).await?;
+    println!("[BACKEND] [list] cleared {deleted} failed rows");
+    let _ = app.emit("import_completed", 0u64);
     Ok(deleted)
 }
@@ -0,0 +1,18 @@
+use crate::maintenance::{MaintenanceTask, MaintenanceTasks};
+use tauri::State;
+
+#[tauri::command]
+pub async fn list_maintenance_tasks(
+    tasks: State<'_, MaintenanceTasks>,
+) -> Result<Vec<MaintenanceTask>, String> {
+    Ok(tasks.list())
+}
+
+/// Returns false when the task is unknown, already finished or not cancelable.
+#[tauri::command]
+pub async fn cancel_maintenance_task(
+    tasks: State<'_, MaintenanceTasks>,
+    id: u32,
+) -> Result<bool, String> {
+    Ok(tasks.cancel(id))
+}
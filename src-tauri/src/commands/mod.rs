@@ -1,9 +1,19 @@
+pub mod config;
+pub mod covers;
 pub mod downloader;
 pub mod files;
+pub mod icons;
 pub mod import;
+pub mod libraries;
 pub mod library;
 pub mod list;
 pub mod log;
+pub mod maintenance;
+pub mod navigation;
 pub mod parse;
+pub mod profiles;
+pub mod sessions;
 pub mod settings_cmd;
+pub mod tags;
 pub mod tools;
+pub mod watch;
@@ -0,0 +1,9 @@
+use tauri::State;
+
+use crate::navigation::LaunchRoute;
+
+/// Page (and item) named by `--open` at launch, if any.
+#[tauri::command]
+pub async fn launch_route(route: State<'_, LaunchRoute>) -> Result<Option<String>, String> {
+    Ok(route.0.clone())
+}
@@ -1,65 +1,9 @@
 use std::path::Path;
 
-/// Extract Instagram (handle, id) from /reel/… or /p/…
-pub fn ig_handle_and_id(url: &str) -> (Option<String>, Option<String>) {
-    if let Some(pos) = url.find("instagram.com/") {
-        let rest = &url[pos + "instagram.com/".len()..];
-        let parts: Vec<&str> = rest.trim_matches('/').split('/').collect();
-        if parts.len() >= 3 {
-            let handle = parts[0].to_string();
-            let typ = parts[1];
-            let id = parts[2].to_string();
-            if typ == "reel" || typ == "p" {
-                return (Some(handle), Some(id));
-            }
-        }
-    }
-    (None, None)
-}
-
-/// Extract TikTok id token after /video/ or /photo/
-pub fn tiktok_id_from_url(url: &str) -> Option<String> {
-    for key in ["/video/", "/photo/"] {
-        if let Some(idx) = url.find(key) {
-            let tail = &url[idx + key.len()..];
-            let id = tail.split(['/', '?', '&']).next().unwrap_or("").to_string();
-            if !id.is_empty() {
-                return Some(id);
-            }
-        }
-    }
-    None
-}
+use clip_core::url::ig_handle_and_id;
 
-/// Extract YouTube video id from v=… or /shorts/…
-pub fn youtube_id_from_url(url: &str) -> Option<String> {
-    if let Some(qidx) = url.find('?') {
-        for pair in url[qidx + 1..].split('&') {
-            if let Some((k, v)) = pair.split_once('=') {
-                if k == "v" && !v.is_empty() {
-                    return Some(v.to_string());
-                }
-            }
-        }
-    }
-    if let Some(idx) = url.find("/shorts/") {
-        let tail = &url[idx + "/shorts/".len()..];
-        let id = tail.split(['/', '?', '&']).next().unwrap_or("").to_string();
-        if !id.is_empty() {
-            return Some(id);
-        }
-    }
-    None
-}
-
-/// Fallback last path segment without trailing slash/query
-pub fn last_segment(url: &str) -> Option<String> {
-    let base = url.split('?').next().unwrap_or(url).trim_end_matches('/');
-    base.rsplit('/')
-        .next()
-        .map(|s| s.to_string())
-        .filter(|s| !s.is_empty())
-}
+/// Site folder names; a file's collection folder sits one level below these.
+const SITE_FOLDERS: &[&str] = &["instagram", "tiktok", "youtube", "twitter", "reddit"];
 
 /// Parse multiple user_handle, clean_name, and file_path from tool output
 /// Returns Vec<(user_handle, clean_name, full_file_path)>
@@ -71,6 +15,8 @@ pub fn parse_multiple_filenames_from_output(
     use std::collections::HashSet;
     use std::path::Path as StdPath;
 
+    let is_instagram = crate::platforms::detect(processed_url)
+        .is_some_and(|d| d.platform == crate::database::Platform::Instagram);
     let mut results = Vec::new();
     let mut candidate_paths: Vec<String> = Vec::new();
 
@@ -105,12 +51,7 @@ pub fn parse_multiple_filenames_from_output(
             }
         }
         // explicit printed paths or joined from hint on "Skipping …"
-        let looks_abs_unix = trimmed.starts_with('/');
-        let looks_abs_win = trimmed.len() > 2
-            && trimmed.as_bytes()[1] == b':'
-            && (trimmed.as_bytes()[2] == b'\\' || trimmed.as_bytes()[2] == b'/');
-        if looks_abs_unix
-            || looks_abs_win
+        if crate::utils::paths::looks_absolute(trimmed)
             || (!dir_hint_str.is_empty() && trimmed.starts_with(&dir_hint_str))
         {
             if trimmed.contains('.') {
@@ -126,7 +67,12 @@ pub fn parse_multiple_filenames_from_output(
                 .unwrap_or("")
                 .trim();
             if !name_part.is_empty() && !dir_hint_str.is_empty() {
-                candidate_paths.push(format!("{}/{}", dir_hint_str, name_part));
+                candidate_paths.push(
+                    Path::new(&dir_hint_str)
+                        .join(name_part)
+                        .display()
+                        .to_string(),
+                );
             }
             continue;
         }
@@ -136,7 +82,8 @@ pub fn parse_multiple_filenames_from_output(
             if let Some(after) = trimmed.strip_prefix("[download] Skipping ") {
                 let fname = after.split(':').next().unwrap_or("").trim();
                 if !fname.is_empty() && !dir_hint_str.is_empty() {
-                    candidate_paths.push(format!("{}/{}", dir_hint_str, fname));
+                    candidate_paths
+                        .push(Path::new(&dir_hint_str).join(fname).display().to_string());
                 }
             }
             continue;
@@ -174,7 +121,7 @@ pub fn parse_multiple_filenames_from_output(
         }
 
         // prefer IG handle from URL; else try parent folders
-        if processed_url.contains("instagram.com/") {
+        if is_instagram {
             if let (Some(h), _) = ig_handle_and_id(processed_url) {
                 user_handle = h;
             }
@@ -182,11 +129,11 @@ pub fn parse_multiple_filenames_from_output(
         if user_handle == "Unknown" {
             if let Some(parent) = full.parent() {
                 if let Some(last) = parent.file_name().and_then(|s| s.to_str()) {
-                    if last != "instagram" && last != "tiktok" && last != "youtube" {
+                    if !SITE_FOLDERS.contains(&last) {
                         user_handle = last.to_string();
                     } else if let Some(pp) = parent.parent() {
                         if let Some(prev) = pp.file_name().and_then(|s| s.to_str()) {
-                            if prev != "instagram" && prev != "tiktok" && prev != "youtube" {
+                            if !SITE_FOLDERS.contains(&prev) {
                                 user_handle = prev.to_string();
                             }
                         }
@@ -196,7 +143,7 @@ pub fn parse_multiple_filenames_from_output(
         }
 
         // Force IG name = ID
-        if processed_url.contains("instagram.com/") {
+        if is_instagram {
             if let (_, Some(id)) = ig_handle_and_id(processed_url) {
                 clean_name = id;
             }
@@ -208,7 +155,7 @@ pub fn parse_multiple_filenames_from_output(
     if results.is_empty() {
         let (h, maybe_id) = ig_handle_and_id(processed_url);
         let handle = h.unwrap_or_else(|| "Unknown".to_string());
-        let name = if processed_url.contains("instagram.com/") {
+        let name = if is_instagram {
             maybe_id.unwrap_or_else(|| "Unknown".to_string())
         } else {
             "Unknown".to_string()
@@ -0,0 +1,44 @@
+use crate::database::Database;
+use crate::profiles::{ProfileEntry, ProfileIndex};
+
+#[tauri::command]
+pub async fn list_profiles() -> Result<ProfileIndex, String> {
+    Ok(crate::profiles::load_index())
+}
+
+#[tauri::command]
+pub async fn create_profile(name: String) -> Result<ProfileEntry, String> {
+    crate::settings::ensure_writable()?;
+    crate::profiles::create_profile(&name)
+}
+
+/// Profiles to choose from at launch; `None` means go straight to the app.
+#[tauri::command]
+pub async fn startup_profile_prompt() -> Result<Option<ProfileIndex>, String> {
+    crate::profiles::startup_prompt()
+}
+
+#[tauri::command]
+pub async fn set_profile_prompt(enabled: bool) -> Result<(), String> {
+    crate::profiles::set_ask_on_startup(enabled)
+}
+
+/// Switch to another profile by restarting the app on it; refused while downloads run.
+#[tauri::command]
+pub async fn switch_profile(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    if crate::profiles::load_index().active == id {
+        return Ok(());
+    }
+    let db = Database::new().map_err(|e| e.to_string())?;
+    if !db
+        .list_downloading_ids()
+        .map_err(|e| e.to_string())?
+        .is_empty()
+    {
+        return Err("wait for active downloads to finish before switching profiles".into());
+    }
+    crate::profiles::set_active(&id)?;
+    println!("[BACKEND] switching to profile {id}; restarting");
+    crate::session::complete_session();
+    app.restart();
+}
@@ -0,0 +1,102 @@
+use tauri::State;
+
+use crate::database::{CrashRecovery, Database, SessionReport};
+use crate::download::manager::{DownloadCommand, DownloadManager};
+use crate::session::PendingRecovery;
+
+/// Newest session report the user has not dismissed yet.
+#[tauri::command]
+pub async fn latest_session_report() -> Result<Option<SessionReport>, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    db.latest_undismissed_session().map_err(|e| e.to_string())
+}
+
+/// What the previous run left unfinished when it crashed; `None` after a
+/// clean exit or once the prompt was answered.
+#[tauri::command]
+pub async fn crash_recovery(
+    pending: State<'_, PendingRecovery>,
+) -> Result<Option<CrashRecovery>, String> {
+    Ok(pending.0.lock().map_err(|e| e.to_string())?.clone())
+}
+
+/// Answer the crash-recovery prompt: start the queue now or keep it paused.
+#[tauri::command]
+pub async fn resolve_crash_recovery(
+    pending: State<'_, PendingRecovery>,
+    manager: State<'_, DownloadManager>,
+    resume: bool,
+) -> Result<(), String> {
+    crate::settings::ensure_writable()?;
+    pending.0.lock().map_err(|e| e.to_string())?.take();
+    println!("[BACKEND] crash recovery answered; resume={resume}");
+    manager
+        .send(DownloadCommand::SetPaused(!resume))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Aggregates for the Stats page; reading file sizes can take a while on
+/// large libraries, so it runs off the async runtime.
+#[tauri::command]
+pub async fn library_stats() -> Result<crate::database::LibraryStats, String> {
+    tauri::async_runtime::spawn_blocking(crate::stats::library_stats)
+        .await
+        .map_err(|e| format!("Join error: {e}"))?
+}
+
+/// Past session reports, newest first.
+#[tauri::command]
+pub async fn list_sessions(limit: Option<usize>) -> Result<Vec<SessionReport>, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    db.list_sessions(limit.unwrap_or(50))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn dismiss_session_report(id: i64) -> Result<(), String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    db.dismiss_session(id).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Deliver the digest immediately using the saved settings; returns the file path
+/// or recipient, or `None` when nothing happened since the last digest.
+#[tauri::command]
+pub async fn send_digest_now() -> Result<Option<String>, String> {
+    let settings = crate::settings::load_settings();
+    if settings.digest_mode == crate::database::DigestMode::Off {
+        return Err("digest is turned off in settings".into());
+    }
+    crate::digest::deliver(settings).await
+}
+
+/// Export library aggregates and session history into a folder the user
+/// picks. Returns the written files, or `None` when the dialog was dismissed.
+#[tauri::command]
+pub async fn export_stats(
+    app: tauri::AppHandle,
+    format: crate::stats::ExportFormat,
+) -> Result<Option<Vec<String>>, String> {
+    use tauri_plugin_dialog::{DialogExt, FilePath};
+
+    let picked = app.dialog().file().blocking_pick_folder();
+    let dir = match picked {
+        Some(FilePath::Path(p)) => p,
+        Some(FilePath::Url(url)) => return Err(format!("Unsupported URL folder: {url}")),
+        None => return Ok(None),
+    };
+    let files = tauri::async_runtime::spawn_blocking({
+        let dir = dir.clone();
+        move || crate::stats::export(&dir, format)
+    })
+    .await
+    .map_err(|e| format!("Join error: {e}"))??;
+    println!("[BACKEND] stats exported to {}", dir.display());
+    Ok(Some(
+        files
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect(),
+    ))
+}
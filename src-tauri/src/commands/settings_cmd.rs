@@ -1,4 +1,4 @@
-use crate::database::Settings;
+use crate::database::{FilesystemKind, Settings};
 use crate::download::manager::{DownloadCommand, DownloadManager};
 use tauri::State;
 
@@ -7,16 +7,38 @@ pub async fn load_settings() -> Settings {
     crate::settings::load_settings()
 }
 
+/// Persist `settings` and apply them. While the library is read-only the
+/// only change accepted is turning read-only off; everything else stays as
+/// saved until then.
 #[tauri::command]
 pub async fn save_settings(
+    app: tauri::AppHandle,
     manager: State<'_, DownloadManager>,
     settings: Settings,
 ) -> Result<(), String> {
+    let current = crate::settings::load_settings();
+    let settings = if current.read_only {
+        if settings.read_only {
+            crate::settings::ensure_writable()?;
+        }
+        println!("[BACKEND] read-only mode turned off");
+        Settings {
+            read_only: false,
+            ..current
+        }
+    } else {
+        settings
+    };
+
     // persist first
     crate::settings::save_settings(&settings)?;
 
     // then live-toggle logging
     crate::logging::set_file_logging_enabled(settings.debug_logs);
+    crate::logging::set_redaction_enabled(settings.redact_logs);
+    crate::download::bandwidth::set_limit_kbps(settings.max_download_rate_kbps);
+    crate::clipboard::set_mode(settings.clipboard_watch);
+    crate::http_api::apply(&app, &crate::settings::load_settings().http_api);
     tracing::info!("settings saved; debug_logs now {}", settings.debug_logs);
 
     // notify download manager to refresh runtime parameters
@@ -27,3 +49,101 @@ pub async fn save_settings(
 
     Ok(())
 }
+
+/// Filesystem of the current download folder, as used for filename rules.
+#[tauri::command]
+pub async fn detect_download_filesystem() -> FilesystemKind {
+    let settings = crate::settings::load_settings();
+    let dir = std::path::PathBuf::from(settings.download_directory);
+    tauri::async_runtime::spawn_blocking(move || crate::utils::filenames::detect_filesystem(&dir))
+        .await
+        .unwrap_or(FilesystemKind::Other)
+}
+
+/// Replace the browser extension's API token; the old one stops working at once.
+#[tauri::command]
+pub async fn regenerate_http_api_token(app: tauri::AppHandle) -> Result<String, String> {
+    let mut settings = crate::settings::load_settings();
+    settings.http_api.token = crate::http_api::new_token();
+    crate::settings::save_settings(&settings)?;
+    crate::http_api::apply(&app, &settings.http_api);
+    Ok(settings.http_api.token)
+}
+
+/// Reach a known page through `proxy_url` (which may not be saved yet);
+/// returns the round trip in milliseconds.
+#[tauri::command]
+pub async fn test_proxy(proxy_url: String) -> Result<u64, String> {
+    let proxy = proxy_url.trim();
+    if proxy.is_empty() {
+        return Err("No proxy entered".into());
+    }
+    let result = crate::download::http::probe_proxy(proxy).await;
+    println!("[BACKEND] proxy test via {proxy}: {result:?}");
+    result
+}
+
+/// Labels of the browsers (and profiles) whose cookies can be used, for the
+/// preferred-browser pickers.
+#[tauri::command]
+pub async fn list_cookie_browsers() -> Vec<String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        crate::utils::os::installed_browsers()
+            .into_iter()
+            .map(|(label, _)| label)
+            .collect()
+    })
+    .await
+    .unwrap_or_default()
+}
+
+/// Pick a Netscape cookies.txt for `platform` and keep a copy in the active
+/// library, so moving or deleting the original doesn't break downloads.
+/// Returns the copy's path for the platform override, or `None` if canceled.
+#[tauri::command]
+pub async fn import_cookie_file(
+    app: tauri::AppHandle,
+    platform: String,
+) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::{DialogExt, FilePath};
+
+    crate::settings::ensure_writable()?;
+    let picked = app
+        .dialog()
+        .file()
+        .add_filter("Cookies", &["txt"])
+        .blocking_pick_file();
+    let path = match picked {
+        Some(FilePath::Path(p)) => p,
+        Some(FilePath::Url(url)) => return Err(format!("Unsupported URL selection: {url}")),
+        None => return Ok(None),
+    };
+    let text = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    if !is_netscape_cookie_file(&text) {
+        return Err("Not a Netscape cookies.txt file".into());
+    }
+    let dir = crate::libraries::active_dir().join("cookies");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let dest = dir.join(format!("{platform}.txt"));
+    std::fs::write(&dest, text).map_err(|e| e.to_string())?;
+    println!(
+        "[BACKEND] cookie file for {platform} imported from {}",
+        path.display()
+    );
+    Ok(Some(dest.to_string_lossy().to_string()))
+}
+
+/// The header browser exporters write, or at least one tab-separated
+/// seven-field cookie line.
+fn is_netscape_cookie_file(text: &str) -> bool {
+    let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+    let Some(first) = lines.next() else {
+        return false;
+    };
+    first.starts_with("# Netscape HTTP Cookie File")
+        || first.starts_with("# HTTP Cookie File")
+        || std::iter::once(first)
+            .chain(lines)
+            .filter(|l| !l.starts_with('#') || l.starts_with("#HttpOnly_"))
+            .any(|l| l.split('\t').count() == 7)
+}
@@ -0,0 +1,51 @@
+use crate::database::{ClipRow, Database, TagCount};
+
+/// Longest tag name accepted; tags are short labels, not notes.
+const MAX_TAG_LEN: usize = 40;
+
+/// Trim and collapse whitespace; rejects empty or overlong names.
+fn clean_tag(tag: &str) -> Result<String, String> {
+    let tag = tag.split_whitespace().collect::<Vec<_>>().join(" ");
+    if tag.is_empty() {
+        return Err("tag name is empty".into());
+    }
+    if tag.chars().count() > MAX_TAG_LEN {
+        return Err(format!("tag names are limited to {MAX_TAG_LEN} characters"));
+    }
+    Ok(tag)
+}
+
+/// Tag a link (all of its rows). Returns the link's tags afterwards.
+#[tauri::command]
+pub async fn add_tag_to_link(link: String, tag: String) -> Result<Vec<String>, String> {
+    crate::settings::ensure_writable()?;
+    let tag = clean_tag(&tag)?;
+    let db = Database::new().map_err(|e| e.to_string())?;
+    db.add_tag_to_link(&link, &tag).map_err(|e| e.to_string())?;
+    db.tags_for_link(&link).map_err(|e| e.to_string())
+}
+
+/// Remove a tag from a link. Returns the link's remaining tags.
+#[tauri::command]
+pub async fn remove_tag(link: String, tag: String) -> Result<Vec<String>, String> {
+    crate::settings::ensure_writable()?;
+    let db = Database::new().map_err(|e| e.to_string())?;
+    db.remove_tag_from_link(&link, tag.trim())
+        .map_err(|e| e.to_string())?;
+    db.tags_for_link(&link).map_err(|e| e.to_string())
+}
+
+/// Every tag in use with its item count, alphabetically.
+#[tauri::command]
+pub async fn list_tags() -> Result<Vec<TagCount>, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    db.list_tags().map_err(|e| e.to_string())
+}
+
+/// Done items carrying `tag`, regardless of platform or collection.
+#[tauri::command]
+pub async fn list_downloads_by_tag(tag: String) -> Result<Vec<ClipRow>, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    db.list_downloads_by_tag(tag.trim())
+        .map_err(|e| e.to_string())
+}
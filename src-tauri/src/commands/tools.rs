@@ -63,3 +63,31 @@ pub async fn check_sidecar_tools(app: tauri::AppHandle) -> Result<SidecarCheck,
         ffmpeg: ffmpeg_ok,
     })
 }
+
+/// Extractor versions and matching advisories. Returns the startup probe's
+/// result unless `refresh` is set or no probe has finished yet.
+#[tauri::command]
+pub async fn tool_report(
+    app: tauri::AppHandle,
+    refresh: Option<bool>,
+) -> Result<crate::database::ToolReport, String> {
+    if !refresh.unwrap_or(false) {
+        if let Some(report) = crate::download::extractors::cached() {
+            return Ok(report);
+        }
+    }
+    Ok(crate::download::extractors::probe(&app).await)
+}
+
+/// Per-platform feature matrix (video, images, stories, playlists, audio-only,
+/// subtitles) for the installed tool versions.
+#[tauri::command]
+pub async fn get_platform_capabilities(
+    app: tauri::AppHandle,
+) -> Result<Vec<crate::database::PlatformCapabilities>, String> {
+    let report = match crate::download::extractors::cached() {
+        Some(report) => report,
+        None => crate::download::extractors::probe(&app).await,
+    };
+    Ok(crate::platforms::capabilities(&report))
+}
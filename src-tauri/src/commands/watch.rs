@@ -0,0 +1,105 @@
+use crate::database::{ContentType, Database, DateRange, WatchedCollection};
+use crate::download::manager::DownloadManager;
+use tauri::State;
+
+/// Hours between checks when none is given.
+const DEFAULT_INTERVAL_HOURS: u32 = 24;
+
+/// Watch a profile, channel or playlist link for new items. Playlists join the
+/// collection named after their title, like an expanded playlist does.
+#[tauri::command]
+pub async fn watch_collection(
+    app: tauri::AppHandle,
+    url: String,
+    interval_hours: Option<u32>,
+    auto_queue: Option<bool>,
+) -> Result<WatchedCollection, String> {
+    crate::settings::ensure_writable()?;
+    let url = url.trim().to_string();
+    let Some(platform) = crate::platforms::detect(&url).map(|d| d.platform) else {
+        return Err("not a supported link".into());
+    };
+    let Some(mut handle) = clip_core::url::collection_handle(platform, &url) else {
+        return Err("not a profile, channel or playlist link".into());
+    };
+    let origin = clip_core::url::infer_origin(platform, &url).unwrap_or(ContentType::Profile);
+    if origin == ContentType::Playlist {
+        let settings = crate::settings::load_settings();
+        if let Some((_, cookie_arg)) =
+            crate::settings::cookie_sources(&settings, &url, None).first()
+        {
+            let listed = crate::download::video::list_playlist(
+                &app,
+                cookie_arg,
+                &url,
+                &DateRange::default(),
+            )
+            .await;
+            if let Ok((Some(title), _)) = listed {
+                handle = title;
+            }
+        }
+    }
+
+    let interval = interval_hours.unwrap_or(DEFAULT_INTERVAL_HOURS).max(1);
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let id = db
+        .watch_collection(
+            &url,
+            platform,
+            &handle,
+            origin,
+            interval,
+            auto_queue.unwrap_or(false),
+        )
+        .map_err(|e| e.to_string())?;
+    println!("[BACKEND] watching {url} as {handle} every {interval}h");
+    db.find_watched_collection(id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "watch not found".into())
+}
+
+#[tauri::command]
+pub async fn list_watched_collections() -> Result<Vec<WatchedCollection>, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    db.list_watched_collections().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_watched_collection(
+    id: i64,
+    interval_hours: u32,
+    auto_queue: bool,
+) -> Result<(), String> {
+    crate::settings::ensure_writable()?;
+    let db = Database::new().map_err(|e| e.to_string())?;
+    db.update_watched_collection(id, interval_hours.max(1), auto_queue)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Stop watching; rows it already added stay.
+#[tauri::command]
+pub async fn unwatch_collection(id: i64) -> Result<(), String> {
+    crate::settings::ensure_writable()?;
+    let db = Database::new().map_err(|e| e.to_string())?;
+    db.unwatch_collection(id)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Check one watch now instead of waiting for its interval. Returns how many
+/// new items it added.
+#[tauri::command]
+pub async fn sync_watched_collection(
+    app: tauri::AppHandle,
+    manager: State<'_, DownloadManager>,
+    id: i64,
+) -> Result<usize, String> {
+    crate::settings::ensure_writable()?;
+    let watch = Database::new()
+        .and_then(|db| db.find_watched_collection(id))
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "watch not found".to_string())?;
+    crate::download::watch::sync(&app, &manager, watch).await
+}
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::database::{Database, DigestMode, SessionReport, Settings, SmtpConfig};
+
+/// How often the scheduler checks whether a digest is due.
+const CHECK_EVERY: Duration = Duration::from_secs(60 * 60);
+const FAILED_ITEMS_LISTED: usize = 20;
+
+/// Session reports since the previous digest, folded into one summary.
+struct Digest {
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    sessions: usize,
+    downloaded: u32,
+    failed: u32,
+    bytes: u64,
+    failures: Vec<(String, u32)>,
+    top_collections: Vec<(String, u32)>,
+    failed_items: Vec<(String, String)>,
+}
+
+fn merge_counts(into: &mut HashMap<String, u32>, from: &[(String, u32)]) {
+    for (k, n) in from {
+        *into.entry(k.clone()).or_default() += n;
+    }
+}
+
+fn sorted_counts(map: HashMap<String, u32>, limit: usize) -> Vec<(String, u32)> {
+    let mut v: Vec<(String, u32)> = map.into_iter().collect();
+    v.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    v.truncate(limit);
+    v
+}
+
+fn build_digest(db: &Database, since: DateTime<Utc>) -> Result<Option<Digest>, String> {
+    let reports: Vec<SessionReport> = db
+        .list_sessions_since(&since.to_rfc3339())
+        .map_err(|e| e.to_string())?;
+    if reports.is_empty() {
+        return Ok(None);
+    }
+    let mut failures = HashMap::new();
+    let mut collections = HashMap::new();
+    for r in &reports {
+        merge_counts(&mut failures, &r.failures);
+        merge_counts(&mut collections, &r.top_collections);
+    }
+    Ok(Some(Digest {
+        since,
+        until: Utc::now(),
+        sessions: reports.len(),
+        downloaded: reports.iter().map(|r| r.downloaded).sum(),
+        failed: reports.iter().map(|r| r.failed).sum(),
+        bytes: reports.iter().map(|r| r.bytes).sum(),
+        failures: sorted_counts(failures, usize::MAX),
+        top_collections: sorted_counts(collections, 10),
+        failed_items: db
+            .list_recent_errors(FAILED_ITEMS_LISTED)
+            .map_err(|e| e.to_string())?,
+    }))
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_html(d: &Digest) -> String {
+    let list = |items: &[(String, u32)]| -> String {
+        items
+            .iter()
+            .map(|(k, n)| format!("<li>{} ({n})</li>", escape(k)))
+            .collect()
+    };
+    let failed_items: String = d
+        .failed_items
+        .iter()
+        .map(|(link, err)| {
+            format!(
+                "<li><a href=\"{0}\">{0}</a><br><small>{1}</small></li>",
+                escape(link),
+                escape(err)
+            )
+        })
+        .collect();
+    format!(
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>Clip Downloader digest</title></head>\n<body style=\"font-family: sans-serif\">\n\
+         <h1>Clip Downloader digest</h1>\n\
+         <p>{} – {} · {} sessions</p>\n\
+         <p><strong>{}</strong> downloaded · <strong>{}</strong> failed · {:.1} MB</p>\n\
+         <h2>Top collections</h2><ul>{}</ul>\n\
+         <h2>Failures by category</h2><ul>{}</ul>\n\
+         <h2>Currently failing</h2><ul>{}</ul>\n\
+         </body></html>\n",
+        d.since.format("%Y-%m-%d %H:%M"),
+        d.until.format("%Y-%m-%d %H:%M"),
+        d.sessions,
+        d.downloaded,
+        d.failed,
+        d.bytes as f64 / (1024.0 * 1024.0),
+        list(&d.top_collections),
+        list(&d.failures),
+        failed_items,
+    )
+}
+
+fn send_email(smtp: &SmtpConfig, subject: &str, html: String) -> Result<(), String> {
+    use lettre::message::{header::ContentType, Mailbox};
+    use lettre::transport::smtp::authentication::Credentials;
+    use lettre::{Message, SmtpTransport, Transport};
+
+    if smtp.host.trim().is_empty() || smtp.to.trim().is_empty() {
+        return Err("SMTP host and recipient are required".into());
+    }
+    let from = if smtp.from.trim().is_empty() {
+        &smtp.username
+    } else {
+        &smtp.from
+    };
+    let email = Message::builder()
+        .from(
+            from.parse::<Mailbox>()
+                .map_err(|e| format!("bad from address: {e}"))?,
+        )
+        .to(smtp
+            .to
+            .parse::<Mailbox>()
+            .map_err(|e| format!("bad to address: {e}"))?)
+        .subject(subject)
+        .header(ContentType::TEXT_HTML)
+        .body(html)
+        .map_err(|e| e.to_string())?;
+    let builder = if smtp.starttls {
+        SmtpTransport::starttls_relay(smtp.host.trim())
+    } else {
+        SmtpTransport::relay(smtp.host.trim())
+    }
+    .map_err(|e| e.to_string())?;
+    let mut builder = builder.port(smtp.port);
+    if !smtp.username.is_empty() {
+        builder = builder.credentials(Credentials::new(
+            smtp.username.clone(),
+            smtp.password.clone(),
+        ));
+    }
+    builder.build().send(&email).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Build and deliver the digest covering everything since the last one (or the last day).
+/// Returns where it went, or `None` when there was nothing to report.
+pub async fn deliver(settings: Settings) -> Result<Option<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = Database::new().map_err(|e| e.to_string())?;
+        let since = db
+            .last_digest_at()
+            .map_err(|e| e.to_string())?
+            .unwrap_or_else(|| Utc::now() - chrono::Duration::days(1));
+        let Some(digest) = build_digest(&db, since)? else {
+            db.mark_digest_sent(Utc::now()).map_err(|e| e.to_string())?;
+            return Ok(None);
+        };
+        let html = render_html(&digest);
+        let target = match settings.digest_mode {
+            DigestMode::Off => return Ok(None),
+            DigestMode::LocalHtml => {
+                let dir = crate::libraries::active_dir().join("digests");
+                fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+                let path = dir.join(format!("digest-{}.html", digest.until.format("%Y-%m-%d")));
+                fs::write(&path, html).map_err(|e| e.to_string())?;
+                path.to_string_lossy().to_string()
+            }
+            DigestMode::Email => {
+                let subject = format!(
+                    "Clip Downloader: {} downloaded, {} failed",
+                    digest.downloaded, digest.failed
+                );
+                send_email(&settings.smtp, &subject, html)?;
+                settings.smtp.to.clone()
+            }
+        };
+        db.mark_digest_sent(digest.until)
+            .map_err(|e| e.to_string())?;
+        println!("[BACKEND] digest delivered to {target}");
+        Ok(Some(target))
+    })
+    .await
+    .map_err(|e| format!("Join error: {e}"))?
+}
+
+/// Background loop: once a day, deliver the digest if one is configured.
+pub async fn run_scheduler() {
+    loop {
+        let settings = crate::settings::load_settings();
+        if settings.digest_mode != DigestMode::Off {
+            let due = Database::new()
+                .and_then(|db| db.last_digest_at())
+                .map(|last| last.map_or(true, |t| Utc::now() - t >= chrono::Duration::days(1)))
+                .unwrap_or(false);
+            if due {
+                if let Err(e) = deliver(settings).await {
+                    println!("[BACKEND] digest failed: {e}");
+                }
+            }
+        }
+        tokio::time::sleep(CHECK_EVERY).await;
+    }
+}
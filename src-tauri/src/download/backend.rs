@@ -0,0 +1,42 @@
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::database::OnDuplicate;
+use crate::download::http::DirectHttpBackend;
+use crate::download::manager::DownloadEvent;
+use crate::download::process::JobHandle;
+use crate::platforms::Tool;
+
+/// Everything a backend needs to fetch one row.
+pub struct BackendJob<'a> {
+    pub row_id: i64,
+    pub url: &'a str,
+    pub dest_dir: &'a Path,
+    pub on_duplicate: &'a OnDuplicate,
+    pub emitter: Arc<dyn Fn(DownloadEvent) + Send + Sync>,
+    /// Records the partial file so a cancel can remove it
+    pub job: &'a JobHandle,
+}
+
+/// Resolves to the final file path (`None` when skipped as a duplicate).
+pub type BackendFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Option<String>, String>> + Send + 'a>>;
+
+/// A downloader that runs in-process instead of through a sidecar tool.
+///
+/// Sidecar tools (yt-dlp, gallery-dl) need browser cookies and stay in the
+/// pipeline's cookie loop; backends registered here are tried before it.
+pub trait DownloadBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn download<'a>(&'a self, job: BackendJob<'a>) -> BackendFuture<'a>;
+}
+
+/// In-process backend registered for `tool`, if any.
+pub fn for_tool(tool: Tool) -> Option<&'static dyn DownloadBackend> {
+    match tool {
+        Tool::DirectHttp => Some(&DirectHttpBackend),
+        Tool::YtDlp | Tool::GalleryDl => None,
+    }
+}
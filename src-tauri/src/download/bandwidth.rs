@@ -0,0 +1,69 @@
+//! Download rate cap shared by every tool. The `max_download_rate_kbps` setting
+//! sets it at startup and on save; `set_bandwidth_limit` changes it at runtime
+//! without touching settings.json.
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+/// Kilobits per second; 0 = unlimited.
+static LIMIT_KBPS: AtomicU32 = AtomicU32::new(0);
+
+pub fn limit_kbps() -> u32 {
+    LIMIT_KBPS.load(Ordering::Relaxed)
+}
+
+pub fn set_limit_kbps(kbps: u32) {
+    LIMIT_KBPS.store(kbps, Ordering::Relaxed);
+}
+
+fn bytes_per_sec(kbps: u32) -> u64 {
+    kbps as u64 * 1000 / 8
+}
+
+/// yt-dlp takes the rate in bytes per second; applies to downloads started from now on.
+pub fn yt_dlp_flags() -> Vec<String> {
+    match limit_kbps() {
+        0 => vec![],
+        kbps => vec!["--limit-rate".into(), bytes_per_sec(kbps).to_string()],
+    }
+}
+
+/// gallery-dl uses the same flag and unit as yt-dlp.
+pub fn gallery_dl_flags() -> Vec<String> {
+    yt_dlp_flags()
+}
+
+/// Paces an in-process transfer; re-reads the limit on every chunk so runtime
+/// changes apply to downloads already running.
+pub struct Throttle {
+    window_start: Instant,
+    window_bytes: u64,
+    window_kbps: u32,
+}
+
+impl Throttle {
+    pub fn new() -> Self {
+        Throttle {
+            window_start: Instant::now(),
+            window_bytes: 0,
+            window_kbps: limit_kbps(),
+        }
+    }
+
+    /// Record `n` bytes and sleep long enough to stay under the limit.
+    pub async fn pace(&mut self, n: usize) {
+        let kbps = limit_kbps();
+        if kbps != self.window_kbps {
+            // Limit changed: measure from here on.
+            *self = Throttle::new();
+        }
+        if kbps == 0 {
+            return;
+        }
+        self.window_bytes += n as u64;
+        let due = Duration::from_secs_f64(self.window_bytes as f64 / bytes_per_sec(kbps) as f64);
+        let elapsed = self.window_start.elapsed();
+        if due > elapsed {
+            tokio::time::sleep(due - elapsed).await;
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::fs::{self, File};
+use std::path::Path;
+
+use crate::database::{CollectionIndex, Database};
+
+/// One listed file: name inside the folder, source link, download date, title.
+struct Entry {
+    file: String,
+    link: String,
+    date: String,
+    title: String,
+}
+
+/// Rewrite the index of the folder download `id` was saved into, listing every
+/// finished item of its collection stored there. Errors are only logged.
+pub fn regenerate(id: i64) {
+    let settings = crate::settings::load_settings();
+    if settings.collection_index == CollectionIndex::Off || settings.read_only {
+        return;
+    }
+    if let Err(e) = regenerate_with(id, settings.collection_index) {
+        eprintln!("[BACKEND] collection index for download {id} not written: {e}");
+    }
+}
+
+fn regenerate_with(id: i64, kind: CollectionIndex) -> Result<(), String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let Some(row) = db.find_download_by_id(id).map_err(|e| e.to_string())? else {
+        return Ok(());
+    };
+    let Some(folder) = Path::new(&row.path).parent() else {
+        return Ok(());
+    };
+    let entries: Vec<Entry> = db
+        .list_done_index_entries(&row.platform, &row.user_handle, &row.origin)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter_map(|(path, link, date, title)| {
+            let path = Path::new(&path);
+            if path.parent() != Some(folder) {
+                return None;
+            }
+            Some(Entry {
+                file: path.file_name()?.to_string_lossy().to_string(),
+                link,
+                date: date
+                    .map(|d| d.chars().take(10).collect())
+                    .unwrap_or_default(),
+                title: title.unwrap_or_default(),
+            })
+        })
+        .collect();
+    if entries.is_empty() {
+        return Ok(());
+    }
+    match kind {
+        CollectionIndex::Off => Ok(()),
+        CollectionIndex::Csv => write_csv(&folder.join("index.csv"), &entries),
+        CollectionIndex::Readme => {
+            let heading = format!("{} | {} ({})", row.user_handle, row.origin, row.platform);
+            fs::write(folder.join("README.md"), readme(&heading, &entries))
+                .map_err(|e| e.to_string())
+        }
+    }
+}
+
+fn write_csv(path: &Path, entries: &[Entry]) -> Result<(), String> {
+    let mut w = csv::Writer::from_writer(File::create(path).map_err(|e| e.to_string())?);
+    w.write_record(["file", "title", "source", "downloaded"])
+        .map_err(|e| e.to_string())?;
+    for e in entries {
+        w.write_record([&e.file, &e.title, &e.link, &e.date])
+            .map_err(|e| e.to_string())?;
+    }
+    w.flush().map_err(|e| e.to_string())
+}
+
+/// Markdown table cells can't hold pipes or line breaks.
+fn md_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+fn readme(heading: &str, entries: &[Entry]) -> String {
+    let mut out = format!(
+        "# {}\n\n{} files, saved by ClipDownloader.\n\n| File | Title | Source | Downloaded |\n|---|---|---|---|\n",
+        md_cell(heading),
+        entries.len()
+    );
+    for e in entries {
+        out.push_str(&format!(
+            "| [{}](<{}>) | {} | <{}> | {} |\n",
+            md_cell(&e.file),
+            e.file.replace('>', "%3E"),
+            md_cell(&e.title),
+            e.link,
+            e.date
+        ));
+    }
+    out
+}
@@ -0,0 +1,178 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tauri::ipc::Channel;
+use tokio::sync::mpsc;
+
+use crate::download::manager::DownloadEvent;
+
+/// Events buffered per subscriber before progress updates start being dropped.
+const SUBSCRIBER_BUFFER: usize = 256;
+
+/// One subscriber's queue and how many events wait in it.
+struct Subscriber {
+    tx: mpsc::UnboundedSender<DownloadEvent>,
+    queued: Arc<AtomicUsize>,
+}
+
+/// Fan-out of `DownloadEvent`s to per-subscription IPC channels.
+///
+/// Every page/window subscribes with its own `Channel`; each subscriber gets a
+/// queue drained in order by a forwarding task, so one slow webview cannot
+/// stall the download manager or the other subscribers.
+pub struct EventHub {
+    next_id: AtomicU32,
+    subscribers: Mutex<HashMap<u32, Subscriber>>,
+}
+
+impl EventHub {
+    pub fn new() -> Self {
+        Self {
+            next_id: AtomicU32::new(1),
+            subscribers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn subscribe(&self, channel: Channel<DownloadEvent>) -> u32 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, mut rx) = mpsc::unbounded_channel::<DownloadEvent>();
+        let queued = Arc::new(AtomicUsize::new(0));
+        let drained = queued.clone();
+        tauri::async_runtime::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                drained.fetch_sub(1, Ordering::Relaxed);
+                if let Err(err) = channel.send(event) {
+                    eprintln!("[EventHub] subscriber {id} send failed: {err}");
+                    break;
+                }
+            }
+        });
+        if let Ok(mut subs) = self.subscribers.lock() {
+            subs.insert(id, Subscriber { tx, queued });
+        }
+        println!("[EventHub] subscriber {id} attached");
+        id
+    }
+
+    pub fn unsubscribe(&self, id: u32) -> bool {
+        let removed = self
+            .subscribers
+            .lock()
+            .map(|mut subs| subs.remove(&id).is_some())
+            .unwrap_or(false);
+        if removed {
+            println!("[EventHub] subscriber {id} detached");
+        }
+        removed
+    }
+
+    /// Deliver `event` to every subscriber.
+    ///
+    /// Once `SUBSCRIBER_BUFFER` events are waiting, `Progress` is dropped (the
+    /// next update supersedes it); any other event is queued behind the rest,
+    /// so state changes are never lost or reordered.
+    pub fn publish(&self, event: &DownloadEvent) {
+        let Ok(mut subs) = self.subscribers.lock() else {
+            return;
+        };
+        let is_progress = matches!(event, DownloadEvent::Progress { .. });
+        subs.retain(|_, sub| {
+            if is_progress && sub.queued.load(Ordering::Relaxed) >= SUBSCRIBER_BUFFER {
+                return !sub.tx.is_closed();
+            }
+            sub.queued.fetch_add(1, Ordering::Relaxed);
+            sub.tx.send(event.clone()).is_ok()
+        });
+    }
+}
+
+impl fmt::Debug for EventHub {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventHub").finish_non_exhaustive()
+    }
+}
+
+/// Per-id rate limiter for `Progress` events.
+///
+/// yt-dlp's `--newline` output can produce hundreds of progress lines per second;
+/// only the latest one inside each interval is kept. Other events pass untouched.
+/// A held-back update goes out once its interval is up (the caller waits out
+/// `arm` and calls `release`), and `flush` hands back whatever progress is
+/// still pending when a job ends.
+pub struct ProgressThrottle {
+    min_interval: Option<Duration>,
+    last_sent: HashMap<i64, Instant>,
+    pending: HashMap<i64, DownloadEvent>,
+    /// Ids with a `release` already scheduled.
+    armed: HashSet<i64>,
+}
+
+impl ProgressThrottle {
+    /// `updates_per_sec == 0` disables throttling.
+    pub fn new(updates_per_sec: u8) -> Self {
+        let min_interval =
+            (updates_per_sec > 0).then(|| Duration::from_millis(1000 / u64::from(updates_per_sec)));
+        Self {
+            min_interval,
+            last_sent: HashMap::new(),
+            pending: HashMap::new(),
+            armed: HashSet::new(),
+        }
+    }
+
+    /// Returns the event to emit now, if any.
+    pub fn admit(&mut self, event: DownloadEvent) -> Option<DownloadEvent> {
+        let DownloadEvent::Progress { id, progress, .. } = &event else {
+            return Some(event);
+        };
+        let (id, finished) = (*id, *progress >= 1.0);
+        let Some(min_interval) = self.min_interval else {
+            return Some(event);
+        };
+
+        let now = Instant::now();
+        let due = self
+            .last_sent
+            .get(&id)
+            .map_or(true, |last| now.duration_since(*last) >= min_interval);
+        if due || finished {
+            self.last_sent.insert(id, now);
+            self.pending.remove(&id);
+            Some(event)
+        } else {
+            self.pending.insert(id, event);
+            None
+        }
+    }
+
+    /// Time left in the interval when an update for `id` is held back and no
+    /// `release` is scheduled yet; the caller calls `release` after it.
+    pub fn arm(&mut self, id: i64) -> Option<Duration> {
+        let min_interval = self.min_interval?;
+        if !self.pending.contains_key(&id) || !self.armed.insert(id) {
+            return None;
+        }
+        let elapsed = self
+            .last_sent
+            .get(&id)
+            .map_or(min_interval, |last| last.elapsed());
+        Some(min_interval.saturating_sub(elapsed))
+    }
+
+    /// The held-back update for `id`, if a newer one hasn't gone out meanwhile.
+    pub fn release(&mut self, id: i64) -> Option<DownloadEvent> {
+        self.armed.remove(&id);
+        let event = self.pending.remove(&id)?;
+        self.last_sent.insert(id, Instant::now());
+        Some(event)
+    }
+
+    pub fn flush(&mut self, id: i64) -> Option<DownloadEvent> {
+        self.last_sent.remove(&id);
+        self.armed.remove(&id);
+        self.pending.remove(&id)
+    }
+}
@@ -0,0 +1,131 @@
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use tauri_plugin_shell::ShellExt;
+use tokio::time::{timeout, Duration};
+
+use crate::database::{Settings, ToolAdvisory, ToolReport};
+
+/// A platform known to misbehave with tool versions older than `fixed_in`.
+/// Maintained by hand; add an entry when an extractor breaks for a site.
+struct Advisory {
+    tool: &'static str,
+    platform: &'static str,
+    fixed_in: &'static str,
+    message: &'static str,
+}
+
+const ADVISORIES: &[Advisory] = &[
+    Advisory {
+        tool: "yt-dlp",
+        platform: "tiktok",
+        fixed_in: "2025.01.15",
+        message: "TikTok downloads are broken before yt-dlp 2025.01.15",
+    },
+    Advisory {
+        tool: "yt-dlp",
+        platform: "youtube",
+        fixed_in: "2024.12.23",
+        message: "YouTube formats go missing before yt-dlp 2024.12.23",
+    },
+    Advisory {
+        tool: "yt-dlp",
+        platform: "instagram",
+        fixed_in: "2024.11.04",
+        message: "Instagram reels fail to extract before yt-dlp 2024.11.04",
+    },
+    Advisory {
+        tool: "gallery-dl",
+        platform: "instagram",
+        fixed_in: "1.27.0",
+        message: "Instagram posts fail with gallery-dl before 1.27.0",
+    },
+];
+
+static REPORT: Lazy<Mutex<Option<ToolReport>>> = Lazy::new(|| Mutex::new(None));
+
+/// Dotted version as comparable numbers ("2024.08.06" → [2024, 8, 6]).
+fn version_key(v: &str) -> Vec<u64> {
+    v.split(|c: char| !c.is_ascii_digit())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse().unwrap_or(0))
+        .collect()
+}
+
+fn advisories_for(tool: &str, version: &str) -> Vec<ToolAdvisory> {
+    let have = version_key(version);
+    ADVISORIES
+        .iter()
+        .filter(|a| a.tool == tool && have < version_key(a.fixed_in))
+        .map(|a| ToolAdvisory {
+            tool: a.tool.to_string(),
+            version: version.to_string(),
+            platform: a.platform.to_string(),
+            message: a.message.to_string(),
+        })
+        .collect()
+}
+
+/// First line of `<tool> --version`, using the same binary the downloads use.
+async fn tool_version(app: &tauri::AppHandle, tool: &str, settings: &Settings) -> Option<String> {
+    let cmd = if settings.use_system_binaries {
+        app.shell().command(tool)
+    } else {
+        app.shell().sidecar(tool).ok()?
+    };
+    let env = crate::settings::tool_env(settings, std::env::var("PATH").unwrap_or_default());
+    let out = timeout(
+        Duration::from_secs(15),
+        cmd.args(["--version"]).envs(env).output(),
+    )
+    .await
+    .ok()?
+    .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .next()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+}
+
+/// Run both extractors once, cache their versions and match them against the advisory list.
+pub async fn probe(app: &tauri::AppHandle) -> ToolReport {
+    let settings = crate::settings::load_settings();
+    let yt_dlp = tool_version(app, "yt-dlp", &settings).await;
+    let gallery_dl = tool_version(app, "gallery-dl", &settings).await;
+    let mut advisories = Vec::new();
+    if let Some(v) = &yt_dlp {
+        advisories.extend(advisories_for("yt-dlp", v));
+    }
+    if let Some(v) = &gallery_dl {
+        advisories.extend(advisories_for("gallery-dl", v));
+    }
+    println!(
+        "[BACKEND] extractors: yt-dlp {} / gallery-dl {}",
+        yt_dlp.as_deref().unwrap_or("missing"),
+        gallery_dl.as_deref().unwrap_or("missing")
+    );
+    for a in &advisories {
+        println!(
+            "[BACKEND] advisory ({} {}): {}",
+            a.tool, a.version, a.message
+        );
+    }
+    let report = ToolReport {
+        yt_dlp,
+        gallery_dl,
+        advisories,
+    };
+    if let Ok(mut cached) = REPORT.lock() {
+        *cached = Some(report.clone());
+    }
+    report
+}
+
+/// Versions from the last probe, if one has finished.
+pub fn cached() -> Option<ToolReport> {
+    REPORT.lock().ok().and_then(|r| r.clone())
+}
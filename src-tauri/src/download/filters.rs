@@ -0,0 +1,111 @@
+//! Per-collection length, size and keyword limits, checked right before a
+//! download starts. Rows whose length, size or title isn't known yet are
+//! probed first.
+use regex::{Regex, RegexBuilder};
+use tauri::AppHandle;
+
+use crate::database::{CollectionFilter, Database};
+
+/// Case-insensitive regex for a filter pattern.
+fn compile(pattern: &str) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| format!("Invalid pattern '{pattern}': {e}"))
+}
+
+/// Reject patterns that don't compile before they are saved.
+pub fn validate(filter: &CollectionFilter) -> Result<(), String> {
+    for pattern in [&filter.include, &filter.exclude].into_iter().flatten() {
+        compile(pattern)?;
+    }
+    Ok(())
+}
+
+/// Why an item titled `title` is left out by the include/exclude patterns.
+/// An unknown title passes; so does a pattern that no longer compiles.
+fn keyword_reason(
+    filter: &CollectionFilter,
+    title: Option<&str>,
+    description: Option<&str>,
+) -> Option<String> {
+    let title = title?;
+    let text = format!("{title}\n{}", description.unwrap_or_default());
+    let matches = |pattern: &str| match compile(pattern) {
+        Ok(re) => Some(re.is_match(&text)),
+        Err(e) => {
+            eprintln!("[BACKEND] [filters] {e}");
+            None
+        }
+    };
+    if let Some(include) = filter.include.as_deref() {
+        if matches(include) == Some(false) {
+            return Some(format!("Skipped: title doesn't match '{include}'"));
+        }
+    }
+    if let Some(exclude) = filter.exclude.as_deref() {
+        if matches(exclude) == Some(true) {
+            return Some(format!("Skipped: title matches '{exclude}'"));
+        }
+    }
+    None
+}
+
+/// Why download `id` should be skipped, or `None` to go ahead. A failed probe
+/// lets the download run rather than guess.
+pub async fn skip_reason(app: &AppHandle, id: i64) -> Option<String> {
+    let found = tauri::async_runtime::spawn_blocking(move || {
+        Database::new()
+            .and_then(|db| db.filter_for_download(id))
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .ok()?;
+    let (filter, mut subject) = match found {
+        Ok(found) => found?,
+        Err(e) => {
+            eprintln!("[BACKEND] [filters] lookup for {id} failed: {e}");
+            return None;
+        }
+    };
+    if filter.is_empty() {
+        return None;
+    }
+
+    if filter.needs_probe(
+        subject.duration_secs,
+        subject.estimated_bytes,
+        subject.title.as_deref(),
+    ) {
+        let settings = crate::settings::load_settings();
+        let (_, cookie_arg) = crate::settings::cookie_sources(&settings, &subject.link, None)
+            .into_iter()
+            .next()?;
+        match crate::download::video::probe_metadata(app, &cookie_arg, &subject.link).await {
+            Ok(meta) => {
+                subject.duration_secs = subject.duration_secs.or(meta.duration_secs);
+                subject.estimated_bytes = subject.estimated_bytes.or(meta.estimated_bytes);
+                subject.title = subject.title.or(meta.title.clone());
+                let _ = tauri::async_runtime::spawn_blocking(move || {
+                    Database::new().and_then(|db| db.set_media_metadata(id, &meta))
+                })
+                .await;
+            }
+            Err(e) => println!("[BACKEND] [filters] probe for {} failed: {e}", subject.link),
+        }
+    }
+
+    let reason = filter
+        .skip_reason(subject.duration_secs, subject.estimated_bytes)
+        .or_else(|| {
+            keyword_reason(
+                &filter,
+                subject.title.as_deref(),
+                subject.description.as_deref(),
+            )
+        });
+    if let Some(reason) = &reason {
+        println!("[BACKEND] [filters] {}: {reason}", subject.link);
+    }
+    reason
+}
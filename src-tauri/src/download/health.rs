@@ -0,0 +1,68 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use clip_core::{FailureKind, PlatformPause};
+
+/// Finished downloads remembered per platform.
+const WINDOW: usize = 20;
+/// Share of failures in a full window that pauses the platform.
+const FAILURE_RATIO: f32 = 0.8;
+
+/// Rolling success/failure record per platform. A platform whose recent
+/// downloads mostly failed is held back until the user resumes it, so one
+/// broken extractor or a rate limit doesn't burn through the whole queue.
+#[derive(Default)]
+pub struct PlatformHealth {
+    /// `None` = success, otherwise the failure's cause; newest last
+    recent: HashMap<String, VecDeque<Option<FailureKind>>>,
+    paused: BTreeMap<String, PlatformPause>,
+}
+
+impl PlatformHealth {
+    /// Note how a download of `platform` ended; returns the pause when this
+    /// result tipped the platform over the threshold.
+    pub fn record(&mut self, platform: &str, error: Option<&str>) -> Option<PlatformPause> {
+        if platform.is_empty() || self.paused.contains_key(platform) {
+            return None;
+        }
+        let recent = self.recent.entry(platform.to_string()).or_default();
+        recent.push_back(error.map(|e| FailureKind::classify(Some(e))));
+        if recent.len() > WINDOW {
+            recent.pop_front();
+        }
+        let failures: Vec<FailureKind> = recent.iter().flatten().copied().collect();
+        if recent.len() < WINDOW || (failures.len() as f32) < FAILURE_RATIO * WINDOW as f32 {
+            return None;
+        }
+        let mut counts: BTreeMap<FailureKind, usize> = BTreeMap::new();
+        for kind in &failures {
+            *counts.entry(*kind).or_default() += 1;
+        }
+        let dominant = counts
+            .into_iter()
+            .max_by_key(|(_, n)| *n)
+            .map_or(FailureKind::Other, |(kind, _)| kind);
+        let pause = PlatformPause {
+            platform: platform.to_string(),
+            failures: failures.len() as u32,
+            window: WINDOW as u32,
+            dominant,
+            paused_at: chrono::Utc::now().to_rfc3339(),
+        };
+        self.paused.insert(platform.to_string(), pause.clone());
+        Some(pause)
+    }
+
+    pub fn is_paused(&self, platform: &str) -> bool {
+        self.paused.contains_key(platform)
+    }
+
+    /// Let the platform dispatch again with a clean record.
+    pub fn resume(&mut self, platform: &str) -> bool {
+        self.recent.remove(platform);
+        self.paused.remove(platform).is_some()
+    }
+
+    pub fn paused(&self) -> Vec<PlatformPause> {
+        self.paused.values().cloned().collect()
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{CONTENT_RANGE, CONTENT_TYPE, RANGE};
+use reqwest::StatusCode;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::download::backend::{BackendFuture, BackendJob, DownloadBackend};
+use crate::download::manager::{DownloadEvent, MessageLevel};
+use crate::download::pipeline::move_with_policy;
+use crate::utils::checksum::sha256_file;
+use crate::utils::filenames::NAME_SUFFIX_RESERVE;
+
+const USER_AGENT: &str = concat!("clip-downloader/", env!("CARGO_PKG_VERSION"));
+
+/// Plain HTTP(S) fetch for direct media links (.mp4, .jpg, ...), no extractor involved.
+///
+/// Bytes land in `<dest>/<name>.part`; an interrupted download is resumed with
+/// a `Range` request the next time the row runs. Canceling removes the partial.
+pub struct DirectHttpBackend;
+
+impl DownloadBackend for DirectHttpBackend {
+    fn name(&self) -> &'static str {
+        "direct-http"
+    }
+
+    fn download<'a>(&'a self, job: BackendJob<'a>) -> BackendFuture<'a> {
+        Box::pin(download_direct(job))
+    }
+}
+
+async fn download_direct(job: BackendJob<'_>) -> Result<Option<String>, String> {
+    let emit_msg = |message: String| {
+        (job.emitter)(DownloadEvent::Message {
+            id: job.row_id,
+            level: MessageLevel::Milestone,
+            message,
+        })
+    };
+
+    fs::create_dir_all(job.dest_dir).map_err(|e| format!("Failed to create dest dir: {e}"))?;
+    let settings = crate::settings::load_settings();
+    let rules = crate::utils::filenames::rules_for(job.dest_dir, &settings);
+    let base_name = rules.fit_file_name(&file_name_from_url(job.url), NAME_SUFFIX_RESERVE);
+    let part_path = job.dest_dir.join(format!("{base_name}.part"));
+    job.job.note_output(job.dest_dir.join(&base_name));
+    let mut resume_from = fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0);
+
+    let mut builder = reqwest::Client::builder().user_agent(USER_AGENT);
+    if let Some(proxy) = crate::settings::proxy_for_url(&settings, job.url) {
+        builder = builder.proxy(proxy_from_url(&proxy)?);
+    }
+    let client = builder.build().map_err(|e| e.to_string())?;
+    let headers = crate::settings::headers_for_url(&settings, job.url);
+    let send = |from: u64| {
+        let mut request = client.get(job.url);
+        for (name, value) in &headers {
+            request = request.header(name, value);
+        }
+        if from > 0 {
+            emit_msg(format!("Resuming at {from} bytes"));
+            request = request.header(RANGE, format!("bytes={from}-"));
+        }
+        request.send()
+    };
+    let mut resp = send(resume_from).await.map_err(connection_error)?;
+    // The server's copy changed size since the partial was written: a resume
+    // can never succeed, so drop the stale `.part` and fetch from the start.
+    if resp.status() == StatusCode::RANGE_NOT_SATISFIABLE
+        && resume_from > 0
+        && range_total(resp.headers().get(CONTENT_RANGE)) != Some(resume_from)
+    {
+        emit_msg("Partial download no longer matches the server; starting over".into());
+        fs::remove_file(&part_path)
+            .map_err(|e| format!("Failed to remove {}: {e}", part_path.display()))?;
+        resume_from = 0;
+        resp = send(0).await.map_err(connection_error)?;
+    }
+    let status = resp.status();
+    let final_name = with_extension_from_content_type(
+        &base_name,
+        resp.headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok()),
+    );
+
+    let already_complete = status == StatusCode::RANGE_NOT_SATISFIABLE
+        && resume_from > 0
+        && range_total(resp.headers().get(CONTENT_RANGE)) == Some(resume_from);
+    if !already_complete {
+        if !status.is_success() {
+            return Err(format!("HTTP {status} for {}", job.url));
+        }
+
+        let resumed = resume_from > 0 && status == StatusCode::PARTIAL_CONTENT;
+        let mut downloaded = if resumed { resume_from } else { 0 };
+        let total = resp.content_length().map(|len| len + downloaded);
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(&part_path)
+            .await
+            .map_err(|e| format!("Failed to open {}: {e}", part_path.display()))?;
+
+        let mut throttle = super::bandwidth::Throttle::new();
+        while let Some(chunk) = resp.chunk().await.map_err(connection_error)? {
+            throttle.pace(chunk.len()).await;
+            file.write_all(&chunk).await.map_err(|e| e.to_string())?;
+            downloaded += chunk.len() as u64;
+            (job.emitter)(DownloadEvent::Progress {
+                id: job.row_id,
+                progress: total.map_or(0.0, |t| downloaded as f32 / t.max(1) as f32),
+                downloaded_bytes: downloaded,
+                total_bytes: total,
+            });
+        }
+        file.flush().await.map_err(|e| e.to_string())?;
+
+        if let Some(total) = total {
+            if downloaded != total {
+                return Err(format!(
+                    "network: incomplete download, got {downloaded} of {total} bytes (will resume)"
+                ));
+            }
+        }
+    }
+
+    let hash_path = part_path.clone();
+    let digest = tauri::async_runtime::spawn_blocking(move || sha256_file(&hash_path))
+        .await
+        .map_err(|e| format!("Join error: {e}"))?
+        .map_err(|e| format!("Checksum failed: {e}"))?;
+
+    match move_with_policy(&part_path, job.dest_dir, &final_name, job.on_duplicate) {
+        Ok((Some(fp), action)) => {
+            emit_msg(format!("{action}: {fp}"));
+            // Stored by the manager once the row is done.
+            job.job.note_content_hash(digest);
+            Ok(Some(fp))
+        }
+        Ok((None, _)) => {
+            emit_msg(format!(
+                "Skipped (exists): {}",
+                job.dest_dir.join(&final_name).display()
+            ));
+            Ok(None)
+        }
+        Err(e) => Err(format!(
+            "Failed to move {} → {}: {e}",
+            part_path.display(),
+            job.dest_dir.join(&final_name).display()
+        )),
+    }
+}
+
+/// A failed request or body read, worded so the retry policy sees it as
+/// network trouble. reqwest's own message ("error sending request for url")
+/// hides the cause in its source chain, so that is spelled out too.
+fn connection_error(e: reqwest::Error) -> String {
+    let mut message = format!("connection error: {e}");
+    let mut source = std::error::Error::source(&e);
+    while let Some(cause) = source {
+        message.push_str(&format!(": {cause}"));
+        source = cause.source();
+    }
+    message
+}
+
+/* ---------- proxy ---------- */
+
+/// Small page fetched through a proxy to check that it works.
+const PROXY_PROBE_URL: &str = "https://www.youtube.com/generate_204";
+
+fn proxy_from_url(proxy: &str) -> Result<reqwest::Proxy, String> {
+    reqwest::Proxy::all(proxy).map_err(|e| format!("Invalid proxy '{proxy}': {e}"))
+}
+
+/// Fetch a tiny page through `proxy`; returns the round trip in milliseconds.
+pub async fn probe_proxy(proxy: &str) -> Result<u64, String> {
+    let client = reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(Duration::from_secs(15))
+        .proxy(proxy_from_url(proxy)?)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let started = std::time::Instant::now();
+    let resp = client
+        .get(PROXY_PROBE_URL)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("Proxy answered {}", resp.status()));
+    }
+    Ok(started.elapsed().as_millis() as u64)
+}
+
+/* ---------- link health check ---------- */
+
+/// Parallel HEAD requests when checking links during an import.
+const LINK_CHECK_CONCURRENCY: usize = 8;
+
+/// HEAD-check `urls` a few at a time; returns the ones that answered 404 or
+/// 410 with that status. Timeouts, other errors and sites that reject HEAD
+/// count as alive, since a false "dead" would drop a good link. Each link
+/// goes through the proxy its platform is set to use.
+pub async fn dead_links(urls: Vec<String>) -> HashMap<String, u16> {
+    let settings = crate::settings::load_settings();
+    // One client per proxy; `None` when it could not be built.
+    let mut clients: HashMap<Option<String>, Option<reqwest::Client>> = HashMap::new();
+    let permits = Arc::new(Semaphore::new(LINK_CHECK_CONCURRENCY));
+    let mut tasks = JoinSet::new();
+    for url in urls {
+        let proxy = crate::settings::proxy_for_url(&settings, &url);
+        let client = clients.entry(proxy).or_insert_with_key(|proxy| {
+            link_check_client(proxy.as_deref())
+                .map_err(|e| eprintln!("[import] link check disabled for some links: {e}"))
+                .ok()
+        });
+        let Some(client) = client.clone() else {
+            continue;
+        };
+        let permits = permits.clone();
+        let headers = crate::settings::headers_for_url(&settings, &url);
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await.ok()?;
+            let mut request = client.head(&url);
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+            let status = request.send().await.ok()?.status();
+            matches!(status, StatusCode::NOT_FOUND | StatusCode::GONE)
+                .then(|| (url, status.as_u16()))
+        });
+    }
+    let mut dead = HashMap::new();
+    while let Some(res) = tasks.join_next().await {
+        if let Ok(Some((url, code))) = res {
+            dead.insert(url, code);
+        }
+    }
+    dead
+}
+
+fn link_check_client(proxy: Option<&str>) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(Duration::from_secs(10));
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(proxy_from_url(proxy)?);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// Last path segment of `url`, reduced to filesystem-safe characters.
+fn file_name_from_url(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let after_host = path.split_once("://").map_or(path, |(_, rest)| rest);
+    let segment = after_host
+        .split_once('/')
+        .and_then(|(_, p)| p.rsplit('/').find(|s| !s.is_empty()))
+        .unwrap_or("download");
+    let cleaned: String = segment
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if cleaned.trim_matches('.').is_empty() {
+        "download".into()
+    } else {
+        cleaned
+    }
+}
+
+fn with_extension_from_content_type(name: &str, content_type: Option<&str>) -> String {
+    if name
+        .rsplit_once('.')
+        .map_or(false, |(s, e)| !s.is_empty() && !e.is_empty())
+    {
+        return name.to_string();
+    }
+    let ext = match content_type.map(|ct| ct.split(';').next().unwrap_or("").trim()) {
+        Some("video/mp4") => "mp4",
+        Some("video/webm") => "webm",
+        Some("image/jpeg") => "jpg",
+        Some("image/png") => "png",
+        Some("image/gif") => "gif",
+        Some("image/webp") => "webp",
+        _ => "bin",
+    };
+    format!("{name}.{ext}")
+}
+
+/// Total size from a `Content-Range: bytes */<total>` header.
+fn range_total(value: Option<&reqwest::header::HeaderValue>) -> Option<u64> {
+    value?
+        .to_str()
+        .ok()?
+        .rsplit_once('/')?
+        .1
+        .trim()
+        .parse()
+        .ok()
+}
@@ -2,23 +2,12 @@ use std::io;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::Manager;
-use tauri_plugin_shell::{
-    process::{CommandChild, CommandEvent},
-    ShellExt,
-};
+use tauri_plugin_shell::{process::CommandEvent, ShellExt};
 use tempfile::tempdir;
 use tokio::time::{timeout, Duration};
 
-use crate::download::manager::DownloadEvent;
-
-struct KillGuard(Option<CommandChild>);
-impl Drop for KillGuard {
-    fn drop(&mut self) {
-        if let Some(c) = self.0.take() {
-            let _ = c.kill();
-        }
-    }
-}
+use crate::download::manager::{DownloadEvent, MessageLevel};
+use crate::download::process::JobHandle;
 
 #[cfg(target_family = "windows")]
 fn path_sep() -> &'static str {
@@ -37,10 +26,12 @@ pub async fn run_gallery_dl_to_temp(
     cookie_arg: &str,
     id: i64,
     emitter: Arc<dyn Fn(DownloadEvent) + Send + Sync>,
+    job: &JobHandle,
 ) -> io::Result<(bool, String, PathBuf)> {
     let tmp = tempdir()?;
     #[allow(deprecated)]
     let tmp_path = tmp.into_path(); // keep the directory; caller cleans up
+    job.note_temp_dir(&tmp_path);
 
     let res_dir = app.path().resource_dir().unwrap_or_else(|_| {
         std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."))
@@ -57,14 +48,17 @@ pub async fn run_gallery_dl_to_temp(
         )
     };
 
-    let args = vec![
+    let mut args: Vec<String> = vec![
         "--verbose".into(),
-        "--cookies-from-browser".into(),
-        cookie_arg.into(),
         "-d".into(),
         tmp_path.display().to_string(),
-        url.into(),
     ];
+    args.extend(crate::utils::os::cookie_flags(cookie_arg));
+    args.extend(crate::settings::get_gallery_dl_header_flags(&settings, url));
+    args.extend(crate::settings::get_gallery_dl_proxy_flags(&settings, url));
+    args.extend(crate::settings::get_gallery_dl_metadata_flags(&settings));
+    args.extend(super::bandwidth::gallery_dl_flags());
+    args.push(url.into());
 
     let cmd = if settings.use_system_binaries {
         app.shell().command("gallery-dl")
@@ -77,14 +71,15 @@ pub async fn run_gallery_dl_to_temp(
         })?
     };
 
-    let (mut rx, child) = cmd.args(args).env("PATH", new_path).spawn().map_err(|e| {
+    let env = crate::settings::tool_env(&settings, new_path);
+    let (mut rx, child) = cmd.args(args).envs(env).spawn().map_err(|e| {
         io::Error::new(
             io::ErrorKind::Other,
             format!("spawn gallery-dl failed: {e}"),
         )
     })?;
 
-    let _guard = KillGuard(Some(child));
+    let _child = job.attach(child);
 
     let mut all_output = String::new();
     let mut ok = false;
@@ -115,6 +110,7 @@ pub async fn run_gallery_dl_to_temp(
                         all_output.push('\n');
                         (emitter)(DownloadEvent::Message {
                             id,
+                            level: MessageLevel::Debug,
                             message: l.to_string(),
                         });
                     }
@@ -125,6 +121,7 @@ pub async fn run_gallery_dl_to_temp(
                 all_output.push_str(&s);
             }
             CommandEvent::Terminated(code) => {
+                job.note_exit(code.code);
                 ok = code.code == Some(0);
             }
             _ => {}
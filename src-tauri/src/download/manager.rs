@@ -1,48 +1,43 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
+use std::path::Path;
 use std::sync::Arc;
 
 use tokio::sync::{mpsc, oneshot};
 
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Manager};
 
 use crate::database::{
-    find_download_by_id_conn, list_all_ui_conn, list_downloading_ids_conn, list_error_ids_conn,
-    list_queued_ids_conn, mark_id_done_conn, reset_stale_downloading_to_queued_conn,
-    set_last_error_by_id_conn, set_status_bulk_conn, set_status_by_id_conn, DownloadStatus,
-    UiBacklogRow,
+    bump_attempts_conn, find_download_by_id_conn, insert_session_conn, list_all_ui_conn,
+    list_downloading_ids_conn, list_error_ids_conn, list_queued_ids_conn,
+    list_queued_platforms_conn, list_queued_priorities_conn, mark_id_done_conn,
+    reset_for_requeue_conn, reset_stale_downloading_to_queued_conn, set_last_error_by_id_conn,
+    set_priorities_conn, set_status_bulk_conn, set_status_by_id_conn, ClipRow, DownloadStatus,
+    FailureKind,
 };
+use crate::download::collection_index;
+use crate::download::events::{EventHub, ProgressThrottle};
+use crate::download::filters;
+use crate::download::health::PlatformHealth;
+use crate::download::mirrors;
 use crate::download::pipeline;
+use crate::download::process::JobHandle;
+use crate::download::schedule::{self, Schedule};
+use crate::download::session_report::{SessionTally, TaskOutcome};
+use crate::download::volume;
+use crate::download::watch;
 use crate::settings;
 use rusqlite::Connection;
 
-#[derive(Debug, Clone, serde::Serialize)]
-#[serde(tag = "type")]
-pub enum DownloadEvent {
-    StatusChanged {
-        id: i64,
-        status: DownloadStatus,
-    },
-    BulkStatusChanged {
-        ids: Vec<i64>,
-        status: DownloadStatus,
-    },
-    Progress {
-        id: i64,
-        progress: f32,
-        downloaded_bytes: u64,
-        total_bytes: Option<u64>,
-    },
-    Message {
-        id: i64,
-        message: String,
-    },
-}
+pub use clip_core::events::{DownloadEvent, MessageLevel};
+use clip_core::PlatformPause;
 
 #[derive(Debug)]
 pub enum DownloadCommand {
     Enqueue {
         ids: Vec<i64>,
+        /// Receives the ids that were actually queued (duplicates are skipped).
+        reply: Option<oneshot::Sender<Vec<i64>>>,
     },
     MoveToBacklog {
         ids: Vec<i64>,
@@ -50,25 +45,74 @@ pub enum DownloadCommand {
     Cancel {
         id: i64,
     },
+    /// Stop one download (or hold it in the queue), keeping its partial files.
+    PauseDownload {
+        id: i64,
+    },
+    /// Queue a paused download again; yt-dlp continues its partial files.
+    ResumeDownload {
+        id: i64,
+    },
+    /// Queue a finished or failed download to be fetched again; `overwrite`
+    /// replaces the file it left behind.
+    Redownload {
+        id: i64,
+        overwrite: bool,
+        reply: Option<oneshot::Sender<Vec<i64>>>,
+    },
     StartNow {
         id: i64,
         overrides: Option<DownloadOverrides>,
     },
     RefreshSettings,
     SetPaused(bool),
+    /// Periodic check whether a download window opened or closed.
+    ScheduleTick,
     ReconcileState,
     RefreshSnapshot {
-        reply: oneshot::Sender<Result<Vec<UiBacklogRow>, String>>,
+        reply: oneshot::Sender<Result<Vec<ClipRow>, String>>,
     },
     TaskFinished {
         id: i64,
+        outcome: TaskOutcome,
+    },
+    /// A download failed in a way worth retrying; queue it again, but not
+    /// before `delay_secs` have passed.
+    RetryLater {
+        id: i64,
+        error: String,
+        delay_secs: u64,
+    },
+    /// A delayed retry may start now.
+    RetryDue,
+    SetPriority {
+        id: i64,
+        priority: i64,
+    },
+    /// Move a queued row right before (or after) `anchor` in start order.
+    ReorderQueue {
+        id: i64,
+        anchor: i64,
+        after: bool,
+        reply: oneshot::Sender<Result<(), String>>,
+    },
+    /// Let a platform held back by its failure rate dispatch again.
+    ResumePlatform {
+        platform: String,
+    },
+    PausedPlatforms {
+        reply: oneshot::Sender<Vec<PlatformPause>>,
     },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct DownloadOverrides {
     pub force_audio: Option<bool>,
     pub flat_destination: bool,
+    /// Continue partial files left by a pause
+    pub resume: bool,
+    /// Replace an existing file of the same name, whatever `on_duplicate` says
+    pub overwrite: bool,
 }
 
 #[derive(Clone)]
@@ -95,8 +139,128 @@ impl fmt::Debug for DownloadManager {
     }
 }
 
+/// Automatic retries of a transiently failed download, with exponential backoff.
+#[derive(Debug, Clone, Copy)]
+struct RetryPolicy {
+    max_retries: u32,
+    delay_secs: u32,
+}
+
+impl RetryPolicy {
+    fn from_settings(s: &crate::database::Settings) -> Self {
+        Self {
+            max_retries: s.max_retries,
+            delay_secs: s.retry_delay_secs,
+        }
+    }
+
+    /// Wait before retry number `attempt` (1-based), capped at one hour.
+    fn delay_for(&self, attempt: u32) -> u64 {
+        let factor = 1u64 << attempt.saturating_sub(1).min(16);
+        (self.delay_secs as u64 * factor).min(3600)
+    }
+}
+
+/// Failures within `FAILURE_WINDOW` that count as a burst.
+const FAILURE_BURST: usize = 3;
+const FAILURE_WINDOW: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// AIMD-style parallelism: a burst of failures drops to one download at a
+/// time, then every `limit` successes in a row allow one more, up to the
+/// configured maximum. Keeps a network hiccup from failing the whole queue.
+#[derive(Debug)]
+struct Ramp {
+    limit: usize,
+    max: usize,
+    successes: usize,
+    failures: VecDeque<std::time::Instant>,
+}
+
+impl Ramp {
+    fn new(max: usize) -> Self {
+        Self {
+            limit: max,
+            max,
+            successes: 0,
+            failures: VecDeque::new(),
+        }
+    }
+
+    /// A new configured maximum. Takes effect at once unless a failure burst
+    /// is still being ramped back up from, which then climbs towards it.
+    fn set_max(&mut self, max: usize) {
+        self.limit = if self.limit >= self.max {
+            max
+        } else {
+            self.limit.min(max)
+        };
+        self.max = max;
+    }
+
+    fn record(&mut self, failed: bool) {
+        if !failed {
+            self.successes += 1;
+            if self.limit < self.max && self.successes >= self.limit {
+                self.limit += 1;
+                self.successes = 0;
+                println!(
+                    "[BACKEND] parallelism ramped up to {}/{}",
+                    self.limit, self.max
+                );
+            }
+            return;
+        }
+        let now = std::time::Instant::now();
+        self.failures.push_back(now);
+        while self
+            .failures
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > FAILURE_WINDOW)
+        {
+            self.failures.pop_front();
+        }
+        self.successes = 0;
+        if self.failures.len() >= FAILURE_BURST && self.limit > 1 {
+            self.limit = 1;
+            self.failures.clear();
+            println!(
+                "[BACKEND] failure burst; parallelism dropped to 1/{}",
+                self.max
+            );
+        }
+    }
+}
+
 struct ActiveTask {
     handle: tauri::async_runtime::JoinHandle<()>,
+    job: JobHandle,
+    /// What the task was started with, restored when it is paused
+    overrides: Option<DownloadOverrides>,
+    /// Platform id, counted against its `max_parallel` override
+    platform: String,
+}
+
+impl ActiveTask {
+    /// Kill the tool process and drop the task, leaving partial files in place.
+    fn halt(self) -> Option<DownloadOverrides> {
+        drop(self.job.cancel());
+        self.handle.abort();
+        self.overrides
+    }
+
+    /// Kill the tool process, drop the task, then remove its partial files
+    /// once the process has let go of them.
+    fn stop(self, id: i64) {
+        let leftovers = self.job.cancel();
+        self.handle.abort();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            let removed = leftovers.remove();
+            if removed > 0 {
+                println!("[BACKEND] removed {removed} partial file(s) of download {id}");
+            }
+        });
+    }
 }
 
 pub async fn run_download_manager(
@@ -109,14 +273,57 @@ pub async fn run_download_manager(
     let mut active: HashMap<i64, ActiveTask> = HashMap::new();
     let mut overrides: HashMap<i64, DownloadOverrides> = HashMap::new();
     let initial_settings = settings::load_settings();
-    let mut paused = !initial_settings.download_automatically;
-    let mut max_parallel = initial_settings.parallel_downloads.max(1) as usize;
+    // The profile picker and the crash-recovery prompt both decide before anything starts.
+    let mut paused = !initial_settings.download_automatically
+        || crate::profiles::prompt_pending()
+        || app.state::<crate::session::PendingRecovery>().is_pending();
+    let mut ramp = Ramp::new(initial_settings.parallel_downloads.max(1) as usize);
     let mut cooldown_secs = initial_settings.cooldown_secs;
     let mut retry_on_queue_empty = initial_settings.retry_on_queue_empty;
+    let mut retry = RetryPolicy::from_settings(&initial_settings);
     let mut auto_retried: HashSet<i64> = HashSet::new();
+    // Queued retries and the earliest time each may start again.
+    let mut not_before: HashMap<i64, std::time::Instant> = HashMap::new();
+    let mut health = PlatformHealth::default();
+    let mut tally = SessionTally::default();
+    let mut schedule = Schedule::from_settings(&initial_settings);
+    let mut window_open = schedule.is_open(chrono::Local::now().naive_local());
+    let mut volume_online = !volume::is_offline(Path::new(&initial_settings.download_directory));
+
+    // Download windows open and close on the clock, not on a command; the
+    // same tick notices the download drive being unplugged or reconnected.
+    {
+        let tick_tx = cmd_tx.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+                schedule::CHECK_INTERVAL_SECS,
+            ));
+            loop {
+                interval.tick().await;
+                if tick_tx.send(DownloadCommand::ScheduleTick).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
 
-    // On startup, recover any rows stuck in 'downloading' from a previous run
+    // Watched collections are listed again once their interval has passed.
     {
+        let watch_app = app.clone();
+        let manager = DownloadManager::new(cmd_tx.clone());
+        tauri::async_runtime::spawn(async move {
+            let mut interval =
+                tokio::time::interval(std::time::Duration::from_secs(watch::CHECK_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                watch::sync_due(&watch_app, &manager).await;
+            }
+        });
+    }
+
+    // On startup, recover any rows stuck in 'downloading' from a previous run.
+    // `priorities` holds non-default priorities of queued rows; missing = 0.
+    let mut priorities: HashMap<i64, i64> = {
         let db_clone = db.clone();
         let queued_ids = tauri::async_runtime::spawn_blocking(move || {
             let conn = db_clone.blocking_lock();
@@ -137,7 +344,8 @@ pub async fn run_download_manager(
                 queue.push_back(id);
             }
         }
-    }
+        load_priorities(db.clone()).await
+    };
 
     maybe_start_next(
         &app,
@@ -145,28 +353,49 @@ pub async fn run_download_manager(
         &mut queue,
         &mut active,
         &mut overrides,
-        paused,
-        max_parallel,
+        &mut priorities,
+        &not_before,
+        &health,
+        paused || !window_open || !volume_online,
+        ramp.limit,
         cooldown_secs,
+        retry,
         &cmd_tx,
         false,
     )
     .await;
+    if !active.is_empty() {
+        tally.start();
+    }
 
     while let Some(cmd) = cmd_rx.recv().await {
         let mut force_start = false;
         match cmd {
-            DownloadCommand::Enqueue { ids } => {
+            DownloadCommand::Enqueue { ids, reply } => {
                 auto_retried.clear();
-                enqueue_ids(
+                for id in &ids {
+                    not_before.remove(id);
+                }
+                let queued = enqueue_ids(
                     &app,
                     db.clone(),
                     &ids,
                     &mut queue,
                     &active,
+                    &mut priorities,
                     DownloadStatus::Queued,
                 )
                 .await;
+                if queued.len() < ids.len() {
+                    println!(
+                        "[BACKEND] enqueue: {} of {} ids were already queued or active",
+                        ids.len() - queued.len(),
+                        ids.len()
+                    );
+                }
+                if let Some(reply) = reply {
+                    let _ = reply.send(queued);
+                }
             }
             DownloadCommand::MoveToBacklog { ids } => {
                 move_to_backlog(
@@ -190,7 +419,60 @@ pub async fn run_download_manager(
                 )
                 .await;
             }
+            DownloadCommand::PauseDownload { id } => {
+                pause_download(
+                    &app,
+                    db.clone(),
+                    id,
+                    &mut queue,
+                    &mut active,
+                    &mut overrides,
+                )
+                .await;
+            }
+            DownloadCommand::ResumeDownload { id } => {
+                overrides.entry(id).or_default().resume = true;
+                let queued = enqueue_ids(
+                    &app,
+                    db.clone(),
+                    &[id],
+                    &mut queue,
+                    &active,
+                    &mut priorities,
+                    DownloadStatus::Queued,
+                )
+                .await;
+                if queued.is_empty() {
+                    overrides.remove(&id);
+                }
+            }
+            DownloadCommand::Redownload {
+                id,
+                overwrite,
+                reply,
+            } => {
+                if overwrite {
+                    overrides.entry(id).or_default().overwrite = true;
+                }
+                let queued = enqueue_ids(
+                    &app,
+                    db.clone(),
+                    &[id],
+                    &mut queue,
+                    &active,
+                    &mut priorities,
+                    DownloadStatus::Queued,
+                )
+                .await;
+                if queued.is_empty() && overwrite {
+                    overrides.remove(&id);
+                }
+                if let Some(reply) = reply {
+                    let _ = reply.send(queued);
+                }
+            }
             DownloadCommand::StartNow { id, overrides: ov } => {
+                not_before.remove(&id);
                 if let Some(custom) = ov {
                     overrides.insert(id, custom);
                 }
@@ -200,6 +482,7 @@ pub async fn run_download_manager(
                     &[id],
                     &mut queue,
                     &active,
+                    &mut priorities,
                     DownloadStatus::Queued,
                 )
                 .await;
@@ -207,28 +490,79 @@ pub async fn run_download_manager(
             }
             DownloadCommand::RefreshSettings => {
                 let s = settings::load_settings();
-                max_parallel = s.parallel_downloads.max(1) as usize;
+                ramp.set_max(s.parallel_downloads.max(1) as usize);
                 cooldown_secs = s.cooldown_secs;
                 retry_on_queue_empty = s.retry_on_queue_empty;
+                retry = RetryPolicy::from_settings(&s);
+                schedule = Schedule::from_settings(&s);
+                window_open = schedule.is_open(chrono::Local::now().naive_local());
+                publish_schedule(&app, &schedule);
+                volume_online = !volume::is_offline(Path::new(&s.download_directory));
+                publish_volume(&app, &s.download_directory);
                 tracing::info!(
-                    "Updated max_parallel={} cooldown={}s retry_on_empty={}",
-                    max_parallel,
+                    "Updated max_parallel={} cooldown={}s retry_on_empty={} retries={}x{}s",
+                    ramp.max,
                     cooldown_secs,
-                    retry_on_queue_empty
+                    retry_on_queue_empty,
+                    retry.max_retries,
+                    retry.delay_secs
                 );
             }
             DownloadCommand::SetPaused(next) => {
                 paused = next;
+                publish_paused(&app, paused);
+            }
+            DownloadCommand::ScheduleTick => {
+                let open = schedule.is_open(chrono::Local::now().naive_local());
+                if open != window_open {
+                    window_open = open;
+                    println!(
+                        "[BACKEND] download window {}",
+                        if open {
+                            "opened; resuming queue"
+                        } else {
+                            "closed; holding queue"
+                        }
+                    );
+                    publish_schedule(&app, &schedule);
+                }
+                let s = settings::load_settings();
+                let online = !volume::is_offline(Path::new(&s.download_directory));
+                if online != volume_online {
+                    volume_online = online;
+                    println!(
+                        "[BACKEND] download drive {}",
+                        if online {
+                            "reconnected; resuming queue"
+                        } else {
+                            "disconnected; holding queue"
+                        }
+                    );
+                    publish_volume(&app, &s.download_directory);
+                }
             }
             DownloadCommand::ReconcileState => {
                 reconcile_state(&app, db.clone(), &mut queue, &active).await;
+                priorities = load_priorities(db.clone()).await;
             }
             DownloadCommand::RefreshSnapshot { reply } => {
                 reconcile_state(&app, db.clone(), &mut queue, &active).await;
+                priorities = load_priorities(db.clone()).await;
                 let _ = reply.send(snapshot_downloads(db.clone()).await);
             }
-            DownloadCommand::TaskFinished { id } => {
-                active.remove(&id);
+            DownloadCommand::TaskFinished { id, outcome } => {
+                let platform = active.remove(&id).map(|t| t.platform).unwrap_or_default();
+                // Filtered-out items say nothing about the platform's health.
+                if !outcome.skipped {
+                    record_result(
+                        &app,
+                        &mut health,
+                        &mut ramp,
+                        &platform,
+                        outcome.error.as_deref(),
+                    );
+                }
+                tally.record(outcome);
                 if retry_on_queue_empty && !paused && queue.is_empty() && active.is_empty() {
                     let db_clone = db.clone();
                     let error_ids = tauri::async_runtime::spawn_blocking(move || {
@@ -244,6 +578,78 @@ pub async fn run_download_manager(
                         }
                     }
                 }
+                if queue.is_empty() && active.is_empty() {
+                    if let Some(report) = tally.finish() {
+                        save_session_report(&app, db.clone(), report).await;
+                    }
+                }
+            }
+            DownloadCommand::RetryLater {
+                id,
+                error,
+                delay_secs,
+            } => {
+                // Moved to the backlog or cancelled meanwhile: leave it there.
+                if let Some(task) = active.remove(&id) {
+                    // A failure worth retrying still counts towards a burst.
+                    record_result(&app, &mut health, &mut ramp, &task.platform, Some(&error));
+                    not_before.insert(
+                        id,
+                        std::time::Instant::now() + std::time::Duration::from_secs(delay_secs),
+                    );
+                    if !queue.contains(&id) {
+                        queue.push_back(id);
+                    }
+                    let tx = cmd_tx.clone();
+                    tauri::async_runtime::spawn(async move {
+                        tokio::time::sleep(std::time::Duration::from_secs(delay_secs)).await;
+                        let _ = tx.send(DownloadCommand::RetryDue).await;
+                    });
+                }
+            }
+            DownloadCommand::RetryDue => {
+                let now = std::time::Instant::now();
+                not_before.retain(|id, at| *at > now && queue.contains(id));
+            }
+            DownloadCommand::ResumePlatform { platform } => {
+                if health.resume(&platform) {
+                    println!("[BACKEND] resuming {platform}");
+                    publish_paused_platforms(&app, &health);
+                }
+            }
+            DownloadCommand::PausedPlatforms { reply } => {
+                let _ = reply.send(health.paused());
+            }
+            DownloadCommand::SetPriority { id, priority } => {
+                if priority == 0 {
+                    priorities.remove(&id);
+                } else {
+                    priorities.insert(id, priority);
+                }
+                save_priorities(db.clone(), vec![(id, priority)]).await;
+            }
+            DownloadCommand::ReorderQueue {
+                id,
+                anchor,
+                after,
+                reply,
+            } => {
+                let result = match reorder_queue(&queue, &priorities, id, anchor, after) {
+                    Some(order) => {
+                        // Renumber the whole queue so the new order is unambiguous.
+                        let n = order.len() as i64;
+                        let renumbered: Vec<(i64, i64)> = order
+                            .iter()
+                            .enumerate()
+                            .map(|(i, qid)| (*qid, n - i as i64))
+                            .collect();
+                        priorities = renumbered.iter().copied().collect();
+                        save_priorities(db.clone(), renumbered).await;
+                        Ok(())
+                    }
+                    None => Err("both rows must be waiting in the queue".to_string()),
+                };
+                let _ = reply.send(result);
             }
         }
 
@@ -253,13 +659,21 @@ pub async fn run_download_manager(
             &mut queue,
             &mut active,
             &mut overrides,
-            paused,
-            max_parallel,
+            &mut priorities,
+            &not_before,
+            &health,
+            paused || !window_open || !volume_online,
+            ramp.limit,
             cooldown_secs,
+            retry,
             &cmd_tx,
-            force_start,
+            force_start && volume_online,
         )
         .await;
+        if !active.is_empty() {
+            tally.start();
+        }
+        crate::tray::update(&app, active.len(), queue.len(), paused);
     }
 }
 
@@ -309,7 +723,7 @@ async fn reconcile_state(
 
 async fn snapshot_downloads(
     db: Arc<tokio::sync::Mutex<Connection>>,
-) -> Result<Vec<UiBacklogRow>, String> {
+) -> Result<Vec<ClipRow>, String> {
     tauri::async_runtime::spawn_blocking(move || {
         let conn = db.blocking_lock();
         list_all_ui_conn(&*conn).map_err(|e| e.to_string())
@@ -318,18 +732,21 @@ async fn snapshot_downloads(
     .map_err(|e| format!("Join error: {e}"))?
 }
 
+/// Queue `ids`, skipping rows that are already active or waiting (and repeats
+/// within `ids`). Returns the ids that were actually queued.
 async fn enqueue_ids(
     app: &AppHandle,
     db: Arc<tokio::sync::Mutex<Connection>>,
     ids: &[i64],
     queue: &mut VecDeque<i64>,
     active: &HashMap<i64, ActiveTask>,
+    priorities: &mut HashMap<i64, i64>,
     status: DownloadStatus,
-) {
+) -> Vec<i64> {
     if ids.len() == 1 {
         let id = ids[0];
         if active.contains_key(&id) || queue.contains(&id) {
-            return;
+            return Vec::new();
         }
         let changed = match set_status(db.clone(), id, status).await {
             Ok(c) => c,
@@ -338,26 +755,30 @@ async fn enqueue_ids(
                     app,
                     DownloadEvent::Message {
                         id,
+                        level: MessageLevel::Error,
                         message: format!("Failed to set status: {err}"),
                     },
                 );
-                return;
+                return Vec::new();
             }
         };
         if changed {
             emit_event(app, DownloadEvent::StatusChanged { id, status });
         }
+        reset_for_requeue(db.clone(), vec![id]).await;
+        priorities.remove(&id);
         queue.push_back(id);
-        return;
+        return vec![id];
     }
 
+    let mut seen = HashSet::new();
     let to_enqueue: Vec<i64> = ids
         .iter()
         .copied()
-        .filter(|id| !active.contains_key(id) && !queue.contains(id))
+        .filter(|id| !active.contains_key(id) && !queue.contains(id) && seen.insert(*id))
         .collect();
     if to_enqueue.is_empty() {
-        return;
+        return Vec::new();
     }
 
     let db_clone = db.clone();
@@ -380,9 +801,12 @@ async fn enqueue_ids(
             },
         );
     }
-    for id in to_enqueue {
-        queue.push_back(id);
+    reset_for_requeue(db.clone(), to_enqueue.clone()).await;
+    for id in &to_enqueue {
+        priorities.remove(id);
+        queue.push_back(*id);
     }
+    to_enqueue
 }
 
 async fn move_to_backlog(
@@ -396,7 +820,7 @@ async fn move_to_backlog(
     for id in ids {
         queue.retain(|queued| queued != id);
         if let Some(task) = active.remove(id) {
-            task.handle.abort();
+            task.stop(*id);
         }
         overrides.remove(id);
         let changed = match set_status(db.clone(), *id, DownloadStatus::Backlog).await {
@@ -406,6 +830,7 @@ async fn move_to_backlog(
                     app,
                     DownloadEvent::Message {
                         id: *id,
+                        level: MessageLevel::Error,
                         message: format!("Failed to move to backlog: {err}"),
                     },
                 );
@@ -435,7 +860,7 @@ async fn cancel_active(
     queue.retain(|queued| *queued != id);
     overrides.remove(&id);
     if let Some(task) = active.remove(&id) {
-        task.handle.abort();
+        task.stop(id);
     }
     let changed = match set_status(db.clone(), id, DownloadStatus::Canceled).await {
         Ok(c) => c,
@@ -444,6 +869,7 @@ async fn cancel_active(
                 app,
                 DownloadEvent::Message {
                     id,
+                    level: MessageLevel::Error,
                     message: format!("Failed to cancel: {err}"),
                 },
             );
@@ -462,25 +888,94 @@ async fn cancel_active(
     }
 }
 
+async fn pause_download(
+    app: &AppHandle,
+    db: Arc<tokio::sync::Mutex<Connection>>,
+    id: i64,
+    queue: &mut VecDeque<i64>,
+    active: &mut HashMap<i64, ActiveTask>,
+    overrides: &mut HashMap<i64, DownloadOverrides>,
+) {
+    let was_queued = queue.contains(&id);
+    queue.retain(|queued| *queued != id);
+    match active.remove(&id) {
+        Some(task) => {
+            if let Some(started_with) = task.halt() {
+                overrides.insert(id, started_with);
+            }
+            println!("[BACKEND] paused download {id}; partial files kept");
+        }
+        None if !was_queued => return,
+        None => {}
+    }
+    let changed = match set_status(db.clone(), id, DownloadStatus::Paused).await {
+        Ok(c) => c,
+        Err(err) => {
+            emit_event(
+                app,
+                DownloadEvent::Message {
+                    id,
+                    level: MessageLevel::Error,
+                    message: format!("Failed to pause: {err}"),
+                },
+            );
+            return;
+        }
+    };
+    if changed {
+        emit_event(
+            app,
+            DownloadEvent::StatusChanged {
+                id,
+                status: DownloadStatus::Paused,
+            },
+        );
+    }
+}
+
 async fn maybe_start_next(
     app: &AppHandle,
     db: Arc<tokio::sync::Mutex<Connection>>,
     queue: &mut VecDeque<i64>,
     active: &mut HashMap<i64, ActiveTask>,
     overrides: &mut HashMap<i64, DownloadOverrides>,
+    priorities: &mut HashMap<i64, i64>,
+    not_before: &HashMap<i64, std::time::Instant>,
+    health: &PlatformHealth,
     paused: bool,
     max_parallel: usize,
     cooldown_secs: u32,
+    retry: RetryPolicy,
     cmd_tx: &mpsc::Sender<DownloadCommand>,
     force: bool,
 ) {
     if paused && !force {
         return;
     }
+    let limits = platform_limits(&settings::load_settings());
+    let platforms = if queue.is_empty() {
+        HashMap::new()
+    } else {
+        load_queued_platforms(db.clone()).await
+    };
     while active.len() < max_parallel {
-        let Some(id) = queue.pop_front() else {
+        let full: HashSet<&str> = limits
+            .iter()
+            .filter(|(p, max)| active.values().filter(|t| t.platform == **p).count() >= **max)
+            .map(|(p, _)| p.as_str())
+            .collect();
+        let now = std::time::Instant::now();
+        let startable = |id: i64| {
+            not_before.get(&id).map_or(true, |at| *at <= now)
+                && platforms
+                    .get(&id)
+                    .map_or(true, |p| !full.contains(p.as_str()) && !health.is_paused(p))
+        };
+        let Some(id) = next_index(queue, priorities, startable).and_then(|i| queue.remove(i))
+        else {
             break;
         };
+        priorities.remove(&id);
         if active.contains_key(&id) {
             continue;
         }
@@ -492,6 +987,7 @@ async fn maybe_start_next(
                     app,
                     DownloadEvent::Message {
                         id,
+                        level: MessageLevel::Error,
                         message: format!("Failed to mark downloading: {err}"),
                     },
                 );
@@ -512,16 +1008,125 @@ async fn maybe_start_next(
         let tx_clone = cmd_tx.clone();
         let db_clone = db.clone();
         let opts = overrides.remove(&id);
+        let job = if opts.as_ref().is_some_and(|o| o.resume) {
+            JobHandle::resuming()
+        } else {
+            JobHandle::default()
+        };
+        let started_with = opts.clone();
+        let task_job = job.clone();
         let handle = tauri::async_runtime::spawn(async move {
             if cooldown_secs > 0 {
                 tokio::time::sleep(std::time::Duration::from_secs(cooldown_secs as u64)).await;
             }
-            match run_download_with_progress(&app_clone, db_clone.clone(), id, opts).await {
+            if let Some(reason) = filters::skip_reason(&app_clone, id).await {
+                let _ = set_status(db_clone.clone(), id, DownloadStatus::Skipped).await;
+                let _ = set_last_error(db_clone.clone(), id, Some(reason.clone())).await;
+                emit_event(
+                    &app_clone,
+                    DownloadEvent::Message {
+                        id,
+                        level: MessageLevel::Milestone,
+                        message: reason,
+                    },
+                );
+                emit_event(
+                    &app_clone,
+                    DownloadEvent::StatusChanged {
+                        id,
+                        status: DownloadStatus::Skipped,
+                    },
+                );
+                let outcome = TaskOutcome {
+                    skipped: true,
+                    ..TaskOutcome::default()
+                };
+                let _ = tx_clone
+                    .send(DownloadCommand::TaskFinished { id, outcome })
+                    .await;
+                return;
+            }
+            let result = run_download_with_progress(
+                &app_clone,
+                db_clone.clone(),
+                id,
+                opts,
+                task_job.clone(),
+            )
+            .await;
+            // Transient failures go back to the queue to wait out their backoff
+            // there, leaving the slot to other downloads; the rest fail now.
+            if let Err(err_msg) = &result {
+                if FailureKind::classify(Some(err_msg)).is_transient() {
+                    let attempt = bump_attempts(db_clone.clone(), id)
+                        .await
+                        .unwrap_or(u32::MAX);
+                    if attempt <= retry.max_retries {
+                        let delay = retry.delay_for(attempt);
+                        let _ = set_last_error(db_clone.clone(), id, Some(err_msg.clone())).await;
+                        let _ = set_status(db_clone.clone(), id, DownloadStatus::Queued).await;
+                        emit_event(
+                            &app_clone,
+                            DownloadEvent::Message {
+                                id,
+                                level: MessageLevel::Error,
+                                message: err_msg.clone(),
+                            },
+                        );
+                        emit_event(
+                            &app_clone,
+                            DownloadEvent::Message {
+                                id,
+                                level: MessageLevel::Milestone,
+                                message: format!(
+                                    "Retry {attempt}/{} in {delay}s",
+                                    retry.max_retries
+                                ),
+                            },
+                        );
+                        emit_event(
+                            &app_clone,
+                            DownloadEvent::StatusChanged {
+                                id,
+                                status: DownloadStatus::Queued,
+                            },
+                        );
+                        let _ = tx_clone
+                            .send(DownloadCommand::RetryLater {
+                                id,
+                                error: err_msg.clone(),
+                                delay_secs: delay,
+                            })
+                            .await;
+                        return;
+                    }
+                }
+            }
+            let outcome = task_outcome(db_clone.clone(), id, &result).await;
+            let label = row_label(db_clone.clone(), id).await;
+            crate::notifications::download_finished(
+                &app_clone,
+                &label,
+                result.as_ref().err().map(String::as_str),
+            );
+            match result {
                 Ok(path) => {
                     let _ = set_status(db_clone.clone(), id, DownloadStatus::Done).await;
                     let _ = set_last_error(db_clone.clone(), id, None).await;
                     let final_path = path.unwrap_or_default();
                     let _ = mark_download_done(db_clone.clone(), id, &final_path).await;
+                    tauri::async_runtime::spawn(store_file_info(
+                        app_clone.clone(),
+                        id,
+                        final_path.clone(),
+                    ));
+                    let known_hash = task_job.take_content_hash();
+                    if known_hash.is_some() || crate::settings::load_settings().hash_downloads {
+                        tauri::async_runtime::spawn_blocking(move || {
+                            store_content_hash(id, &final_path, known_hash)
+                        });
+                    }
+                    tauri::async_runtime::spawn_blocking(move || collection_index::regenerate(id));
                     emit_event(
                         &app_clone,
                         DownloadEvent::StatusChanged {
@@ -533,10 +1138,14 @@ async fn maybe_start_next(
                 Err(err_msg) => {
                     let _ = set_last_error(db_clone.clone(), id, Some(err_msg.clone())).await;
                     let _ = set_status(db_clone.clone(), id, DownloadStatus::Error).await;
+                    if FailureKind::classify(Some(&err_msg)) == FailureKind::Unavailable {
+                        tauri::async_runtime::spawn(mirrors::look_for(app_clone.clone(), id));
+                    }
                     emit_event(
                         &app_clone,
                         DownloadEvent::Message {
                             id,
+                            level: MessageLevel::Error,
                             message: err_msg.clone(),
                         },
                     );
@@ -549,10 +1158,160 @@ async fn maybe_start_next(
                     );
                 }
             }
-            let _ = tx_clone.send(DownloadCommand::TaskFinished { id }).await;
+            let _ = tx_clone
+                .send(DownloadCommand::TaskFinished { id, outcome })
+                .await;
         });
 
-        active.insert(id, ActiveTask { handle });
+        active.insert(
+            id,
+            ActiveTask {
+                handle,
+                job,
+                overrides: started_with,
+                platform: platforms.get(&id).cloned().unwrap_or_default(),
+            },
+        );
+    }
+}
+
+/// Feed a finished attempt to the platform's failure window and the
+/// parallelism ramp; `error` is `None` on success.
+fn record_result(
+    app: &AppHandle,
+    health: &mut PlatformHealth,
+    ramp: &mut Ramp,
+    platform: &str,
+    error: Option<&str>,
+) {
+    if let Some(pause) = health.record(platform, error) {
+        println!(
+            "[BACKEND] pausing {platform}: {} of the last {} downloads failed ({})",
+            pause.failures,
+            pause.window,
+            pause.dominant.label()
+        );
+        publish_paused_platforms(app, health);
+    }
+    ramp.record(error.is_some());
+}
+
+/// Platform id -> most downloads of it running at once, from the overrides.
+fn platform_limits(settings: &crate::database::Settings) -> HashMap<String, usize> {
+    settings
+        .platform_overrides
+        .iter()
+        .filter_map(|(p, ov)| Some((p.clone(), ov.max_parallel.filter(|n| *n > 0)? as usize)))
+        .collect()
+}
+
+async fn load_queued_platforms(db: Arc<tokio::sync::Mutex<Connection>>) -> HashMap<i64, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = db.blocking_lock();
+        list_queued_platforms_conn(&*conn).unwrap_or_default()
+    })
+    .await
+    .unwrap_or_default()
+    .into_iter()
+    .collect()
+}
+
+/// Collection label and byte count for the session tally.
+async fn task_outcome(
+    db: Arc<tokio::sync::Mutex<Connection>>,
+    id: i64,
+    result: &Result<Option<String>, String>,
+) -> TaskOutcome {
+    let bytes = match result {
+        Ok(Some(path)) => std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+        _ => 0,
+    };
+    let collection = tauri::async_runtime::spawn_blocking(move || {
+        let conn = db.blocking_lock();
+        find_download_by_id_conn(&*conn, id).ok().flatten()
+    })
+    .await
+    .ok()
+    .flatten()
+    .map(|row| format!("{}/{} | {}", row.platform, row.user_handle, row.origin))
+    .unwrap_or_else(|| "unknown".into());
+    TaskOutcome {
+        error: result.as_ref().err().cloned(),
+        bytes,
+        collection,
+        skipped: false,
+    }
+}
+
+/// Name of a row for notifications, or its link before the name is known.
+async fn row_label(db: Arc<tokio::sync::Mutex<Connection>>, id: i64) -> String {
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = db.blocking_lock();
+        find_download_by_id_conn(&*conn, id).ok().flatten()
+    })
+    .await
+    .ok()
+    .flatten()
+    .map(|row| {
+        if row.name.is_empty() {
+            row.link
+        } else {
+            row.name
+        }
+    })
+    .unwrap_or_else(|| format!("Download {id}"))
+}
+
+/// Tell the UI whether queued downloads may start and when the next window opens.
+fn publish_schedule(app: &AppHandle, schedule: &Schedule) {
+    use tauri::Emitter;
+    let _ = app.emit("download_schedule", schedule.status(chrono::Local::now()));
+}
+
+/// Tell the UI the queue was paused or resumed, possibly from the tray.
+fn publish_paused(app: &AppHandle, paused: bool) {
+    use tauri::Emitter;
+    let _ = app.emit("queue_paused", paused);
+}
+
+/// Tell the UI which platforms are held back by their failure rate.
+fn publish_paused_platforms(app: &AppHandle, health: &PlatformHealth) {
+    use tauri::Emitter;
+    let _ = app.emit("paused_platforms", health.paused());
+}
+
+/// Tell the UI whether the download drive is connected.
+fn publish_volume(app: &AppHandle, root: &str) {
+    use tauri::Emitter;
+    let _ = app.emit("download_volume", volume::status(Path::new(root)));
+}
+
+/// Persist a drained session and tell the UI about it.
+async fn save_session_report(
+    app: &AppHandle,
+    db: Arc<tokio::sync::Mutex<Connection>>,
+    mut report: crate::database::SessionReport,
+) {
+    use tauri::Emitter;
+    let to_insert = report.clone();
+    let inserted = tauri::async_runtime::spawn_blocking(move || {
+        let conn = db.blocking_lock();
+        insert_session_conn(&*conn, &to_insert).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("Join error: {e}"))
+    .and_then(|r| r);
+    match inserted {
+        Ok(id) => {
+            report.id = id;
+            println!(
+                "[BACKEND] session {id} finished: {} downloaded, {} failed",
+                report.downloaded, report.failed
+            );
+            crate::notifications::session_finished(app, &report);
+            let _ = app.emit("session_report", &report);
+        }
+        Err(e) => println!("[BACKEND] failed to save session report: {e}"),
     }
 }
 
@@ -570,6 +1329,87 @@ async fn set_status(
     Ok(changed > 0)
 }
 
+async fn bump_attempts(db: Arc<tokio::sync::Mutex<Connection>>, id: i64) -> Result<u32, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = db.blocking_lock();
+        bump_attempts_conn(&*conn, id).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("Join error: {e}"))?
+}
+
+async fn reset_for_requeue(db: Arc<tokio::sync::Mutex<Connection>>, ids: Vec<i64>) {
+    let _ = tauri::async_runtime::spawn_blocking(move || {
+        let conn = db.blocking_lock();
+        reset_for_requeue_conn(&*conn, &ids)
+    })
+    .await;
+}
+
+async fn load_priorities(db: Arc<tokio::sync::Mutex<Connection>>) -> HashMap<i64, i64> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = db.blocking_lock();
+        list_queued_priorities_conn(&*conn).unwrap_or_default()
+    })
+    .await
+    .unwrap_or_default()
+    .into_iter()
+    .collect()
+}
+
+async fn save_priorities(db: Arc<tokio::sync::Mutex<Connection>>, values: Vec<(i64, i64)>) {
+    let saved = tauri::async_runtime::spawn_blocking(move || {
+        let mut conn = db.blocking_lock();
+        set_priorities_conn(&mut conn, &values).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("Join error: {e}"))
+    .and_then(|r| r);
+    if let Err(e) = saved {
+        println!("[BACKEND] failed to save queue priorities: {e}");
+    }
+}
+
+/// Queue position of the next row to start: highest priority, then first in,
+/// among rows `startable` accepts.
+fn next_index(
+    queue: &VecDeque<i64>,
+    priorities: &HashMap<i64, i64>,
+    startable: impl Fn(i64) -> bool,
+) -> Option<usize> {
+    let mut best: Option<(usize, i64)> = None;
+    for (i, id) in queue.iter().enumerate() {
+        if !startable(*id) {
+            continue;
+        }
+        let p = priorities.get(id).copied().unwrap_or(0);
+        if best.map_or(true, |(_, bp)| p > bp) {
+            best = Some((i, p));
+        }
+    }
+    best.map(|(i, _)| i)
+}
+
+/// Start order of the queue with `id` moved next to `anchor`; `None` if either isn't queued.
+fn reorder_queue(
+    queue: &VecDeque<i64>,
+    priorities: &HashMap<i64, i64>,
+    id: i64,
+    anchor: i64,
+    after: bool,
+) -> Option<Vec<i64>> {
+    if id == anchor || !queue.contains(&id) || !queue.contains(&anchor) {
+        return None;
+    }
+    let mut order: Vec<i64> = queue.iter().copied().collect();
+    // Stable sort keeps first-in order among equal priorities.
+    order.sort_by_key(|qid| std::cmp::Reverse(priorities.get(qid).copied().unwrap_or(0)));
+    order.retain(|qid| *qid != id);
+    let pos = order.iter().position(|qid| *qid == anchor)?;
+    order.insert(if after { pos + 1 } else { pos }, id);
+    Some(order)
+}
+
 async fn set_last_error(
     db: Arc<tokio::sync::Mutex<Connection>>,
     id: i64,
@@ -586,11 +1426,76 @@ async fn set_last_error(
     Ok(())
 }
 
-fn emit_event(app: &AppHandle, event: DownloadEvent) {
-    if let Err(err) = app.emit("download_event", &event) {
-        eprintln!("emit_event failed: {err}");
-    } else {
-        println!("[DownloadEvent] {:?}", event);
+pub(crate) fn emit_event(app: &AppHandle, event: DownloadEvent) {
+    match app.try_state::<EventHub>() {
+        Some(hub) => {
+            hub.publish(&event);
+            println!("[DownloadEvent] {:?}", event);
+        }
+        None => eprintln!("emit_event failed: EventHub not managed"),
+    }
+}
+
+/// Record the checksum of a finished file for the library check, reusing
+/// `known` when the backend already hashed it. Folders (galleries) are left
+/// without one.
+fn store_content_hash(id: i64, path: &str, known: Option<String>) {
+    let path = std::path::Path::new(path);
+    if !path.is_file() {
+        return;
+    }
+    let hash = match known {
+        Some(hash) => Ok(hash),
+        None => crate::utils::checksum::sha256_file(path),
+    };
+    let stored = hash.map_err(|e| e.to_string()).and_then(|hash| {
+        crate::database::Database::new()
+            .and_then(|db| db.set_content_hash(id, &hash))
+            .map_err(|e| e.to_string())
+    });
+    if let Err(e) = stored {
+        eprintln!("[BACKEND] checksum for {id} failed: {e}");
+    }
+}
+
+/// Record the size of a finished file or folder, and its length from ffprobe
+/// when the metadata didn't carry one.
+async fn store_file_info(app: AppHandle, id: i64, path: String) {
+    let disk_path = crate::utils::paths::extended(Path::new(&path));
+    let probe_path = disk_path.clone();
+    let known = tauri::async_runtime::spawn_blocking(move || {
+        let bytes = crate::utils::paths::path_bytes(&probe_path);
+        let duration = crate::database::Database::new()
+            .and_then(|db| db.duration_secs(id))
+            .map_err(|e| e.to_string())?;
+        Ok::<_, String>((bytes, duration))
+    })
+    .await;
+    let (bytes, duration) = match known {
+        Ok(Ok(known)) => known,
+        Ok(Err(e)) => {
+            eprintln!("[BACKEND] file info for {id} failed: {e}");
+            return;
+        }
+        Err(e) => {
+            eprintln!("[BACKEND] file info join error: {e}");
+            return;
+        }
+    };
+    let duration = match duration {
+        None if disk_path.is_file() => {
+            crate::download::video::probe_duration(&app, Path::new(&path)).await
+        }
+        _ => None,
+    };
+    let stored = tauri::async_runtime::spawn_blocking(move || {
+        crate::database::Database::new()
+            .and_then(|db| db.set_file_info(id, bytes, duration))
+            .map_err(|e| e.to_string())
+    })
+    .await;
+    if let Ok(Err(e)) = stored {
+        eprintln!("[BACKEND] storing file info for {id} failed: {e}");
     }
 }
 
@@ -616,6 +1521,7 @@ async fn run_download_with_progress(
     db: Arc<tokio::sync::Mutex<Connection>>,
     id: i64,
     overrides: Option<DownloadOverrides>,
+    job: JobHandle,
 ) -> Result<Option<String>, String> {
     let db_clone = db.clone();
     let row = tauri::async_runtime::spawn_blocking(move || {
@@ -627,12 +1533,49 @@ async fn run_download_with_progress(
     .await
     .map_err(|e| format!("Join error: {e}"))??;
 
+    let s = settings::load_settings();
+    let verbosity = s.event_verbosity;
+    let throttle = Arc::new(std::sync::Mutex::new(ProgressThrottle::new(
+        s.progress_updates_per_sec,
+    )));
     let emitter: Arc<dyn Fn(DownloadEvent) + Send + Sync> = {
         let app_clone = app.clone();
+        let throttle = throttle.clone();
         Arc::new(move |event: DownloadEvent| {
-            emit_event(&app_clone, event);
+            if let DownloadEvent::Message { level, .. } = &event {
+                if !verbosity.admits(*level) {
+                    return;
+                }
+            }
+            // Emitted under the lock so a timed release can't overtake a newer update.
+            let Ok(mut t) = throttle.lock() else {
+                return;
+            };
+            if let Some(event) = t.admit(event) {
+                emit_event(&app_clone, event);
+            }
+            let wait = t.arm(id);
+            drop(t);
+            // Don't leave a held-back update waiting for more output.
+            if let Some(wait) = wait {
+                let (app, throttle) = (app_clone.clone(), throttle.clone());
+                tauri::async_runtime::spawn(async move {
+                    tokio::time::sleep(wait).await;
+                    if let Ok(mut t) = throttle.lock() {
+                        if let Some(event) = t.release(id) {
+                            emit_event(&app, event);
+                        }
+                    }
+                });
+            }
         })
     };
 
-    pipeline::execute_download_job(app.clone(), row, overrides, emitter).await
+    let result = pipeline::execute_download_job(app.clone(), row, overrides, emitter, job).await;
+
+    // Flush the last throttled progress before the terminal status goes out.
+    if let Some(event) = throttle.lock().ok().and_then(|mut t| t.flush(id)) {
+        emit_event(app, event);
+    }
+    result
 }
@@ -0,0 +1,128 @@
+//! Other places a removed item may still be found: the Wayback Machine's copy
+//! of a YouTube video, or an upload with the same title found by a YouTube
+//! search. A match is stored on the failed row so it can be queued again from
+//! there with one click.
+use tauri::AppHandle;
+
+use crate::database::{Database, Platform};
+use crate::download::manager::{emit_event, DownloadEvent};
+
+/// Links worth probing for `link`, with the label shown for each.
+fn candidates(
+    link: &str,
+    title: Option<&str>,
+    uploader: Option<&str>,
+) -> Vec<(&'static str, String)> {
+    let mut out = Vec::new();
+    if let Some(d) = crate::platforms::detect(link) {
+        if d.platform == Platform::Youtube {
+            if let Some(video_id) = d.item_id(link) {
+                out.push((
+                    "Wayback Machine",
+                    format!(
+                        "https://web.archive.org/web/https://www.youtube.com/watch?v={video_id}"
+                    ),
+                ));
+            }
+        }
+    }
+    if let Some(title) = title {
+        let query = match uploader {
+            Some(uploader) => format!("{uploader} {title}"),
+            None => title.to_string(),
+        };
+        out.push(("YouTube search", format!("ytsearch1:{query}")));
+    }
+    out
+}
+
+/// Titles compared without case or spacing differences.
+fn same_title(a: &str, b: &str) -> bool {
+    let norm = |s: &str| {
+        s.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    };
+    norm(a) == norm(b)
+}
+
+/// Look for download `id` elsewhere and store the first match. Does nothing
+/// unless `find_mirrors` is on.
+pub async fn look_for(app: AppHandle, id: i64) {
+    if !crate::settings::load_settings().find_mirrors {
+        return;
+    }
+    let subject = tauri::async_runtime::spawn_blocking(move || {
+        Database::new()
+            .and_then(|db| db.mirror_subject(id))
+            .map_err(|e| e.to_string())
+    })
+    .await;
+    let (link, title, uploader) = match subject {
+        Ok(Ok(Some(subject))) => subject,
+        Ok(Ok(None)) => return,
+        Ok(Err(e)) => {
+            eprintln!("[BACKEND] [mirrors] lookup for {id} failed: {e}");
+            return;
+        }
+        Err(e) => {
+            eprintln!("[BACKEND] [mirrors] join error: {e}");
+            return;
+        }
+    };
+
+    let settings = crate::settings::load_settings();
+    for (source, target) in candidates(&link, title.as_deref(), uploader.as_deref()) {
+        let Some((_, cookie_arg)) = crate::settings::cookie_sources(&settings, &target, None)
+            .into_iter()
+            .next()
+        else {
+            return;
+        };
+        let meta = match crate::download::video::probe_metadata(&app, &cookie_arg, &target).await {
+            Ok(meta) => meta,
+            Err(e) => {
+                println!("[BACKEND] [mirrors] {source} has nothing for {link}: {e}");
+                continue;
+            }
+        };
+        // A search hit only counts when it carries the same title.
+        if target.starts_with("ytsearch") {
+            let matches = match (title.as_deref(), meta.title.as_deref()) {
+                (Some(want), Some(found)) => same_title(want, found),
+                _ => false,
+            };
+            if !matches {
+                println!("[BACKEND] [mirrors] {source} found no upload titled like {link}");
+                continue;
+            }
+        }
+        let url = meta.webpage_url.unwrap_or(target);
+        if url == link {
+            continue;
+        }
+
+        let stored_url = url.clone();
+        let stored = tauri::async_runtime::spawn_blocking(move || {
+            Database::new()
+                .and_then(|db| db.set_mirror(id, &stored_url, source))
+                .map_err(|e| e.to_string())
+        })
+        .await;
+        if let Ok(Err(e)) = stored {
+            eprintln!("[BACKEND] [mirrors] failed to store mirror for {id}: {e}");
+            return;
+        }
+        println!("[BACKEND] [mirrors] {link} found on {source}: {url}");
+        emit_event(
+            &app,
+            DownloadEvent::MirrorFound {
+                id,
+                url,
+                source: source.to_string(),
+            },
+        );
+        return;
+    }
+}
@@ -1,4 +1,21 @@
+pub mod backend;
+pub mod bandwidth;
+pub mod collection_index;
+pub mod events;
+pub mod extractors;
+pub mod filters;
+pub mod health;
+pub mod http;
 pub mod image;
 pub mod manager;
+pub mod mirrors;
 pub mod pipeline;
+pub mod prefetch;
+pub mod process;
+pub mod schedule;
+pub mod session_report;
+pub mod sidecar;
+pub mod thumbnails;
 pub mod video;
+pub mod volume;
+pub mod watch;
@@ -1,16 +1,24 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::commands::parse::parse_multiple_filenames_from_output;
 use crate::database::DbDownloadRow;
-use crate::database::{Database, DefaultOutput, OnDuplicate};
+use crate::database::{AudioFormat, Database, DefaultOutput, OnDuplicate};
+use crate::download::backend::{self, BackendJob};
 use crate::download::image;
-use crate::download::manager::{DownloadEvent, DownloadOverrides};
+use crate::download::manager::{DownloadEvent, DownloadOverrides, MessageLevel};
+use crate::download::process::JobHandle;
+use crate::download::sidecar;
+use crate::download::thumbnails;
 use crate::download::video;
+use crate::platforms::{self, Tool};
 
 use crate::settings;
 use crate::utils;
+use crate::utils::filenames::FilenameRules;
+use crate::utils::paths;
 
 use tauri::AppHandle;
 
@@ -22,7 +30,16 @@ fn ensure_parent_dir(p: &Path) {
     }
 }
 
-fn move_with_policy(
+/// Copy then delete, so moves across drives and onto network shares work.
+/// Both ends are extended-length (see `utils::paths`) for deep folders.
+fn move_file(src: &Path, target: &Path) -> std::io::Result<()> {
+    let (src, target) = (paths::extended(src), paths::extended(target));
+    ensure_parent_dir(&target);
+    fs::copy(&src, &target)?;
+    fs::remove_file(&src)
+}
+
+pub(crate) fn move_with_policy(
     src: &Path,
     dest_dir: &Path,
     file_name: &str,
@@ -32,44 +49,39 @@ fn move_with_policy(
         Some((s, e)) if !s.is_empty() && !e.is_empty() => (s.to_string(), e.to_string()),
         _ => (file_name.to_string(), String::from("bin")),
     };
+    let exists = |p: &Path| paths::extended(p).exists();
     let mut target = dest_dir.join(format!("{stem}.{ext}"));
     match on_duplicate {
         OnDuplicate::Overwrite => {
-            ensure_parent_dir(&target);
-            if target.exists() {
-                fs::remove_file(&target).ok();
+            if exists(&target) {
+                fs::remove_file(paths::extended(&target)).ok();
             }
-            fs::copy(src, &target)?;
-            fs::remove_file(src)?;
-            Ok((Some(target.display().to_string()), "Overwrote"))
+            move_file(src, &target)?;
+            Ok((Some(paths::plain(&target)), "Overwrote"))
         }
         OnDuplicate::DoNothing => {
-            if target.exists() {
-                let _ = fs::remove_file(src);
+            if exists(&target) {
+                let _ = fs::remove_file(paths::extended(src));
                 Ok((None, "Skipped"))
             } else {
-                ensure_parent_dir(&target);
-                fs::copy(src, &target)?;
-                fs::remove_file(src)?;
-                Ok((Some(target.display().to_string()), "Created new"))
+                move_file(src, &target)?;
+                Ok((Some(paths::plain(&target)), "Created new"))
             }
         }
         OnDuplicate::CreateNew => {
-            if target.exists() {
+            if exists(&target) {
                 let mut n = 1usize;
                 loop {
                     let cand = dest_dir.join(format!("{stem} ({n}).{ext}"));
-                    if !cand.exists() {
+                    if !exists(&cand) {
                         target = cand;
                         break;
                     }
                     n += 1;
                 }
             }
-            ensure_parent_dir(&target);
-            fs::copy(src, &target)?;
-            fs::remove_file(src)?;
-            Ok((Some(target.display().to_string()), "Created new"))
+            move_file(src, &target)?;
+            Ok((Some(paths::plain(&target)), "Created new"))
         }
     }
 }
@@ -78,25 +90,38 @@ fn move_tmp_into_site_dir(
     tmp: &Path,
     dest_dir: &Path,
     on_duplicate: &OnDuplicate,
+    rules: &FilenameRules,
     mut notify: impl FnMut(String),
 ) -> std::io::Result<(bool, Vec<String>)> {
     let mut moved_any = false;
     let mut finals = Vec::new();
-    fs::create_dir_all(dest_dir).ok();
+    // source file name -> where it ended up, for the sidecars below
+    let mut moved: HashMap<String, String> = HashMap::new();
+    fs::create_dir_all(paths::extended(dest_dir))?;
 
-    for entry in WalkDir::new(tmp).into_iter().filter_map(|e| e.ok()) {
-        if !entry.file_type().is_file() {
-            continue;
-        }
-        let src = entry.path();
-        let file_name = src
+    let (sidecars, files): (Vec<PathBuf>, Vec<PathBuf>) = WalkDir::new(tmp)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .partition(|p| {
+            p.file_name()
+                .and_then(|s| s.to_str())
+                .is_some_and(sidecar::is_sidecar_name)
+        });
+
+    for src in &files {
+        let src_name = src
             .file_name()
             .and_then(|s| s.to_str())
             .unwrap_or("image.bin");
+        // Room for the " (n)" counter `move_with_policy` may add.
+        let file_name = &rules.fit_file_name(src_name, 6);
         match move_with_policy(src, dest_dir, file_name, on_duplicate) {
             Ok((Some(fp), action)) => {
                 moved_any = true;
                 notify(format!("{action}: {fp}"));
+                moved.insert(src_name.to_string(), fp.clone());
                 finals.push(fp);
             }
             Ok((None, _)) => notify(format!(
@@ -110,6 +135,24 @@ fn move_tmp_into_site_dir(
             )),
         }
     }
+
+    // Sidecars take their file's final name, so renamed copies still pair up.
+    for src in &sidecars {
+        let Some(final_path) = src
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|media_name| moved.get(media_name))
+        else {
+            continue;
+        };
+        let Some(media_name) = Path::new(final_path).file_name() else {
+            continue;
+        };
+        let target_name = format!("{}.json", media_name.to_string_lossy());
+        if let Err(e) = move_with_policy(src, dest_dir, &target_name, on_duplicate) {
+            notify(format!("Failed to move {}: {e}", src.display()));
+        }
+    }
     Ok((moved_any, finals))
 }
 
@@ -123,20 +166,126 @@ fn friendly_browser_error(browser: &str, output: &str) -> Option<String> {
     None
 }
 
+/// Lines of tool output kept per attempt.
+const ATTEMPT_TAIL_LINES: usize = 40;
+/// Attempts kept per download; older ones are dropped.
+const ATTEMPTS_KEPT: usize = 20;
+
+/// Store how one tool run went, for the row's "Details" view. `outcome` is
+/// (success, tool output) when the tool ran, or why it couldn't. `note` goes
+/// above the output tail so it survives the cut (e.g. the geo-bypass country).
+/// The output is redacted when `redact_logs` is on. Never fails the download itself.
+fn record_attempt(
+    row_id: i64,
+    tool: &str,
+    browser: Option<&str>,
+    job: &JobHandle,
+    note: Option<&str>,
+    outcome: Result<(bool, &str), String>,
+) {
+    let (ok, output) = match outcome {
+        Ok((ok, output)) => (ok, output.to_string()),
+        Err(e) => (false, e),
+    };
+    let lines: Vec<&str> = output.lines().collect();
+    let mut output_tail = lines[lines.len().saturating_sub(ATTEMPT_TAIL_LINES)..].join("\n");
+    if let Some(note) = note {
+        output_tail = format!("{note}\n{output_tail}");
+    }
+    if crate::settings::load_settings().redact_logs {
+        output_tail = crate::utils::redact::redact(&output_tail);
+    }
+    let attempt = crate::database::DownloadAttempt {
+        id: 0,
+        download_id: row_id,
+        attempted_at: chrono::Utc::now().to_rfc3339(),
+        tool: tool.to_string(),
+        browser: browser.map(str::to_string),
+        exit_code: job.take_exit(),
+        ok,
+        output_tail,
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let saved =
+            Database::new().and_then(|db| db.insert_download_attempt(&attempt, ATTEMPTS_KEPT));
+        if let Err(e) = saved {
+            eprintln!("[BACKEND] attempt of download {row_id} not recorded: {e}");
+        }
+    });
+}
+
+/// Remember the first subtitle file yt-dlp wrote, if any. Fire-and-forget like
+/// `record_attempt`.
+fn record_subtitles(row_id: i64, output: &str) {
+    let Some(path) = video::subtitle_paths_from_output(output).into_iter().next() else {
+        return;
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(e) = Database::new().and_then(|db| db.set_subtitle_path(row_id, &path)) {
+            eprintln!("[BACKEND] subtitles of download {row_id} not recorded: {e}");
+        }
+    });
+}
+
+/// Cache a preview image for the Library grid. Fire-and-forget like
+/// `record_attempt`.
+fn record_thumbnail(row_id: i64, first_file: Option<&str>) {
+    let first_file = first_file.map(str::to_string);
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(path) = thumbnails::capture(row_id, first_file.as_deref()) else {
+            return;
+        };
+        let saved =
+            Database::new().and_then(|db| db.set_thumbnail_path(row_id, &path.to_string_lossy()));
+        if let Err(e) = saved {
+            eprintln!("[BACKEND] thumbnail of download {row_id} not recorded: {e}");
+        }
+    });
+}
+
+/// Copy title, description, date, duration and views from the metadata
+/// sidecar of the saved file, when one was written. Fire-and-forget like
+/// `record_attempt`.
+fn record_sidecar(row_id: i64, first_file: Option<&str>) {
+    let Some(path) = first_file.and_then(|f| sidecar::find_for(Path::new(f))) else {
+        return;
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(meta) = sidecar::read(&path) else {
+            return;
+        };
+        if let Err(e) = Database::new().and_then(|db| db.set_sidecar_metadata(row_id, &meta)) {
+            eprintln!("[BACKEND] sidecar of download {row_id} not recorded: {e}");
+        }
+    });
+}
+
 pub async fn execute_download_job(
     app: AppHandle,
     row: DbDownloadRow,
     overrides: Option<DownloadOverrides>,
     emitter: Arc<dyn Fn(DownloadEvent) + Send + Sync>,
+    job: JobHandle,
 ) -> Result<Option<String>, String> {
-    let settings = settings::load_settings();
+    let mut settings = settings::load_settings();
+    if overrides.as_ref().is_some_and(|ov| ov.overwrite) {
+        settings.on_duplicate = OnDuplicate::Overwrite;
+    }
+    // Read-only mode never replaces an existing file.
+    if settings.read_only && matches!(settings.on_duplicate, OnDuplicate::Overwrite) {
+        settings.on_duplicate = OnDuplicate::DoNothing;
+    }
+    if crate::download::volume::is_offline(Path::new(&settings.download_directory)) {
+        return Err("Download drive is not connected".into());
+    }
     let download_root = PathBuf::from(settings.download_directory.clone());
-    if let Err(e) = fs::create_dir_all(&download_root) {
+    if let Err(e) = fs::create_dir_all(paths::extended(&download_root)) {
         return Err(format!("Failed to create download dir: {e}"));
     }
 
     let mut want_audio_pref = overrides.as_ref().and_then(|ov| ov.force_audio);
-    let (mut cleaned_url, legacy_audio_flag, legacy_flat_flag) = strip_legacy_flags(&row.link);
+    let mut cleaned_url = row.link.trim().to_string();
+    let platform_ov = settings::platform_override_for_url(&settings, &cleaned_url);
     if want_audio_pref.is_none() {
         want_audio_pref = match row.output_format.to_lowercase().as_str() {
             "audio" => Some(true),
@@ -145,74 +294,133 @@ pub async fn execute_download_job(
         };
     }
     if want_audio_pref.is_none() {
-        want_audio_pref = Some(matches!(settings.default_output, DefaultOutput::Audio));
-    }
-    if legacy_audio_flag {
-        want_audio_pref = Some(true);
+        let output = platform_ov
+            .output
+            .as_ref()
+            .unwrap_or(&settings.default_output);
+        want_audio_pref = Some(matches!(output, DefaultOutput::Audio));
     }
     let want_audio_only = want_audio_pref.unwrap_or(false);
+    // A row's own audio format wins over the setting.
+    let audio_format = want_audio_only.then(|| {
+        row.audio_format
+            .as_deref()
+            .and_then(AudioFormat::parse)
+            .unwrap_or(settings.audio_format)
+    });
 
-    let mut use_flat = overrides
+    let use_flat = overrides
         .as_ref()
         .map(|ov| ov.flat_destination)
         .unwrap_or(false);
-    if legacy_flat_flag {
-        use_flat = true;
-    }
 
-    if cleaned_url.contains("instagram.com/") {
-        if let Some((base, _)) = cleaned_url.split_once('?') {
-            cleaned_url = base.to_string();
-        }
+    let descriptor = platforms::detect(&cleaned_url);
+    if let Some(d) = descriptor.filter(|d| d.strip_query) {
+        cleaned_url = clip_core::url::retain_query(&cleaned_url, d.info().keep_params);
     }
 
-    let site = infer_site(&cleaned_url);
-    let collection_dir_label = Database::collection_folder_label(&row.origin, &row.user_handle);
-    let dest_dir = if use_flat {
+    let site = descriptor.map_or("other", |d| d.id());
+    let name_rules = utils::filenames::rules_for(&download_root, &settings);
+    let collection_dir = settings::collection_dir(
+        &settings,
+        &settings.folder_template,
+        &settings.collection_folder_template,
+        site,
+        &row.origin,
+        &row.user_handle,
+    );
+    let dest_dir = if let Some(dir) = row.destination_dir.as_deref().filter(|d| !d.is_empty()) {
+        PathBuf::from(dir)
+    } else if use_flat {
         download_root.clone()
     } else {
-        download_root.join(site).join(collection_dir_label)
+        collection_dir
     };
-    let _ = fs::create_dir_all(&dest_dir);
+    let _ = fs::create_dir_all(paths::extended(&dest_dir));
+
+    let with_gallery_fallback = descriptor.map_or(false, |d| d.gallery_fallback);
+    let is_image_post = descriptor.map_or(false, |d| d.is_image_url(&cleaned_url));
+    let tool = descriptor.map_or(Tool::YtDlp, |d| d.tool_for(&cleaned_url));
 
-    let is_instagram = cleaned_url.contains("instagram.com/");
-    let is_ig_post_p = is_instagram && cleaned_url.contains("/p/");
-    let is_tt_photo = utils::url::is_tiktok_photo(&cleaned_url);
+    if let Some(backend) = backend::for_tool(tool) {
+        (emitter)(DownloadEvent::Message {
+            id: row.id,
+            level: MessageLevel::Milestone,
+            message: format!(
+                "Downloading via {}; dest={}",
+                backend.name(),
+                dest_dir.display()
+            ),
+        });
+        let result = backend
+            .download(BackendJob {
+                row_id: row.id,
+                url: &cleaned_url,
+                dest_dir: &dest_dir,
+                on_duplicate: &settings.on_duplicate,
+                emitter: emitter.clone(),
+                job: &job,
+            })
+            .await;
+        record_attempt(
+            row.id,
+            backend.name(),
+            None,
+            &job,
+            None,
+            result.as_ref().map(|_| (true, "")).map_err(String::clone),
+        );
+        if let Ok(path) = &result {
+            record_thumbnail(row.id, path.as_deref());
+        }
+        return result;
+    }
 
-    let browsers = utils::os::installed_browsers();
+    let browsers = settings::cookie_sources(&settings, &cleaned_url, row.cookie_browser.as_deref());
     if browsers.is_empty() {
         return Err("No logged-in browsers detected for cookies.".into());
     }
 
+    let geo_note = settings::geo_bypass_for_url(&settings, &cleaned_url)
+        .map(|country| format!("geo-bypass: {country}"));
     let mut last_error: Option<String> = None;
     let mut specific_cookie_error: Option<String> = None;
     for (browser, cookie_arg) in &browsers {
         (emitter)(DownloadEvent::Message {
             id: row.id,
+            level: MessageLevel::Debug,
             message: format!("Trying {} cookies; dest={}", browser, dest_dir.display()),
         });
 
-        if is_instagram {
-            let effective_url = if want_audio_only {
-                format!("{}#__audio_only__", cleaned_url)
-            } else {
-                cleaned_url.clone()
-            };
-            match video::run_yt_dlp_with_progress(
+        if with_gallery_fallback {
+            let run = video::run_yt_dlp_with_progress(
                 &app,
                 &dest_dir,
                 cookie_arg,
-                &effective_url,
+                &cleaned_url,
                 false,
+                audio_format,
                 &settings.on_duplicate,
                 row.id,
                 emitter.clone(),
+                &job,
             )
-            .await
-            {
+            .await;
+            record_attempt(
+                row.id,
+                "yt-dlp",
+                Some(browser.as_str()),
+                &job,
+                geo_note.as_deref(),
+                run.as_ref()
+                    .map(|(ok, out)| (*ok, out.as_str()))
+                    .map_err(|e| e.to_string()),
+            );
+            match run {
                 Ok((true, output)) => {
                     (emitter)(DownloadEvent::Message {
                         id: row.id,
+                        level: MessageLevel::Milestone,
                         message: if want_audio_only {
                             "Saved (audio)".into()
                         } else {
@@ -224,32 +432,49 @@ pub async fn execute_download_job(
                         &cleaned_url,
                         Some(&dest_dir),
                     );
-                    return Ok(files.get(0).map(|t| t.2.clone()));
+                    let first = files.get(0).map(|t| t.2.clone());
+                    record_thumbnail(row.id, first.as_deref());
+                    record_sidecar(row.id, first.as_deref());
+                    return Ok(first);
                 }
                 Ok((false, _)) | Err(_) => {
-                    if is_ig_post_p {
+                    if is_image_post {
                         (emitter)(DownloadEvent::Message {
                             id: row.id,
+                            level: MessageLevel::Milestone,
                             message: "Video fetch failed, trying image fallback".into(),
                         });
-                        match image::run_gallery_dl_to_temp(
+                        let run = image::run_gallery_dl_to_temp(
                             &app,
                             &download_root,
                             &cleaned_url,
                             cookie_arg,
                             row.id,
                             emitter.clone(),
+                            &job,
                         )
-                        .await
-                        {
+                        .await;
+                        record_attempt(
+                            row.id,
+                            "gallery-dl",
+                            Some(browser.as_str()),
+                            &job,
+                            None,
+                            run.as_ref()
+                                .map(|(ok, out, _)| (*ok, out.as_str()))
+                                .map_err(|e| e.to_string()),
+                        );
+                        match run {
                             Ok((ok, _out, tmp_dir)) if ok => {
                                 let (moved_any, finals) = move_tmp_into_site_dir(
                                     &tmp_dir,
                                     &dest_dir,
                                     &settings.on_duplicate,
+                                    &name_rules,
                                     |line| {
                                         (emitter)(DownloadEvent::Message {
                                             id: row.id,
+                                            level: MessageLevel::Milestone,
                                             message: line,
                                         });
                                     },
@@ -259,8 +484,11 @@ pub async fn execute_download_job(
                                 if moved_any {
                                     (emitter)(DownloadEvent::Message {
                                         id: row.id,
+                                        level: MessageLevel::Milestone,
                                         message: "Saved images".into(),
                                     });
+                                    record_thumbnail(row.id, finals.first().map(String::as_str));
+                                    record_sidecar(row.id, finals.first().map(String::as_str));
                                     return Ok(finals.get(0).cloned());
                                 } else {
                                     last_error.get_or_insert_with(|| {
@@ -284,6 +512,7 @@ pub async fn execute_download_job(
                                 last_error.get_or_insert(msg.clone());
                                 (emitter)(DownloadEvent::Message {
                                     id: row.id,
+                                    level: MessageLevel::Error,
                                     message: msg,
                                 });
                                 let _ = fs::remove_dir_all(&tmp_dir);
@@ -298,29 +527,43 @@ pub async fn execute_download_job(
             continue;
         }
 
-        if site == "pinterest" || is_tt_photo {
+        if tool == Tool::GalleryDl {
             (emitter)(DownloadEvent::Message {
                 id: row.id,
+                level: MessageLevel::Milestone,
                 message: "Preparing image download".into(),
             });
-            match image::run_gallery_dl_to_temp(
+            let run = image::run_gallery_dl_to_temp(
                 &app,
                 &download_root,
                 &cleaned_url,
                 cookie_arg,
                 row.id,
                 emitter.clone(),
+                &job,
             )
-            .await
-            {
+            .await;
+            record_attempt(
+                row.id,
+                "gallery-dl",
+                Some(browser.as_str()),
+                &job,
+                None,
+                run.as_ref()
+                    .map(|(ok, out, _)| (*ok, out.as_str()))
+                    .map_err(|e| e.to_string()),
+            );
+            match run {
                 Ok((ok, _output, tmp_dir)) if ok => {
                     let (moved_any, finals) = move_tmp_into_site_dir(
                         &tmp_dir,
                         &dest_dir,
                         &settings.on_duplicate,
+                        &name_rules,
                         |line| {
                             (emitter)(DownloadEvent::Message {
                                 id: row.id,
+                                level: MessageLevel::Milestone,
                                 message: line,
                             });
                         },
@@ -330,8 +573,11 @@ pub async fn execute_download_job(
                     if moved_any {
                         (emitter)(DownloadEvent::Message {
                             id: row.id,
+                            level: MessageLevel::Milestone,
                             message: "Saved images".into(),
                         });
+                        record_thumbnail(row.id, finals.first().map(String::as_str));
+                        record_sidecar(row.id, finals.first().map(String::as_str));
                         return Ok(finals.get(0).cloned());
                     } else {
                         last_error.get_or_insert_with(|| {
@@ -349,6 +595,7 @@ pub async fn execute_download_job(
                     last_error.get_or_insert(msg.clone());
                     (emitter)(DownloadEvent::Message {
                         id: row.id,
+                        level: MessageLevel::Error,
                         message: msg,
                     });
                     let _ = fs::remove_dir_all(&tmp_dir);
@@ -360,35 +607,47 @@ pub async fn execute_download_job(
             continue;
         }
 
-        let effective_url = if want_audio_only {
-            format!("{}#__audio_only__", cleaned_url)
-        } else {
-            cleaned_url.clone()
-        };
-        match video::run_yt_dlp_with_progress(
+        let run = video::run_yt_dlp_with_progress(
             &app,
             &dest_dir,
             cookie_arg,
-            &effective_url,
+            &cleaned_url,
             false,
+            audio_format,
             &settings.on_duplicate,
             row.id,
             emitter.clone(),
+            &job,
         )
-        .await
-        {
+        .await;
+        record_attempt(
+            row.id,
+            "yt-dlp",
+            Some(browser.as_str()),
+            &job,
+            geo_note.as_deref(),
+            run.as_ref()
+                .map(|(ok, out)| (*ok, out.as_str()))
+                .map_err(|e| e.to_string()),
+        );
+        match run {
             Ok((true, output)) => {
                 (emitter)(DownloadEvent::Message {
                     id: row.id,
+                    level: MessageLevel::Milestone,
                     message: if want_audio_only {
                         "Saved (audio)".into()
                     } else {
                         "Saved (video)".into()
                     },
                 });
+                record_subtitles(row.id, &output);
                 let files =
                     parse_multiple_filenames_from_output(&output, &cleaned_url, Some(&dest_dir));
-                return Ok(files.get(0).map(|t| t.2.clone()));
+                let first = files.get(0).map(|t| t.2.clone());
+                record_thumbnail(row.id, first.as_deref());
+                record_sidecar(row.id, first.as_deref());
+                return Ok(first);
             }
             Ok((false, output)) => {
                 let msg = friendly_browser_error(browser, &output).unwrap_or_else(|| {
@@ -400,6 +659,7 @@ pub async fn execute_download_job(
                 last_error.get_or_insert(msg.clone());
                 (emitter)(DownloadEvent::Message {
                     id: row.id,
+                    level: MessageLevel::Error,
                     message: msg,
                 });
             }
@@ -410,7 +670,7 @@ pub async fn execute_download_job(
     }
 
     Err(specific_cookie_error.or(last_error).unwrap_or_else(|| {
-        if is_instagram || is_tt_photo {
+        if with_gallery_fallback || tool == Tool::GalleryDl {
             "Failed to fetch media. Ensure bundled tools are present and your browser is logged in."
                 .into()
         } else {
@@ -418,32 +678,3 @@ pub async fn execute_download_job(
         }
     }))
 }
-
-fn strip_legacy_flags(url: &str) -> (String, bool, bool) {
-    let mut cleaned = url.to_string();
-    let mut want_audio = false;
-    let mut flat = false;
-    if cleaned.contains("#__audio_only__") {
-        want_audio = true;
-        cleaned = cleaned.replace("#__audio_only__", "");
-    }
-    if cleaned.contains("#__flat__") {
-        flat = true;
-        cleaned = cleaned.replace("#__flat__", "");
-    }
-    (cleaned, want_audio, flat)
-}
-
-fn infer_site(url: &str) -> &'static str {
-    if url.contains("instagram.com") {
-        "instagram"
-    } else if url.contains("tiktok.com") {
-        "tiktok"
-    } else if url.contains("youtube.com") || url.contains("youtu.be") {
-        "youtube"
-    } else if url.contains("pinterest.com") || url.contains("pin.it") {
-        "pinterest"
-    } else {
-        "other"
-    }
-}
@@ -0,0 +1,126 @@
+//! Background metadata lookups for queued and backlog rows, so the Downloads
+//! page can show titles and expected sizes before anything has been fetched.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use tauri::AppHandle;
+
+use crate::database::{Database, MediaMetadata};
+use crate::download::manager::{emit_event, DownloadEvent};
+use crate::platforms::{PlatformDescriptor, Tool, PLATFORMS};
+
+/// How often the prefetcher looks for a row to probe.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Rows considered per pass; the first whose site is not cooling down is probed.
+const CANDIDATES: usize = 50;
+
+/// Probe one waiting row at a time for the lifetime of the app, waiting each
+/// platform's `probe_delay_secs` between requests to the same site. Nothing
+/// runs while a download is active, in read-only mode, or with the setting off.
+pub async fn run(app: AppHandle) {
+    let mut last_probe: HashMap<&'static str, Instant> = HashMap::new();
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        let settings = crate::settings::load_settings();
+        if !settings.prefetch_metadata || settings.read_only {
+            continue;
+        }
+        let cooling: Vec<&'static str> = PLATFORMS
+            .iter()
+            .filter(|d| {
+                last_probe
+                    .get(d.id())
+                    .is_some_and(|at| at.elapsed() < Duration::from_secs(d.probe_delay_secs))
+            })
+            .map(|d| d.id())
+            .collect();
+        let next = tauri::async_runtime::spawn_blocking(move || next_candidate(&cooling)).await;
+        let (id, descriptor, link) = match next {
+            Ok(Ok(Some(next))) => next,
+            Ok(Ok(None)) => continue,
+            Ok(Err(e)) => {
+                eprintln!("[BACKEND] metadata prefetch: {e}");
+                continue;
+            }
+            Err(_) => continue,
+        };
+        let settings = crate::settings::load_settings();
+        let Some((_, cookie_arg)) = crate::settings::cookie_sources(&settings, &link, None)
+            .into_iter()
+            .next()
+        else {
+            continue;
+        };
+
+        last_probe.insert(descriptor.id(), Instant::now());
+        let probed = crate::download::video::probe_metadata(&app, &cookie_arg, &link).await;
+        let app = app.clone();
+        let stored =
+            tauri::async_runtime::spawn_blocking(move || store(&app, id, &link, probed)).await;
+        if let Ok(Err(e)) = stored {
+            eprintln!("[BACKEND] metadata prefetch: {e}");
+        }
+    }
+}
+
+/// The next unprobed row whose site may be asked again, or `None`
+/// while downloads are running. Rows yt-dlp cannot describe are marked as
+/// checked and skipped.
+fn next_candidate(
+    cooling: &[&str],
+) -> Result<Option<(i64, &'static PlatformDescriptor, String)>, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    if !db
+        .list_downloading_ids()
+        .map_err(|e| e.to_string())?
+        .is_empty()
+    {
+        return Ok(None);
+    }
+    let rows = db.list_unprobed(CANDIDATES).map_err(|e| e.to_string())?;
+    for (id, platform, link) in rows {
+        let descriptor = PLATFORMS.iter().find(|d| d.id() == platform);
+        let Some(descriptor) = descriptor.filter(|d| d.info().preferred_tool != Tool::DirectHttp)
+        else {
+            db.mark_metadata_checked(id).map_err(|e| e.to_string())?;
+            continue;
+        };
+        if !cooling.contains(&descriptor.id()) {
+            return Ok(Some((id, descriptor, link)));
+        }
+    }
+    Ok(None)
+}
+
+/// Save a probe result; failures are only remembered so the row is not retried.
+fn store(
+    app: &AppHandle,
+    id: i64,
+    link: &str,
+    probed: Result<MediaMetadata, String>,
+) -> Result<(), String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    match probed {
+        Ok(meta) => {
+            db.set_media_metadata(id, &meta)
+                .map_err(|e| e.to_string())?;
+            emit_event(
+                app,
+                DownloadEvent::MetadataUpdated {
+                    id,
+                    title: meta.title,
+                    duration_secs: meta.duration_secs,
+                    thumbnail_url: meta.thumbnail_url,
+                    estimated_bytes: meta.estimated_bytes,
+                },
+            );
+        }
+        Err(e) => {
+            println!("[BACKEND] metadata prefetch failed for {link}: {e}");
+            db.mark_metadata_checked(id).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
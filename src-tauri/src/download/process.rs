@@ -0,0 +1,177 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use tauri_plugin_shell::process::CommandChild;
+
+/// The tool process of one running download and the partial files it is
+/// writing. Shared between the manager (which cancels) and the runner (which
+/// spawns), so a cancel stops the tool itself rather than just the task
+/// waiting on it.
+#[derive(Clone, Default)]
+pub struct JobHandle(Arc<Mutex<JobState>>);
+
+#[derive(Default)]
+struct JobState {
+    child: Option<CommandChild>,
+    canceled: bool,
+    /// Continue the partial files of a paused run instead of starting over
+    resume: bool,
+    /// Exit code of the last tool run, until the attempt log takes it
+    exit_code: Option<i32>,
+    /// Final output paths announced by the tool; `<path>.part` etc. are the partials
+    outputs: Vec<PathBuf>,
+    /// Scratch folders (gallery-dl) removed wholesale on cancel
+    temp_dirs: Vec<PathBuf>,
+    /// SHA-256 a backend already computed for the finished file
+    content_hash: Option<String>,
+}
+
+/// Files and folders a canceled job left behind.
+pub struct Leftovers {
+    outputs: Vec<PathBuf>,
+    temp_dirs: Vec<PathBuf>,
+}
+
+impl JobHandle {
+    /// Handle for a download that picks up where a paused run stopped.
+    pub fn resuming() -> Self {
+        let job = Self::default();
+        if let Ok(mut state) = job.0.lock() {
+            state.resume = true;
+        }
+        job
+    }
+
+    pub fn is_resume(&self) -> bool {
+        self.0.lock().is_ok_and(|state| state.resume)
+    }
+
+    /// Hand over the spawned tool. Killed right away if the job was already
+    /// canceled, otherwise when the returned guard drops (normal exit or abort).
+    pub fn attach(&self, child: CommandChild) -> AttachedChild<'_> {
+        if let Ok(mut state) = self.0.lock() {
+            if state.canceled {
+                let _ = child.kill();
+            } else {
+                state.child = Some(child);
+            }
+        }
+        AttachedChild(self)
+    }
+
+    /// Remember how the tool exited, for the attempt history.
+    pub fn note_exit(&self, code: Option<i32>) {
+        if let Ok(mut state) = self.0.lock() {
+            state.exit_code = code;
+        }
+    }
+
+    pub fn take_exit(&self) -> Option<i32> {
+        self.0
+            .lock()
+            .ok()
+            .and_then(|mut state| state.exit_code.take())
+    }
+
+    pub fn note_output(&self, path: impl Into<PathBuf>) {
+        if let Ok(mut state) = self.0.lock() {
+            state.outputs.push(path.into());
+        }
+    }
+
+    /// Hand over a checksum computed while downloading, so the file isn't hashed twice.
+    pub fn note_content_hash(&self, hash: String) {
+        if let Ok(mut state) = self.0.lock() {
+            state.content_hash = Some(hash);
+        }
+    }
+
+    pub fn take_content_hash(&self) -> Option<String> {
+        self.0
+            .lock()
+            .ok()
+            .and_then(|mut state| state.content_hash.take())
+    }
+
+    pub fn note_temp_dir(&self, dir: impl Into<PathBuf>) {
+        if let Ok(mut state) = self.0.lock() {
+            state.temp_dirs.push(dir.into());
+        }
+    }
+
+    /// Kill the running tool (if any) and keep later spawns from starting.
+    pub fn cancel(&self) -> Leftovers {
+        let Ok(mut state) = self.0.lock() else {
+            return Leftovers {
+                outputs: Vec::new(),
+                temp_dirs: Vec::new(),
+            };
+        };
+        state.canceled = true;
+        if let Some(child) = state.child.take() {
+            let pid = child.pid();
+            match child.kill() {
+                Ok(()) => println!("[BACKEND] killed tool process {pid}"),
+                Err(e) => eprintln!("[BACKEND] failed to kill tool process {pid}: {e}"),
+            }
+        }
+        Leftovers {
+            outputs: std::mem::take(&mut state.outputs),
+            temp_dirs: std::mem::take(&mut state.temp_dirs),
+        }
+    }
+
+    fn detach(&self) {
+        let child = self.0.lock().ok().and_then(|mut state| state.child.take());
+        if let Some(child) = child {
+            let _ = child.kill();
+        }
+    }
+}
+
+/// Keeps the tool alive only as long as the runner that spawned it.
+pub struct AttachedChild<'a>(&'a JobHandle);
+
+impl Drop for AttachedChild<'_> {
+    fn drop(&mut self) {
+        self.0.detach();
+    }
+}
+
+impl Leftovers {
+    /// Delete partial downloads (`.part`, `.part-Frag*`, `.ytdl`) next to each
+    /// announced output, plus scratch folders. Returns how many entries went.
+    pub fn remove(&self) -> usize {
+        let mut removed = 0;
+        for output in &self.outputs {
+            removed += remove_partials_of(output);
+        }
+        for dir in &self.temp_dirs {
+            if fs::remove_dir_all(dir).is_ok() {
+                removed += 1;
+            }
+        }
+        removed
+    }
+}
+
+fn remove_partials_of(output: &Path) -> usize {
+    let (Some(dir), Some(name)) = (output.parent(), output.file_name()) else {
+        return 0;
+    };
+    let name = name.to_string_lossy();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter(|e| {
+            let candidate = e.file_name().to_string_lossy().to_string();
+            candidate.strip_prefix(name.as_ref()).is_some_and(|rest| {
+                rest == ".part" || rest == ".ytdl" || rest.starts_with(".part-Frag")
+            })
+        })
+        .filter(|e| fs::remove_file(e.path()).is_ok())
+        .count()
+}
@@ -0,0 +1,117 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDateTime, NaiveTime, Timelike};
+
+use crate::database::{DownloadWindow, ScheduleStatus, Settings};
+
+/// Seconds between schedule checks; a window boundary is noticed within this delay.
+pub const CHECK_INTERVAL_SECS: u64 = 30;
+
+/// One parsed `DownloadWindow`: minutes since midnight plus a weekday mask
+/// (bit 0 = Monday). `start == end` means the whole day.
+#[derive(Debug, Clone, Copy)]
+struct Window {
+    start: u32,
+    end: u32,
+    days: u8,
+}
+
+impl Window {
+    fn parse(w: &DownloadWindow) -> Option<Self> {
+        let start = parse_hhmm(&w.start)?;
+        let end = parse_hhmm(&w.end)?;
+        let days = if w.days.is_empty() {
+            0x7f
+        } else {
+            w.days
+                .iter()
+                .filter(|d| **d < 7)
+                .fold(0u8, |m, d| m | 1 << d)
+        };
+        Some(Self { start, end, days })
+    }
+
+    fn on_day(&self, weekday: u32) -> bool {
+        self.days & (1 << weekday) != 0
+    }
+
+    /// A window that runs past midnight belongs to the day it starts on.
+    fn contains(&self, now: NaiveDateTime) -> bool {
+        let day = now.weekday().num_days_from_monday();
+        let prev = (day + 6) % 7;
+        let minute = now.hour() * 60 + now.minute();
+        if self.start == self.end {
+            self.on_day(day)
+        } else if self.start < self.end {
+            self.on_day(day) && (self.start..self.end).contains(&minute)
+        } else {
+            (self.on_day(day) && minute >= self.start) || (self.on_day(prev) && minute < self.end)
+        }
+    }
+}
+
+fn parse_hhmm(s: &str) -> Option<u32> {
+    let (h, m) = s.trim().split_once(':')?;
+    let (h, m) = (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?);
+    (h < 24 && m < 60).then_some(h * 60 + m)
+}
+
+/// Time windows in which queued downloads may start. No windows = always open.
+#[derive(Debug, Clone, Default)]
+pub struct Schedule {
+    windows: Vec<Window>,
+}
+
+impl Schedule {
+    pub fn from_settings(s: &Settings) -> Self {
+        let windows = s
+            .download_windows
+            .iter()
+            .filter_map(|w| {
+                let parsed = Window::parse(w);
+                if parsed.is_none() {
+                    eprintln!("[schedule] ignoring invalid window {}-{}", w.start, w.end);
+                }
+                parsed
+            })
+            .collect();
+        Self { windows }
+    }
+
+    pub fn is_open(&self, now: NaiveDateTime) -> bool {
+        self.windows.is_empty() || self.windows.iter().any(|w| w.contains(now))
+    }
+
+    /// Next time a window opens after `now`, looking one week ahead.
+    pub fn next_start(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        (0..=7)
+            .flat_map(|offset| {
+                let date = now.date() + Duration::days(offset);
+                let day = date.weekday().num_days_from_monday();
+                self.windows
+                    .iter()
+                    .filter(move |w| w.on_day(day))
+                    .filter_map(move |w| {
+                        NaiveTime::from_hms_opt(w.start / 60, w.start % 60, 0)
+                            .map(|t| date.and_time(t))
+                    })
+            })
+            .filter(|t| *t > now)
+            .min()
+    }
+
+    pub fn status(&self, now: DateTime<Local>) -> ScheduleStatus {
+        let now = now.naive_local();
+        let open = self.is_open(now);
+        let starts_at = (!open).then(|| self.next_start(now)).flatten().map(|t| {
+            if t.date() == now.date() {
+                t.format("%H:%M").to_string()
+            } else {
+                t.format("%a %H:%M").to_string()
+            }
+        });
+        ScheduleStatus {
+            scheduled: !self.windows.is_empty(),
+            open,
+            starts_at,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use clip_core::FailureKind;
+
+use crate::database::SessionReport;
+
+/// How many collections a report lists.
+const TOP_COLLECTIONS: usize = 5;
+
+/// What a finished download contributes to the running session.
+#[derive(Debug, Clone, Default)]
+pub struct TaskOutcome {
+    pub error: Option<String>,
+    pub bytes: u64,
+    /// "{platform}/{handle} | {origin}"
+    pub collection: String,
+    /// Left out by its collection's filter; neither downloaded nor failed
+    pub skipped: bool,
+}
+
+/// Counters for the current queue run; turned into a `SessionReport` when the queue drains.
+#[derive(Default)]
+pub struct SessionTally {
+    started_at: Option<DateTime<Utc>>,
+    downloaded: u32,
+    failed: u32,
+    bytes: u64,
+    failures: HashMap<FailureKind, u32>,
+    collections: HashMap<String, u32>,
+}
+
+impl SessionTally {
+    /// Mark the session as running; no-op if it already is.
+    pub fn start(&mut self) {
+        self.started_at.get_or_insert_with(Utc::now);
+    }
+
+    pub fn record(&mut self, outcome: TaskOutcome) {
+        self.start();
+        if outcome.skipped {
+            return;
+        }
+        match outcome.error {
+            Some(err) => {
+                self.failed += 1;
+                *self
+                    .failures
+                    .entry(FailureKind::classify(Some(&err)))
+                    .or_default() += 1;
+            }
+            None => {
+                self.downloaded += 1;
+                self.bytes += outcome.bytes;
+                *self.collections.entry(outcome.collection).or_default() += 1;
+            }
+        }
+    }
+
+    /// Close the session and reset; `None` if nothing finished during it.
+    pub fn finish(&mut self) -> Option<SessionReport> {
+        let tally = std::mem::take(self);
+        let started_at = tally.started_at?;
+        if tally.downloaded == 0 && tally.failed == 0 {
+            return None;
+        }
+        let ended_at = Utc::now();
+
+        let mut failures: Vec<(String, u32)> = tally
+            .failures
+            .into_iter()
+            .map(|(kind, n)| (kind.label().to_string(), n))
+            .collect();
+        failures.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let mut top_collections: Vec<(String, u32)> = tally.collections.into_iter().collect();
+        top_collections.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_collections.truncate(TOP_COLLECTIONS);
+
+        Some(SessionReport {
+            id: 0,
+            started_at: started_at.to_rfc3339(),
+            ended_at: ended_at.to_rfc3339(),
+            elapsed_secs: (ended_at - started_at).num_seconds().max(0) as u64,
+            downloaded: tally.downloaded,
+            failed: tally.failed,
+            bytes: tally.bytes,
+            failures,
+            top_collections,
+            dismissed: false,
+        })
+    }
+}
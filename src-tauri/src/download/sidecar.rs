@@ -0,0 +1,53 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::database::SidecarMetadata;
+
+/// Whether `name` is a gallery-dl metadata file (`photo.jpg.json`) rather than
+/// a download of its own.
+pub fn is_sidecar_name(name: &str) -> bool {
+    name.to_lowercase().ends_with(".json")
+}
+
+/// The sidecar saved next to `media`: yt-dlp's `stem.info.json` or gallery-dl's
+/// `file.ext.json`.
+pub fn find_for(media: &Path) -> Option<PathBuf> {
+    let mut gallery = media.as_os_str().to_owned();
+    gallery.push(".json");
+    [media.with_extension("info.json"), PathBuf::from(gallery)]
+        .into_iter()
+        .find(|p| p.is_file())
+}
+
+/// "20240501" (yt-dlp) or "2024-05-01 12:00:00" (gallery-dl) as YYYY-MM-DD.
+fn normalize_date(raw: &str) -> Option<String> {
+    let digits: String = raw.chars().filter(|c| c.is_ascii_digit()).take(8).collect();
+    (digits.len() == 8).then(|| format!("{}-{}-{}", &digits[..4], &digits[4..6], &digits[6..]))
+}
+
+fn text(json: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|k| json.get(*k)?.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// Title, description, upload date, duration and view count from a sidecar.
+pub fn read(path: &Path) -> Option<SidecarMetadata> {
+    let json: Value = serde_json::from_slice(&fs::read(path).ok()?).ok()?;
+    Some(SidecarMetadata {
+        title: text(&json, &["title", "fulltitle"]),
+        description: text(&json, &["description", "content"]),
+        upload_date: text(&json, &["upload_date", "date"]).and_then(|d| normalize_date(&d)),
+        duration_secs: json
+            .get("duration")
+            .and_then(Value::as_f64)
+            .map(|d| d.round() as u32),
+        view_count: ["view_count", "video_view_count"]
+            .iter()
+            .find_map(|k| json.get(*k)?.as_u64()),
+    })
+}
@@ -0,0 +1,57 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const IMAGE_EXTS: &[&str] = &["jpg", "jpeg", "png", "webp", "gif"];
+
+/// Preview images for the Library grid, one `<download id>.<ext>` per item.
+pub fn cache_dir() -> PathBuf {
+    crate::libraries::active_dir().join("thumbnails")
+}
+
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| IMAGE_EXTS.contains(&e.to_lowercase().as_str()))
+}
+
+/// yt-dlp flags that write the video's thumbnail straight into the cache,
+/// named after the download so it can be found afterwards.
+pub fn yt_dlp_flags(id: i64) -> Vec<String> {
+    let template = cache_dir().join(format!("{id}.%(ext)s"));
+    vec![
+        "--write-thumbnail".into(),
+        "-o".into(),
+        format!("thumbnail:{}", template.to_string_lossy()),
+    ]
+}
+
+/// The cached thumbnail of download `id`, whatever format it was saved in.
+fn cached(id: i64) -> Option<PathBuf> {
+    IMAGE_EXTS
+        .iter()
+        .map(|ext| cache_dir().join(format!("{id}.{ext}")))
+        .find(|p| p.is_file())
+}
+
+/// Thumbnail for a finished download: the one yt-dlp wrote, or else a copy
+/// of the downloaded file itself when it is an image (first one of a gallery).
+pub fn capture(id: i64, first_file: Option<&str>) -> Option<PathBuf> {
+    if let Some(path) = cached(id) {
+        return Some(path);
+    }
+    let src = Path::new(first_file?);
+    if !is_image(src) || !src.is_file() {
+        return None;
+    }
+    let ext = src.extension()?.to_string_lossy().to_lowercase();
+    let dir = cache_dir();
+    fs::create_dir_all(&dir).ok()?;
+    let dest = dir.join(format!("{id}.{ext}"));
+    match fs::copy(src, &dest) {
+        Ok(_) => Some(dest),
+        Err(e) => {
+            eprintln!("[BACKEND] thumbnail copy failed for {id}: {e}");
+            None
+        }
+    }
+}
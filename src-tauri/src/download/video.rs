@@ -3,24 +3,14 @@ use std::path::Path;
 use std::sync::Arc;
 use tokio::time::{timeout, Duration};
 
-use crate::commands::parse::{last_segment, tiktok_id_from_url, youtube_id_from_url};
-use crate::database::OnDuplicate;
-use crate::download::manager::DownloadEvent;
+use crate::database::{AudioFormat, DateRange, MediaMetadata, OnDuplicate, Platform, Settings};
+use crate::download::manager::{DownloadEvent, MessageLevel};
+use crate::download::process::JobHandle;
+use crate::utils::filenames::NAME_SUFFIX_RESERVE;
+use clip_core::url::last_segment;
 
 use tauri::Manager;
-use tauri_plugin_shell::{
-    process::{CommandChild, CommandEvent},
-    ShellExt,
-};
-
-struct KillGuard(Option<CommandChild>);
-impl Drop for KillGuard {
-    fn drop(&mut self) {
-        if let Some(c) = self.0.take() {
-            let _ = c.kill();
-        }
-    }
-}
+use tauri_plugin_shell::{process::CommandEvent, ShellExt};
 
 #[cfg(target_family = "windows")]
 fn path_sep() -> &'static str {
@@ -31,52 +21,54 @@ fn path_sep() -> &'static str {
     ":"
 }
 
-fn base_ytdlp_args(cookie_arg: &str, is_ig_images: bool, audio_only: bool) -> Vec<String> {
+fn base_ytdlp_args(
+    settings: &Settings,
+    cookie_arg: &str,
+    url: &str,
+    is_ig_images: bool,
+    audio: Option<AudioFormat>,
+) -> Vec<String> {
     let mut args: Vec<String> = vec![
         "--newline".into(),
         "-N".into(),
         "8".into(),
-        "--cookies-from-browser".into(),
-        cookie_arg.into(),
         "--ignore-config".into(),
         "--no-cache-dir".into(),
     ];
+    args.extend(crate::utils::os::cookie_flags(cookie_arg));
     if is_ig_images {
         args.push("--ignore-no-formats-error".into());
-    } else if audio_only {
+    } else if let Some(format) = audio {
         args.extend(vec![
             "-x".into(),
             "--audio-format".into(),
-            "mp3".into(),
+            format.ext().into(),
             "--audio-quality".into(),
-            "0".into(),
+            settings.audio_quality.clone(),
         ]);
     } else {
         args.extend(vec![
             "-f".into(),
-            "bestvideo+bestaudio/best".into(),
+            crate::settings::get_yt_dlp_format(settings, url),
             "--merge-output-format".into(),
             "mp4".into(),
         ]);
+        args.extend(crate::settings::get_yt_dlp_subtitle_flags(settings));
     }
     args
 }
 
-/* ---------- helpers to read parts from the URL ---------- */
-fn ig_id_from_url(url: &str) -> Option<String> {
-    if let Some(pos) = url.find("instagram.com/") {
-        let rest = &url[pos + "instagram.com/".len()..];
-        let parts: Vec<&str> = rest.trim_matches('/').split('/').collect();
-        if parts.len() >= 3 {
-            let typ = parts[1];
-            if typ == "reel" || typ == "p" {
-                return Some(parts[2].to_string());
-            }
-        }
-    }
-    None
+/// Subtitle files yt-dlp reports writing ("[info] Writing video subtitles to: …").
+pub fn subtitle_paths_from_output(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("[info] Writing video subtitles to:"))
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
 }
 
+/* ---------- helpers to read parts from the URL ---------- */
 fn ig_handle_from_url(url: &str) -> Option<String> {
     if let Some(pos) = url.find("instagram.com/") {
         let first = &url[pos + "instagram.com/".len()..];
@@ -104,22 +96,10 @@ fn tiktok_username_from_url(url: &str) -> Option<String> {
 /// - TikTok: id after /video/ or /photo/, else last path segment
 /// - YouTube: v=… or /shorts/…
 fn rest_token_from_url(url: &str) -> String {
-    if url.contains("instagram.com/") {
-        if let Some(id) = ig_id_from_url(url) {
-            return id;
-        }
-    }
-    if url.contains("tiktok.com/") {
-        if let Some(id) = tiktok_id_from_url(url) {
-            return id;
-        }
-    }
-    if url.contains("youtube.com/") || url.contains("youtu.be/") {
-        if let Some(id) = youtube_id_from_url(url) {
-            return id;
-        }
-    }
-    last_segment(url).unwrap_or_else(|| "media".into())
+    crate::platforms::detect(url)
+        .and_then(|d| d.item_id(url))
+        .or_else(|| last_segment(url))
+        .unwrap_or_else(|| "media".into())
 }
 
 fn sanitize<S: Into<String>>(s: S) -> String {
@@ -172,7 +152,20 @@ async fn probe_uploader(
     processed_url: &str,
     is_ig_images: bool,
 ) -> Option<String> {
-    let mut args = base_ytdlp_args(cookie_arg, is_ig_images, false);
+    let settings = crate::settings::load_settings();
+    let mut args = base_ytdlp_args(&settings, cookie_arg, processed_url, is_ig_images, None);
+    args.extend(crate::settings::get_yt_dlp_header_flags(
+        &settings,
+        processed_url,
+    ));
+    args.extend(crate::settings::get_yt_dlp_geo_flags(
+        &settings,
+        processed_url,
+    ));
+    args.extend(crate::settings::get_yt_dlp_proxy_flags(
+        &settings,
+        processed_url,
+    ));
     args.push("--simulate".into());
     args.extend(vec![
         "--print".into(),
@@ -180,7 +173,6 @@ async fn probe_uploader(
         processed_url.into(),
     ]);
 
-    let settings = crate::settings::load_settings();
     let cmd = if settings.use_system_binaries {
         app.shell().command("yt-dlp")
     } else {
@@ -207,7 +199,8 @@ async fn probe_uploader(
         )
     };
 
-    let Ok((mut rx, _child)) = cmd.args(args).env("PATH", new_path).spawn() else {
+    let env = crate::settings::tool_env(&settings, new_path);
+    let Ok((mut rx, _child)) = cmd.args(args).envs(env).spawn() else {
         return None;
     };
 
@@ -229,6 +222,275 @@ async fn probe_uploader(
         .map(sanitize)
 }
 
+/// Length of a downloaded media file in whole seconds, per ffprobe. `None`
+/// for images or when ffprobe can't read the file.
+pub async fn probe_duration(app: &tauri::AppHandle, path: &Path) -> Option<u32> {
+    let settings = crate::settings::load_settings();
+    let cmd = if settings.use_system_binaries {
+        app.shell().command("ffprobe")
+    } else {
+        app.shell().sidecar("ffprobe").ok()?
+    };
+    let env = crate::settings::tool_env(&settings, std::env::var("PATH").unwrap_or_default());
+    let args = [
+        "-v".to_string(),
+        "error".into(),
+        "-show_entries".into(),
+        "format=duration".into(),
+        "-of".into(),
+        "csv=p=0".into(),
+        path.to_string_lossy().to_string(),
+    ];
+    let out = timeout(Duration::from_secs(30), cmd.args(args).envs(env).output())
+        .await
+        .ok()?
+        .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let secs: f64 = String::from_utf8_lossy(&out.stdout).trim().parse().ok()?;
+    (secs.is_finite() && secs > 0.0).then(|| secs.round() as u32)
+}
+
+/// Read a link's metadata with yt-dlp without downloading anything.
+pub async fn probe_metadata(
+    app: &tauri::AppHandle,
+    cookie_arg: &str,
+    url: &str,
+) -> Result<MediaMetadata, String> {
+    let settings = crate::settings::load_settings();
+    let mut args: Vec<String> = vec![
+        "--ignore-config".into(),
+        "--no-cache-dir".into(),
+        "--no-warnings".into(),
+        "--no-playlist".into(),
+    ];
+    args.extend(crate::utils::os::cookie_flags(cookie_arg));
+    args.extend(crate::settings::get_yt_dlp_header_flags(&settings, url));
+    args.extend(crate::settings::get_yt_dlp_geo_flags(&settings, url));
+    args.extend(crate::settings::get_yt_dlp_proxy_flags(&settings, url));
+    args.extend([
+        "--skip-download".into(),
+        "--print".into(),
+        "%(title)s\t%(uploader)s\t%(upload_date)s\t%(duration)s\t%(thumbnail)s\t%(filesize,filesize_approx)s\t%(webpage_url)s"
+            .into(),
+        url.into(),
+    ]);
+
+    let cmd = if settings.use_system_binaries {
+        app.shell().command("yt-dlp")
+    } else {
+        app.shell().sidecar("yt-dlp").map_err(|e| e.to_string())?
+    };
+    let env = crate::settings::tool_env(&settings, std::env::var("PATH").unwrap_or_default());
+    let out = timeout(Duration::from_secs(60), cmd.args(args).envs(env).output())
+        .await
+        .map_err(|_| "timed out".to_string())?
+        .map_err(|e| e.to_string())?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(stderr
+            .lines()
+            .last()
+            .unwrap_or("yt-dlp failed")
+            .trim()
+            .to_string());
+    }
+
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let line = stdout.lines().next().unwrap_or_default();
+    let mut fields = line.split('\t').map(|f| {
+        let f = f.trim();
+        (!f.is_empty() && !f.eq_ignore_ascii_case("na")).then(|| f.to_string())
+    });
+    let title = fields.next().flatten();
+    let uploader = fields.next().flatten();
+    let upload_date =
+        fields
+            .next()
+            .flatten()
+            .map(|d| match (d.get(..4), d.get(4..6), d.get(6..8)) {
+                (Some(y), Some(m), Some(day)) if d.len() == 8 => format!("{y}-{m}-{day}"),
+                _ => d,
+            });
+    // yt-dlp reports fractional seconds for some sites.
+    let duration_secs = fields
+        .next()
+        .flatten()
+        .and_then(|d| d.parse::<f64>().ok())
+        .map(|d| d.round() as u32);
+    let thumbnail_url = fields.next().flatten();
+    let estimated_bytes = fields
+        .next()
+        .flatten()
+        .and_then(|b| b.parse::<f64>().ok())
+        .map(|b| b as u64);
+    let webpage_url = fields.next().flatten();
+    Ok(MediaMetadata {
+        title,
+        uploader,
+        upload_date,
+        duration_secs,
+        thumbnail_url,
+        estimated_bytes,
+        webpage_url,
+    })
+}
+
+/// Item links of a profile, channel or playlist, read with yt-dlp's flat
+/// listing so nothing is downloaded. Items dated outside `range` are left
+/// out. `on_entry` gets the running count; returning `false` from
+/// `keep_going` stops the listing early.
+pub async fn list_collection_entries(
+    app: &tauri::AppHandle,
+    cookie_arg: &str,
+    url: &str,
+    range: &DateRange,
+    keep_going: impl Fn() -> bool,
+    mut on_entry: impl FnMut(usize),
+) -> Result<Vec<String>, String> {
+    let settings = crate::settings::load_settings();
+    let mut args: Vec<String> = vec![
+        "--ignore-config".into(),
+        "--no-cache-dir".into(),
+        "--no-warnings".into(),
+        "--flat-playlist".into(),
+    ];
+    args.extend(crate::utils::os::cookie_flags(cookie_arg));
+    args.extend(crate::settings::get_yt_dlp_header_flags(&settings, url));
+    args.extend(crate::settings::get_yt_dlp_geo_flags(&settings, url));
+    args.extend(crate::settings::get_yt_dlp_proxy_flags(&settings, url));
+    args.extend(crate::settings::get_yt_dlp_date_flags(range));
+    args.extend([
+        "--print".into(),
+        "%(webpage_url,url)s\t%(upload_date)s".into(),
+        url.into(),
+    ]);
+
+    let cmd = if settings.use_system_binaries {
+        app.shell().command("yt-dlp")
+    } else {
+        app.shell().sidecar("yt-dlp").map_err(|e| e.to_string())?
+    };
+    let env = crate::settings::tool_env(&settings, std::env::var("PATH").unwrap_or_default());
+    let (mut rx, child) = cmd
+        .args(args)
+        .envs(env)
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    let mut seen = std::collections::HashSet::new();
+    let mut links = Vec::new();
+    let mut last_err = String::new();
+    let mut exit = None;
+    while let Some(event) = rx.recv().await {
+        if !keep_going() {
+            let _ = child.kill();
+            return Err("canceled".into());
+        }
+        match event {
+            CommandEvent::Stdout(line) => {
+                let line = String::from_utf8_lossy(&line).trim().to_string();
+                let (link, date) = line.split_once('\t').unwrap_or((&line, ""));
+                if link.starts_with("http")
+                    && range.contains(Some(date))
+                    && seen.insert(link.to_string())
+                {
+                    links.push(link.to_string());
+                    on_entry(links.len());
+                }
+            }
+            CommandEvent::Stderr(line) => {
+                let line = String::from_utf8_lossy(&line).trim().to_string();
+                if !line.is_empty() {
+                    last_err = line;
+                }
+            }
+            CommandEvent::Terminated(payload) => exit = payload.code,
+            _ => {}
+        }
+    }
+    // A private or removed item makes yt-dlp exit non-zero after listing the rest.
+    if exit != Some(0) && links.is_empty() {
+        return Err(if last_err.is_empty() {
+            "yt-dlp failed".into()
+        } else {
+            last_err
+        });
+    }
+    Ok(links)
+}
+
+/// Title and video links of a playlist, from yt-dlp's flat JSON dump,
+/// without videos dated outside `range`.
+pub async fn list_playlist(
+    app: &tauri::AppHandle,
+    cookie_arg: &str,
+    url: &str,
+    range: &DateRange,
+) -> Result<(Option<String>, Vec<String>), String> {
+    let settings = crate::settings::load_settings();
+    let mut args: Vec<String> = vec![
+        "--ignore-config".into(),
+        "--no-cache-dir".into(),
+        "--no-warnings".into(),
+        "--flat-playlist".into(),
+    ];
+    args.extend(crate::utils::os::cookie_flags(cookie_arg));
+    args.extend(crate::settings::get_yt_dlp_header_flags(&settings, url));
+    args.extend(crate::settings::get_yt_dlp_geo_flags(&settings, url));
+    args.extend(crate::settings::get_yt_dlp_proxy_flags(&settings, url));
+    args.extend(crate::settings::get_yt_dlp_date_flags(range));
+    args.extend(["-J".into(), url.into()]);
+
+    let cmd = if settings.use_system_binaries {
+        app.shell().command("yt-dlp")
+    } else {
+        app.shell().sidecar("yt-dlp").map_err(|e| e.to_string())?
+    };
+    let env = crate::settings::tool_env(&settings, std::env::var("PATH").unwrap_or_default());
+    let out = cmd
+        .args(args)
+        .envs(env)
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !out.status.success() && out.stdout.is_empty() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(stderr
+            .lines()
+            .last()
+            .unwrap_or("yt-dlp failed")
+            .trim()
+            .to_string());
+    }
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&out.stdout).map_err(|e| format!("bad yt-dlp JSON: {e}"))?;
+    let title = json
+        .get("title")
+        .and_then(|t| t.as_str())
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty());
+    let mut seen = std::collections::HashSet::new();
+    let links = json
+        .get("entries")
+        .and_then(|e| e.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|entry| range.contains(entry.get("upload_date").and_then(|d| d.as_str())))
+        .filter_map(|entry| {
+            ["webpage_url", "url"]
+                .iter()
+                .find_map(|key| entry.get(*key).and_then(|v| v.as_str()))
+        })
+        .filter(|link| link.starts_with("http"))
+        .map(str::to_string)
+        .filter(|link| seen.insert(link.clone()))
+        .collect();
+    Ok((title, links))
+}
+
 /* ---------- output template selection ---------- */
 
 async fn choose_output_template(
@@ -237,17 +499,17 @@ async fn choose_output_template(
     cookie_arg: &str,
     processed_url: &str,
     is_ig_images: bool,
-    audio_only: bool,
+    audio: Option<AudioFormat>,
     on_duplicate: &OnDuplicate,
 ) -> io::Result<String> {
     let rest_id = sanitize(rest_token_from_url(processed_url));
 
-    let url_author = if processed_url.contains("instagram.com/") {
-        ig_handle_from_url(processed_url)
-    } else if processed_url.contains("tiktok.com/") {
-        tiktok_username_from_url(processed_url)
-    } else {
-        None
+    let url_author = match crate::platforms::detect(processed_url).map(|d| d.platform) {
+        Some(Platform::Instagram) => ig_handle_from_url(processed_url),
+        Some(Platform::Tiktok) => tiktok_username_from_url(processed_url),
+        Some(Platform::Twitter) => clip_core::url::twitter_handle_and_id(processed_url).0,
+        Some(Platform::Reddit) => clip_core::url::reddit_handle_and_id(processed_url).0,
+        _ => None,
     };
 
     let mut author_real = if let Some(author) = url_author {
@@ -259,8 +521,18 @@ async fn choose_output_template(
     };
     author_real = sanitize(author_real);
 
-    let base_stem = format!("{author_real} [{rest_id}]");
-    let ext = if audio_only { "mp3" } else { "mp4" };
+    // Shorten the author part so the whole name fits the destination filesystem.
+    let rules = crate::utils::filenames::rules_for(out_dir, &crate::settings::load_settings());
+    // Keep a few units for the author even when the id token is huge.
+    let rest_id = rules.fit(&rules.clean(&rest_id), NAME_SUFFIX_RESERVE + 16);
+    let id_part = format!(" [{rest_id}]");
+    let author_real = rules.fit(
+        &rules.clean(&author_real),
+        NAME_SUFFIX_RESERVE + rules.measure(&id_part),
+    );
+
+    let base_stem = format!("{author_real}{id_part}");
+    let ext = audio.map_or("mp4", AudioFormat::ext);
 
     let mut chosen_stem = base_stem.clone();
     let chosen_path = out_dir.join(format!("{chosen_stem}.{ext}"));
@@ -291,21 +563,24 @@ pub async fn run_yt_dlp_with_progress(
     app: &tauri::AppHandle,
     out_dir: &Path,
     cookie_arg: &str,
-    processed_url: &str,
+    real_url: &str,
     is_ig_images: bool,
+    audio: Option<AudioFormat>,
     on_duplicate: &OnDuplicate,
     id: i64,
     emitter: Arc<dyn Fn(DownloadEvent) + Send + Sync>,
+    job: &JobHandle,
 ) -> io::Result<(bool, String)> {
-    let audio_only = processed_url.ends_with("#__audio_only__");
-    let real_url = if audio_only {
-        &processed_url[..processed_url.len() - "#__audio_only__".len()]
+    // Load settings to determine whether to use system binaries
+    let settings = crate::settings::load_settings();
+    let mut args = base_ytdlp_args(&settings, cookie_arg, real_url, is_ig_images, audio);
+    if job.is_resume() {
+        // Overwrite and skip policies both imply --no-continue, which would
+        // throw away the partial file a pause left behind.
+        args.push("--continue".into());
     } else {
-        processed_url
-    };
-
-    let mut args = base_ytdlp_args(cookie_arg, is_ig_images, audio_only);
-    args.extend(crate::settings::get_yt_dlp_duplicate_flags(on_duplicate));
+        args.extend(crate::settings::get_yt_dlp_duplicate_flags(on_duplicate));
+    }
 
     // Prints used by parse_multiple_filenames_from_output
     args.extend(vec![
@@ -320,9 +595,8 @@ pub async fn run_yt_dlp_with_progress(
     // Destination directory (avoid spills)
     args.push("-P".into());
     args.push(out_dir.to_string_lossy().to_string());
-
-    // Load settings to determine whether to use system binaries
-    let settings = crate::settings::load_settings();
+    args.extend(super::thumbnails::yt_dlp_flags(id));
+    args.extend(crate::settings::get_yt_dlp_info_json_flags(&settings));
 
     // Determine resource dir for bundled ffmpeg (when not using system binaries)
     use tauri::path::BaseDirectory;
@@ -335,9 +609,32 @@ pub async fn run_yt_dlp_with_progress(
         args.push("--ffmpeg-location".into());
         args.push(res_dir.to_string_lossy().to_string());
     }
+    args.extend(crate::settings::get_yt_dlp_header_flags(
+        &settings, real_url,
+    ));
+    if let Some(country) = crate::settings::geo_bypass_for_url(&settings, real_url) {
+        (emitter)(DownloadEvent::Message {
+            id,
+            level: MessageLevel::Milestone,
+            message: format!("Geo-bypass: pretending to be in {country}"),
+        });
+    }
+    args.extend(crate::settings::get_yt_dlp_geo_flags(&settings, real_url));
+    args.extend(crate::settings::get_yt_dlp_proxy_flags(&settings, real_url));
+    let trim = crate::settings::get_yt_dlp_trim_flags(&settings, real_url);
+    if let Some(section) = trim.get(1) {
+        (emitter)(DownloadEvent::Message {
+            id,
+            level: MessageLevel::Milestone,
+            message: format!("Trimming to the link's timestamp ({})", &section[1..]),
+        });
+    }
+    args.extend(trim);
+    args.extend(super::bandwidth::yt_dlp_flags());
 
     (emitter)(DownloadEvent::Message {
         id,
+        level: MessageLevel::Milestone,
         message: "Inspecting media metadata".into(),
     });
 
@@ -348,7 +645,7 @@ pub async fn run_yt_dlp_with_progress(
         cookie_arg,
         real_url,
         is_ig_images,
-        audio_only,
+        audio,
         on_duplicate,
     )
     .await?;
@@ -359,7 +656,7 @@ pub async fn run_yt_dlp_with_progress(
     args.push(real_url.to_string());
 
     let planned_path =
-        out_dir.join(output_template.replace("%(ext)s", if audio_only { "mp3" } else { "mp4" }));
+        out_dir.join(output_template.replace("%(ext)s", audio.map_or("mp4", AudioFormat::ext)));
     println!(
         "[YT-DLP][sidecar] policy={:?} dir='{}'\nurl='{}'\nout='{}'",
         on_duplicate,
@@ -369,6 +666,7 @@ pub async fn run_yt_dlp_with_progress(
     );
     (emitter)(DownloadEvent::Message {
         id,
+        level: MessageLevel::Debug,
         message: format!("Prepared output file {}", planned_path.display()),
     });
 
@@ -393,17 +691,19 @@ pub async fn run_yt_dlp_with_progress(
         )
     };
 
+    let env = crate::settings::tool_env(&settings, new_path);
     let (mut rx, child) =
-        cmd.args(args).env("PATH", new_path).spawn().map_err(|e| {
+        cmd.args(args).envs(env).spawn().map_err(|e| {
             io::Error::new(io::ErrorKind::Other, format!("spawn yt-dlp failed: {e}"))
         })?;
 
     (emitter)(DownloadEvent::Message {
         id,
+        level: MessageLevel::Milestone,
         message: "Launching downloader".into(),
     });
 
-    let _guard = KillGuard(Some(child));
+    let _child = job.attach(child);
 
     let mut all_output = String::new();
     let mut already_downloaded = false;
@@ -437,11 +737,15 @@ pub async fn run_yt_dlp_with_progress(
                             last_stage = Some(stage);
                             (emitter)(DownloadEvent::Message {
                                 id,
+                                level: MessageLevel::Milestone,
                                 message: stage.into(),
                             });
                         }
                     }
 
+                    if let Some(dest) = l.strip_prefix("[download] Destination:") {
+                        job.note_output(dest.trim());
+                    }
                     if l.contains("has already been downloaded") {
                         already_downloaded = true;
                     }
@@ -451,12 +755,13 @@ pub async fn run_yt_dlp_with_progress(
                         file_skipped = true;
                     }
 
-                    if let Some(progress) = parse_progress_percentage(l) {
+                    if let Some((progress, downloaded_bytes, total_bytes)) = parse_progress_line(l)
+                    {
                         (emitter)(DownloadEvent::Progress {
                             id,
                             progress,
-                            downloaded_bytes: 0,
-                            total_bytes: None,
+                            downloaded_bytes,
+                            total_bytes,
                         });
                     } else if (l.contains("[download]") || l.contains("[info]"))
                         && !l.contains("Starting download for")
@@ -465,6 +770,7 @@ pub async fn run_yt_dlp_with_progress(
                     {
                         (emitter)(DownloadEvent::Message {
                             id,
+                            level: MessageLevel::Debug,
                             message: l.to_string(),
                         });
                     }
@@ -482,18 +788,21 @@ pub async fn run_yt_dlp_with_progress(
                                 last_stage = Some(stage);
                                 (emitter)(DownloadEvent::Message {
                                     id,
+                                    level: MessageLevel::Milestone,
                                     message: stage.into(),
                                 });
                             }
                         }
                         (emitter)(DownloadEvent::Message {
                             id,
+                            level: MessageLevel::Debug,
                             message: l.to_string(),
                         });
                     }
                 }
             }
             CommandEvent::Terminated(code) => {
+                job.note_exit(code.code);
                 ok = code.code == Some(0) || already_downloaded || file_skipped;
             }
             _ => {}
@@ -517,3 +826,37 @@ fn parse_progress_percentage(line: &str) -> Option<f32> {
         .ok()
         .map(|p| (p / 100.0).clamp(0.0, 1.0))
 }
+
+/// Parse `[download]  42.3% of ~ 10.5MiB at 2.1MiB/s ETA 00:12` into
+/// (fraction, downloaded bytes, total bytes). yt-dlp only prints a percentage
+/// until it knows the size, so the byte counts are optional.
+fn parse_progress_line(line: &str) -> Option<(f32, u64, Option<u64>)> {
+    let progress = parse_progress_percentage(line)?;
+    let total = line
+        .split_once(" of ")
+        .map(|(_, rest)| rest.trim_start().trim_start_matches('~').trim_start())
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(parse_size);
+    let downloaded = total.map_or(0, |t| (t as f64 * progress as f64) as u64);
+    Some((progress, downloaded, total))
+}
+
+/// "10.50MiB" → bytes; yt-dlp prints binary units, some builds decimal ones.
+fn parse_size(s: &str) -> Option<u64> {
+    let split = s.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+    let (num, unit) = s.split_at(split);
+    let value: f64 = num.parse().ok()?;
+    let factor = match unit {
+        "B" => 1.0,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        "kB" | "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        _ => return None,
+    };
+    Some((value * factor) as u64)
+}
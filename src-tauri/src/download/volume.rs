@@ -0,0 +1,122 @@
+use std::path::{Path, PathBuf};
+
+use crate::database::{StorageLevel, StorageStatus, VolumeStatus};
+
+/// Free space below this turns the storage badge yellow...
+const LOW_SPACE_BYTES: u64 = 10 * 1024 * 1024 * 1024;
+/// ...and below this red: one long video may no longer fit.
+const CRITICAL_SPACE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Mount point of the removable or network volume holding `root`, or `None`
+/// when the folder lives on the system disk.
+///
+/// Recognised: `/Volumes/<name>` (macOS), `/media/<user>/<name>`,
+/// `/run/media/<user>/<name>` and `/mnt/<name>` (Linux), any drive other than
+/// the system drive and `\\server\share` roots (Windows).
+pub fn mount_point(root: &Path) -> Option<PathBuf> {
+    let s = root.to_string_lossy();
+    if cfg!(windows) {
+        return windows_mount_point(&s);
+    }
+    let parts: Vec<&str> = s.split('/').filter(|p| !p.is_empty()).collect();
+    let depth = match parts.as_slice() {
+        ["Volumes", _, ..] if cfg!(target_os = "macos") => 2,
+        ["media", _, _, ..] => 3,
+        ["run", "media", _, _, ..] => 4,
+        ["mnt", _, ..] => 2,
+        _ => return None,
+    };
+    Some(PathBuf::from(format!("/{}", parts[..depth].join("/"))))
+}
+
+fn windows_mount_point(s: &str) -> Option<PathBuf> {
+    let s = s
+        .strip_prefix(r"\\?\UNC\")
+        .map(|rest| format!(r"\\{rest}"))
+        .unwrap_or_else(|| s.strip_prefix(r"\\?\").unwrap_or(s).to_string());
+    if let Some(rest) = s.strip_prefix(r"\\") {
+        let mut parts = rest.split(['\\', '/']).filter(|p| !p.is_empty());
+        let (server, share) = (parts.next()?, parts.next()?);
+        return Some(PathBuf::from(format!(r"\\{server}\{share}\")));
+    }
+    let drive = s
+        .get(..2)
+        .filter(|d| d.ends_with(':'))?
+        .to_ascii_uppercase();
+    let system = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".into());
+    (!drive.eq_ignore_ascii_case(&system)).then(|| PathBuf::from(format!(r"{drive}\")))
+}
+
+/// Whether something is mounted at `mount`. On Linux an unmounted drive often
+/// leaves its empty folder behind, so the folder must also sit on a different
+/// device than its parent.
+fn is_mounted(mount: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let Ok(meta) = std::fs::metadata(mount) else {
+            return false;
+        };
+        if cfg!(target_os = "linux") {
+            return match mount.parent().map(std::fs::metadata) {
+                Some(Ok(parent)) => parent.dev() != meta.dev(),
+                _ => true,
+            };
+        }
+        true
+    }
+    #[cfg(not(unix))]
+    {
+        mount.exists()
+    }
+}
+
+/// Connection state of the drive holding `root`.
+pub fn status(root: &Path) -> VolumeStatus {
+    match mount_point(root) {
+        Some(mount) => VolumeStatus {
+            offline: !is_mounted(&mount),
+            mount_point: Some(mount.to_string_lossy().to_string()),
+        },
+        None => VolumeStatus::default(),
+    }
+}
+
+/// `root` is on a removable drive that is not connected.
+pub fn is_offline(root: &Path) -> bool {
+    status(root).offline
+}
+
+/// Free and total space of the drive holding `root`. A folder on an unplugged
+/// drive, or one that can't be read, is unreachable.
+pub fn storage_status(root: &Path) -> StorageStatus {
+    let path = root.to_string_lossy().to_string();
+    let space = if is_offline(root) {
+        None
+    } else {
+        fs2::available_space(root)
+            .and_then(|free| Ok((free, fs2::total_space(root)?)))
+            .ok()
+    };
+    let Some((free_bytes, total_bytes)) = space else {
+        return StorageStatus {
+            level: StorageLevel::Unreachable,
+            path,
+            ..Default::default()
+        };
+    };
+    // Big drives also warn once less than 5% is left.
+    let level = if free_bytes < CRITICAL_SPACE_BYTES {
+        StorageLevel::Critical
+    } else if free_bytes < LOW_SPACE_BYTES || free_bytes < total_bytes / 20 {
+        StorageLevel::Low
+    } else {
+        StorageLevel::Ok
+    };
+    StorageStatus {
+        level,
+        path,
+        free_bytes,
+        total_bytes,
+    }
+}
@@ -0,0 +1,137 @@
+//! Watched collections: profiles, channels and playlists listed again every
+//! few hours so new uploads land in the backlog (and the queue, when asked)
+//! without pasting the link again.
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use tauri::{AppHandle, Emitter};
+
+use crate::database::{Database, DateRange, WatchedCollection};
+use crate::download::manager::{DownloadCommand, DownloadManager};
+
+/// How often the manager looks for watches that are due.
+pub const CHECK_INTERVAL_SECS: u64 = 10 * 60;
+
+/// Watches being listed right now, so a manual check and the timer don't
+/// list the same collection twice.
+static SYNCING: Lazy<Mutex<HashSet<i64>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+fn is_due(watch: &WatchedCollection, now: DateTime<Utc>) -> bool {
+    let Some(last) = watch
+        .last_checked_at
+        .as_deref()
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+    else {
+        return true;
+    };
+    now.signed_duration_since(last) >= chrono::Duration::hours(watch.interval_hours.max(1) as i64)
+}
+
+/// Check every watch whose interval has passed. Nothing runs while the
+/// library is read-only.
+pub async fn sync_due(app: &AppHandle, manager: &DownloadManager) {
+    if crate::settings::ensure_writable().is_err() {
+        return;
+    }
+    let watches = tauri::async_runtime::spawn_blocking(|| {
+        Database::new()
+            .and_then(|db| db.list_watched_collections())
+            .map_err(|e| e.to_string())
+    })
+    .await;
+    let watches = match watches {
+        Ok(Ok(watches)) => watches,
+        Ok(Err(e)) => {
+            eprintln!("[BACKEND] [watch] listing watches failed: {e}");
+            return;
+        }
+        Err(e) => {
+            eprintln!("[BACKEND] [watch] join error: {e}");
+            return;
+        }
+    };
+    let now = Utc::now();
+    for watch in watches.into_iter().filter(|w| is_due(w, now)) {
+        let _ = sync(app, manager, watch).await;
+    }
+}
+
+/// List `watch` again and add the links not seen before; they are queued
+/// when the watch auto-queues. Returns how many were added.
+pub async fn sync(
+    app: &AppHandle,
+    manager: &DownloadManager,
+    watch: WatchedCollection,
+) -> Result<usize, String> {
+    if !SYNCING.lock().unwrap().insert(watch.id) {
+        return Err(format!("{} is already being checked", watch.handle));
+    }
+    let result = list_and_insert(app, &watch).await;
+    SYNCING.lock().unwrap().remove(&watch.id);
+
+    let id = watch.id;
+    let (added, error) = match &result {
+        Ok(ids) => (ids.len() as u32, None),
+        Err(e) => (0, Some(e.clone())),
+    };
+    let recorded = tauri::async_runtime::spawn_blocking(move || {
+        Database::new()
+            .and_then(|db| db.record_watch_check(id, Utc::now(), added, error.as_deref()))
+            .map_err(|e| e.to_string())
+    })
+    .await;
+    if let Ok(Err(e)) = recorded {
+        eprintln!("[BACKEND] [watch] failed to record check of {id}: {e}");
+    }
+
+    let ids = match result {
+        Ok(ids) => ids,
+        Err(e) => {
+            eprintln!("[BACKEND] [watch] {} failed: {e}", watch.url);
+            return Err(e);
+        }
+    };
+    println!("[BACKEND] [watch] {}: {} new item(s)", watch.url, ids.len());
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let _ = app.emit("import_completed", ids.len());
+    let added = ids.len();
+    if watch.auto_queue {
+        manager
+            .send(DownloadCommand::Enqueue { ids, reply: None })
+            .await?;
+    }
+    Ok(added)
+}
+
+async fn list_and_insert(app: &AppHandle, watch: &WatchedCollection) -> Result<Vec<i64>, String> {
+    let settings = crate::settings::load_settings();
+    let browsers = crate::settings::cookie_sources(&settings, &watch.url, None);
+    let Some((_, cookie_arg)) = browsers.first() else {
+        return Err("No logged-in browsers detected for cookies.".into());
+    };
+    let links = crate::download::video::list_collection_entries(
+        app,
+        cookie_arg,
+        &watch.url,
+        &DateRange::default(),
+        || true,
+        |_| {},
+    )
+    .await?;
+    let (platform, origin, handle) = (watch.platform, watch.origin, watch.handle.clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::commands::downloader::insert_collection_links(
+            &links,
+            platform,
+            origin,
+            &handle,
+            |_, _| true,
+        )
+    })
+    .await
+    .map_err(|e| format!("Join error: {e}"))?
+}
@@ -0,0 +1,289 @@
+//! Optional HTTP API on 127.0.0.1 for the companion browser extension, so it
+//! can push links straight into the backlog. Every route except the CORS
+//! preflight needs `Authorization: Bearer <token>`.
+//!
+//! - `POST /enqueue` `{"urls": [...], "queue": false}`: add links from supported
+//!   sites to the backlog (and queue them when `queue` is true)
+//! - `GET /status`: app version, row counts per status, drive state
+//! - `GET /library?limit=100&offset=0`: finished downloads
+//! - `GET /archived?url=...`: the row saved for a link (status, path, date),
+//!   matched the same loose way imports are
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::database::{Database, DownloadStatus, HttpApiConfig, RowFilter, RowSort};
+use crate::download::manager::{DownloadCommand, DownloadManager};
+
+/// Larger bodies are refused; a few thousand links fit comfortably.
+const MAX_BODY_BYTES: u64 = 1024 * 1024;
+const DEFAULT_PAGE: usize = 100;
+const MAX_PAGE: usize = 1000;
+
+struct Running {
+    config: HttpApiConfig,
+    server: Arc<Server>,
+}
+
+static RUNNING: Lazy<Mutex<Option<Running>>> = Lazy::new(|| Mutex::new(None));
+
+pub fn new_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Start, restart or stop the server to match `config`. Called at startup and
+/// whenever settings are saved; an unchanged config leaves the server alone.
+pub fn apply(app: &AppHandle, config: &HttpApiConfig) {
+    let mut running = RUNNING.lock().unwrap_or_else(|e| e.into_inner());
+    if running.as_ref().is_some_and(|r| r.config == *config) {
+        return;
+    }
+    if let Some(old) = running.take() {
+        old.server.unblock();
+        println!("[BACKEND] http api stopped");
+    }
+    if !config.enabled || config.token.is_empty() {
+        return;
+    }
+    let server = match Server::http(("127.0.0.1", config.port)) {
+        Ok(server) => Arc::new(server),
+        Err(e) => {
+            eprintln!(
+                "[BACKEND] http api could not listen on 127.0.0.1:{}: {e}",
+                config.port
+            );
+            return;
+        }
+    };
+    println!("[BACKEND] http api listening on 127.0.0.1:{}", config.port);
+    *running = Some(Running {
+        config: config.clone(),
+        server: server.clone(),
+    });
+
+    let app = app.clone();
+    let token = config.token.clone();
+    std::thread::spawn(move || {
+        // Ends once `unblock` is called by the next `apply`.
+        for request in server.incoming_requests() {
+            handle(&app, &token, request);
+        }
+    });
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("valid header")
+}
+
+/// The extension runs on its own origin, so every answer carries CORS headers.
+fn respond(request: Request, status: u16, body: Option<Value>) {
+    let body = body.map(|v| v.to_string()).unwrap_or_default();
+    let response = Response::from_string(body)
+        .with_status_code(status)
+        .with_header(header("Content-Type", "application/json"))
+        .with_header(header("Access-Control-Allow-Origin", "*"))
+        .with_header(header(
+            "Access-Control-Allow-Headers",
+            "Authorization, Content-Type",
+        ))
+        .with_header(header("Access-Control-Allow-Methods", "GET, POST, OPTIONS"));
+    if let Err(e) = request.respond(response) {
+        eprintln!("[BACKEND] http api response not sent: {e}");
+    }
+}
+
+fn error(request: Request, status: u16, message: &str) {
+    respond(request, status, Some(json!({ "error": message })));
+}
+
+fn authorized(request: &Request, token: &str) -> bool {
+    request.headers().iter().any(|h| {
+        h.field.equiv("Authorization")
+            && h.value.as_str().strip_prefix("Bearer ").map(str::trim) == Some(token)
+    })
+}
+
+fn handle(app: &AppHandle, token: &str, mut request: Request) {
+    if *request.method() == Method::Options {
+        return respond(request, 204, None);
+    }
+    if !authorized(&request, token) {
+        return error(request, 401, "missing or wrong token");
+    }
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let result = match (request.method(), path) {
+        (Method::Post, "/enqueue") => read_body(&mut request).and_then(|b| enqueue(app, &b)),
+        (Method::Get, "/status") => status(),
+        (Method::Get, "/library") => library(query),
+        (Method::Get, "/archived") => archived(query),
+        (_, "/enqueue" | "/status" | "/library" | "/archived") => {
+            return error(request, 405, "method not allowed")
+        }
+        _ => return error(request, 404, "not found"),
+    };
+    match result {
+        Ok(body) => respond(request, 200, Some(body)),
+        Err((status, message)) => error(request, status, &message),
+    }
+}
+
+type ApiResult = Result<Value, (u16, String)>;
+
+fn internal(e: impl ToString) -> (u16, String) {
+    (500, e.to_string())
+}
+
+fn read_body(request: &mut Request) -> Result<String, (u16, String)> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_string(&mut body)
+        .map_err(|e| (400, format!("unreadable body: {e}")))?;
+    if body.len() as u64 > MAX_BODY_BYTES {
+        return Err((413, "body too large".into()));
+    }
+    Ok(body)
+}
+
+#[derive(Deserialize)]
+struct EnqueueBody {
+    #[serde(default)]
+    urls: Vec<String>,
+    /// Single-link shorthand
+    url: Option<String>,
+    /// Queue for download right away instead of leaving the links in the backlog
+    #[serde(default)]
+    queue: bool,
+}
+
+fn enqueue(app: &AppHandle, body: &str) -> ApiResult {
+    crate::settings::ensure_writable().map_err(|e| (403, e))?;
+    let body: EnqueueBody =
+        serde_json::from_str(body).map_err(|e| (400, format!("invalid JSON: {e}")))?;
+    let mut links: Vec<String> = Vec::new();
+    let mut unsupported = Vec::new();
+    for link in body.urls.into_iter().chain(body.url) {
+        let link = link.trim().to_string();
+        if crate::platforms::detect(&link).is_none() {
+            unsupported.push(link);
+        } else if !links.contains(&link) {
+            links.push(link);
+        }
+    }
+    let requested = links.len();
+    let fresh = crate::clipboard::unknown_links(links).map_err(internal)?;
+    let ids = crate::clipboard::add_to_backlog(&fresh).map_err(internal)?;
+    if !ids.is_empty() {
+        println!(
+            "[BACKEND] http api added {} links to the backlog",
+            ids.len()
+        );
+        let _ = app.emit("import_completed", ids.len());
+    }
+    let queued = body.queue && !ids.is_empty();
+    if queued {
+        let manager = app.state::<DownloadManager>();
+        tauri::async_runtime::block_on(manager.send(DownloadCommand::Enqueue {
+            ids: ids.clone(),
+            reply: None,
+        }))
+        .map_err(internal)?;
+    }
+    Ok(json!({
+        "added": ids,
+        "already_known": requested - fresh.len(),
+        "unsupported": unsupported,
+        "queued": queued,
+    }))
+}
+
+fn status() -> ApiResult {
+    let db = Database::new().map_err(internal)?;
+    let counts: serde_json::Map<String, Value> = db
+        .count_by_status()
+        .map_err(internal)?
+        .into_iter()
+        .map(|(status, n)| (status, json!(n)))
+        .collect();
+    let settings = crate::settings::load_settings();
+    let dir = std::path::Path::new(&settings.download_directory);
+    Ok(json!({
+        "app": "clip-downloader",
+        "version": env!("CARGO_PKG_VERSION"),
+        "counts": counts,
+        "download_directory": settings.download_directory,
+        "drive_offline": crate::download::volume::is_offline(dir),
+    }))
+}
+
+fn query_param(query: &str, name: &str) -> Option<usize> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == name)
+        .and_then(|(_, v)| v.parse().ok())
+}
+
+/// Percent-decoded text parameter; `+` stands for a space as in form encoding.
+fn query_text(query: &str, name: &str) -> Option<String> {
+    let (_, raw) = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == name)?;
+    let bytes = raw.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = raw.get(i + 1..i + 3)?;
+                out.push(u8::from_str_radix(hex, 16).ok()?);
+                i += 3;
+            }
+            b'+' => {
+                out.push(b' ');
+                i += 1;
+            }
+            b => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+fn library(query: &str) -> ApiResult {
+    let limit = query_param(query, "limit")
+        .unwrap_or(DEFAULT_PAGE)
+        .min(MAX_PAGE);
+    let offset = query_param(query, "offset").unwrap_or(0);
+    let db = Database::new().map_err(internal)?;
+    let filter = RowFilter::default();
+    let items = db
+        .list_done_paged(offset as u64, limit as u64, RowSort::Collection, &filter)
+        .map_err(internal)?;
+    let total = db.count_done(&filter).map_err(internal)?;
+    Ok(json!({ "total": total, "offset": offset, "items": items }))
+}
+
+fn archived(query: &str) -> ApiResult {
+    let url = query_text(query, "url")
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty())
+        .ok_or((400, "missing url".to_string()))?;
+    let db = Database::new().map_err(internal)?;
+    let row = db.archived_row(&url).map_err(internal)?;
+    Ok(json!({
+        "url": url,
+        "archived": row.as_ref().is_some_and(|r| r.status == DownloadStatus::Done),
+        "row": row,
+    }))
+}
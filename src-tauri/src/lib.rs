@@ -1,29 +1,59 @@
+mod backup;
+mod browser_history;
+pub mod cli;
+mod clipboard;
 mod commands;
 mod database;
+mod digest;
 mod download;
+mod http_api;
+mod libraries;
 mod logging;
+mod maintenance;
+mod navigation;
+mod notifications;
+mod platforms;
+mod profiles;
+mod session;
 mod settings;
+mod stats;
+mod takeout;
+mod tray;
 mod utils;
 
 use std::sync::Arc;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    crate::backup::apply_pending_restore();
     let s = crate::settings::load_settings();
-    crate::logging::init(s.debug_logs);
+    crate::logging::init(s.debug_logs, s.redact_logs);
     tracing::info!("App starting; debug_logs={}", s.debug_logs);
+    download::bandwidth::set_limit_kbps(s.max_download_rate_kbps);
+    clipboard::set_mode(s.clipboard_watch);
 
     let (cmd_tx, cmd_rx) = tokio::sync::mpsc::channel(100);
     let download_manager = crate::download::manager::DownloadManager::new(cmd_tx.clone());
     let raw_conn = crate::database::open_connection().expect("failed to open downloads.db");
     let shared_conn = Arc::new(tokio::sync::Mutex::new(raw_conn));
+    let last_session = crate::session::load_last_session();
+    // Queueing writes to the library, so read-only mode skips it.
+    let queue_new_since = (s.queue_new_on_startup && !s.read_only).then_some(last_session.0);
+    let pending_recovery = crate::session::detect_unclean_exit();
+    let launch_args: Vec<String> = std::env::args().skip(1).collect();
 
     tauri::Builder::default()
         .manage(download_manager)
+        .manage(last_session)
+        .manage(pending_recovery)
+        .manage(crate::navigation::launch_route(&launch_args))
+        .manage(crate::download::events::EventHub::new())
+        .manage(crate::maintenance::MaintenanceTasks::new())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_clipboard::init())
+        .plugin(tauri_plugin_notification::init())
         .setup({
             let shared_conn = shared_conn.clone();
             move |app| {
@@ -36,37 +66,153 @@ pub fn run() {
                     cmd_rx,
                     tx_clone,
                 ));
+                tauri::async_runtime::spawn(crate::digest::run_scheduler());
+                tauri::async_runtime::spawn(crate::session::run_heartbeat());
+                tauri::async_runtime::spawn(crate::clipboard::run_watcher(app_handle.clone()));
+                tauri::async_runtime::spawn(crate::download::prefetch::run(app_handle.clone()));
+                crate::http_api::apply(app_handle, &crate::settings::load_settings().http_api);
+                if let Err(e) = crate::tray::setup(app_handle) {
+                    eprintln!("[BACKEND] tray icon not created: {e}");
+                }
+                {
+                    // Warm up the extractors and report their versions early.
+                    let app = app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        crate::download::extractors::probe(&app).await;
+                    });
+                }
+                if let Some(since) = queue_new_since {
+                    let tx = cmd_tx.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let last = crate::session::LastSession(since);
+                        match crate::session::backlog_ids_added_since(&last, None) {
+                            Ok(ids) if !ids.is_empty() => {
+                                println!(
+                                    "[BACKEND] queueing {} rows new since last session",
+                                    ids.len()
+                                );
+                                let _ = tx
+                                    .send(crate::download::manager::DownloadCommand::Enqueue {
+                                        ids,
+                                        reply: None,
+                                    })
+                                    .await;
+                            }
+                            Ok(_) => {}
+                            Err(e) => println!("[BACKEND] queue-new-on-startup failed: {e}"),
+                        }
+                    });
+                }
                 Ok(())
             }
         })
+        .on_window_event(|window, event| {
+            // Keep running in the tray instead of quitting.
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                if window.label() == "main" && crate::settings::load_settings().close_to_tray {
+                    api.prevent_close();
+                    let _ = window.hide();
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             // SETTINGS
             commands::settings_cmd::load_settings,
             commands::settings_cmd::save_settings,
+            commands::settings_cmd::detect_download_filesystem,
+            commands::settings_cmd::test_proxy,
+            commands::settings_cmd::import_cookie_file,
+            commands::settings_cmd::list_cookie_browsers,
+            commands::config::export_config,
+            commands::config::import_config,
+            commands::config::backup_database,
+            commands::config::restore_database,
+            commands::settings_cmd::regenerate_http_api_token,
+            commands::libraries::list_libraries,
+            commands::libraries::create_library,
+            commands::libraries::switch_library,
+            commands::profiles::list_profiles,
+            commands::profiles::create_profile,
+            commands::profiles::startup_profile_prompt,
+            commands::navigation::launch_route,
+            commands::profiles::set_profile_prompt,
+            commands::profiles::switch_profile,
             // HOME / DOWNLOAD
             commands::downloader::download_url,
+            commands::downloader::expand_collection_url,
+            commands::downloader::expand_playlist,
+            commands::downloader::enqueue_to_folder,
             commands::downloader::cancel_download,
             commands::downloader::enqueue_downloads,
+            commands::downloader::retry_failed_collection,
+            commands::downloader::use_mirror,
+            commands::downloader::requeue_link,
+            commands::downloader::download_urls,
+            commands::downloader::paused_platforms,
+            commands::downloader::resume_platform,
+            commands::downloader::queue_copied_links,
+            commands::downloader::enqueue_new_since,
             commands::downloader::move_downloads_to_backlog,
             commands::downloader::set_download_paused,
+            commands::downloader::pause_download,
+            commands::downloader::get_download_history,
+            commands::downloader::resume_download,
+            commands::downloader::set_download_priority,
+            commands::downloader::set_bandwidth_limit,
+            commands::downloader::get_bandwidth_limit,
+            commands::downloader::download_schedule_status,
+            commands::downloader::download_volume_status,
+            commands::downloader::get_storage_status,
+            commands::downloader::move_queued_download,
             commands::downloader::refresh_download_settings,
             commands::downloader::reconcile_downloads,
             commands::downloader::refresh_downloads_snapshot,
+            commands::downloader::subscribe_download_events,
+            commands::downloader::unsubscribe_download_events,
+            commands::sessions::latest_session_report,
+            commands::sessions::crash_recovery,
+            commands::sessions::resolve_crash_recovery,
+            commands::sessions::list_sessions,
+            commands::sessions::dismiss_session_report,
+            commands::sessions::send_digest_now,
+            commands::sessions::export_stats,
+            commands::sessions::library_stats,
             // TOOLS / SYSTEM
             commands::tools::check_sidecar_tools,
+            commands::tools::tool_report,
+            commands::tools::get_platform_capabilities,
+            commands::icons::get_platform_icon,
             // FILES / IMPORT
             commands::files::pick_csv_and_read,
             commands::files::read_csv_from_path,
+            commands::import::preview_csv,
+            commands::import::import_takeout,
+            commands::files::read_links_from_path,
             commands::files::pick_directory,
             commands::files::open_directory,
             commands::import::import_csv_to_db,
+            commands::import::list_imports,
+            commands::import::rerun_import,
+            commands::import::rollback_import,
+            commands::import::import_browser_history,
             // LIBRARY / LIST
             commands::list::list_backlog,
             commands::list::list_queue,
+            commands::list::list_errors,
+            commands::list::list_trash,
+            commands::list::restore_from_trash,
+            commands::list::empty_trash,
             commands::list::list_done,
             commands::list::toggle_output_format,
             commands::list::set_output_format,
+            commands::list::set_cookie_browser,
+            commands::list::get_collection_filter,
+            commands::list::set_collection_filter,
             commands::list::list_downloads,
+            commands::list::list_library_tree,
+            commands::list::list_library_collection,
+            commands::list::list_done_paged,
+            commands::list::search_downloads,
             // STATUS MUTATIONS
             commands::list::move_link_to_queue,
             commands::list::move_collection_to_queue,
@@ -74,6 +220,8 @@ pub fn run() {
             commands::list::move_link_to_backlog,
             commands::list::move_collection_to_backlog,
             commands::list::move_platform_to_backlog,
+            commands::list::approve_pending_collection,
+            commands::list::reject_pending_collection,
             commands::list::delete_rows_by_platform,
             commands::list::delete_rows_by_collection,
             // FRONTEND LOGGING
@@ -84,8 +232,38 @@ pub fn run() {
             commands::library::open_platform_folder,
             commands::library::open_collection_folder,
             commands::library::delete_library_item,
+            commands::covers::get_collection_cover,
+            commands::covers::pick_collection_cover,
+            commands::covers::clear_collection_cover,
+            commands::covers::get_download_thumbnail,
+            commands::tags::add_tag_to_link,
+            commands::tags::remove_tag,
+            commands::tags::list_tags,
+            commands::tags::list_downloads_by_tag,
+            commands::watch::watch_collection,
+            commands::watch::list_watched_collections,
+            commands::watch::update_watched_collection,
+            commands::watch::unwatch_collection,
+            commands::watch::sync_watched_collection,
+            commands::library::preview_collection_folders,
+            commands::library::apply_collection_folder_template,
+            commands::library::refresh_metadata,
+            commands::library::verify_library,
+            commands::library::repair_missing,
+            commands::library::export_collection_zip,
             commands::list::delete_rows_by_link,
+            commands::list::dedupe_downloads,
+            commands::list::retry_all_errors,
+            commands::list::clear_errors,
+            // MAINTENANCE
+            commands::maintenance::list_maintenance_tasks,
+            commands::maintenance::cancel_maintenance_task,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                crate::session::complete_session();
+            }
+        });
 }
@@ -0,0 +1,148 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::profiles::active_root;
+
+/// The library that existed before multi-library support; its data stays
+/// directly in the profile's root dir.
+pub const DEFAULT_LIBRARY_ID: &str = "default";
+
+/// A library is a download root plus its own downloads.db and settings.json.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LibraryEntry {
+    pub id: String,
+    pub name: String,
+}
+
+/// Contents of libraries.json: every known library and the one in use.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LibraryIndex {
+    pub active: String,
+    pub libraries: Vec<LibraryEntry>,
+}
+
+impl Default for LibraryIndex {
+    fn default() -> Self {
+        LibraryIndex {
+            active: DEFAULT_LIBRARY_ID.to_string(),
+            libraries: vec![LibraryEntry {
+                id: DEFAULT_LIBRARY_ID.to_string(),
+                name: "Main library".to_string(),
+            }],
+        }
+    }
+}
+
+/// Data dir of the active library, fixed for the process lifetime;
+/// switching libraries restarts the app.
+static ACTIVE_DIR: Lazy<PathBuf> = Lazy::new(|| library_dir(&load_index().active));
+
+fn index_path() -> PathBuf {
+    active_root().join("libraries.json")
+}
+
+pub fn library_dir(id: &str) -> PathBuf {
+    if id == DEFAULT_LIBRARY_ID {
+        active_root()
+    } else {
+        active_root().join("libraries").join(id)
+    }
+}
+
+/// Where the active library keeps settings.json, downloads.db and its caches.
+pub fn active_dir() -> PathBuf {
+    ACTIVE_DIR.clone()
+}
+
+pub fn load_index() -> LibraryIndex {
+    let mut index = fs::read_to_string(index_path())
+        .ok()
+        .and_then(|s| serde_json::from_str::<LibraryIndex>(&s).ok())
+        .unwrap_or_default();
+    if !index.libraries.iter().any(|l| l.id == DEFAULT_LIBRARY_ID) {
+        index
+            .libraries
+            .insert(0, LibraryIndex::default().libraries.remove(0));
+    }
+    if !index.libraries.iter().any(|l| l.id == index.active) {
+        index.active = DEFAULT_LIBRARY_ID.to_string();
+    }
+    index
+}
+
+fn save_index(index: &LibraryIndex) -> Result<(), String> {
+    fs::create_dir_all(active_root()).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(index).map_err(|e| e.to_string())?;
+    fs::write(index_path(), json).map_err(|e| e.to_string())
+}
+
+/// Add a library whose settings start as a copy of the current ones, pointed at
+/// `download_directory` (default: a folder named after the library next to the
+/// current download root). It has its own empty database.
+pub fn create_library(
+    name: &str,
+    download_directory: Option<String>,
+) -> Result<LibraryEntry, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("library name is required".into());
+    }
+    let mut index = load_index();
+    if index
+        .libraries
+        .iter()
+        .any(|l| l.name.eq_ignore_ascii_case(name))
+    {
+        return Err(format!("a library named \"{name}\" already exists"));
+    }
+    let slug: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let short = uuid::Uuid::new_v4().simple().to_string();
+    let id = format!("{}-{}", slug.trim_matches('-'), &short[..6]);
+
+    let mut settings = crate::settings::load_settings();
+    settings.id = None;
+    settings.download_directory = match download_directory.filter(|d| !d.trim().is_empty()) {
+        Some(dir) => dir,
+        None => {
+            let current = PathBuf::from(&settings.download_directory);
+            let parent = current.parent().unwrap_or(&current);
+            let rules = crate::utils::filenames::rules_for(parent, &settings);
+            parent.join(rules.clean(name)).to_string_lossy().to_string()
+        }
+    };
+    fs::create_dir_all(&settings.download_directory).map_err(|e| e.to_string())?;
+
+    let dir = library_dir(&id);
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+    fs::write(dir.join("settings.json"), json).map_err(|e| e.to_string())?;
+
+    let entry = LibraryEntry {
+        id,
+        name: name.to_string(),
+    };
+    index.libraries.push(entry.clone());
+    save_index(&index)?;
+    println!(
+        "[BACKEND] created library {} at {}",
+        entry.id,
+        dir.display()
+    );
+    Ok(entry)
+}
+
+/// Make `id` the library opened on next launch.
+pub fn set_active(id: &str) -> Result<(), String> {
+    let mut index = load_index();
+    if !index.libraries.iter().any(|l| l.id == id) {
+        return Err(format!("unknown library: {id}"));
+    }
+    index.active = id.to_string();
+    save_index(&index)
+}
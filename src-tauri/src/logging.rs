@@ -1,17 +1,52 @@
 use once_cell::sync::OnceCell;
+use std::io;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use tracing_appender::{
     non_blocking::{self, WorkerGuard},
     rolling::RollingFileAppender,
 };
 use tracing_subscriber::{
-    filter::LevelFilter, fmt, prelude::*, reload, util::SubscriberInitExt, EnvFilter,
+    filter::LevelFilter,
+    fmt::{self, MakeWriter},
+    prelude::*,
+    reload,
+    util::SubscriberInitExt,
+    EnvFilter,
 };
 
 static FILE_FILTER_HANDLE: OnceCell<reload::Handle<EnvFilter, tracing_subscriber::Registry>> =
     OnceCell::new();
 static _GUARD: OnceCell<WorkerGuard> = OnceCell::new(); // keep writer alive
+static REDACT: AtomicBool = AtomicBool::new(false);
+
+/// File writer that passes each formatted line through `utils::redact` while redaction is on.
+#[derive(Clone)]
+struct RedactingWriter<W>(W);
+
+impl<W: io::Write> io::Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !REDACT.load(Ordering::Relaxed) {
+            return self.0.write(buf);
+        }
+        let line = crate::utils::redact::redact(&String::from_utf8_lossy(buf));
+        self.0.write_all(line.as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl<'a, W: io::Write + Clone + 'a> MakeWriter<'a> for RedactingWriter<W> {
+    type Writer = RedactingWriter<W>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
 
 fn log_dir() -> PathBuf {
     // ~/Library/Application Support/clip-downloader/logs (macOS)
@@ -22,7 +57,8 @@ fn log_dir() -> PathBuf {
 }
 
 /// Initialize global subscriber. Call once at app start.
-pub fn init(file_enabled: bool) {
+pub fn init(file_enabled: bool, redact: bool) {
+    set_redaction_enabled(redact);
     let dir = log_dir();
     let _ = std::fs::create_dir_all(&dir);
 
@@ -44,7 +80,7 @@ pub fn init(file_enabled: bool) {
 
     // File layer (no ANSI, include target + line for debugging).
     let file_layer = fmt::layer()
-        .with_writer(nb_writer)
+        .with_writer(RedactingWriter(nb_writer))
         .with_ansi(false)
         .with_target(true)
         .with_level(true)
@@ -82,6 +118,11 @@ pub fn set_file_logging_enabled(enabled: bool) {
     }
 }
 
+/// Hash links and handles in the log file from now on (the console is left as-is).
+pub fn set_redaction_enabled(enabled: bool) {
+    REDACT.store(enabled, Ordering::Relaxed);
+}
+
 /// Optional: keep the last ~10 rotated logs to avoid unbounded growth.
 fn prune_old_logs() {
     use std::fs;
@@ -0,0 +1,188 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use tauri::{AppHandle, Emitter, Manager};
+
+pub use clip_core::{MaintenanceTask, TaskStatus};
+
+/// Finished tasks kept for the Maintenance panel; older ones are dropped.
+const KEEP_FINISHED: usize = 20;
+
+struct Slot {
+    task: MaintenanceTask,
+    cancel: Arc<AtomicBool>,
+}
+
+/// Registry of long-running maintenance jobs. Each job runs on a blocking
+/// thread and reports through its `TaskHandle`; every change is emitted as a
+/// `maintenance_progress` event carrying the task.
+pub struct MaintenanceTasks {
+    next_id: AtomicU32,
+    slots: Mutex<Vec<Slot>>,
+}
+
+/// Given to a job body to report progress and notice cancellation.
+pub struct TaskHandle {
+    app: AppHandle,
+    id: u32,
+    cancel: Arc<AtomicBool>,
+}
+
+impl TaskHandle {
+    pub fn is_canceled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    pub fn progress(&self, done: u64, total: Option<u64>, message: impl Into<String>) {
+        let message = message.into();
+        update(&self.app, self.id, |t| {
+            t.done = done;
+            t.total = total;
+            t.message = message;
+        });
+    }
+}
+
+fn update(app: &AppHandle, id: u32, f: impl FnOnce(&mut MaintenanceTask)) {
+    let Some(tasks) = app.try_state::<MaintenanceTasks>() else {
+        return;
+    };
+    let snapshot = {
+        let Ok(mut slots) = tasks.slots.lock() else {
+            return;
+        };
+        let Some(slot) = slots.iter_mut().find(|s| s.task.id == id) else {
+            return;
+        };
+        f(&mut slot.task);
+        slot.task.clone()
+    };
+    let _ = app.emit("maintenance_progress", &snapshot);
+}
+
+impl MaintenanceTasks {
+    pub fn new() -> Self {
+        Self {
+            next_id: AtomicU32::new(1),
+            slots: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Start `work` in the background and return its task id. Only one task of
+    /// a given `kind` runs at a time. `work` returns a summary line or an error.
+    pub fn spawn<F>(
+        &self,
+        app: &AppHandle,
+        kind: &str,
+        label: impl Into<String>,
+        cancelable: bool,
+        work: F,
+    ) -> Result<u32, String>
+    where
+        F: FnOnce(&TaskHandle) -> Result<String, String> + Send + 'static,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let cancel = Arc::new(AtomicBool::new(false));
+        let task = MaintenanceTask {
+            id,
+            kind: kind.to_string(),
+            label: label.into(),
+            status: TaskStatus::Running,
+            done: 0,
+            total: None,
+            message: String::new(),
+            cancelable,
+            started_at: Utc::now().to_rfc3339(),
+            finished_at: None,
+        };
+        {
+            let mut slots = self.slots.lock().map_err(|e| e.to_string())?;
+            if slots
+                .iter()
+                .any(|s| s.task.kind == kind && s.task.status == TaskStatus::Running)
+            {
+                return Err(format!("a {kind} task is already running"));
+            }
+            slots.push(Slot {
+                task: task.clone(),
+                cancel: cancel.clone(),
+            });
+            prune(&mut slots);
+        }
+        let _ = app.emit("maintenance_progress", &task);
+        println!("[BACKEND] maintenance task {id} ({kind}) started");
+
+        let handle = TaskHandle {
+            app: app.clone(),
+            id,
+            cancel,
+        };
+        tauri::async_runtime::spawn_blocking(move || {
+            let result = work(&handle);
+            let canceled = handle.is_canceled();
+            update(&handle.app, id, |t| {
+                t.finished_at = Some(Utc::now().to_rfc3339());
+                match result {
+                    Ok(summary) => {
+                        t.status = if canceled {
+                            TaskStatus::Canceled
+                        } else {
+                            TaskStatus::Completed
+                        };
+                        t.message = summary;
+                    }
+                    Err(e) => {
+                        t.status = TaskStatus::Failed;
+                        t.message = e;
+                    }
+                }
+                println!(
+                    "[BACKEND] maintenance task {id} ({}) {:?}: {}",
+                    t.kind, t.status, t.message
+                );
+            });
+        });
+        Ok(id)
+    }
+
+    /// Running tasks first, then finished ones, newest first.
+    pub fn list(&self) -> Vec<MaintenanceTask> {
+        let Ok(slots) = self.slots.lock() else {
+            return Vec::new();
+        };
+        let mut out: Vec<MaintenanceTask> = slots.iter().map(|s| s.task.clone()).collect();
+        out.sort_by_key(|t| (t.status != TaskStatus::Running, std::cmp::Reverse(t.id)));
+        out
+    }
+
+    /// Ask a running task to stop; it finishes as Canceled at its next check.
+    pub fn cancel(&self, id: u32) -> bool {
+        let Ok(slots) = self.slots.lock() else {
+            return false;
+        };
+        match slots.iter().find(|s| s.task.id == id) {
+            Some(s) if s.task.cancelable && s.task.status == TaskStatus::Running => {
+                s.cancel.store(true, Ordering::Relaxed);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+fn prune(slots: &mut Vec<Slot>) {
+    let finished = slots
+        .iter()
+        .filter(|s| s.task.status != TaskStatus::Running)
+        .count();
+    let mut excess = finished.saturating_sub(KEEP_FINISHED);
+    slots.retain(|s| {
+        if excess > 0 && s.task.status != TaskStatus::Running {
+            excess -= 1;
+            false
+        } else {
+            true
+        }
+    });
+}
@@ -0,0 +1,16 @@
+//! Internal links into the window: short paths such as `library` or
+//! `downloads/42` (a failed download's details). The frontend follows them
+//! from `navigate` events and from the `--open <path>` launch argument.
+
+/// Link path passed as `--open <path>` when the app was started.
+pub struct LaunchRoute(pub Option<String>);
+
+pub fn launch_route(args: &[String]) -> LaunchRoute {
+    let path = args
+        .iter()
+        .position(|a| a == "--open")
+        .and_then(|i| args.get(i + 1))
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty());
+    LaunchRoute(path)
+}
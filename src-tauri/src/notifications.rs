@@ -0,0 +1,44 @@
+//! Desktop notifications for finished and failed downloads and for the end
+//! of a session. Each kind is switched on separately in the settings.
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+use crate::database::SessionReport;
+
+fn show(app: &AppHandle, title: &str, body: &str) {
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        eprintln!("[BACKEND] [notifications] {e}");
+    }
+}
+
+/// A download ended; `error` is set when it failed after its retries.
+pub fn download_finished(app: &AppHandle, label: &str, error: Option<&str>) {
+    let settings = crate::settings::load_settings();
+    if !settings.notifications_enabled {
+        return;
+    }
+    match error {
+        None if settings.notify_on_done => show(app, "Download finished", label),
+        Some(error) if settings.notify_on_error => {
+            show(app, "Download failed", &format!("{label}\n{error}"))
+        }
+        _ => {}
+    }
+}
+
+/// The queue emptied. Sessions of a single download are left to
+/// `download_finished`.
+pub fn session_finished(app: &AppHandle, report: &SessionReport) {
+    let settings = crate::settings::load_settings();
+    if !settings.notifications_enabled
+        || !settings.notify_on_session_end
+        || report.downloaded + report.failed < 2
+    {
+        return;
+    }
+    let body = match report.failed {
+        0 => format!("{} downloaded", report.downloaded),
+        failed => format!("{} downloaded, {failed} failed", report.downloaded),
+    };
+    show(app, "Queue finished", &body);
+}
@@ -0,0 +1,184 @@
+pub use clip_core::platform::Tool;
+use clip_core::platform::{self, PlatformInfo};
+
+use crate::database::{MediaKind, Platform, PlatformCapabilities, ToolReport};
+
+/// Backend-only knowledge about a platform: how its tool is driven.
+/// URL matching, labels, icons and tool choice come from `clip_core::platform`;
+/// adding a platform means adding an entry there and one to `PLATFORMS` here.
+pub struct PlatformDescriptor {
+    pub platform: Platform,
+    /// Retry image posts with gallery-dl when yt-dlp finds no formats.
+    pub gallery_fallback: bool,
+    /// Drop the query string before downloading (tracking params break
+    /// extractors), except the platform's `keep_params`.
+    pub strip_query: bool,
+    /// Minimum gap between background metadata probes, to stay clear of the
+    /// site's rate limiting.
+    pub probe_delay_secs: u64,
+    /// Story links download (with browser cookies).
+    pub stories: bool,
+    /// Playlist links can be expanded into one row per video.
+    pub playlists: bool,
+    /// The site serves subtitle tracks yt-dlp can save.
+    pub subtitles: bool,
+}
+
+impl PlatformDescriptor {
+    pub fn info(&self) -> &'static PlatformInfo {
+        platform::info(self.platform)
+    }
+
+    /// Token stored in the DB and used as the site folder name.
+    pub fn id(&self) -> &'static str {
+        self.platform.as_str()
+    }
+
+    pub fn is_image_url(&self, url: &str) -> bool {
+        self.info().is_image_url(url)
+    }
+
+    pub fn tool_for(&self, url: &str) -> Tool {
+        self.info().tool_for(url)
+    }
+
+    pub fn media_for(&self, url: &str) -> MediaKind {
+        self.info().media_for(url)
+    }
+
+    /// Stable per-item id extracted from a link, used for file and row names.
+    pub fn item_id(&self, url: &str) -> Option<String> {
+        clip_core::url::item_id(self.platform, url)
+    }
+}
+
+pub static PLATFORMS: &[PlatformDescriptor] = &[
+    PlatformDescriptor {
+        platform: Platform::Instagram,
+        gallery_fallback: true,
+        strip_query: true,
+        probe_delay_secs: 30,
+        stories: true,
+        playlists: false,
+        subtitles: false,
+    },
+    PlatformDescriptor {
+        platform: Platform::Tiktok,
+        gallery_fallback: false,
+        strip_query: false,
+        probe_delay_secs: 15,
+        stories: false,
+        playlists: false,
+        subtitles: true,
+    },
+    PlatformDescriptor {
+        platform: Platform::Youtube,
+        gallery_fallback: false,
+        strip_query: false,
+        probe_delay_secs: 5,
+        stories: false,
+        playlists: true,
+        subtitles: true,
+    },
+    PlatformDescriptor {
+        platform: Platform::Pinterest,
+        gallery_fallback: false,
+        strip_query: false,
+        probe_delay_secs: 10,
+        stories: false,
+        playlists: false,
+        subtitles: false,
+    },
+    PlatformDescriptor {
+        platform: Platform::Twitter,
+        // Tweets with only pictures have no formats for yt-dlp.
+        gallery_fallback: true,
+        strip_query: true,
+        probe_delay_secs: 20,
+        stories: false,
+        playlists: false,
+        subtitles: false,
+    },
+    PlatformDescriptor {
+        platform: Platform::Reddit,
+        // Picture posts linked via /comments/ only turn out to be images once yt-dlp fails.
+        gallery_fallback: true,
+        strip_query: true,
+        probe_delay_secs: 10,
+        stories: false,
+        playlists: false,
+        subtitles: false,
+    },
+    PlatformDescriptor {
+        platform: Platform::Other,
+        gallery_fallback: false,
+        strip_query: false,
+        probe_delay_secs: 5,
+        stories: false,
+        playlists: false,
+        subtitles: false,
+    },
+];
+
+/// Descriptor for the platform `url` belongs to.
+pub fn detect(url: &str) -> Option<&'static PlatformDescriptor> {
+    let info = platform::detect(url)?;
+    PLATFORMS.iter().find(|d| d.platform == info.platform)
+}
+
+fn tool_name(tool: Tool) -> &'static str {
+    match tool {
+        Tool::YtDlp => "yt-dlp",
+        Tool::GalleryDl => "gallery-dl",
+        Tool::DirectHttp => "direct",
+    }
+}
+
+/// What works per platform given the installed extractors: a feature is off
+/// when the tool it needs is missing. Advisories for the installed versions
+/// are listed as notes.
+pub fn capabilities(report: &ToolReport) -> Vec<PlatformCapabilities> {
+    let available = |tool: Tool| match tool {
+        Tool::YtDlp => report.yt_dlp.is_some(),
+        Tool::GalleryDl => report.gallery_dl.is_some(),
+        Tool::DirectHttp => true,
+    };
+    PLATFORMS
+        .iter()
+        .map(|d| {
+            let info = d.info();
+            let yt_dlp = available(Tool::YtDlp);
+            let video = info.default_media == MediaKind::Video && available(info.preferred_tool);
+            let images = (info.default_media == MediaKind::Image
+                || !info.image_patterns.is_empty()
+                || d.gallery_fallback)
+                && (available(info.image_tool)
+                    || (d.gallery_fallback && available(Tool::GalleryDl)));
+            let mut notes: Vec<String> = [info.preferred_tool, info.image_tool]
+                .into_iter()
+                .filter(|t| !available(*t))
+                .map(|t| format!("needs {}", tool_name(t)))
+                .collect();
+            notes.dedup();
+            notes.extend(
+                report
+                    .advisories
+                    .iter()
+                    .filter(|a| a.platform == d.id())
+                    .map(|a| a.message.clone()),
+            );
+            PlatformCapabilities {
+                platform: d.platform,
+                label: info.label.to_string(),
+                tool: tool_name(info.preferred_tool).to_string(),
+                video,
+                images,
+                stories: d.stories && yt_dlp,
+                playlists: d.playlists && yt_dlp,
+                audio_only: video && info.preferred_tool == Tool::YtDlp,
+                subtitles: d.subtitles && yt_dlp,
+                notes,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,176 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::settings::app_support_dir;
+
+/// The profile that existed before profiles were added; its data stays
+/// directly in the app support dir.
+pub const DEFAULT_PROFILE_ID: &str = "default";
+
+/// A profile is a separate set of libraries (databases + settings) for one
+/// person sharing the computer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProfileEntry {
+    pub id: String,
+    pub name: String,
+}
+
+/// Contents of profiles.json.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProfileIndex {
+    pub active: String,
+    pub profiles: Vec<ProfileEntry>,
+    /// Show the profile picker on launch when there is more than one profile
+    #[serde(default = "default_true")]
+    pub ask_on_startup: bool,
+    /// Set when the app restarts into a profile just picked, so the picker
+    /// isn't shown twice for the same launch.
+    #[serde(default)]
+    pub just_picked: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for ProfileIndex {
+    fn default() -> Self {
+        ProfileIndex {
+            active: DEFAULT_PROFILE_ID.to_string(),
+            profiles: vec![ProfileEntry {
+                id: DEFAULT_PROFILE_ID.to_string(),
+                name: "Default".to_string(),
+            }],
+            ask_on_startup: true,
+            just_picked: false,
+        }
+    }
+}
+
+/// Data root of the active profile, fixed for the process lifetime;
+/// switching profiles restarts the app.
+static ACTIVE_ROOT: Lazy<PathBuf> = Lazy::new(|| profile_dir(&load_index().active));
+
+fn index_path() -> PathBuf {
+    app_support_dir().join("profiles.json")
+}
+
+pub fn profile_dir(id: &str) -> PathBuf {
+    if id == DEFAULT_PROFILE_ID {
+        app_support_dir()
+    } else {
+        app_support_dir().join("profiles").join(id)
+    }
+}
+
+/// Where the active profile keeps libraries.json and its main library.
+pub fn active_root() -> PathBuf {
+    ACTIVE_ROOT.clone()
+}
+
+pub fn load_index() -> ProfileIndex {
+    let mut index = fs::read_to_string(index_path())
+        .ok()
+        .and_then(|s| serde_json::from_str::<ProfileIndex>(&s).ok())
+        .unwrap_or_default();
+    if !index.profiles.iter().any(|p| p.id == DEFAULT_PROFILE_ID) {
+        index
+            .profiles
+            .insert(0, ProfileIndex::default().profiles.remove(0));
+    }
+    if !index.profiles.iter().any(|p| p.id == index.active) {
+        index.active = DEFAULT_PROFILE_ID.to_string();
+    }
+    index
+}
+
+fn save_index(index: &ProfileIndex) -> Result<(), String> {
+    fs::create_dir_all(app_support_dir()).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(index).map_err(|e| e.to_string())?;
+    fs::write(index_path(), json).map_err(|e| e.to_string())
+}
+
+/// Add a profile with default settings and its own download folder
+/// (named after the profile, next to the default download root).
+pub fn create_profile(name: &str) -> Result<ProfileEntry, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("profile name is required".into());
+    }
+    let mut index = load_index();
+    if index
+        .profiles
+        .iter()
+        .any(|p| p.name.eq_ignore_ascii_case(name))
+    {
+        return Err(format!("a profile named \"{name}\" already exists"));
+    }
+    let slug: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let short = uuid::Uuid::new_v4().simple().to_string();
+    let id = format!("{}-{}", slug.trim_matches('-'), &short[..6]);
+
+    let mut settings = crate::database::Settings::default();
+    let root = PathBuf::from(&settings.download_directory);
+    let rules = crate::utils::filenames::rules_for(&root, &settings);
+    settings.download_directory = root.join(rules.clean(name)).to_string_lossy().to_string();
+    fs::create_dir_all(&settings.download_directory).map_err(|e| e.to_string())?;
+
+    let dir = profile_dir(&id);
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+    fs::write(dir.join("settings.json"), json).map_err(|e| e.to_string())?;
+
+    let entry = ProfileEntry {
+        id,
+        name: name.to_string(),
+    };
+    index.profiles.push(entry.clone());
+    save_index(&index)?;
+    println!(
+        "[BACKEND] created profile {} at {}",
+        entry.id,
+        dir.display()
+    );
+    Ok(entry)
+}
+
+/// Make `id` the profile opened on next launch, skipping the picker once.
+pub fn set_active(id: &str) -> Result<(), String> {
+    let mut index = load_index();
+    if !index.profiles.iter().any(|p| p.id == id) {
+        return Err(format!("unknown profile: {id}"));
+    }
+    index.active = id.to_string();
+    index.just_picked = true;
+    save_index(&index)
+}
+
+pub fn set_ask_on_startup(enabled: bool) -> Result<(), String> {
+    let mut index = load_index();
+    index.ask_on_startup = enabled;
+    save_index(&index)
+}
+
+/// Whether this launch will show the profile picker; the queue waits for it.
+pub fn prompt_pending() -> bool {
+    let index = load_index();
+    !index.just_picked && index.ask_on_startup && index.profiles.len() > 1
+}
+
+/// Profiles to offer at launch, or `None` when the picker should be skipped.
+pub fn startup_prompt() -> Result<Option<ProfileIndex>, String> {
+    let mut index = load_index();
+    if index.just_picked {
+        index.just_picked = false;
+        save_index(&index)?;
+        return Ok(None);
+    }
+    let ask = index.ask_on_startup && index.profiles.len() > 1;
+    Ok(ask.then_some(index))
+}
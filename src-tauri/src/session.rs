@@ -0,0 +1,128 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use walkdir::WalkDir;
+
+use crate::database::{CrashRecovery, Database};
+
+/// How often the run marker's heartbeat is rewritten.
+const HEARTBEAT_SECS: u64 = 60;
+/// Partial files listed in the recovery prompt at most.
+const MAX_PARTIAL_FILES: usize = 50;
+
+/// End of the previous completed run, read once at startup because this run
+/// overwrites the marker when it exits.
+pub struct LastSession(pub Option<DateTime<Utc>>);
+
+pub fn load_last_session() -> LastSession {
+    let at = Database::new()
+        .and_then(|db| db.last_session_completed_at())
+        .unwrap_or_else(|e| {
+            println!("[BACKEND] failed to read last_session: {e}");
+            None
+        });
+    LastSession(at)
+}
+
+/// Record that this run ended cleanly; called on app exit.
+pub fn complete_session() {
+    match Database::new().and_then(|db| db.mark_session_completed(Utc::now())) {
+        Ok(_) => println!("[BACKEND] session completed"),
+        Err(e) => println!("[BACKEND] failed to mark session completed: {e}"),
+    }
+    let _ = std::fs::remove_file(run_marker_path());
+}
+
+/* ---------- crash recovery ---------- */
+
+/// What the last run left behind, until the user answers the startup prompt.
+pub struct PendingRecovery(pub Mutex<Option<CrashRecovery>>);
+
+impl PendingRecovery {
+    pub fn is_pending(&self) -> bool {
+        self.0.lock().map(|r| r.is_some()).unwrap_or(false)
+    }
+}
+
+/// Exists while the app runs and holds its last heartbeat (RFC 3339);
+/// removed on a clean exit.
+fn run_marker_path() -> PathBuf {
+    crate::libraries::active_dir().join("running.lock")
+}
+
+fn write_heartbeat() {
+    if let Err(e) = std::fs::write(run_marker_path(), Utc::now().to_rfc3339()) {
+        eprintln!("[BACKEND] run marker not written: {e}");
+    }
+}
+
+/// Check for a marker left by a run that never exited cleanly, then claim
+/// the marker for this run. Must run before the download manager requeues
+/// rows stuck in 'downloading'.
+pub fn detect_unclean_exit() -> PendingRecovery {
+    let marker = run_marker_path();
+    let recovery = marker.is_file().then(|| {
+        let last_alive = std::fs::read_to_string(&marker)
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        let (recovered, queued) = Database::new()
+            .and_then(|db| Ok((db.list_in_flight()?, db.count_queued()?)))
+            .unwrap_or_else(|e| {
+                eprintln!("[BACKEND] crash recovery: database unreadable: {e}");
+                (Vec::new(), 0)
+            });
+        let settings = crate::settings::load_settings();
+        CrashRecovery {
+            last_alive,
+            queued: queued + recovered.len(),
+            recovered,
+            partial_files: partial_files(Path::new(&settings.download_directory)),
+        }
+    });
+    if let Some(r) = &recovery {
+        println!(
+            "[BACKEND] previous run ended uncleanly: {} in flight, {} partial files",
+            r.recovered.len(),
+            r.partial_files.len()
+        );
+    }
+    write_heartbeat();
+    PendingRecovery(Mutex::new(recovery))
+}
+
+/// Keep the run marker's timestamp fresh so a crash report can say when
+/// the app was last alive.
+pub async fn run_heartbeat() {
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(HEARTBEAT_SECS)).await;
+        write_heartbeat();
+    }
+}
+
+/// Unfinished yt-dlp/direct downloads under `root`.
+fn partial_files(root: &Path) -> Vec<String> {
+    WalkDir::new(root)
+        .max_depth(6)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().to_lowercase();
+            name.ends_with(".part") || name.ends_with(".ytdl")
+        })
+        .take(MAX_PARTIAL_FILES)
+        .map(|e| e.path().display().to_string())
+        .collect()
+}
+
+/// Backlog ids added since `since`, or since the last completed session when `None`.
+pub fn backlog_ids_added_since(
+    last: &LastSession,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<i64>, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    db.list_backlog_ids_added_since(since.or(last.0))
+        .map_err(|e| e.to_string())
+}
@@ -1,20 +1,39 @@
-use crate::database::{DefaultOutput, DeleteMode, OnDuplicate, Settings};
+use crate::database::{
+    default_audio_quality, default_collection_folder_template, default_folder_template,
+    default_subtitle_langs, AudioFormat, ClipboardWatch, CollectionIndex, DateRange, DefaultOutput,
+    DeleteMode, DigestMode, EnvVar, EventVerbosity, FilesystemKind, HttpApiConfig, ImportDuplicate,
+    OnDuplicate, PlatformOverride, Settings, SmtpConfig, StartupPage, VideoCodec,
+    DEFAULT_HTTP_API_PORT,
+};
 use std::{
     fs,
     path::{Path, PathBuf},
 };
 use uuid::Uuid;
 
-/// Where we store settings.json on macOS:
-///   ~/Library/Application Support/clip-downloader/settings.json
+/// App data root, shared by all profiles and libraries:
+///   ~/Library/Application Support/clip-downloader (macOS)
 /// For other OSes, this still resolves to the platform's "config dir".
-fn app_support_dir() -> PathBuf {
+pub(crate) fn app_support_dir() -> PathBuf {
     let base = dirs::config_dir().unwrap_or_else(|| dirs::home_dir().unwrap_or_default());
     base.join("clip-downloader")
 }
 
+/// settings.json of the active library (see `libraries::active_dir`).
 fn settings_json_path() -> PathBuf {
-    app_support_dir().join("settings.json")
+    crate::libraries::active_dir().join("settings.json")
+}
+
+/// Parse settings.json without the fallbacks of `load_settings`; a missing file
+/// is fine (defaults), an unreadable one is an error.
+pub(crate) fn read_settings_strict() -> Result<Settings, String> {
+    let path = settings_json_path();
+    match fs::read_to_string(&path) {
+        Ok(text) => serde_json::from_str(&text)
+            .map_err(|e| format!("{} is not valid settings: {e}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Settings::default()),
+        Err(e) => Err(format!("cannot read {}: {e}", path.display())),
+    }
 }
 
 impl Default for Settings {
@@ -32,6 +51,52 @@ impl Default for Settings {
             use_system_binaries: false,
             cooldown_secs: 0,
             retry_on_queue_empty: false,
+            max_retries: 2,
+            retry_delay_secs: 30,
+            max_download_rate_kbps: 0,
+            progress_updates_per_sec: 4,
+            event_verbosity: EventVerbosity::Milestones,
+            collection_folder_template: default_collection_folder_template(),
+            folder_template: default_folder_template(),
+            hard_delete_to_trash: true,
+            queue_new_on_startup: false,
+            check_links_on_import: false,
+            find_mirrors: false,
+            notifications_enabled: false,
+            close_to_tray: false,
+            hash_downloads: false,
+            notify_on_done: true,
+            notify_on_error: true,
+            notify_on_session_end: true,
+            download_windows: Vec::new(),
+            digest_mode: DigestMode::Off,
+            smtp: SmtpConfig::default(),
+            redact_logs: false,
+            http_headers: Vec::new(),
+            geo_bypass_country: Default::default(),
+            proxy_url: String::new(),
+            per_platform_proxy: Default::default(),
+            preferred_cookie_browser: None,
+            platform_overrides: Default::default(),
+            filename_filesystem: FilesystemKind::Auto,
+            max_filename_len: 0,
+            read_only: false,
+            trim_to_link_timestamp: false,
+            tool_env: Vec::new(),
+            clipboard_watch: ClipboardWatch::Off,
+            on_import_duplicate: ImportDuplicate::Skip,
+            http_api: HttpApiConfig::default(),
+            prefetch_metadata: false,
+            download_subtitles: false,
+            subtitle_langs: default_subtitle_langs(),
+            embed_subs: false,
+            collection_index: CollectionIndex::Off,
+            write_info_json: false,
+            max_resolution: 0,
+            preferred_codec: VideoCodec::Any,
+            audio_format: AudioFormat::Mp3,
+            audio_quality: default_audio_quality(),
+            startup_page: StartupPage::Home,
         }
     }
 }
@@ -72,6 +137,11 @@ fn validated_download_dir<S: Into<String>>(candidate: S) -> String {
         path = default_download_dir();
     }
 
+    // A folder on an unplugged drive stays put; the queue waits for it to return.
+    if crate::download::volume::is_offline(&path) {
+        return path.to_string_lossy().to_string();
+    }
+
     // Try to create if missing; if that fails, revert to default
     if !path.exists() {
         if let Err(_) = fs::create_dir_all(&path) {
@@ -143,6 +213,100 @@ pub fn save_settings(settings: &Settings) -> Result<(), String> {
         use_system_binaries: settings.use_system_binaries,
         cooldown_secs: settings.cooldown_secs,
         retry_on_queue_empty: settings.retry_on_queue_empty,
+        max_retries: settings.max_retries,
+        retry_delay_secs: settings.retry_delay_secs,
+        max_download_rate_kbps: settings.max_download_rate_kbps,
+        progress_updates_per_sec: settings.progress_updates_per_sec,
+        event_verbosity: settings.event_verbosity.clone(),
+        collection_folder_template: settings.collection_folder_template.clone(),
+        folder_template: settings.folder_template.clone(),
+        hard_delete_to_trash: settings.hard_delete_to_trash,
+        queue_new_on_startup: settings.queue_new_on_startup,
+        check_links_on_import: settings.check_links_on_import,
+        find_mirrors: settings.find_mirrors,
+        notifications_enabled: settings.notifications_enabled,
+        close_to_tray: settings.close_to_tray,
+        hash_downloads: settings.hash_downloads,
+        notify_on_done: settings.notify_on_done,
+        notify_on_error: settings.notify_on_error,
+        notify_on_session_end: settings.notify_on_session_end,
+        download_windows: settings.download_windows.clone(),
+        digest_mode: settings.digest_mode.clone(),
+        smtp: settings.smtp.clone(),
+        redact_logs: settings.redact_logs,
+        http_headers: settings
+            .http_headers
+            .iter()
+            .filter(|h| !h.name.trim().is_empty())
+            .cloned()
+            .collect(),
+        geo_bypass_country: settings
+            .geo_bypass_country
+            .iter()
+            .map(|(platform, code)| (platform.clone(), code.trim().to_uppercase()))
+            .filter(|(_, code)| code.len() == 2 && code.chars().all(|c| c.is_ascii_alphabetic()))
+            .collect(),
+        proxy_url: settings.proxy_url.trim().to_string(),
+        per_platform_proxy: settings
+            .per_platform_proxy
+            .iter()
+            .map(|(platform, proxy)| (platform.clone(), proxy.trim().to_string()))
+            .filter(|(_, proxy)| !proxy.is_empty())
+            .collect(),
+        preferred_cookie_browser: settings
+            .preferred_cookie_browser
+            .as_deref()
+            .map(str::trim)
+            .filter(|b| !b.is_empty())
+            .map(str::to_string),
+        platform_overrides: settings
+            .platform_overrides
+            .iter()
+            .map(|(platform, ov)| (platform.clone(), clean_platform_override(ov)))
+            .filter(|(_, ov)| *ov != PlatformOverride::default())
+            .collect(),
+        filename_filesystem: settings.filename_filesystem,
+        max_filename_len: settings.max_filename_len,
+        read_only: settings.read_only,
+        trim_to_link_timestamp: settings.trim_to_link_timestamp,
+        tool_env: settings
+            .tool_env
+            .iter()
+            .map(|v| EnvVar {
+                name: v.name.trim().to_string(),
+                value: v.value.clone(),
+            })
+            .filter(|v| !v.name.is_empty() && !v.name.contains('='))
+            .collect(),
+        clipboard_watch: settings.clipboard_watch,
+        on_import_duplicate: settings.on_import_duplicate,
+        http_api: HttpApiConfig {
+            port: if settings.http_api.port == 0 {
+                DEFAULT_HTTP_API_PORT
+            } else {
+                settings.http_api.port
+            },
+            // The extension can't be paired without a token, so turning the API on makes one.
+            token: match settings.http_api.token.trim() {
+                "" if settings.http_api.enabled => crate::http_api::new_token(),
+                token => token.to_string(),
+            },
+            ..settings.http_api.clone()
+        },
+        prefetch_metadata: settings.prefetch_metadata,
+        download_subtitles: settings.download_subtitles,
+        subtitle_langs: settings.subtitle_langs.trim().to_string(),
+        embed_subs: settings.embed_subs,
+        collection_index: settings.collection_index,
+        write_info_json: settings.write_info_json,
+        max_resolution: settings.max_resolution,
+        preferred_codec: settings.preferred_codec,
+        audio_format: settings.audio_format,
+        audio_quality: match settings.audio_quality.trim() {
+            "" => default_audio_quality(),
+            q => q.to_string(),
+        },
+        startup_page: settings.startup_page,
     };
 
     let body = serde_json::to_string_pretty(&to_write)
@@ -162,3 +326,328 @@ pub fn get_yt_dlp_duplicate_flags(on_duplicate: &OnDuplicate) -> Vec<String> {
         OnDuplicate::DoNothing => vec!["--no-overwrites".into(), "--no-continue".into()],
     }
 }
+
+/// Custom headers that apply to `url`: rules for its platform plus the "all" rules.
+pub fn headers_for_url(settings: &Settings, url: &str) -> Vec<(String, String)> {
+    let platform = clip_core::platform::detect(url).map_or("other", |p| p.id());
+    settings
+        .http_headers
+        .iter()
+        .filter(|h| !h.name.trim().is_empty())
+        .filter(|h| {
+            let p = h.platform.trim();
+            p.eq_ignore_ascii_case("all") || p.eq_ignore_ascii_case(platform)
+        })
+        .map(|h| (h.name.trim().to_string(), h.value.trim().to_string()))
+        .collect()
+}
+
+/// `--add-header Name:Value` pairs for yt-dlp.
+pub fn get_yt_dlp_header_flags(settings: &Settings, url: &str) -> Vec<String> {
+    headers_for_url(settings, url)
+        .into_iter()
+        .flat_map(|(name, value)| ["--add-header".to_string(), format!("{name}:{value}")])
+        .collect()
+}
+
+/// gallery-dl takes extra headers as a JSON object through the extractor `headers` option.
+pub fn get_gallery_dl_header_flags(settings: &Settings, url: &str) -> Vec<String> {
+    let headers: serde_json::Map<String, serde_json::Value> = headers_for_url(settings, url)
+        .into_iter()
+        .map(|(name, value)| (name, serde_json::Value::String(value)))
+        .collect();
+    if headers.is_empty() {
+        return Vec::new();
+    }
+    vec![
+        "-o".into(),
+        format!("headers={}", serde_json::Value::Object(headers)),
+    ]
+}
+
+/// Blank text and zero limits mean "use the global setting".
+fn clean_platform_override(ov: &PlatformOverride) -> PlatformOverride {
+    let text = |v: &Option<String>| {
+        v.as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    PlatformOverride {
+        output: ov.output.clone(),
+        max_height: ov.max_height.filter(|h| *h > 0),
+        max_parallel: ov.max_parallel.filter(|n| *n > 0),
+        subfolder: text(&ov.subfolder),
+        cookie_browser: text(&ov.cookie_browser),
+        cookie_file: text(&ov.cookie_file),
+    }
+}
+
+/// Overrides configured for the platform of `url`; all unset when none are.
+pub fn platform_override_for_url(settings: &Settings, url: &str) -> PlatformOverride {
+    let platform = clip_core::platform::detect(url).map_or("other", |p| p.id());
+    settings
+        .platform_overrides
+        .get(platform)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// (label, cookie arg) pairs to try for `url`, in order: the platform's
+/// cookies.txt file when it exists, then the browsers with the preferred one
+/// first. `row_browser` (a download's own choice) wins over the platform's,
+/// which wins over `preferred_cookie_browser`. See `utils::os::cookie_flags`.
+pub fn cookie_sources(
+    settings: &Settings,
+    url: &str,
+    row_browser: Option<&str>,
+) -> Vec<(String, String)> {
+    let ov = platform_override_for_url(settings, url);
+    let mut sources = Vec::new();
+    if let Some(file) = ov.cookie_file.as_deref() {
+        if Path::new(file).is_file() {
+            sources.push((
+                "cookies.txt".to_string(),
+                crate::utils::os::cookie_file_arg(file),
+            ));
+        } else {
+            eprintln!("[BACKEND] cookie file missing, using browsers: {file}");
+        }
+    }
+    let mut browsers = crate::utils::os::installed_browsers();
+    let preferred = row_browser
+        .or(ov.cookie_browser.as_deref())
+        .or(settings.preferred_cookie_browser.as_deref());
+    if let Some(preferred) = preferred {
+        // Stable, so the remaining browsers keep their usual fallback order.
+        browsers.sort_by_key(|(label, _)| !label.starts_with(preferred));
+    }
+    sources.extend(browsers);
+    sources
+}
+
+/// Folder a platform's downloads are laid out under: the download directory,
+/// or the platform's override subfolder inside it.
+pub fn platform_root(settings: &Settings, platform: &str) -> PathBuf {
+    let root = PathBuf::from(&settings.download_directory);
+    let Some(sub) = settings
+        .platform_overrides
+        .get(platform)
+        .and_then(|ov| ov.subfolder.as_deref())
+    else {
+        return root;
+    };
+    sub.split(['/', '\\'])
+        .map(str::trim)
+        .filter(|part| !part.is_empty() && *part != "." && *part != "..")
+        .fold(root, |dir, part| dir.join(part))
+}
+
+/// Folder names from `platform_root` down to a collection's folder under the
+/// given templates, cleaned and shortened for the download directory's
+/// filesystem exactly as the pipeline creates them.
+pub fn collection_dir_names(
+    settings: &Settings,
+    folder_template: &str,
+    collection_template: &str,
+    platform: &str,
+    origin: &str,
+    user_handle: &str,
+) -> Vec<String> {
+    let rules =
+        crate::utils::filenames::rules_for(Path::new(&settings.download_directory), settings);
+    crate::database::Database::collection_dir_segments(
+        folder_template,
+        collection_template,
+        platform,
+        origin,
+        user_handle,
+    )
+    .iter()
+    .map(|part| rules.fit(&rules.clean(part), 0))
+    .collect()
+}
+
+/// Absolute folder a collection's downloads land in; see `collection_dir_names`.
+pub fn collection_dir(
+    settings: &Settings,
+    folder_template: &str,
+    collection_template: &str,
+    platform: &str,
+    origin: &str,
+    user_handle: &str,
+) -> PathBuf {
+    collection_dir_names(
+        settings,
+        folder_template,
+        collection_template,
+        platform,
+        origin,
+        user_handle,
+    )
+    .iter()
+    .fold(platform_root(settings, platform), |dir, part| {
+        dir.join(part)
+    })
+}
+
+/// yt-dlp `-f` selector: capped at the platform's (or global) maximum height
+/// and trying the preferred codec first. Each step falls back to the next so
+/// a video is never skipped for lacking a matching format.
+pub fn get_yt_dlp_format(settings: &Settings, url: &str) -> String {
+    let height = platform_override_for_url(settings, url)
+        .max_height
+        .or(Some(settings.max_resolution))
+        .filter(|h| *h > 0)
+        .map(|h| format!("[height<={h}]"))
+        .unwrap_or_default();
+    let codec = match settings.preferred_codec {
+        VideoCodec::Any => None,
+        VideoCodec::H264 => Some("[vcodec^=avc1]"),
+        VideoCodec::Vp9 => Some("[vcodec^=vp]"),
+        VideoCodec::Av1 => Some("[vcodec^=av01]"),
+    };
+    let mut choices = Vec::new();
+    if let Some(codec) = codec {
+        choices.push(format!("bestvideo{height}{codec}+bestaudio"));
+    }
+    choices.push(format!("bestvideo{height}+bestaudio"));
+    if !height.is_empty() {
+        choices.push(format!("best{height}"));
+    }
+    choices.push("best".into());
+    choices.join("/")
+}
+
+/// Country code configured for geo-bypass on the platform of `url`, if any.
+pub fn geo_bypass_for_url(settings: &Settings, url: &str) -> Option<String> {
+    let platform = clip_core::platform::detect(url).map_or("other", |p| p.id());
+    settings
+        .geo_bypass_country
+        .get(platform)
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty())
+}
+
+/// yt-dlp fakes an X-Forwarded-For address from the given country.
+pub fn get_yt_dlp_geo_flags(settings: &Settings, url: &str) -> Vec<String> {
+    match geo_bypass_for_url(settings, url) {
+        Some(code) => vec!["--xff".into(), code],
+        None => vec![],
+    }
+}
+
+/// Proxy for the platform of `url`: its own entry, else the global one.
+pub fn proxy_for_url(settings: &Settings, url: &str) -> Option<String> {
+    let platform = clip_core::platform::detect(url).map_or("other", |p| p.id());
+    let proxy = settings
+        .per_platform_proxy
+        .get(platform)
+        .unwrap_or(&settings.proxy_url)
+        .trim();
+    (!proxy.is_empty()).then(|| proxy.to_string())
+}
+
+pub fn get_yt_dlp_proxy_flags(settings: &Settings, url: &str) -> Vec<String> {
+    match proxy_for_url(settings, url) {
+        Some(proxy) => vec!["--proxy".into(), proxy],
+        None => vec![],
+    }
+}
+
+/// gallery-dl takes the same `--proxy` option as yt-dlp.
+pub fn get_gallery_dl_proxy_flags(settings: &Settings, url: &str) -> Vec<String> {
+    match proxy_for_url(settings, url) {
+        Some(proxy) => vec!["--proxy".into(), proxy],
+        None => vec![],
+    }
+}
+
+/// yt-dlp skips items published outside `range` when it knows their date.
+pub fn get_yt_dlp_date_flags(range: &DateRange) -> Vec<String> {
+    let mut flags = Vec::new();
+    if let Some(after) = range.after.as_deref().and_then(DateRange::compact) {
+        flags.extend(["--dateafter".into(), after]);
+    }
+    if let Some(before) = range.before.as_deref().and_then(DateRange::compact) {
+        flags.extend(["--datebefore".into(), before]);
+    }
+    flags
+}
+
+/// Subtitle files in the chosen languages, optionally embedded into the video.
+pub fn get_yt_dlp_subtitle_flags(settings: &Settings) -> Vec<String> {
+    if !settings.download_subtitles {
+        return vec![];
+    }
+    let langs = match settings.subtitle_langs.trim() {
+        "" => default_subtitle_langs(),
+        langs => langs.to_string(),
+    };
+    let mut flags = vec!["--write-subs".into(), "--sub-langs".into(), langs];
+    if settings.embed_subs {
+        flags.push("--embed-subs".into());
+    }
+    flags
+}
+
+/// Metadata sidecar next to each yt-dlp download.
+pub fn get_yt_dlp_info_json_flags(settings: &Settings) -> Vec<String> {
+    if settings.write_info_json {
+        vec!["--write-info-json".into()]
+    } else {
+        vec![]
+    }
+}
+
+/// Metadata sidecar next to each gallery-dl file.
+pub fn get_gallery_dl_metadata_flags(settings: &Settings) -> Vec<String> {
+    if settings.write_info_json {
+        vec!["--write-metadata".into()]
+    } else {
+        vec![]
+    }
+}
+
+/// yt-dlp only fetches from the link's timestamp onwards when trimming is on.
+pub fn get_yt_dlp_trim_flags(settings: &Settings, url: &str) -> Vec<String> {
+    match clip_core::url::start_offset_secs(url) {
+        Some(secs) if settings.trim_to_link_timestamp => {
+            vec!["--download-sections".into(), format!("*{secs}-inf")]
+        }
+        _ => vec![],
+    }
+}
+
+/// Environment for a spawned tool: `base_path` as PATH plus the user's
+/// variables. A user PATH is put in front of `base_path` rather than replacing it.
+pub fn tool_env(settings: &Settings, base_path: String) -> Vec<(String, String)> {
+    let mut path = base_path;
+    let mut env = Vec::new();
+    for var in settings
+        .tool_env
+        .iter()
+        .filter(|v| !v.name.trim().is_empty())
+    {
+        let name = var.name.trim();
+        if name.eq_ignore_ascii_case("PATH") {
+            let sep = if cfg!(windows) { ";" } else { ":" };
+            path = format!("{}{sep}{path}", var.value.trim());
+        } else {
+            env.push((name.to_string(), var.value.clone()));
+        }
+    }
+    env.push(("PATH".to_string(), path));
+    env
+}
+
+/// Guard for every entry point that changes the library while read-only mode
+/// is on: deleting or editing rows and settings, but also adding links and
+/// queueing downloads, since those write new files into it.
+pub fn ensure_writable() -> Result<(), String> {
+    if load_settings().read_only {
+        Err("the library is read-only; turn off read-only mode in Settings first".into())
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+//! Library aggregates for the Stats page, and their export with the session
+//! history for analysis in spreadsheets or scripts.
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::database::{Database, LibraryStats, SessionReport};
+
+/// Days covered by the downloads-per-day chart.
+const CHART_DAYS: i64 = 90;
+
+/// Totals per platform, origin and media kind, bytes on disk, the last
+/// 90 days of downloads and the average time per download.
+pub fn library_stats() -> Result<LibraryStats, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let by_platform = db.count_done_by("platform").map_err(|e| e.to_string())?;
+    let by_origin = db.count_done_by("origin").map_err(|e| e.to_string())?;
+    let by_media = db.count_done_by("media").map_err(|e| e.to_string())?;
+    let total = by_platform.iter().map(|(_, n)| n).sum();
+
+    // Sizes recorded on completion; only files finished before that are walked.
+    let (sized, unmeasured) = db.done_file_bytes().map_err(|e| e.to_string())?;
+    let bytes_on_disk = sized
+        + unmeasured
+            .iter()
+            .map(|path| {
+                crate::utils::paths::path_bytes(&crate::utils::paths::extended(Path::new(path)))
+            })
+            .sum::<u64>();
+
+    let today = chrono::Utc::now().date_naive();
+    let first = today - chrono::Duration::days(CHART_DAYS - 1);
+    let counts: std::collections::HashMap<String, u64> = db
+        .count_done_per_day(&first.to_string())
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .collect();
+    let per_day = first
+        .iter_days()
+        .take(CHART_DAYS as usize)
+        .map(|day| {
+            let day = day.to_string();
+            let n = counts.get(&day).copied().unwrap_or(0);
+            (day, n)
+        })
+        .collect();
+
+    let (secs, downloads) = db.session_totals().map_err(|e| e.to_string())?;
+    let avg_download_secs = (downloads > 0).then(|| secs as f64 / downloads as f64);
+
+    Ok(LibraryStats {
+        total,
+        by_platform,
+        by_origin,
+        by_media,
+        bytes_on_disk,
+        per_day,
+        avg_download_secs,
+    })
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// Pairs as "a:1; b:2" so a CSV cell stays readable.
+fn join_counts(pairs: &[(String, u32)]) -> String {
+    pairs
+        .iter()
+        .map(|(k, n)| format!("{k}:{n}"))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn write_sessions_csv(path: &Path, sessions: &[SessionReport]) -> Result<(), String> {
+    let mut w = csv::Writer::from_writer(File::create(path).map_err(|e| e.to_string())?);
+    w.write_record([
+        "id",
+        "started_at",
+        "ended_at",
+        "elapsed_secs",
+        "downloaded",
+        "failed",
+        "bytes",
+        "failures",
+        "top_collections",
+    ])
+    .map_err(|e| e.to_string())?;
+    for s in sessions {
+        w.write_record([
+            s.id.to_string(),
+            s.started_at.clone(),
+            s.ended_at.clone(),
+            s.elapsed_secs.to_string(),
+            s.downloaded.to_string(),
+            s.failed.to_string(),
+            s.bytes.to_string(),
+            join_counts(&s.failures),
+            join_counts(&s.top_collections),
+        ])
+        .map_err(|e| e.to_string())?;
+    }
+    w.flush().map_err(|e| e.to_string())
+}
+
+/// Write the export into `dest_dir`; returns the files written.
+///
+/// CSV gives two files: `-aggregates.csv` with one row per platform, status and
+/// month downloaded, and `-sessions.csv`. JSON puts both, plus totals per
+/// status, into a single file.
+pub fn export(dest_dir: &Path, format: ExportFormat) -> Result<Vec<PathBuf>, String> {
+    fs::create_dir_all(dest_dir).map_err(|e| e.to_string())?;
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let aggregates = db
+        .count_by_platform_status_month()
+        .map_err(|e| e.to_string())?;
+    let sessions = db
+        .list_sessions(i64::MAX as usize)
+        .map_err(|e| e.to_string())?;
+    let stem = format!(
+        "clip-downloader-stats-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+
+    let written = match format {
+        ExportFormat::Csv => {
+            let agg_path = dest_dir.join(format!("{stem}-aggregates.csv"));
+            let mut w =
+                csv::Writer::from_writer(File::create(&agg_path).map_err(|e| e.to_string())?);
+            w.write_record(["platform", "status", "month", "count"])
+                .map_err(|e| e.to_string())?;
+            for (platform, status, month, count) in &aggregates {
+                w.write_record([platform, status, month, &count.to_string()])
+                    .map_err(|e| e.to_string())?;
+            }
+            w.flush().map_err(|e| e.to_string())?;
+
+            let sessions_path = dest_dir.join(format!("{stem}-sessions.csv"));
+            write_sessions_csv(&sessions_path, &sessions)?;
+            vec![agg_path, sessions_path]
+        }
+        ExportFormat::Json => {
+            let totals = db.count_by_status().map_err(|e| e.to_string())?;
+            let body = json!({
+                "exported_at": chrono::Utc::now().to_rfc3339(),
+                "totals": totals.into_iter().collect::<std::collections::BTreeMap<_, _>>(),
+                "aggregates": aggregates
+                    .iter()
+                    .map(|(platform, status, month, count)| json!({
+                        "platform": platform,
+                        "status": status,
+                        "month": month,
+                        "count": count,
+                    }))
+                    .collect::<Vec<_>>(),
+                "sessions": sessions,
+            });
+            let path = dest_dir.join(format!("{stem}.json"));
+            let text = serde_json::to_string_pretty(&body).map_err(|e| e.to_string())?;
+            fs::write(&path, text).map_err(|e| e.to_string())?;
+            vec![path]
+        }
+    };
+    Ok(written)
+}
@@ -0,0 +1,192 @@
+//! Platform data exports: YouTube Takeout (`watch-history.json` and the
+//! playlist CSVs) and TikTok's "Download your data" JSON. Each entry becomes
+//! a link with the collection it belongs to, so a history or a liked list can
+//! be imported without running the scraper.
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::database::{ContentType, Platform};
+
+/// One link read from an export.
+pub struct TakeoutEntry {
+    pub platform: Platform,
+    pub origin: ContentType,
+    /// Handle of the collection the row joins
+    pub handle: String,
+    pub link: String,
+}
+
+/// The entries of the export at `path` (its contents in `text`), or `None`
+/// when the file isn't an export this module knows.
+pub fn parse(path: &Path, text: &str) -> Option<Result<Vec<TakeoutEntry>, String>> {
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "json" => {
+            let value: Value = match serde_json::from_str(text) {
+                Ok(v) => v,
+                Err(e) => return Some(Err(format!("{}: {e}", path.display()))),
+            };
+            match &value {
+                Value::Array(items) => Some(Ok(youtube_history(items))),
+                Value::Object(_) if tiktok_activity(&value).is_some() => {
+                    Some(Ok(tiktok_export(&value)))
+                }
+                _ => None,
+            }
+        }
+        "csv" => youtube_playlist(path, text).map(Ok),
+        _ => None,
+    }
+}
+
+fn youtube_watch_link(id: &str) -> String {
+    format!("https://www.youtube.com/watch?v={id}")
+}
+
+/// `watch-history.json`: every watched video once. Ads and videos that were
+/// removed since (no link) are left out.
+fn youtube_history(items: &[Value]) -> Vec<TakeoutEntry> {
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    for item in items {
+        let Some(url) = item.get("titleUrl").and_then(Value::as_str) else {
+            continue;
+        };
+        let is_ad = item
+            .get("details")
+            .and_then(Value::as_array)
+            .is_some_and(|d| {
+                d.iter()
+                    .any(|d| d.get("name").and_then(Value::as_str) == Some("From Google Ads"))
+            });
+        if is_ad || !url.contains("/watch?v=") {
+            continue;
+        }
+        let Some(id) = url
+            .split("v=")
+            .nth(1)
+            .map(|s| s.split('&').next().unwrap_or(s))
+        else {
+            continue;
+        };
+        if seen.insert(id.to_string()) {
+            out.push(TakeoutEntry {
+                platform: Platform::Youtube,
+                origin: ContentType::History,
+                handle: "Watch history".into(),
+                link: youtube_watch_link(id),
+            });
+        }
+    }
+    out
+}
+
+fn is_video_id(s: &str) -> bool {
+    s.len() == 11
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// A playlist CSV (`Watch later-videos.csv`, `Liked videos.csv`, ...): video
+/// ids under a "Video ID" header, sometimes after a block describing the
+/// playlist. Liked videos and Watch later get their own origins; any other
+/// playlist is named after the file.
+fn youtube_playlist(path: &Path, text: &str) -> Option<Vec<TakeoutEntry>> {
+    let mut lines = text.lines().map(str::trim);
+    lines.find(|l| l.to_lowercase().starts_with("video id"))?;
+
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = stem.trim_end_matches("-videos").trim().to_string();
+    let lower = name.to_lowercase();
+    let (origin, handle) = if lower.starts_with("liked") {
+        (ContentType::Liked, "Liked videos".to_string())
+    } else if lower.starts_with("watch later") {
+        (ContentType::Bookmarks, "Watch later".to_string())
+    } else {
+        (ContentType::Playlist, name)
+    };
+
+    let mut seen = HashSet::new();
+    let entries = lines
+        .filter_map(|l| l.split(',').next().map(str::trim))
+        .filter(|id| is_video_id(id) && seen.insert(id.to_string()))
+        .map(|id| TakeoutEntry {
+            platform: Platform::Youtube,
+            origin,
+            handle: handle.clone(),
+            link: youtube_watch_link(id),
+        })
+        .collect();
+    Some(entries)
+}
+
+/// The "Activity" section of a TikTok export (renamed in newer exports).
+fn tiktok_activity(root: &Value) -> Option<&Value> {
+    root.get("Activity").or_else(|| root.get("Your Activity"))
+}
+
+/// TikTok export links point at `tiktokv.com/share/video/<id>/`; use the
+/// regular video page instead.
+fn tiktok_link(raw: &str) -> Option<String> {
+    let id: String = raw
+        .split("/video/")
+        .nth(1)?
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    (!id.is_empty()).then(|| format!("https://www.tiktok.com/@/video/{id}"))
+}
+
+/// Liked and favorited videos of a TikTok export, in the account's collections.
+fn tiktok_export(root: &Value) -> Vec<TakeoutEntry> {
+    let handle = root
+        .pointer("/Profile/Profile Information/ProfileMap/userName")
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+        .unwrap_or("Unknown")
+        .to_string();
+    let Some(activity) = tiktok_activity(root) else {
+        return Vec::new();
+    };
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    for (section, list, origin) in [
+        ("Like List", "ItemFavoriteList", ContentType::Liked),
+        (
+            "Favorite Videos",
+            "FavoriteVideoList",
+            ContentType::Bookmarks,
+        ),
+    ] {
+        let items = activity
+            .get(section)
+            .and_then(|s| s.get(list))
+            .and_then(Value::as_array);
+        for item in items.into_iter().flatten() {
+            let raw = item
+                .get("Link")
+                .or_else(|| item.get("link"))
+                .and_then(Value::as_str);
+            let Some(link) = raw.and_then(tiktok_link) else {
+                continue;
+            };
+            if seen.insert((link.clone(), origin)) {
+                out.push(TakeoutEntry {
+                    platform: Platform::Tiktok,
+                    origin,
+                    handle: handle.clone(),
+                    link,
+                });
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,152 @@
+//! Tray icon so the app can keep downloading with its window closed: the
+//! tooltip shows how many downloads are running and waiting, and the menu
+//! pauses the queue, queues the link on the clipboard or quits.
+use std::sync::Mutex;
+
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::download::manager::{DownloadCommand, DownloadManager};
+
+const TOOLTIP: &str = "Clip Downloader";
+
+struct Tray {
+    icon: TrayIcon,
+    pause: MenuItem,
+    /// Last `(active, queued, paused)` shown, to skip redundant updates
+    shown: Mutex<Option<(usize, usize, bool)>>,
+}
+
+pub fn setup(app: &AppHandle) -> tauri::Result<()> {
+    let show = MenuItem::with_id(app, "show", "Show window", true, None::<&str>)?;
+    let library = MenuItem::with_id(app, "library", "Open library", true, None::<&str>)?;
+    let pause = MenuItem::with_id(app, "pause", "Pause queue", true, None::<&str>)?;
+    let paste = MenuItem::with_id(app, "paste", "Paste URL from clipboard", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let menu = Menu::with_items(
+        app,
+        &[
+            &show,
+            &library,
+            &PredefinedMenuItem::separator(app)?,
+            &pause,
+            &paste,
+            &PredefinedMenuItem::separator(app)?,
+            &quit,
+        ],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id("main")
+        .tooltip(TOOLTIP)
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| match event.id().as_ref() {
+            "show" => show_window(app),
+            "library" => {
+                show_window(app);
+                let _ = app.emit("navigate", "library");
+            }
+            "pause" => toggle_pause(app),
+            "paste" => paste_links(app),
+            "quit" => app.exit(0),
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    let icon = builder.build(app)?;
+    app.manage(Tray {
+        icon,
+        pause,
+        shown: Mutex::new(None),
+    });
+    Ok(())
+}
+
+fn show_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn toggle_pause(app: &AppHandle) {
+    let paused = app
+        .try_state::<Tray>()
+        .and_then(|t| t.shown.lock().ok().and_then(|s| *s))
+        .is_some_and(|(_, _, paused)| paused);
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let manager = app.state::<DownloadManager>();
+        if let Err(e) = manager.send(DownloadCommand::SetPaused(!paused)).await {
+            eprintln!("[BACKEND] [tray] pause toggle not sent: {e}");
+        }
+    });
+}
+
+/// Add the supported links on the clipboard to the backlog and queue them.
+fn paste_links(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let added = {
+            let app = app.clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                crate::clipboard::add_clipboard_links(&app)
+            })
+            .await
+            .map_err(|e| format!("Join error: {e}"))
+            .and_then(|r| r)
+        };
+        let ids = match added {
+            Ok(ids) if ids.is_empty() => {
+                println!("[BACKEND] [tray] no new links on the clipboard");
+                return;
+            }
+            Ok(ids) => ids,
+            Err(e) => {
+                eprintln!("[BACKEND] [tray] clipboard links not added: {e}");
+                return;
+            }
+        };
+        let manager = app.state::<DownloadManager>();
+        let _ = manager
+            .send(DownloadCommand::Enqueue { ids, reply: None })
+            .await;
+    });
+}
+
+/// Show the queue state in the tooltip and the pause entry. Called by the
+/// download manager after each command.
+pub fn update(app: &AppHandle, active: usize, queued: usize, paused: bool) {
+    let Some(tray) = app.try_state::<Tray>() else {
+        return;
+    };
+    let Ok(mut shown) = tray.shown.lock() else {
+        return;
+    };
+    if *shown == Some((active, queued, paused)) {
+        return;
+    }
+    *shown = Some((active, queued, paused));
+    let state = if paused { ", paused" } else { "" };
+    let _ = tray.icon.set_tooltip(Some(format!(
+        "{TOOLTIP}: {active} downloading, {queued} queued{state}"
+    )));
+    let _ = tray.pause.set_text(if paused {
+        "Resume queue"
+    } else {
+        "Pause queue"
+    });
+}
@@ -0,0 +1,23 @@
+use std::io;
+use std::path::Path;
+
+/// Remove a downloaded file for a hard delete.
+/// With `to_trash` the file goes to the OS recycle bin / trash so it can be restored;
+/// otherwise it is unlinked. Placeholder paths and already-missing files are not errors.
+pub fn remove_media_file(path: &str, to_trash: bool) -> io::Result<()> {
+    if path.is_empty() || path == "unknown_path" {
+        return Ok(());
+    }
+    let p = Path::new(path);
+    if !p.exists() {
+        return Ok(());
+    }
+    if to_trash {
+        trash::delete(p).map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))
+    } else {
+        match std::fs::remove_file(p) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            other => other,
+        }
+    }
+}
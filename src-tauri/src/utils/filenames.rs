@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use crate::database::{FilesystemKind, Settings};
+
+/// Room left after a stem for a duplicate counter, extension and yt-dlp's
+/// intermediate names ("x.f137.mp4.part").
+pub const NAME_SUFFIX_RESERVE: usize = 20;
+
+/// Detection shells out on macOS/Windows, so remember the answer per directory.
+static DETECTED: Lazy<Mutex<HashMap<PathBuf, FilesystemKind>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Length limit and forbidden characters for names on one filesystem.
+#[derive(Debug, Clone, Copy)]
+pub struct FilenameRules {
+    pub max_len: usize,
+    /// Length counted in UTF-16 code units (FAT/exFAT/NTFS/HFS+) rather than UTF-8 bytes.
+    pub utf16: bool,
+    /// Windows-family filesystems: no `<>:"/\|?*`, no trailing dots or spaces.
+    pub windows: bool,
+}
+
+impl FilenameRules {
+    pub fn for_filesystem(kind: FilesystemKind) -> Self {
+        match kind {
+            FilesystemKind::Fat | FilesystemKind::ExFat | FilesystemKind::Ntfs => FilenameRules {
+                max_len: 255,
+                utf16: true,
+                windows: true,
+            },
+            FilesystemKind::HfsPlus => FilenameRules {
+                max_len: 255,
+                utf16: true,
+                windows: false,
+            },
+            // APFS, ext4 and anything unknown: 255 bytes of UTF-8.
+            _ => FilenameRules {
+                max_len: 255,
+                utf16: false,
+                windows: cfg!(windows),
+            },
+        }
+    }
+
+    pub fn measure(&self, s: &str) -> usize {
+        if self.utf16 {
+            s.encode_utf16().count()
+        } else {
+            s.len()
+        }
+    }
+
+    /// Replace characters the filesystem rejects and drop control characters.
+    pub fn clean(&self, name: &str) -> String {
+        let cleaned: String = name
+            .chars()
+            .filter(|c| !c.is_control())
+            .map(|c| match c {
+                '/' | '\\' => '_',
+                '<' | '>' | ':' | '"' | '|' | '?' | '*' if self.windows => '_',
+                c => c,
+            })
+            .collect();
+        if self.windows {
+            cleaned.trim_end_matches(['.', ' ']).to_string()
+        } else {
+            cleaned
+        }
+    }
+
+    /// Cut `text` on a char boundary so it plus `reserve` units fits the limit.
+    pub fn fit(&self, text: &str, reserve: usize) -> String {
+        let budget = self.max_len.saturating_sub(reserve);
+        let mut used = 0;
+        let mut out = String::new();
+        for c in text.chars() {
+            let w = if self.utf16 {
+                c.len_utf16()
+            } else {
+                c.len_utf8()
+            };
+            if used + w > budget {
+                break;
+            }
+            used += w;
+            out.push(c);
+        }
+        if self.windows {
+            out.trim_end_matches(['.', ' ']).to_string()
+        } else {
+            out
+        }
+    }
+
+    /// Clean and shorten a full file name, keeping its extension intact.
+    pub fn fit_file_name(&self, name: &str, reserve: usize) -> String {
+        let name = self.clean(name);
+        match name.rsplit_once('.') {
+            Some((stem, ext)) if !stem.is_empty() && !ext.is_empty() => {
+                let ext = format!(".{ext}");
+                format!("{}{ext}", self.fit(stem, reserve + self.measure(&ext)))
+            }
+            _ => self.fit(&name, reserve),
+        }
+    }
+}
+
+/// Rules for names created under `dir`, from the settings override or the detected filesystem.
+pub fn rules_for(dir: &Path, settings: &Settings) -> FilenameRules {
+    let kind = match settings.filename_filesystem {
+        FilesystemKind::Auto => detect_filesystem(dir),
+        kind => kind,
+    };
+    let mut rules = FilenameRules::for_filesystem(kind);
+    if settings.max_filename_len > 0 {
+        rules.max_len = rules.max_len.min(settings.max_filename_len as usize);
+    }
+    rules
+}
+
+/// Filesystem holding `dir` (or its nearest existing ancestor); `Other` if unknown.
+pub fn detect_filesystem(dir: &Path) -> FilesystemKind {
+    let mut probe = dir.to_path_buf();
+    while !probe.exists() {
+        if !probe.pop() {
+            return FilesystemKind::Other;
+        }
+    }
+    let probe = probe.canonicalize().unwrap_or(probe);
+    if let Some(kind) = DETECTED.lock().ok().and_then(|m| m.get(&probe).copied()) {
+        return kind;
+    }
+    let kind = filesystem_name(&probe)
+        .map(|name| kind_from_name(&name))
+        .unwrap_or(FilesystemKind::Other);
+    println!("[BACKEND] filesystem of {} is {kind:?}", probe.display());
+    if let Ok(mut m) = DETECTED.lock() {
+        m.insert(probe, kind);
+    }
+    kind
+}
+
+fn kind_from_name(name: &str) -> FilesystemKind {
+    match name.trim().to_lowercase().as_str() {
+        "vfat" | "msdos" | "fat" | "fat16" | "fat32" => FilesystemKind::Fat,
+        "exfat" => FilesystemKind::ExFat,
+        // fuseblk is almost always ntfs-3g
+        "ntfs" | "ntfs3" | "fuseblk" | "refs" => FilesystemKind::Ntfs,
+        "apfs" => FilesystemKind::Apfs,
+        "hfs" | "hfsplus" => FilesystemKind::HfsPlus,
+        "ext2" | "ext3" | "ext4" | "btrfs" | "xfs" | "zfs" | "f2fs" => FilesystemKind::Ext4,
+        _ => FilesystemKind::Other,
+    }
+}
+
+/// Longest mount point that contains `dir`, from (mount point, fs name) pairs.
+#[cfg_attr(not(any(target_os = "linux", target_os = "macos")), allow(dead_code))]
+fn fs_of_mount(dir: &Path, mounts: impl Iterator<Item = (String, String)>) -> Option<String> {
+    mounts
+        .filter(|(mount, _)| dir.starts_with(mount))
+        .max_by_key(|(mount, _)| mount.len())
+        .map(|(_, fs)| fs)
+}
+
+#[cfg(target_os = "linux")]
+fn filesystem_name(dir: &Path) -> Option<String> {
+    let mounts = std::fs::read_to_string("/proc/self/mounts").ok()?;
+    fs_of_mount(
+        dir,
+        mounts.lines().filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let _dev = parts.next()?;
+            let mount = parts.next()?.replace("\\040", " ");
+            Some((mount, parts.next()?.to_string()))
+        }),
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn filesystem_name(dir: &Path) -> Option<String> {
+    // "/dev/disk4s1 on /Volumes/USB (msdos, local, nodev, nosuid, noowners)"
+    let out = std::process::Command::new("/sbin/mount").output().ok()?;
+    let text = String::from_utf8_lossy(&out.stdout);
+    fs_of_mount(
+        dir,
+        text.lines().filter_map(|line| {
+            let (_, rest) = line.split_once(" on ")?;
+            let (mount, opts) = rest.rsplit_once(" (")?;
+            let fs = opts.split([',', ')']).next()?;
+            Some((mount.to_string(), fs.to_string()))
+        }),
+    )
+}
+
+#[cfg(windows)]
+fn filesystem_name(dir: &Path) -> Option<String> {
+    let s = dir.to_string_lossy();
+    let s = s.trim_start_matches(r"\\?\");
+    let letter = s.chars().next().filter(|c| c.is_ascii_alphabetic())?;
+    if s.chars().nth(1) != Some(':') {
+        return None;
+    }
+    let out = std::process::Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            &format!("(Get-Volume -DriveLetter {letter}).FileSystemType"),
+        ])
+        .output()
+        .ok()?;
+    Some(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn filesystem_name(_dir: &Path) -> Option<String> {
+    None
+}
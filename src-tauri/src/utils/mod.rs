@@ -1,2 +1,6 @@
+pub mod checksum;
+pub mod delete;
+pub mod filenames;
 pub mod os;
-pub mod url;
+pub mod paths;
+pub mod redact;
@@ -86,6 +86,24 @@ fn safari_cookie_readable() -> bool {
     false
 }
 
+/// Prefix marking a cookie source as a Netscape cookies.txt file rather than
+/// a browser profile.
+const COOKIE_FILE_PREFIX: &str = "file:";
+
+/// Cookie source for a cookies.txt file, usable wherever a browser arg is.
+pub fn cookie_file_arg(path: &str) -> String {
+    format!("{COOKIE_FILE_PREFIX}{path}")
+}
+
+/// yt-dlp / gallery-dl flags for a cookie source: `--cookies <file>` for a
+/// cookies.txt file, `--cookies-from-browser` otherwise.
+pub fn cookie_flags(cookie_arg: &str) -> Vec<String> {
+    match cookie_arg.strip_prefix(COOKIE_FILE_PREFIX) {
+        Some(path) => vec!["--cookies".into(), path.into()],
+        None => vec!["--cookies-from-browser".into(), cookie_arg.into()],
+    }
+}
+
 // Return (browser_label, cookies-from-browser arg) only for usable browsers.
 pub fn installed_browsers() -> Vec<(String, String)> {
     let mut v = Vec::new();
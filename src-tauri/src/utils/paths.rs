@@ -0,0 +1,144 @@
+use std::path::{Path, PathBuf};
+
+/// Windows refuses plain paths longer than MAX_PATH (260) in many APIs and
+/// mangles UNC shares (`\\server\share`) joined with `/`. These helpers give
+/// file operations the extended-length form (`\\?\C:\…`, `\\?\UNC\server\share\…`)
+/// and everything user-facing (DB rows, Explorer, tool output) the plain one.
+const VERBATIM: &str = r"\\?\";
+const VERBATIM_UNC: &str = r"\\?\UNC\";
+
+/// Absolute path in any of the forms tools print: `/x`, `C:\x`, `C:/x`,
+/// `\\server\share` or `//server/share`.
+pub fn looks_absolute(s: &str) -> bool {
+    let b = s.as_bytes();
+    s.starts_with('/')
+        || s.starts_with(r"\\")
+        || (b.len() > 2
+            && b[0].is_ascii_alphabetic()
+            && b[1] == b':'
+            && matches!(b[2], b'\\' | b'/'))
+}
+
+/// Extended-length spelling of an absolute Windows path; other input is
+/// returned unchanged. `.` and `..` are resolved because `\\?\` paths are
+/// taken literally.
+fn to_verbatim(s: &str) -> String {
+    if s.starts_with(VERBATIM) {
+        return s.to_string();
+    }
+    let norm = s.replace('/', "\\");
+    let (prefix, rest) = if let Some(share) = norm.strip_prefix(r"\\") {
+        (VERBATIM_UNC.to_string(), share.to_string())
+    } else if looks_absolute(&norm) && !norm.starts_with('\\') {
+        (format!("{VERBATIM}{}", &norm[..2]), norm[2..].to_string())
+    } else {
+        return s.to_string();
+    };
+    let mut parts: Vec<&str> = Vec::new();
+    for part in rest.split('\\') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            p => parts.push(p),
+        }
+    }
+    if prefix == VERBATIM_UNC {
+        format!("{prefix}{}", parts.join("\\"))
+    } else {
+        format!("{prefix}\\{}", parts.join("\\"))
+    }
+}
+
+/// Plain spelling of a possibly extended-length path.
+fn from_verbatim(s: &str) -> String {
+    if let Some(share) = s.strip_prefix(VERBATIM_UNC) {
+        format!(r"\\{share}")
+    } else if let Some(rest) = s.strip_prefix(VERBATIM) {
+        rest.to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+/// Path to hand to `std::fs` so deep folders and UNC shares work on Windows.
+/// A no-op elsewhere.
+pub fn extended(path: &Path) -> PathBuf {
+    if cfg!(windows) {
+        PathBuf::from(to_verbatim(&path.to_string_lossy()))
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Size of a file, or of everything under a folder; 0 when it's gone.
+pub fn path_bytes(path: &Path) -> u64 {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| path_bytes(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Path as the user would type it, for storing, logging and opening in Explorer.
+pub fn plain(path: &Path) -> String {
+    from_verbatim(&path.to_string_lossy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_verbatim_resolves_dot_dot() {
+        assert_eq!(to_verbatim(r"C:\a\..\b"), r"\\?\C:\b");
+        assert_eq!(to_verbatim(r"C:\a\.\b\"), r"\\?\C:\a\b");
+    }
+
+    #[test]
+    fn to_verbatim_accepts_forward_slashes() {
+        assert_eq!(to_verbatim("C:/x"), r"\\?\C:\x");
+        assert_eq!(to_verbatim("C:/"), r"\\?\C:\");
+    }
+
+    #[test]
+    fn to_verbatim_maps_unc_shares() {
+        assert_eq!(to_verbatim(r"\\server\share\x"), r"\\?\UNC\server\share\x");
+        assert_eq!(to_verbatim("//server/share"), r"\\?\UNC\server\share");
+    }
+
+    #[test]
+    fn to_verbatim_leaves_verbatim_and_relative_paths() {
+        assert_eq!(to_verbatim(r"\\?\C:\a\..\b"), r"\\?\C:\a\..\b");
+        assert_eq!(
+            to_verbatim(r"\\?\UNC\server\share"),
+            r"\\?\UNC\server\share"
+        );
+        assert_eq!(to_verbatim("a/b"), "a/b");
+        assert_eq!(to_verbatim(r"..\b"), r"..\b");
+        assert_eq!(to_verbatim("/home/x"), "/home/x");
+    }
+
+    #[test]
+    fn from_verbatim_restores_plain_paths() {
+        assert_eq!(from_verbatim(r"\\?\C:\b"), r"C:\b");
+        assert_eq!(
+            from_verbatim(r"\\?\UNC\server\share\x"),
+            r"\\server\share\x"
+        );
+        assert_eq!(from_verbatim(r"C:\b"), r"C:\b");
+        assert_eq!(from_verbatim("a/b"), "a/b");
+    }
+
+    #[test]
+    fn verbatim_round_trips() {
+        for p in [r"C:\a\b", r"\\server\share\x"] {
+            assert_eq!(from_verbatim(&to_verbatim(p)), p);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use sha2::{Digest, Sha256};
+
+/// Short stable tag for a private value, so the same link or handle can still be
+/// followed across log lines without being readable.
+fn tag(value: &str) -> String {
+    let digest = Sha256::digest(value.as_bytes());
+    digest[..4].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// "https://www.tiktok.com/@user/video/1" -> "https://www.tiktok.com/[1a2b3c4d]".
+/// Scheme and host stay so it is still clear which site a line is about.
+fn redact_url(url: &str) -> String {
+    let (scheme, rest) = url.split_once("://").unwrap_or(("", url));
+    match rest.split_once('/') {
+        Some((host, path)) if !path.is_empty() => format!("{scheme}://{host}/[{}]", tag(path)),
+        _ => url.to_string(),
+    }
+}
+
+fn redact_token(token: &str) -> String {
+    if let Some(start) = token.find("http://").or_else(|| token.find("https://")) {
+        let (before, url) = token.split_at(start);
+        return format!("{before}{}", redact_url(url));
+    }
+    if let Some(handle) = token.strip_prefix('@').filter(|h| !h.is_empty()) {
+        return format!("@[{}]", tag(handle));
+    }
+    if let Some(handle) = token.strip_prefix("handle=").filter(|h| !h.is_empty()) {
+        return format!("handle=[{}]", tag(handle));
+    }
+    token.to_string()
+}
+
+/// Replace links, @handles, `handle=` fields and the home directory in free text.
+/// Whitespace, quotes and brackets delimit tokens and are kept as-is.
+pub fn redact(text: &str) -> String {
+    let home = dirs::home_dir()
+        .map(|h| h.to_string_lossy().to_string())
+        .filter(|h| h.len() > 1);
+    let text = match &home {
+        Some(h) => text.replace(h.as_str(), "~"),
+        None => text.to_string(),
+    };
+    let is_delim = |c: char| c.is_whitespace() || "\"'<>()[]{},".contains(c);
+    let mut out = String::with_capacity(text.len());
+    let mut token = String::new();
+    for c in text.chars() {
+        if is_delim(c) {
+            if !token.is_empty() {
+                out.push_str(&redact_token(&token));
+                token.clear();
+            }
+            out.push(c);
+        } else {
+            token.push(c);
+        }
+    }
+    if !token.is_empty() {
+        out.push_str(&redact_token(&token));
+    }
+    out
+}
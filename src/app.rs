@@ -1,11 +1,20 @@
+use crate::components::clipboard_prompt::ClipboardPrompt;
+use crate::components::crash_recovery_prompt::{CrashRecoveryPrompt, RecoveryAnswer};
+use crate::components::csv_import_prompt::CsvImportPrompt;
+use crate::components::profile_picker::ProfilePicker;
 use crate::components::sidebar::Sidebar;
+use crate::events::{subscribe_download_events, DownloadEvent};
 use crate::log;
 use crate::pages;
 use crate::pages::downloads::ActiveDownload;
 use crate::pages::settings::Settings;
-use crate::types::{ClipRow, ContentType, DownloadStatus, Platform};
+use crate::router::{use_router, Page, Route};
+use crate::types::{
+    ClipRow, ContentType, CrashRecovery, CsvPreview, DateRange, DownloadStatus, Platform,
+    PlatformPause, ScheduleStatus, SessionReport, ToolReport, VolumeStatus,
+};
 use std::cell::{Cell, RefCell};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
@@ -25,16 +34,6 @@ pub fn log_invoke_err(cmd: &str, e: JsValue) {
     web_sys::console::error_2(&format!("invoke({cmd}) failed").into(), &e);
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Page {
-    Home,
-    Downloads,
-    Library,
-    Settings,
-    Extension,
-    Sponsor,
-}
-
 #[derive(Clone, Debug, PartialEq)]
 struct DownloadEntry {
     row: ClipRow,
@@ -43,36 +42,98 @@ struct DownloadEntry {
     total_bytes: Option<u64>,
     stage_text: String,
     last_message: Option<String>,
+    rate: RateEstimate,
+}
+
+/// Smoothed download speed from successive progress byte counts, for the ETA.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct RateEstimate {
+    bytes_per_sec: f64,
+    /// (timestamp ms, downloaded bytes) of the last sample used
+    last: Option<(f64, u64)>,
+}
+
+impl RateEstimate {
+    fn sample(&mut self, now_ms: f64, bytes: u64) {
+        match self.last {
+            Some((t, b)) if bytes >= b => {
+                let dt = (now_ms - t) / 1000.0;
+                if dt < 0.5 {
+                    return;
+                }
+                let current = (bytes - b) as f64 / dt;
+                self.bytes_per_sec = if self.bytes_per_sec > 0.0 {
+                    0.7 * self.bytes_per_sec + 0.3 * current
+                } else {
+                    current
+                };
+                self.last = Some((now_ms, bytes));
+            }
+            // First sample, or the byte count went back (next file of a multi-part download).
+            _ => {
+                *self = RateEstimate {
+                    bytes_per_sec: 0.0,
+                    last: Some((now_ms, bytes)),
+                };
+            }
+        }
+    }
+
+    fn eta_secs(&self, downloaded: u64, total: Option<u64>) -> Option<u64> {
+        let remaining = total?.checked_sub(downloaded)?;
+        (self.bytes_per_sec > 0.0).then(|| (remaining as f64 / self.bytes_per_sec).round() as u64)
+    }
 }
 
 fn log_download_snapshot(rows: &[ClipRow]) {
+    let mut cnt_pending = 0usize;
     let mut cnt_backlog = 0usize;
     let mut cnt_queue = 0usize;
     let mut cnt_down = 0usize;
     let mut cnt_done = 0usize;
     let mut cnt_err = 0usize;
     let mut cnt_cancel = 0usize;
+    let mut cnt_invalid = 0usize;
+    let mut cnt_paused = 0usize;
+    let mut cnt_skipped = 0usize;
+    let mut cnt_missing = 0usize;
+    let mut cnt_deleted = 0usize;
     for row in rows {
         match row.status {
+            DownloadStatus::Pending => cnt_pending += 1,
             DownloadStatus::Backlog => cnt_backlog += 1,
             DownloadStatus::Queued => cnt_queue += 1,
             DownloadStatus::Downloading => cnt_down += 1,
             DownloadStatus::Done => cnt_done += 1,
             DownloadStatus::Error => cnt_err += 1,
             DownloadStatus::Canceled => cnt_cancel += 1,
+            DownloadStatus::Invalid => cnt_invalid += 1,
+            DownloadStatus::Paused => cnt_paused += 1,
+            DownloadStatus::Skipped => cnt_skipped += 1,
+            DownloadStatus::Missing => cnt_missing += 1,
+            DownloadStatus::Deleted => cnt_deleted += 1,
         }
     }
-    web_sys::console::log_1(&format!("[UI] list_downloads loaded: backlog={} queue={} downloading={} done={} error={} canceled={}",cnt_backlog, cnt_queue, cnt_down, cnt_done, cnt_err, cnt_cancel).into());
+    web_sys::console::log_1(&format!("[UI] list_downloads loaded: pending={} backlog={} queue={} downloading={} paused={} done={} error={} canceled={} invalid={} skipped={} missing={} deleted={}",cnt_pending, cnt_backlog, cnt_queue, cnt_down, cnt_paused, cnt_done, cnt_err, cnt_cancel, cnt_invalid, cnt_skipped, cnt_missing, cnt_deleted).into());
 }
 
 fn default_stage_text(row: &ClipRow) -> String {
     match row.status {
+        DownloadStatus::Pending => "Pending review".into(),
         DownloadStatus::Backlog => "Backlog".into(),
         DownloadStatus::Queued => "Queued".into(),
         DownloadStatus::Downloading => "Preparing download".into(),
         DownloadStatus::Done => "Done".into(),
         DownloadStatus::Error => row.last_error.clone().unwrap_or_else(|| "Failed".into()),
         DownloadStatus::Canceled => "Canceled".into(),
+        DownloadStatus::Invalid => row.last_error.clone().unwrap_or_else(|| "Dead link".into()),
+        DownloadStatus::Paused => "Paused".into(),
+        DownloadStatus::Skipped => row.last_error.clone().unwrap_or_else(|| "Skipped".into()),
+        DownloadStatus::Missing => row
+            .last_error
+            .clone()
+            .unwrap_or_else(|| "File missing".into()),
+        DownloadStatus::Deleted => "In the trash".into(),
     }
 }
 
@@ -83,7 +144,7 @@ fn merge_download_entries(
     let mut map = HashMap::new();
 
     for row in rows {
-        if matches!(row.status, DownloadStatus::Done | DownloadStatus::Canceled) {
+        if matches!(row.status, DownloadStatus::Done | DownloadStatus::Deleted) {
             continue;
         }
 
@@ -96,6 +157,7 @@ fn merge_download_entries(
             total_bytes: None,
             stage_text: default_stage_text(&row),
             last_message: persisted_error.clone(),
+            rate: RateEstimate::default(),
         });
 
         entry.row = row;
@@ -104,7 +166,7 @@ fn merge_download_entries(
         }
 
         match status {
-            DownloadStatus::Backlog | DownloadStatus::Queued => {
+            DownloadStatus::Pending | DownloadStatus::Backlog | DownloadStatus::Queued => {
                 entry.progress = 0.0;
                 entry.downloaded_bytes = 0;
                 entry.total_bytes = None;
@@ -118,7 +180,15 @@ fn merge_download_entries(
                     entry.stage_text = "Preparing download".into();
                 }
             }
-            DownloadStatus::Error => {
+            // Progress so far stays visible until the download is resumed.
+            DownloadStatus::Paused => {
+                entry.row.last_error = None;
+                entry.stage_text = default_stage_text(&entry.row);
+            }
+            DownloadStatus::Error
+            | DownloadStatus::Invalid
+            | DownloadStatus::Skipped
+            | DownloadStatus::Missing => {
                 entry.progress = 0.0;
                 entry.downloaded_bytes = 0;
                 entry.total_bytes = None;
@@ -131,7 +201,13 @@ fn merge_download_entries(
                     .clone()
                     .unwrap_or_else(|| "Failed".into());
             }
-            DownloadStatus::Done | DownloadStatus::Canceled => {}
+            DownloadStatus::Canceled => {
+                entry.progress = 0.0;
+                entry.downloaded_bytes = 0;
+                entry.total_bytes = None;
+                entry.stage_text = default_stage_text(&entry.row);
+            }
+            DownloadStatus::Done | DownloadStatus::Deleted => {}
         }
 
         map.insert(entry.row.id, entry);
@@ -218,6 +294,14 @@ fn schedule_download_refresh(
 
 thread_local! {
     static LAST_DROP: RefCell<(String, f64)> = RefCell::new(("".to_string(), 0.0));
+    /// "Review before backlog" toggle from the Home page; applies to picker and drop imports.
+    static REVIEW_IMPORTS: Cell<bool> = const { Cell::new(false) };
+}
+pub fn review_imports() -> bool {
+    REVIEW_IMPORTS.with(|c| c.get())
+}
+pub fn set_review_imports(on: bool) {
+    REVIEW_IMPORTS.with(|c| c.set(on));
 }
 fn now_ms() -> f64 {
     js_sys::Date::now()
@@ -238,31 +322,97 @@ fn should_handle_drop(path: &str) -> bool {
     allow
 }
 
-fn spawn_import_from_path(path: String) {
+/// Read a dropped or picked CSV's columns and hand them to the mapping dialog.
+fn spawn_csv_preview(path: Option<String>, on_preview: Callback<CsvPreview>) {
+    spawn_local(async move {
+        let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "path": path })).unwrap();
+        match invoke("preview_csv", args).await {
+            Ok(v) => match serde_wasm_bindgen::from_value::<CsvPreview>(v) {
+                Ok(preview) => on_preview.emit(preview),
+                Err(e) => web_sys::console::error_1(&format!("bad CSV preview: {e}").into()),
+            },
+            Err(e) => log_invoke_err("preview_csv", e),
+        }
+    });
+}
+
+fn spawn_import_from_path(path: String, on_preview: Callback<CsvPreview>) {
     if !should_handle_drop(&path) {
         web_sys::console::log_1(&format!("⏭️ Ignored duplicate drop for {path}").into());
         return;
     }
+    if path.to_lowercase().ends_with(".txt") {
+        log::info("links_drop_request", serde_json::json!({ "path": path }));
+        spawn_local(async move {
+            let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "path": path })).unwrap();
+            match invoke("read_links_from_path", args).await {
+                Ok(_) => web_sys::console::log_1(&"✅ Queued links from dropped list".into()),
+                Err(e) => log_invoke_err("read_links_from_path", e),
+            }
+        });
+        return;
+    }
     log::info("csv_drop_request", serde_json::json!({ "path": path }));
+    spawn_csv_preview(Some(path), on_preview);
+}
+
+/// Links dragged in as text (from a browser's address bar or a page) go to
+/// the same batch path as the Home paste box.
+fn spawn_enqueue_dropped_text(text: String) {
+    if !should_handle_drop(&text) {
+        return;
+    }
+    log::info(
+        "links_drop_text",
+        serde_json::json!({ "lines": text.lines().count() }),
+    );
     spawn_local(async move {
-        let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "path": path })).unwrap();
-        match invoke("read_csv_from_path", args).await {
-            Ok(_) => {
-                log::info("csv_drop_imported", serde_json::json!({ "status": "ok" }));
-                web_sys::console::log_1(&"✅ Imported CSV from drop (backend)".into())
-            }
-            Err(e) => {
-                log::error(
-                    "csv_drop_failed",
-                    serde_json::json!({ "error": format!("{e:?}") }),
-                );
-                log_invoke_err("read_csv_from_path", e)
-            }
+        let args =
+            serde_wasm_bindgen::to_value(&serde_json::json!({ "text": text, "queue": true }))
+                .unwrap();
+        if let Err(e) = invoke("download_urls", args).await {
+            log_invoke_err("download_urls", e);
         }
     });
 }
 
-async fn start_dragdrop_listener() {
+/// Window-wide HTML drop handler for dragged text; file drops come through
+/// the webview's drag-drop event instead.
+fn start_text_drop_listener() {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let on_dragover = Closure::<dyn FnMut(web_sys::DragEvent)>::new(|e: web_sys::DragEvent| {
+        e.prevent_default();
+    });
+    let on_drop = Closure::<dyn FnMut(web_sys::DragEvent)>::new(|e: web_sys::DragEvent| {
+        let Some(data) = e.data_transfer() else {
+            return;
+        };
+        if data.files().is_some_and(|f| f.length() > 0) {
+            return;
+        }
+        e.prevent_default();
+        // text/uri-list may carry "#" comment lines, which download_urls skips
+        let text = data
+            .get_data("text/uri-list")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .or_else(|| data.get_data("text/plain").ok())
+            .unwrap_or_default();
+        if !text.trim().is_empty() {
+            spawn_enqueue_dropped_text(text);
+        }
+    });
+    let _ =
+        window.add_event_listener_with_callback("dragover", on_dragover.as_ref().unchecked_ref());
+    let _ = window.add_event_listener_with_callback("drop", on_drop.as_ref().unchecked_ref());
+    on_dragover.forget();
+    on_drop.forget();
+}
+
+async fn start_dragdrop_listener(on_preview: Callback<CsvPreview>) {
+    start_text_drop_listener();
     web_sys::console::log_1(&"🧩 init drag-drop listener".into());
     let mut attached = false;
 
@@ -271,6 +421,7 @@ async fn start_dragdrop_listener() {
         if let Ok(on_fn) = js_sys::Reflect::get(&webview, &JsValue::from_str("onDragDropEvent")) {
             if on_fn.is_function() {
                 let on = js_sys::Function::from(on_fn);
+                let on_preview = on_preview.clone();
                 let handler = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
                     web_sys::console::log_1(&"🔥 onDragDropEvent fired".into());
                     let payload = js_sys::Reflect::get(&event, &JsValue::from_str("payload"))
@@ -286,7 +437,7 @@ async fn start_dragdrop_listener() {
                             let arr = js_sys::Array::from(&paths);
                             if arr.length() > 0 {
                                 if let Some(path) = arr.get(0).as_string() {
-                                    spawn_import_from_path(path);
+                                    spawn_import_from_path(path, on_preview.clone());
                                 }
                             }
                         }
@@ -313,7 +464,7 @@ async fn start_dragdrop_listener() {
                         let arr = js_sys::Array::from(&paths);
                         if arr.length() > 0 {
                             if let Some(path) = arr.get(0).as_string() {
-                                spawn_import_from_path(path);
+                                spawn_import_from_path(path, on_preview.clone());
                             }
                         }
                     }
@@ -343,29 +494,71 @@ pub enum MoveBackItem {
     Row(String),
 }
 
+/// Queue reorder from the Downloads page (row id first).
+pub enum QueueMove {
+    Before(i64, i64),
+    After(i64, i64),
+    /// Row id and the priority that puts it ahead of everything queued
+    Top(i64, i64),
+}
+
+pub enum ReviewItem {
+    Approve(Platform, String, ContentType),
+    Reject(Platform, String, ContentType),
+}
+
 #[function_component(App)]
 pub fn app() -> Html {
-    let page = use_state(|| Page::Home);
+    let router = use_router(Page::Home);
     let settings = use_state(Settings::default);
 
     let downloads = use_state(HashMap::<i64, DownloadEntry>::new);
     let downloads_ref = use_mut_ref(HashMap::<i64, DownloadEntry>::new);
+    // Ids with an enqueue request still in flight, so double clicks don't resend them.
+    let enqueue_in_flight = use_mut_ref(HashSet::<i64>::new);
     let downloads_ready = use_state(|| false);
     let paused = use_state(|| false);
+    // publish-date window applied when moving backlog rows to the queue
+    let queue_range = use_state(DateRange::default);
+    let session_report = use_state(|| None::<SessionReport>);
+    let schedule = use_state(|| None::<ScheduleStatus>);
+    let volume = use_state(VolumeStatus::default);
+    let paused_platforms = use_state(Vec::<PlatformPause>::new);
+    let tool_report = use_state(ToolReport::default);
+    let show_profile_picker = use_state(|| false);
+    let crash_report = use_state(|| None::<CrashRecovery>);
 
     {
         let settings = settings.clone();
+        let show_profile_picker = show_profile_picker.clone();
+        let crash_report = crash_report.clone();
+        let router = router.clone();
         use_effect_with((), move |_| {
             spawn_local(async move {
                 if let Ok(loaded) = invoke("load_settings", JsValue::NULL).await {
                     if let Ok(s) = serde_wasm_bindgen::from_value::<Settings>(loaded) {
                         settings.set(s.clone());
-                        let paused = !s.download_automatically;
-                        let args =
-                            serde_wasm_bindgen::to_value(&serde_json::json!({ "paused": paused }))
-                                .unwrap();
-                        let _ = invoke("set_download_paused", args).await;
-                        let _ = invoke("refresh_download_settings", JsValue::NULL).await;
+                        // An `--open` launch argument wins over the startup page.
+                        let launch = invoke("launch_route", JsValue::NULL)
+                            .await
+                            .ok()
+                            .and_then(|v| v.as_string())
+                            .and_then(|path| Route::parse(&path));
+                        match launch {
+                            Some(route) => router.go(route),
+                            None if s.startup_page != Page::Home => router.go(s.startup_page),
+                            None => {}
+                        }
+                        // Downloads stay paused until the person at the keyboard picks a profile.
+                        let ask = invoke("startup_profile_prompt", JsValue::NULL)
+                            .await
+                            .map(|v| !v.is_null() && !v.is_undefined())
+                            .unwrap_or(false);
+                        if ask {
+                            show_profile_picker.set(true);
+                        } else {
+                            continue_startup(&s, crash_report).await;
+                        }
                     }
                 }
             });
@@ -373,6 +566,33 @@ pub fn app() -> Html {
         });
     }
 
+    let on_profile_picker_close = {
+        let settings = settings.clone();
+        let show_profile_picker = show_profile_picker.clone();
+        let crash_report = crash_report.clone();
+        Callback::from(move |_: ()| {
+            show_profile_picker.set(false);
+            let s = (*settings).clone();
+            let crash_report = crash_report.clone();
+            spawn_local(async move {
+                continue_startup(&s, crash_report).await;
+            });
+        })
+    };
+
+    let on_crash_answer = {
+        let crash_report = crash_report.clone();
+        let paused = paused.clone();
+        let router = router.clone();
+        Callback::from(move |answer: RecoveryAnswer| {
+            crash_report.set(None);
+            paused.set(answer != RecoveryAnswer::Resume);
+            if answer == RecoveryAnswer::Inspect {
+                router.go(Page::Downloads);
+            }
+        })
+    };
+
     {
         let paused_state = paused.clone();
         use_effect_with(settings.download_automatically, move |auto| {
@@ -399,7 +619,7 @@ pub fn app() -> Html {
         let downloads = downloads.clone();
         let downloads_ref = downloads_ref.clone();
         let downloads_ready = downloads_ready.clone();
-        use_effect_with(*page, move |p| {
+        use_effect_with(router.page(), move |p| {
             if *p == Page::Downloads {
                 spawn_refresh_downloads(
                     downloads.clone(),
@@ -418,164 +638,172 @@ pub fn app() -> Html {
         use_effect_with((), move |_| {
             let refresh_pending = Rc::new(Cell::new(false));
 
-            spawn_local(async move {
-                #[derive(serde::Deserialize, Debug)]
-                #[serde(tag = "type")]
-                enum DownloadEventPayload {
-                    StatusChanged {
-                        id: i64,
-                        status: DownloadStatus,
-                    },
-                    BulkStatusChanged {
-                        ids: Vec<i64>,
-                        status: DownloadStatus,
-                    },
-                    Progress {
-                        id: i64,
-                        progress: f32,
-                        downloaded_bytes: u64,
-                        total_bytes: Option<u64>,
-                    },
-                    Message {
-                        id: i64,
-                        message: String,
-                    },
-                }
+            let subscription = subscribe_download_events(move |evt| {
+                let mut map = downloads_ref.borrow().clone();
+                let mut commit = false;
+                let mut should_refresh = false;
 
-                let handler = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
-                    let payload = js_sys::Reflect::get(&event, &JsValue::from_str("payload"))
-                        .unwrap_or(event.clone());
-
-                    if let Ok(evt) = serde_wasm_bindgen::from_value::<DownloadEventPayload>(payload)
-                    {
-                        let mut map = downloads_ref.borrow().clone();
-                        let mut commit = false;
-                        let mut should_refresh = false;
-
-                        match evt {
-                            DownloadEventPayload::BulkStatusChanged { ids, status } => {
-                                for id in &ids {
-                                    if let Some(entry) = map.get_mut(id) {
-                                        entry.row.status = status;
-                                        entry.row.last_error = None;
-                                        entry.progress = 0.0;
-                                        entry.downloaded_bytes = 0;
-                                        entry.total_bytes = None;
-                                        entry.stage_text = default_stage_text(&entry.row);
-                                        entry.last_message = None;
-                                    }
+                match evt {
+                    DownloadEvent::BulkStatusChanged { ids, status } => {
+                        for id in &ids {
+                            if let Some(entry) = map.get_mut(id) {
+                                entry.row.status = status;
+                                entry.row.last_error = None;
+                                entry.progress = 0.0;
+                                entry.downloaded_bytes = 0;
+                                entry.total_bytes = None;
+                                entry.stage_text = default_stage_text(&entry.row);
+                                entry.last_message = None;
+                            }
+                        }
+                        commit = true;
+                        should_refresh = true;
+                    }
+                    DownloadEvent::StatusChanged { id, status } => match status {
+                        DownloadStatus::Done | DownloadStatus::Deleted => {
+                            if map.remove(&id).is_none() {
+                                should_refresh = true;
+                            } else {
+                                commit = true;
+                            }
+                            should_refresh = true;
+                        }
+                        DownloadStatus::Error => {
+                            if let Some(entry) = map.get_mut(&id) {
+                                entry.row.status = DownloadStatus::Error;
+                                if entry.row.last_error.is_none() {
+                                    entry.row.last_error = entry.last_message.clone();
                                 }
+                                entry.progress = 0.0;
+                                entry.downloaded_bytes = 0;
+                                entry.total_bytes = None;
+                                entry.stage_text = entry
+                                    .row
+                                    .last_error
+                                    .clone()
+                                    .unwrap_or_else(|| "Failed".into());
                                 commit = true;
+                            } else {
                                 should_refresh = true;
                             }
-                            DownloadEventPayload::StatusChanged { id, status } => match status {
-                                DownloadStatus::Done | DownloadStatus::Canceled => {
-                                    if map.remove(&id).is_none() {
-                                        should_refresh = true;
-                                    } else {
-                                        commit = true;
-                                    }
-                                    should_refresh = true;
-                                }
-                                DownloadStatus::Error => {
-                                    if let Some(entry) = map.get_mut(&id) {
-                                        entry.row.status = DownloadStatus::Error;
-                                        if entry.row.last_error.is_none() {
-                                            entry.row.last_error = entry.last_message.clone();
-                                        }
-                                        entry.progress = 0.0;
-                                        entry.downloaded_bytes = 0;
-                                        entry.total_bytes = None;
-                                        entry.stage_text = entry
-                                            .row
-                                            .last_error
-                                            .clone()
-                                            .unwrap_or_else(|| "Failed".into());
-                                        commit = true;
-                                    } else {
-                                        should_refresh = true;
-                                    }
-                                    should_refresh = true;
-                                }
-                                DownloadStatus::Backlog | DownloadStatus::Queued => {
-                                    if let Some(entry) = map.get_mut(&id) {
-                                        entry.row.status = status;
-                                        entry.row.last_error = None;
-                                        entry.progress = 0.0;
-                                        entry.downloaded_bytes = 0;
-                                        entry.total_bytes = None;
-                                        entry.stage_text = default_stage_text(&entry.row);
-                                        entry.last_message = None;
-                                        commit = true;
-                                    } else {
-                                        should_refresh = true;
-                                    }
-                                }
-                                DownloadStatus::Downloading => {
-                                    if let Some(entry) = map.get_mut(&id) {
-                                        entry.row.status = DownloadStatus::Downloading;
-                                        entry.row.last_error = None;
-                                        entry.stage_text = "Preparing download".into();
-                                        entry.last_message = None;
-                                        commit = true;
-                                    } else {
-                                        should_refresh = true;
-                                    }
-                                }
-                            },
-                            DownloadEventPayload::Progress {
-                                id,
-                                progress,
-                                downloaded_bytes,
-                                total_bytes,
-                            } => {
-                                if let Some(entry) = map.get_mut(&id) {
-                                    entry.row.status = DownloadStatus::Downloading;
-                                    entry.progress = progress;
-                                    entry.downloaded_bytes = downloaded_bytes;
-                                    entry.total_bytes = total_bytes;
-                                    if progress > 0.0 {
-                                        entry.stage_text = "Downloading".into();
-                                    }
-                                    commit = true;
-                                }
+                            should_refresh = true;
+                        }
+                        // The reload picks up the stored reason.
+                        DownloadStatus::Invalid
+                        | DownloadStatus::Skipped
+                        | DownloadStatus::Missing
+                        | DownloadStatus::Canceled => should_refresh = true,
+                        DownloadStatus::Pending
+                        | DownloadStatus::Backlog
+                        | DownloadStatus::Queued => {
+                            if let Some(entry) = map.get_mut(&id) {
+                                entry.row.status = status;
+                                entry.row.last_error = None;
+                                entry.progress = 0.0;
+                                entry.downloaded_bytes = 0;
+                                entry.total_bytes = None;
+                                entry.stage_text = default_stage_text(&entry.row);
+                                entry.last_message = None;
+                                commit = true;
+                            } else {
+                                should_refresh = true;
                             }
-                            DownloadEventPayload::Message { id, message } => {
-                                log::info(
-                                    "download_event_message",
-                                    serde_json::json!({ "id": id, "message": message.clone() }),
-                                );
-                                if let Some(entry) = map.get_mut(&id) {
-                                    entry.last_message = Some(message.clone());
-                                    if entry.row.status == DownloadStatus::Error {
-                                        entry.row.last_error = Some(message.clone());
-                                        entry.stage_text = message;
-                                    } else {
-                                        entry.stage_text = summarize_download_message(&message);
-                                    }
-                                    commit = true;
-                                }
+                        }
+                        DownloadStatus::Downloading => {
+                            if let Some(entry) = map.get_mut(&id) {
+                                entry.row.status = DownloadStatus::Downloading;
+                                entry.row.last_error = None;
+                                entry.stage_text = "Preparing download".into();
+                                entry.last_message = None;
+                                commit = true;
+                            } else {
+                                should_refresh = true;
                             }
                         }
-
-                        if commit {
-                            commit_download_map(&downloads, &downloads_ref, map);
+                        DownloadStatus::Paused => {
+                            if let Some(entry) = map.get_mut(&id) {
+                                entry.row.status = DownloadStatus::Paused;
+                                entry.stage_text = "Paused".into();
+                                entry.rate = RateEstimate::default();
+                                commit = true;
+                            } else {
+                                should_refresh = true;
+                            }
+                        }
+                    },
+                    DownloadEvent::Progress {
+                        id,
+                        progress,
+                        downloaded_bytes,
+                        total_bytes,
+                    } => {
+                        if let Some(entry) = map.get_mut(&id) {
+                            entry.row.status = DownloadStatus::Downloading;
+                            entry.progress = progress;
+                            entry.downloaded_bytes = downloaded_bytes;
+                            entry.total_bytes = total_bytes;
+                            if downloaded_bytes > 0 {
+                                entry.rate.sample(js_sys::Date::now(), downloaded_bytes);
+                            }
+                            if progress > 0.0 {
+                                entry.stage_text = "Downloading".into();
+                            }
+                            commit = true;
                         }
-                        if should_refresh {
-                            schedule_download_refresh(
-                                refresh_pending.clone(),
-                                downloads.clone(),
-                                downloads_ref.clone(),
-                                downloads_ready.clone(),
-                            );
+                    }
+                    DownloadEvent::Message { id, message, .. } => {
+                        log::info(
+                            "download_event_message",
+                            serde_json::json!({ "id": id, "message": message.clone() }),
+                        );
+                        if let Some(entry) = map.get_mut(&id) {
+                            entry.last_message = Some(message.clone());
+                            if entry.row.status == DownloadStatus::Error {
+                                entry.row.last_error = Some(message.clone());
+                                entry.stage_text = message;
+                            } else {
+                                entry.stage_text = summarize_download_message(&message);
+                            }
+                            commit = true;
                         }
                     }
-                });
+                    DownloadEvent::MetadataUpdated {
+                        id,
+                        title,
+                        duration_secs,
+                        thumbnail_url,
+                        estimated_bytes,
+                    } => {
+                        if let Some(entry) = map.get_mut(&id) {
+                            entry.row.estimated_bytes = estimated_bytes;
+                            entry.row.title = title;
+                            entry.row.duration_secs = duration_secs;
+                            entry.row.thumbnail_url = thumbnail_url;
+                            commit = true;
+                        }
+                    }
+                    DownloadEvent::MirrorFound { id, url, source } => {
+                        if let Some(entry) = map.get_mut(&id) {
+                            entry.row.mirror_url = Some(url);
+                            entry.row.mirror_source = Some(source);
+                            commit = true;
+                        }
+                    }
+                }
 
-                let _ = listen("download_event", &handler).await;
-                handler.forget();
+                if commit {
+                    commit_download_map(&downloads, &downloads_ref, map);
+                }
+                if should_refresh {
+                    schedule_download_refresh(
+                        refresh_pending.clone(),
+                        downloads.clone(),
+                        downloads_ref.clone(),
+                        downloads_ready.clone(),
+                    );
+                }
             });
-            || ()
+            move || drop(subscription)
         });
     }
 
@@ -602,6 +830,163 @@ pub fn app() -> Html {
         });
     }
 
+    {
+        let session_report = session_report.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                if let Ok(v) = invoke("latest_session_report", JsValue::NULL).await {
+                    if let Ok(report) = serde_wasm_bindgen::from_value::<Option<SessionReport>>(v) {
+                        session_report.set(report);
+                    }
+                }
+                let handler = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+                    let payload = js_sys::Reflect::get(&event, &JsValue::from_str("payload"))
+                        .unwrap_or(JsValue::NULL);
+                    if let Ok(report) = serde_wasm_bindgen::from_value::<SessionReport>(payload) {
+                        session_report.set(Some(report));
+                    }
+                });
+                let _ = listen("session_report", &handler).await;
+                handler.forget();
+            });
+            || ()
+        });
+    }
+
+    {
+        let schedule = schedule.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                if let Ok(v) = invoke("download_schedule_status", JsValue::NULL).await {
+                    if let Ok(status) = serde_wasm_bindgen::from_value::<ScheduleStatus>(v) {
+                        schedule.set(Some(status));
+                    }
+                }
+                let handler = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+                    let payload = js_sys::Reflect::get(&event, &JsValue::from_str("payload"))
+                        .unwrap_or(JsValue::NULL);
+                    if let Ok(status) = serde_wasm_bindgen::from_value::<ScheduleStatus>(payload) {
+                        schedule.set(Some(status));
+                    }
+                });
+                let _ = listen("download_schedule", &handler).await;
+                handler.forget();
+            });
+            || ()
+        });
+    }
+
+    {
+        let volume = volume.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                if let Ok(v) = invoke("download_volume_status", JsValue::NULL).await {
+                    if let Ok(status) = serde_wasm_bindgen::from_value::<VolumeStatus>(v) {
+                        volume.set(status);
+                    }
+                }
+                let handler = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+                    let payload = js_sys::Reflect::get(&event, &JsValue::from_str("payload"))
+                        .unwrap_or(JsValue::NULL);
+                    if let Ok(status) = serde_wasm_bindgen::from_value::<VolumeStatus>(payload) {
+                        volume.set(status);
+                    }
+                });
+                let _ = listen("download_volume", &handler).await;
+                handler.forget();
+            });
+            || ()
+        });
+    }
+
+    {
+        let router = router.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                let handler = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+                    let payload = js_sys::Reflect::get(&event, &JsValue::from_str("payload"))
+                        .unwrap_or(JsValue::NULL);
+                    if let Some(path) = payload.as_string() {
+                        router.open(&path);
+                    }
+                });
+                let _ = listen("navigate", &handler).await;
+                handler.forget();
+            });
+            || ()
+        });
+    }
+
+    {
+        // The tray menu can pause or resume the queue too.
+        let paused = paused.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                let handler = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+                    let payload = js_sys::Reflect::get(&event, &JsValue::from_str("payload"))
+                        .unwrap_or(JsValue::NULL);
+                    if let Some(next) = payload.as_bool() {
+                        paused.set(next);
+                    }
+                });
+                let _ = listen("queue_paused", &handler).await;
+                handler.forget();
+            });
+            || ()
+        });
+    }
+
+    {
+        let paused_platforms = paused_platforms.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                if let Ok(v) = invoke("paused_platforms", JsValue::NULL).await {
+                    if let Ok(list) = serde_wasm_bindgen::from_value::<Vec<PlatformPause>>(v) {
+                        paused_platforms.set(list);
+                    }
+                }
+                let handler = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+                    let payload = js_sys::Reflect::get(&event, &JsValue::from_str("payload"))
+                        .unwrap_or(JsValue::NULL);
+                    if let Ok(list) = serde_wasm_bindgen::from_value::<Vec<PlatformPause>>(payload)
+                    {
+                        paused_platforms.set(list);
+                    }
+                });
+                let _ = listen("paused_platforms", &handler).await;
+                handler.forget();
+            });
+            || ()
+        });
+    }
+
+    {
+        let tool_report = tool_report.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                if let Ok(v) = invoke("tool_report", JsValue::NULL).await {
+                    if let Ok(report) = serde_wasm_bindgen::from_value::<ToolReport>(v) {
+                        tool_report.set(report);
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_dismiss_report = {
+        let session_report = session_report.clone();
+        Callback::from(move |id: i64| {
+            session_report.set(None);
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "id": id })).unwrap();
+                if let Err(e) = invoke("dismiss_session_report", args).await {
+                    log_invoke_err("dismiss_session_report", e);
+                }
+            });
+        })
+    };
+
     let on_toggle_pause = {
         let paused_state = paused.clone();
         Callback::from(move |_| {
@@ -630,6 +1015,8 @@ pub fn app() -> Html {
 
     let on_move_to_queue = {
         let downloads_ref = downloads_ref.clone();
+        let enqueue_in_flight = enqueue_in_flight.clone();
+        let range = (*queue_range).clone();
         Callback::from(move |item: crate::app::MoveItem| {
             let ids: Vec<i64> = downloads_ref
                 .borrow()
@@ -637,22 +1024,19 @@ pub fn app() -> Html {
                 .filter(|entry| {
                     entry.row.status == DownloadStatus::Backlog
                         && matches_move_item(&entry.row, &item)
+                        && range.contains(entry.row.upload_date.as_deref())
                 })
                 .map(|entry| entry.row.id)
                 .collect();
-            if ids.is_empty() {
-                return;
-            }
-            spawn_local(async move {
-                let args =
-                    serde_wasm_bindgen::to_value(&serde_json::json!({ "ids": ids })).unwrap();
-                if let Err(e) = invoke("enqueue_downloads", args).await {
-                    log_invoke_err("enqueue_downloads", e);
-                }
-            });
+            spawn_enqueue(ids, enqueue_in_flight.clone());
         })
     };
 
+    let on_queue_range = {
+        let queue_range = queue_range.clone();
+        Callback::from(move |range: DateRange| queue_range.set(range))
+    };
+
     let on_move_to_backlog = {
         let downloads_ref = downloads_ref.clone();
         Callback::from(move |item: crate::app::MoveBackItem| {
@@ -683,33 +1067,127 @@ pub fn app() -> Html {
         })
     };
 
-    let on_retry_issue = Callback::from(move |id: i64| {
+    let on_reorder = {
+        let downloads = downloads.clone();
+        let downloads_ref = downloads_ref.clone();
+        let downloads_ready = downloads_ready.clone();
+        Callback::from(move |m: QueueMove| {
+            let downloads = downloads.clone();
+            let downloads_ref = downloads_ref.clone();
+            let downloads_ready = downloads_ready.clone();
+            spawn_local(async move {
+                let (cmd, args) = match m {
+                    QueueMove::Before(id, anchor) => (
+                        "move_queued_download",
+                        serde_json::json!({ "id": id, "anchor": anchor, "after": false }),
+                    ),
+                    QueueMove::After(id, anchor) => (
+                        "move_queued_download",
+                        serde_json::json!({ "id": id, "anchor": anchor, "after": true }),
+                    ),
+                    QueueMove::Top(id, priority) => (
+                        "set_download_priority",
+                        serde_json::json!({ "id": id, "priority": priority }),
+                    ),
+                };
+                let args = serde_wasm_bindgen::to_value(&args).unwrap();
+                match invoke(cmd, args).await {
+                    Ok(_) => spawn_refresh_downloads(downloads, downloads_ref, downloads_ready),
+                    Err(e) => log_invoke_err(cmd, e),
+                }
+            });
+        })
+    };
+
+    let on_retry_issue = {
+        let enqueue_in_flight = enqueue_in_flight.clone();
+        Callback::from(move |id: i64| spawn_enqueue(vec![id], enqueue_in_flight.clone()))
+    };
+
+    // Per-download pause; the queue as a whole keeps running.
+    let set_download_paused = |cmd: &'static str| {
+        Callback::from(move |id: i64| {
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "id": id })).unwrap();
+                let _ = invoke(cmd, args).await;
+            });
+        })
+    };
+    let on_pause_download = set_download_paused("pause_download");
+    let on_resume_download = set_download_paused("resume_download");
+
+    let on_review_pending = {
+        let downloads = downloads.clone();
+        let downloads_ref = downloads_ref.clone();
+        let downloads_ready = downloads_ready.clone();
+        Callback::from(move |item: ReviewItem| {
+            let (cmd, platform, handle, ctype) = match item {
+                ReviewItem::Approve(p, h, t) => ("approve_pending_collection", p, h, t),
+                ReviewItem::Reject(p, h, t) => ("reject_pending_collection", p, h, t),
+            };
+            log::info(
+                "pending_review",
+                serde_json::json!({ "action": cmd, "platform": platform.as_str(), "handle": handle }),
+            );
+            let downloads = downloads.clone();
+            let downloads_ref = downloads_ref.clone();
+            let downloads_ready = downloads_ready.clone();
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&serde_json::json!({
+                    "platform": platform.as_str(),
+                    "handle": handle,
+                    "contentType": ctype.as_str(),
+                }))
+                .unwrap();
+                if let Err(e) = invoke(cmd, args).await {
+                    log_invoke_err(cmd, e);
+                }
+                spawn_refresh_downloads(downloads, downloads_ref, downloads_ready);
+            });
+        })
+    };
+
+    let on_queue_new = Callback::from(move |_: ()| {
         spawn_local(async move {
-            let args =
-                serde_wasm_bindgen::to_value(&serde_json::json!({ "ids": vec![id] })).unwrap();
-            if let Err(e) = invoke("enqueue_downloads", args).await {
-                log_invoke_err("enqueue_downloads", e);
+            match invoke("enqueue_new_since", JsValue::NULL).await {
+                Ok(n) => log::info(
+                    "queue_new_since",
+                    serde_json::json!({ "queued": n.as_f64().unwrap_or(0.0) }),
+                ),
+                Err(e) => log_invoke_err("enqueue_new_since", e),
             }
         });
     });
 
+    // CSV waiting for its columns to be mapped
+    let csv_preview = use_state(|| None::<CsvPreview>);
+    let on_csv_preview = {
+        let csv_preview = csv_preview.clone();
+        Callback::from(move |preview: CsvPreview| csv_preview.set(Some(preview)))
+    };
+    let on_csv_import_close = {
+        let csv_preview = csv_preview.clone();
+        Callback::from(move |_: ()| csv_preview.set(None))
+    };
+
     let on_csv_load = Callback::from(move |_csv_text: String| {});
-    let on_open_file = Callback::from(move |_: ()| {
-        spawn_local(async move {
-            match invoke("pick_csv_and_read", JsValue::NULL).await {
-                Ok(_) => web_sys::console::log_1(&"✅ Imported CSV from picker (backend)".into()),
-                Err(e) => log_invoke_err("pick_csv_and_read", e),
-            }
-        });
-    });
+    let on_open_file = {
+        let on_csv_preview = on_csv_preview.clone();
+        Callback::from(move |_: ()| spawn_csv_preview(None, on_csv_preview.clone()))
+    };
 
     {
         use_effect_with((), move |_| {
-            spawn_local(start_dragdrop_listener());
+            spawn_local(start_dragdrop_listener(on_csv_preview));
             || ()
         });
     }
 
+    let pending_rows_vec: Vec<ClipRow> = (*downloads)
+        .values()
+        .filter(|entry| entry.row.status == DownloadStatus::Pending)
+        .map(|entry| entry.row.clone())
+        .collect();
     let backlog_rows_vec: Vec<ClipRow> = (*downloads)
         .values()
         .filter(|entry| entry.row.status == DownloadStatus::Backlog)
@@ -722,7 +1200,16 @@ pub fn app() -> Html {
         .collect();
     let issue_rows_vec: Vec<ClipRow> = (*downloads)
         .values()
-        .filter(|entry| entry.row.status == DownloadStatus::Error)
+        .filter(|entry| {
+            matches!(
+                entry.row.status,
+                DownloadStatus::Error
+                    | DownloadStatus::Canceled
+                    | DownloadStatus::Invalid
+                    | DownloadStatus::Skipped
+                    | DownloadStatus::Missing
+            )
+        })
         .map(|entry| {
             let mut row = entry.row.clone();
             if row.last_error.is_none() {
@@ -733,7 +1220,12 @@ pub fn app() -> Html {
         .collect();
     let active_downloads_vec: Vec<ActiveDownload> = (*downloads)
         .values()
-        .filter(|entry| entry.row.status == DownloadStatus::Downloading)
+        .filter(|entry| {
+            matches!(
+                entry.row.status,
+                DownloadStatus::Downloading | DownloadStatus::Paused
+            )
+        })
         .map(|entry| ActiveDownload {
             row: entry.row.clone(),
             progress: if entry.progress > 0.0 {
@@ -741,17 +1233,25 @@ pub fn app() -> Html {
             } else {
                 None
             },
+            fraction: (entry.progress > 0.0).then_some(entry.progress),
+            downloaded_bytes: entry.downloaded_bytes,
+            total_bytes: entry.total_bytes,
+            eta_secs: entry
+                .rate
+                .eta_secs(entry.downloaded_bytes, entry.total_bytes),
             stage: entry.stage_text.clone(),
         })
         .collect();
 
-    let body = match *page {
+    let route = router.route();
+    let body = match route.page {
         Page::Home => {
             html! { <pages::home::HomePage on_open_file={on_open_file} on_csv_load={on_csv_load.clone()} /> }
         }
         Page::Downloads => {
             html! {
                 <pages::downloads::DownloadsPage
+                    pending={pending_rows_vec}
                     backlog={backlog_rows_vec}
                     queue={queue_rows_vec}
                     issues={issue_rows_vec}
@@ -761,18 +1261,110 @@ pub fn app() -> Html {
                     on_toggle_pause={on_toggle_pause}
                     on_delete={on_delete}
                     on_move_to_queue={on_move_to_queue}
+                    queue_range={(*queue_range).clone()}
+                    on_queue_range={on_queue_range}
                     on_move_to_backlog={on_move_to_backlog}
                     on_retry_issue={on_retry_issue}
+                    on_pause_download={on_pause_download}
+                    on_resume_download={on_resume_download}
+                    on_review_pending={on_review_pending}
+                    on_queue_new={on_queue_new}
+                    on_reorder={on_reorder}
+                    session_report={(*session_report).clone()}
+                    schedule={(*schedule).clone()}
+                    volume={(*volume).clone()}
+                    paused_platforms={(*paused_platforms).clone()}
+                    advisories={tool_report.advisories.clone()}
+                    on_dismiss_report={on_dismiss_report}
+                    focus={route.item}
                 />
             }
         }
-        Page::Library => html! { <pages::library::LibraryPage /> },
+        Page::Library => html! { <pages::library::LibraryPage volume={(*volume).clone()} /> },
+        Page::Stats => html! { <pages::stats::StatsPage /> },
         Page::Settings => html! { <pages::settings::SettingsPage /> },
         Page::Extension => html! { <pages::extension::ExtensionPage /> },
         Page::Sponsor => html! { <pages::sponsor::SponsorPage /> },
     };
 
-    html! { <><Sidebar page={page} />{ body }</> }
+    html! {
+        <>
+            <Sidebar router={router.clone()} />
+            { body }
+            <ClipboardPrompt />
+            {
+                if *show_profile_picker {
+                    html! { <ProfilePicker on_close={on_profile_picker_close} /> }
+                } else { html! {} }
+            }
+            {
+                if let Some(preview) = (*csv_preview).clone() {
+                    html! { <CsvImportPrompt {preview} review={review_imports()} on_close={on_csv_import_close} /> }
+                } else { html! {} }
+            }
+            {
+                if let Some(report) = (*crash_report).clone() {
+                    html! { <CrashRecoveryPrompt {report} on_answer={on_crash_answer} /> }
+                } else { html! {} }
+            }
+        </>
+    }
+}
+
+/// Once the profile is settled: offer recovery when the last run crashed,
+/// else start or hold the queue per settings.
+async fn continue_startup(s: &Settings, crash_report: UseStateHandle<Option<CrashRecovery>>) {
+    let pending = invoke("crash_recovery", JsValue::NULL)
+        .await
+        .ok()
+        .and_then(|v| serde_wasm_bindgen::from_value::<Option<CrashRecovery>>(v).ok())
+        .flatten();
+    match pending {
+        Some(report) => crash_report.set(Some(report)),
+        None => apply_download_autostart(s).await,
+    }
+}
+
+/// Start or hold the queue per the "download automatically" setting.
+async fn apply_download_autostart(s: &Settings) {
+    let paused = !s.download_automatically;
+    let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "paused": paused })).unwrap();
+    let _ = invoke("set_download_paused", args).await;
+    let _ = invoke("refresh_download_settings", JsValue::NULL).await;
+}
+
+/// Ask the backend to queue `ids`, skipping any whose earlier request hasn't
+/// come back yet. The backend answers with the ids it actually queued.
+fn spawn_enqueue(ids: Vec<i64>, in_flight: Rc<RefCell<HashSet<i64>>>) {
+    let ids: Vec<i64> = {
+        let mut pending = in_flight.borrow_mut();
+        ids.into_iter().filter(|id| pending.insert(*id)).collect()
+    };
+    if ids.is_empty() {
+        return;
+    }
+    spawn_local(async move {
+        let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "ids": ids })).unwrap();
+        match invoke("enqueue_downloads", args).await {
+            Ok(v) => {
+                let queued: Vec<i64> = serde_wasm_bindgen::from_value(v).unwrap_or_default();
+                if queued.len() < ids.len() {
+                    log::info(
+                        "enqueue_skipped",
+                        serde_json::json!({
+                            "requested": ids.len(),
+                            "queued": queued.len(),
+                        }),
+                    );
+                }
+            }
+            Err(e) => log_invoke_err("enqueue_downloads", e),
+        }
+        let mut pending = in_flight.borrow_mut();
+        for id in &ids {
+            pending.remove(id);
+        }
+    });
 }
 
 fn matches_delete_item(row: &ClipRow, item: &DeleteItem) -> bool {
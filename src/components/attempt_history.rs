@@ -0,0 +1,78 @@
+use crate::types::DownloadAttempt;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+}
+
+#[derive(Properties, PartialEq)]
+pub struct AttemptHistoryProps {
+    pub download_id: i64,
+    /// Start expanded, e.g. when linked to directly
+    #[prop_or_default]
+    pub open: bool,
+}
+
+/// "Details" expander of a failed row: every recorded tool run with the
+/// browser cookies used, exit code and the end of the tool's output. Loaded
+/// when opened.
+#[function_component(AttemptHistory)]
+pub fn attempt_history(props: &AttemptHistoryProps) -> Html {
+    let attempts = use_state(|| None::<Vec<DownloadAttempt>>);
+
+    let on_toggle = {
+        let attempts = attempts.clone();
+        let id = props.download_id;
+        Callback::from(move |e: Event| {
+            let open = e
+                .target_unchecked_into::<web_sys::Element>()
+                .has_attribute("open");
+            if !open {
+                return;
+            }
+            let attempts = attempts.clone();
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "id": id })).unwrap();
+                let v = invoke("get_download_history", args).await;
+                attempts.set(Some(serde_wasm_bindgen::from_value(v).unwrap_or_default()));
+            });
+        })
+    };
+
+    let body = match &*attempts {
+        None => html! { <p class="attempt-empty">{"Loading…"}</p> },
+        Some(list) if list.is_empty() => {
+            html! { <p class="attempt-empty">{"No attempts recorded yet."}</p> }
+        }
+        Some(list) => html! {
+            <ol class="attempt-list">
+                { for list.iter().map(|a| {
+                    let when = a.attempted_at.get(..19).unwrap_or(&a.attempted_at).replace('T', " ");
+                    let cookies = a.browser.as_deref().map(|b| format!(" · {b} cookies")).unwrap_or_default();
+                    let exit = a.exit_code.map_or_else(|| "no exit code".to_string(), |c| format!("exit {c}"));
+                    html! {
+                        <li key={a.id} class={classes!("attempt", (!a.ok).then_some("failed"))}>
+                            <div class="attempt-head">
+                                { format!("{when} UTC · {}{cookies} · {exit} · {}", a.tool, if a.ok { "ok" } else { "failed" }) }
+                            </div>
+                            if !a.output_tail.is_empty() {
+                                <pre class="attempt-output">{ a.output_tail.clone() }</pre>
+                            }
+                        </li>
+                    }
+                }) }
+            </ol>
+        },
+    };
+
+    html! {
+        <details class="attempt-history" open={props.open} ontoggle={on_toggle}>
+            <summary>{"Details"}</summary>
+            { body }
+        </details>
+    }
+}
@@ -0,0 +1,107 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "event"])]
+    async fn listen(event: &str, f: &Closure<dyn FnMut(JsValue)>) -> JsValue;
+}
+
+type Listener = (js_sys::Function, Closure<dyn FnMut(JsValue)>);
+
+/// "Add to queue?" toast for supported links copied anywhere on the system,
+/// shown when the clipboard watcher is set to ask. Links copied while it is
+/// open are added to the same prompt.
+#[function_component(ClipboardPrompt)]
+pub fn clipboard_prompt() -> Html {
+    // Outlives the listener's captured handles, so back-to-back copies accumulate.
+    let links = use_mut_ref(Vec::<String>::new);
+    let redraw = use_force_update();
+
+    {
+        let links = links.clone();
+        let redraw = redraw.clone();
+        use_effect_with((), move |_| {
+            let alive = Rc::new(Cell::new(true));
+            let listener: Rc<RefCell<Option<Listener>>> = Rc::new(RefCell::new(None));
+            {
+                let alive = alive.clone();
+                let listener = listener.clone();
+                spawn_local(async move {
+                    let handler = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+                        let payload = js_sys::Reflect::get(&event, &JsValue::from_str("payload"))
+                            .unwrap_or(JsValue::NULL);
+                        if let Ok(copied) = serde_wasm_bindgen::from_value::<Vec<String>>(payload) {
+                            let mut pending = links.borrow_mut();
+                            for link in copied {
+                                if !pending.contains(&link) {
+                                    pending.push(link);
+                                }
+                            }
+                            redraw.force_update();
+                        }
+                    });
+                    let unlisten: js_sys::Function =
+                        listen("clipboard_links", &handler).await.unchecked_into();
+                    if alive.get() {
+                        *listener.borrow_mut() = Some((unlisten, handler));
+                    } else {
+                        let _ = unlisten.call0(&JsValue::NULL);
+                    }
+                });
+            }
+            move || {
+                alive.set(false);
+                if let Some((unlisten, _handler)) = listener.borrow_mut().take() {
+                    let _ = unlisten.call0(&JsValue::NULL);
+                }
+            }
+        });
+    }
+
+    let shown = links.borrow().clone();
+    if shown.is_empty() {
+        return html! {};
+    }
+
+    let on_queue = {
+        let links = links.clone();
+        let redraw = redraw.clone();
+        Callback::from(move |_: MouseEvent| {
+            let to_queue = std::mem::take(&mut *links.borrow_mut());
+            redraw.force_update();
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "links": to_queue }))
+                    .unwrap();
+                let _ = invoke("queue_copied_links", args).await;
+            });
+        })
+    };
+    let on_dismiss = {
+        Callback::from(move |_: MouseEvent| {
+            links.borrow_mut().clear();
+            redraw.force_update();
+        })
+    };
+
+    let title = match shown.len() {
+        1 => "Add copied link to queue?".to_string(),
+        n => format!("Add {n} copied links to queue?"),
+    };
+    html! {
+        <div id="clipboard-prompt" class="clipboard-prompt">
+            <strong>{ title }</strong>
+            <div class="clipboard-prompt-link" title={shown.join("\n")}>{ shown[0].clone() }</div>
+            <div class="clipboard-prompt-actions">
+                <button id="clipboard-prompt-queue-button" type="button" onclick={on_queue}>{"Add to queue"}</button>
+                <button id="clipboard-prompt-dismiss-button" type="button" onclick={on_dismiss}>{"Dismiss"}</button>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,116 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_icons::{Icon, IconId};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CollectionCoverProps {
+    pub platform: String,
+    pub handle: String,
+    pub origin: String,
+}
+
+fn collection_args(props: &CollectionCoverProps) -> JsValue {
+    serde_wasm_bindgen::to_value(&serde_json::json!({
+        "platform": props.platform,
+        "handle": props.handle,
+        "origin": props.origin,
+    }))
+    .unwrap()
+}
+
+/// Cover image of a Library card with "choose" / "reset to auto" actions.
+#[function_component(CollectionCover)]
+pub fn collection_cover(props: &CollectionCoverProps) -> Html {
+    // None = still loading, Some(None) = no image available
+    let cover = use_state(|| None::<Option<String>>);
+
+    {
+        let cover = cover.clone();
+        let args = collection_args(props);
+        use_effect_with(
+            (
+                props.platform.clone(),
+                props.handle.clone(),
+                props.origin.clone(),
+            ),
+            move |_| {
+                spawn_local(async move {
+                    let v = invoke("get_collection_cover", args).await;
+                    cover.set(Some(
+                        serde_wasm_bindgen::from_value::<Option<String>>(v)
+                            .ok()
+                            .flatten(),
+                    ));
+                });
+            },
+        );
+    }
+
+    let on_pick = {
+        let cover = cover.clone();
+        let args = collection_args(props);
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            e.stop_propagation();
+            let cover = cover.clone();
+            let args = args.clone();
+            spawn_local(async move {
+                let v = invoke("pick_collection_cover", args).await;
+                if let Ok(Some(url)) = serde_wasm_bindgen::from_value::<Option<String>>(v) {
+                    cover.set(Some(Some(url)));
+                }
+            });
+        })
+    };
+
+    let on_reset = {
+        let cover = cover.clone();
+        let args = collection_args(props);
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            e.stop_propagation();
+            let cover = cover.clone();
+            let args = args.clone();
+            spawn_local(async move {
+                let _ = invoke("clear_collection_cover", args.clone()).await;
+                let v = invoke("get_collection_cover", args).await;
+                cover.set(Some(
+                    serde_wasm_bindgen::from_value::<Option<String>>(v)
+                        .ok()
+                        .flatten(),
+                ));
+            });
+        })
+    };
+
+    html! {
+        <div class="collection-cover">
+            {
+                match (*cover).clone() {
+                    Some(Some(src)) => html!{ <img class="collection-cover-img" {src} /> },
+                    Some(None) => html!{
+                        <div class="collection-cover-empty">
+                            <Icon icon_id={IconId::LucideImage} width={"32"} height={"32"} />
+                        </div>
+                    },
+                    None => html!{ <div class="collection-cover-empty" /> },
+                }
+            }
+            <div class="collection-cover-actions">
+                <button class="icon-btn" type_="button" title="Choose cover" onclick={on_pick}>
+                    <Icon icon_id={IconId::LucideImage} width={"16"} height={"16"} />
+                </button>
+                <button class="icon-btn" type_="button" title="Use newest download as cover" onclick={on_reset}>
+                    <Icon icon_id={IconId::LucideRotateCcw} width={"16"} height={"16"} />
+                </button>
+            </div>
+        </div>
+    }
+}
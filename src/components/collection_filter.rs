@@ -0,0 +1,141 @@
+use crate::types::CollectionFilter;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+
+    #[wasm_bindgen(catch, js_namespace = ["window", "__TAURI__", "core"], js_name = invoke)]
+    async fn try_invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+const MB: u64 = 1024 * 1024;
+
+#[derive(Properties, PartialEq)]
+pub struct CollectionFilterProps {
+    pub platform: String,
+    pub handle: String,
+    pub origin: String,
+}
+
+fn number(input: &NodeRef) -> Option<f64> {
+    input
+        .cast::<web_sys::HtmlInputElement>()
+        .map(|i| i.value_as_number())
+        .filter(|n| n.is_finite() && *n > 0.0)
+}
+
+fn text(input: &NodeRef) -> Option<String> {
+    input
+        .cast::<web_sys::HtmlInputElement>()
+        .map(|i| i.value().trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// "Filters" expander of a collection: items shorter or longer than the given
+/// length, larger than the given size, or whose title fails the keyword
+/// patterns are skipped when they come up in the queue. Loaded when opened.
+#[function_component(CollectionFilterEditor)]
+pub fn collection_filter_editor(props: &CollectionFilterProps) -> Html {
+    let filter = use_state(|| None::<CollectionFilter>);
+    // outcome of the last save
+    let status = use_state(|| None::<String>);
+    let min_secs = use_node_ref();
+    let max_mins = use_node_ref();
+    let max_mb = use_node_ref();
+    let include = use_node_ref();
+    let exclude = use_node_ref();
+
+    let collection = serde_json::json!({
+        "platform": props.platform,
+        "handle": props.handle,
+        "origin": props.origin,
+    });
+
+    let on_toggle = {
+        let filter = filter.clone();
+        let collection = collection.clone();
+        Callback::from(move |e: Event| {
+            let open = e
+                .target_unchecked_into::<web_sys::Element>()
+                .has_attribute("open");
+            if !open {
+                return;
+            }
+            let filter = filter.clone();
+            let args = serde_wasm_bindgen::to_value(&collection).unwrap();
+            spawn_local(async move {
+                let v = invoke("get_collection_filter", args).await;
+                filter.set(Some(serde_wasm_bindgen::from_value(v).unwrap_or_default()));
+            });
+        })
+    };
+
+    let on_save = {
+        let status = status.clone();
+        let (min_secs, max_mins, max_mb) = (min_secs.clone(), max_mins.clone(), max_mb.clone());
+        let (include, exclude) = (include.clone(), exclude.clone());
+        Callback::from(move |e: MouseEvent| {
+            e.stop_propagation();
+            let new_filter = CollectionFilter {
+                min_duration_secs: number(&min_secs).map(|n| n as u32),
+                max_duration_secs: number(&max_mins).map(|n| (n * 60.0) as u32),
+                max_bytes: number(&max_mb).map(|n| (n * MB as f64) as u64),
+                include: text(&include),
+                exclude: text(&exclude),
+            };
+            let mut args = collection.clone();
+            args["filter"] = serde_json::to_value(&new_filter).unwrap();
+            let args = serde_wasm_bindgen::to_value(&args).unwrap();
+            let status = status.clone();
+            spawn_local(async move {
+                status.set(Some(
+                    match try_invoke("set_collection_filter", args).await {
+                        Ok(_) => "Saved".to_string(),
+                        Err(e) => e.as_string().unwrap_or_else(|| "Not saved".into()),
+                    },
+                ));
+            });
+        })
+    };
+
+    let body = match &*filter {
+        None => html! { <p class="attempt-empty">{"Loading…"}</p> },
+        Some(f) => {
+            let value = |n: Option<u64>| n.map(|n| n.to_string()).unwrap_or_default();
+            html! {
+                <div class="collection-filter-fields" onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}>
+                    <label>{"Skip shorter than (s)"}
+                        <input type="number" min="0" ref={min_secs} value={value(f.min_duration_secs.map(u64::from))} />
+                    </label>
+                    <label>{"Skip longer than (min)"}
+                        <input type="number" min="0" ref={max_mins} value={value(f.max_duration_secs.map(|s| u64::from(s) / 60))} />
+                    </label>
+                    <label>{"Skip files over (MB)"}
+                        <input type="number" min="0" ref={max_mb} value={value(f.max_bytes.map(|b| b / MB))} />
+                    </label>
+                    <label title="Regular expression, matched against the title and description">{"Only titles matching"}
+                        <input type="text" class="collection-filter-pattern" ref={include} value={f.include.clone().unwrap_or_default()} />
+                    </label>
+                    <label title="Regular expression, matched against the title and description">{"Skip titles matching"}
+                        <input type="text" class="collection-filter-pattern" ref={exclude} value={f.exclude.clone().unwrap_or_default()} />
+                    </label>
+                    <button type="button" onclick={on_save}>{"Save"}</button>
+                    if let Some(status) = (*status).clone() {
+                        <span class="collection-filter-saved">{ status }</span>
+                    }
+                </div>
+            }
+        }
+    };
+
+    html! {
+        <details class="collection-filter" ontoggle={on_toggle}>
+            <summary>{"Filters"}</summary>
+            { body }
+        </details>
+    }
+}
@@ -0,0 +1,97 @@
+use crate::types::CrashRecovery;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+}
+
+/// Recovered rows listed by name before collapsing into "and N more".
+const SHOWN_ROWS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryAnswer {
+    Resume,
+    /// Stay paused and open the Downloads page
+    Inspect,
+    StayPaused,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CrashRecoveryPromptProps {
+    pub report: CrashRecovery,
+    pub on_answer: Callback<RecoveryAnswer>,
+}
+
+/// Startup dialog after a run that ended without shutting down: what was in
+/// flight and the partial files found. The queue stays paused until answered.
+#[function_component(CrashRecoveryPrompt)]
+pub fn crash_recovery_prompt(props: &CrashRecoveryPromptProps) -> Html {
+    let answer = |choice: RecoveryAnswer| {
+        let on_answer = props.on_answer.clone();
+        Callback::from(move |_: MouseEvent| {
+            let on_answer = on_answer.clone();
+            spawn_local(async move {
+                let resume = choice == RecoveryAnswer::Resume;
+                let args =
+                    serde_wasm_bindgen::to_value(&serde_json::json!({ "resume": resume })).unwrap();
+                let _ = invoke("resolve_crash_recovery", args).await;
+                on_answer.emit(choice);
+            });
+        })
+    };
+
+    let report = &props.report;
+    let when = report
+        .last_alive
+        .as_deref()
+        .map(|t| {
+            format!(
+                " It was last running at {} UTC.",
+                t.get(..19).unwrap_or(t).replace('T', " ")
+            )
+        })
+        .unwrap_or_default();
+    let hidden = report.recovered.len().saturating_sub(SHOWN_ROWS);
+
+    html! {
+        <div id="crash-recovery-backdrop" class="profile-picker-backdrop">
+            <div id="crash-recovery" class="profile-picker crash-recovery">
+                <h2 id="crash-recovery-title">{"Clip Downloader didn't shut down cleanly"}</h2>
+                <p id="crash-recovery-summary">
+                    { format!(
+                        "{} downloads were in progress and are back in the queue ({} queued in total).{when}",
+                        report.recovered.len(),
+                        report.queued
+                    ) }
+                </p>
+                if !report.recovered.is_empty() {
+                    <ul id="crash-recovery-rows" class="crash-recovery-list">
+                        { for report.recovered.iter().take(SHOWN_ROWS).map(|r| html!{
+                            <li key={r.id}>{ &r.label }</li>
+                        }) }
+                        if hidden > 0 {
+                            <li class="crash-recovery-more">{ format!("and {hidden} more") }</li>
+                        }
+                    </ul>
+                }
+                if !report.partial_files.is_empty() {
+                    <details id="crash-recovery-partials">
+                        <summary>{ format!("{} partial files found", report.partial_files.len()) }</summary>
+                        <ul class="crash-recovery-list">
+                            { for report.partial_files.iter().map(|p| html!{ <li>{ p }</li> }) }
+                        </ul>
+                    </details>
+                }
+                <div id="crash-recovery-actions" class="crash-recovery-actions">
+                    <button id="crash-recovery-resume-button" onclick={answer(RecoveryAnswer::Resume)}>{"Resume queue"}</button>
+                    <button id="crash-recovery-inspect-button" onclick={answer(RecoveryAnswer::Inspect)}>{"Inspect"}</button>
+                    <button id="crash-recovery-paused-button" onclick={answer(RecoveryAnswer::StayPaused)}>{"Stay paused"}</button>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,188 @@
+use crate::types::{CsvMapping, CsvPreview};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(catch, js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Platform,
+    Origin,
+    Handle,
+    Media,
+    Link,
+}
+
+impl Field {
+    const ALL: [Field; 5] = [
+        Field::Platform,
+        Field::Origin,
+        Field::Handle,
+        Field::Media,
+        Field::Link,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Field::Platform => "Platform",
+            Field::Origin => "Type",
+            Field::Handle => "Handle",
+            Field::Media => "Media",
+            Field::Link => "Link",
+        }
+    }
+
+    fn column(self, mapping: &CsvMapping) -> Option<usize> {
+        match self {
+            Field::Platform => mapping.platform,
+            Field::Origin => mapping.origin,
+            Field::Handle => mapping.handle,
+            Field::Media => mapping.media,
+            Field::Link => mapping.link,
+        }
+    }
+
+    fn slot(self, mapping: &mut CsvMapping) -> &mut Option<usize> {
+        match self {
+            Field::Platform => &mut mapping.platform,
+            Field::Origin => &mut mapping.origin,
+            Field::Handle => &mut mapping.handle,
+            Field::Media => &mut mapping.media,
+            Field::Link => &mut mapping.link,
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CsvImportPromptProps {
+    pub preview: CsvPreview,
+    /// Whether imported rows wait for review
+    pub review: bool,
+    pub on_close: Callback<()>,
+}
+
+/// Dialog shown before a CSV import: which column holds each field, guessed
+/// from the header names, with the first rows for reference. Unmapped fields
+/// are worked out from the link.
+#[function_component(CsvImportPrompt)]
+pub fn csv_import_prompt(props: &CsvImportPromptProps) -> Html {
+    let mapping = use_state(|| props.preview.mapping);
+    let error = use_state(|| None::<String>);
+
+    let on_column = |field: Field| {
+        let mapping = mapping.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            let mut next = *mapping;
+            *field.slot(&mut next) = value.parse().ok();
+            mapping.set(next);
+        })
+    };
+
+    let on_header_row = {
+        let mapping = mapping.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            mapping.set(CsvMapping {
+                header_row: checked,
+                ..*mapping
+            });
+        })
+    };
+
+    let on_import = {
+        let mapping = mapping.clone();
+        let error = error.clone();
+        let path = props.preview.path.clone();
+        let review = props.review;
+        let on_close = props.on_close.clone();
+        Callback::from(move |_: MouseEvent| {
+            let args = serde_wasm_bindgen::to_value(&serde_json::json!({
+                "path": path,
+                "review": review,
+                "mapping": *mapping,
+            }))
+            .unwrap();
+            let (error, on_close) = (error.clone(), on_close.clone());
+            spawn_local(async move {
+                match invoke("read_csv_from_path", args).await {
+                    Ok(_) => on_close.emit(()),
+                    Err(e) => error.set(Some(
+                        e.as_string().unwrap_or_else(|| "Import failed".into()),
+                    )),
+                }
+            });
+        })
+    };
+
+    let on_cancel = {
+        let on_close = props.on_close.clone();
+        Callback::from(move |_: MouseEvent| on_close.emit(()))
+    };
+
+    let preview = &props.preview;
+    let file_name = preview
+        .path
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(&preview.path)
+        .to_string();
+    let current = *mapping;
+
+    html! {
+        <div id="csv-import-backdrop" class="profile-picker-backdrop">
+            <div id="csv-import" class="profile-picker csv-import">
+                <h2 id="csv-import-title">{ format!("Import {file_name}") }</h2>
+                <label id="csv-import-header-row-label" class="profile-picker-ask">
+                    <input type="checkbox" id="csv-import-header-row-checkbox" checked={current.header_row} onchange={on_header_row} />
+                    {"The first line names the columns"}
+                </label>
+                <div id="csv-import-columns" class="csv-import-columns">
+                    { for Field::ALL.into_iter().map(|field| {
+                        let selected = field.column(&current);
+                        html! {
+                            <label>{ field.label() }
+                                <select onchange={on_column(field)}>
+                                    <option value="" selected={selected.is_none()}>
+                                        { if field == Field::Link { "—" } else { "From the link" } }
+                                    </option>
+                                    { for preview.headers.iter().enumerate().map(|(i, name)| html! {
+                                        <option value={i.to_string()} selected={selected == Some(i)}>{ name }</option>
+                                    }) }
+                                </select>
+                            </label>
+                        }
+                    }) }
+                </div>
+                <div class="csv-import-sample">
+                    <table>
+                        <thead>
+                            <tr>{ for preview.headers.iter().map(|h| html! { <th>{ h }</th> }) }</tr>
+                        </thead>
+                        <tbody>
+                            { for preview.sample.iter().map(|row| html! {
+                                <tr>{ for row.iter().map(|cell| html! { <td>{ cell }</td> }) }</tr>
+                            }) }
+                        </tbody>
+                    </table>
+                </div>
+                if let Some(e) = (*error).clone() {
+                    <span id="csv-import-error" class="profile-picker-error">{ e }</span>
+                }
+                <div id="csv-import-actions" class="crash-recovery-actions">
+                    <button id="csv-import-button" disabled={current.link.is_none()} onclick={on_import}>{"Import"}</button>
+                    <button id="csv-import-cancel-button" onclick={on_cancel}>{"Cancel"}</button>
+                </div>
+            </div>
+        </div>
+    }
+}
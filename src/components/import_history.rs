@@ -0,0 +1,175 @@
+use crate::types::{ImportRecord, ImportSummary};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "event"])]
+    async fn listen(event: &str, f: &Closure<dyn FnMut(JsValue)>) -> JsValue;
+}
+
+type Listener = (js_sys::Function, Closure<dyn FnMut(JsValue)>);
+
+fn short_time(rfc3339: &str) -> String {
+    // "2024-05-01T12:34:56+00:00" -> "2024-05-01 12:34"
+    rfc3339.get(..16).unwrap_or(rfc3339).replace('T', " ")
+}
+
+fn duplicates_text(skipped: u32, moved: u32) -> String {
+    match (skipped, moved) {
+        (s, 0) => format!("{s} already known"),
+        (0, m) => format!("{m} moved here"),
+        (s, m) => format!("{s} already known, {m} moved here"),
+    }
+}
+
+fn summary_text(s: &ImportSummary) -> String {
+    let mut text = format!("Added {} new rows", s.inserted);
+    if s.skipped + s.updated > 0 {
+        text.push_str(&format!(
+            " ({})",
+            duplicates_text(s.skipped as u32, s.updated as u32)
+        ));
+    }
+    text
+}
+
+/// Past list imports with re-run / roll back actions; refreshed on `import_completed`.
+#[function_component(ImportHistory)]
+pub fn import_history() -> Html {
+    let imports = use_state(Vec::<ImportRecord>::new);
+    let status = use_state(|| None::<String>);
+
+    let reload = {
+        let imports = imports.clone();
+        Callback::from(move |_: ()| {
+            let imports = imports.clone();
+            spawn_local(async move {
+                let v = invoke("list_imports", JsValue::NULL).await;
+                if let Ok(list) = serde_wasm_bindgen::from_value::<Vec<ImportRecord>>(v) {
+                    imports.set(list);
+                }
+            });
+        })
+    };
+
+    {
+        let reload = reload.clone();
+        use_effect_with((), move |_| {
+            reload.emit(());
+            let alive = Rc::new(Cell::new(true));
+            let listener: Rc<RefCell<Option<Listener>>> = Rc::new(RefCell::new(None));
+            {
+                let alive = alive.clone();
+                let listener = listener.clone();
+                spawn_local(async move {
+                    let handler = Closure::<dyn FnMut(JsValue)>::new(move |_event: JsValue| {
+                        reload.emit(());
+                    });
+                    let unlisten: js_sys::Function =
+                        listen("import_completed", &handler).await.unchecked_into();
+                    if alive.get() {
+                        *listener.borrow_mut() = Some((unlisten, handler));
+                    } else {
+                        let _ = unlisten.call0(&JsValue::NULL);
+                    }
+                });
+            }
+            move || {
+                alive.set(false);
+                if let Some((unlisten, _handler)) = listener.borrow_mut().take() {
+                    let _ = unlisten.call0(&JsValue::NULL);
+                }
+            }
+        });
+    }
+
+    let run_action = {
+        let status = status.clone();
+        Callback::from(move |(cmd, id): (&'static str, i64)| {
+            let status = status.clone();
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "id": id })).unwrap();
+                let v = invoke(cmd, args).await;
+                let message = match (v.as_f64(), v.as_string()) {
+                    (Some(n), _) => format!("Removed {n} rows"),
+                    (_, Some(err)) => err,
+                    _ => match serde_wasm_bindgen::from_value::<ImportSummary>(v) {
+                        Ok(s) => summary_text(&s),
+                        Err(_) => String::new(),
+                    },
+                };
+                status.set(Some(message));
+            });
+        })
+    };
+
+    if imports.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <details id="import-history" class="import-history">
+            <summary id="import-history-summary">{"Import history"}</summary>
+            {
+                if let Some(msg) = (*status).clone() {
+                    html!{ <div id="import-history-status" class="import-history-status">{msg}</div> }
+                } else { html!{} }
+            }
+            <ul id="import-history-list" class="import-history-list">
+                { for imports.iter().map(|rec| {
+                    let id = rec.id;
+                    let rolled_back = rec.rolled_back_at.is_some();
+                    let on_rerun = {
+                        let run_action = run_action.clone();
+                        Callback::from(move |_| run_action.emit(("rerun_import", id)))
+                    };
+                    let on_rollback = {
+                        let run_action = run_action.clone();
+                        Callback::from(move |_| {
+                            let confirmed = web_sys::window()
+                                .and_then(|w| w.confirm_with_message("Delete the rows this import created?").ok())
+                                .unwrap_or(false);
+                            if confirmed {
+                                run_action.emit(("rollback_import", id));
+                            }
+                        })
+                    };
+                    html!{
+                        <li id={format!("import-history-{id}")} class={classes!("import-history-item", rolled_back.then_some("rolled-back"))}>
+                            <span class="import-history-source" title={rec.source_path.clone().unwrap_or_default()}>{&rec.source_name}</span>
+                            <span class="import-history-time">{short_time(&rec.imported_at)}</span>
+                            <span class="import-history-count">
+                                {
+                                    if rolled_back {
+                                        format!("{} rows (rolled back)", rec.row_count)
+                                    } else {
+                                        format!("{} rows", rec.row_count)
+                                    }
+                                }
+                            </span>
+                            if rec.skipped_count + rec.updated_count > 0 {
+                                <span class="import-history-duplicates" title="Links that already had a row">
+                                    { duplicates_text(rec.skipped_count, rec.updated_count) }
+                                </span>
+                            }
+                            if rec.invalid_count > 0 {
+                                <span class="import-history-invalid" title="Links that answered 404/410 during import">
+                                    { format!("{} dead", rec.invalid_count) }
+                                </span>
+                            }
+                            <button class="import-history-rerun" disabled={rec.source_path.is_none()} onclick={on_rerun}>{"Re-run"}</button>
+                            <button class="import-history-rollback" disabled={rolled_back || rec.row_count == 0} onclick={on_rollback}>{"Roll back"}</button>
+                        </li>
+                    }
+                }) }
+            </ul>
+        </details>
+    }
+}
@@ -0,0 +1,58 @@
+use crate::types::MediaKind;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_icons::{Icon, IconId};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ItemThumbnailProps {
+    pub id: i64,
+    /// Whether a thumbnail was cached; skips the lookup when not.
+    pub cached: bool,
+    pub media: MediaKind,
+}
+
+/// Preview tile of one Library item, falling back to its media icon.
+#[function_component(ItemThumbnail)]
+pub fn item_thumbnail(props: &ItemThumbnailProps) -> Html {
+    let src = use_state(|| None::<String>);
+
+    {
+        let src = src.clone();
+        let id = props.id;
+        use_effect_with((id, props.cached), move |(id, cached)| {
+            if *cached {
+                let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "id": id })).unwrap();
+                spawn_local(async move {
+                    let v = invoke("get_download_thumbnail", args).await;
+                    src.set(
+                        serde_wasm_bindgen::from_value::<Option<String>>(v)
+                            .ok()
+                            .flatten(),
+                    );
+                });
+            }
+        });
+    }
+
+    let fallback = match props.media {
+        MediaKind::Image => IconId::LucideImage,
+        MediaKind::Video => IconId::LucideVideo,
+    };
+    html! {
+        <div class="item-thumbnail">
+            {
+                match (*src).clone() {
+                    Some(src) => html!{ <img class="item-thumbnail-img" {src} loading="lazy" /> },
+                    None => html!{ <Icon icon_id={fallback} width={"32"} height={"32"} /> },
+                }
+            }
+        </div>
+    }
+}
@@ -0,0 +1,153 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+}
+
+#[derive(serde::Deserialize, Clone, Debug, PartialEq)]
+struct LibraryEntry {
+    id: String,
+    name: String,
+}
+
+#[derive(serde::Deserialize, Clone, Debug, PartialEq)]
+struct LibraryIndex {
+    active: String,
+    libraries: Vec<LibraryEntry>,
+}
+
+fn initial(name: &str) -> String {
+    name.chars()
+        .next()
+        .map(|c| c.to_uppercase().to_string())
+        .unwrap_or_else(|| "?".into())
+}
+
+/// Sidebar badge for the active library; opens a list to switch or create one.
+/// Switching restarts the app on the chosen library.
+#[function_component(LibrarySwitcher)]
+pub fn library_switcher() -> Html {
+    let index = use_state(|| None::<LibraryIndex>);
+    let open = use_state(|| false);
+    let error = use_state(|| None::<String>);
+
+    let reload = {
+        let index = index.clone();
+        Callback::from(move |_: ()| {
+            let index = index.clone();
+            spawn_local(async move {
+                let v = invoke("list_libraries", JsValue::NULL).await;
+                if let Ok(list) = serde_wasm_bindgen::from_value::<LibraryIndex>(v) {
+                    index.set(Some(list));
+                }
+            });
+        })
+    };
+    {
+        let reload = reload.clone();
+        use_effect_with((), move |_| {
+            reload.emit(());
+            || ()
+        });
+    }
+
+    let Some(current) = (*index).clone() else {
+        return html! {};
+    };
+    let active_name = current
+        .libraries
+        .iter()
+        .find(|l| l.id == current.active)
+        .map(|l| l.name.clone())
+        .unwrap_or_default();
+
+    let on_toggle = {
+        let open = open.clone();
+        let error = error.clone();
+        Callback::from(move |_| {
+            error.set(None);
+            open.set(!*open);
+        })
+    };
+
+    let on_switch = {
+        let error = error.clone();
+        Callback::from(move |id: String| {
+            let error = error.clone();
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "id": id })).unwrap();
+                // On success the app restarts and this never resolves.
+                let v = invoke("switch_library", args).await;
+                if let Some(msg) = v.as_string() {
+                    error.set(Some(msg));
+                }
+            });
+        })
+    };
+
+    let on_create = {
+        let error = error.clone();
+        let reload = reload.clone();
+        Callback::from(move |_| {
+            let Some(name) = web_sys::window()
+                .and_then(|w| w.prompt_with_message("Name of the new library").ok())
+                .flatten()
+                .filter(|n| !n.trim().is_empty())
+            else {
+                return;
+            };
+            let error = error.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                let args =
+                    serde_wasm_bindgen::to_value(&serde_json::json!({ "name": name })).unwrap();
+                let v = invoke("create_library", args).await;
+                match v.as_string() {
+                    Some(msg) => error.set(Some(msg)),
+                    None => reload.emit(()),
+                }
+            });
+        })
+    };
+
+    html! {
+        <div id="library-switcher" class="library-switcher">
+            <button id="library-switcher-button" class="nav-btn library-badge" title={format!("Library: {active_name}")} onclick={on_toggle}>
+                {initial(&active_name)}
+            </button>
+            {
+                if *open {
+                    html!{
+                        <div id="library-switcher-menu" class="library-switcher-menu">
+                            { for current.libraries.iter().map(|l| {
+                                let id = l.id.clone();
+                                let on_switch = on_switch.clone();
+                                let active = l.id == current.active;
+                                html!{
+                                    <button
+                                        id={format!("library-switcher-{}", l.id)}
+                                        class={classes!("library-switcher-item", active.then_some("active"))}
+                                        disabled={active}
+                                        onclick={Callback::from(move |_| on_switch.emit(id.clone()))}
+                                    >
+                                        {&l.name}
+                                    </button>
+                                }
+                            }) }
+                            <button id="library-switcher-new" class="library-switcher-item" onclick={on_create}>{"New library…"}</button>
+                            {
+                                if let Some(msg) = (*error).clone() {
+                                    html!{ <div id="library-switcher-error" class="library-switcher-error">{msg}</div> }
+                                } else { html!{} }
+                            }
+                        </div>
+                    }
+                } else { html!{} }
+            }
+        </div>
+    }
+}
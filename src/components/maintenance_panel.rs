@@ -0,0 +1,155 @@
+use crate::types::{MaintenanceTask, TaskStatus};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "event"])]
+    async fn listen(event: &str, f: &Closure<dyn FnMut(JsValue)>) -> JsValue;
+}
+
+type Listener = (js_sys::Function, Closure<dyn FnMut(JsValue)>);
+
+fn upsert(list: &mut Vec<MaintenanceTask>, task: MaintenanceTask) {
+    match list.iter_mut().find(|t| t.id == task.id) {
+        Some(existing) => *existing = task,
+        None => list.insert(0, task),
+    }
+}
+
+fn status_label(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Running => "Running",
+        TaskStatus::Completed => "Done",
+        TaskStatus::Failed => "Failed",
+        TaskStatus::Canceled => "Canceled",
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct MaintenancePanelProps {
+    /// Called when a task reaches a final status.
+    #[prop_or_default]
+    pub on_task_finished: Option<Callback<MaintenanceTask>>,
+}
+
+/// Running and recently finished maintenance tasks, live-updated from
+/// `maintenance_progress` events.
+#[function_component(MaintenancePanel)]
+pub fn maintenance_panel(props: &MaintenancePanelProps) -> Html {
+    let tasks = use_state(Vec::<MaintenanceTask>::new);
+    let tasks_ref = use_mut_ref(Vec::<MaintenanceTask>::new);
+
+    {
+        let tasks = tasks.clone();
+        let tasks_ref = tasks_ref.clone();
+        let on_task_finished = props.on_task_finished.clone();
+        use_effect_with((), move |_| {
+            let alive = Rc::new(Cell::new(true));
+            let listener: Rc<RefCell<Option<Listener>>> = Rc::new(RefCell::new(None));
+            {
+                let alive = alive.clone();
+                let listener = listener.clone();
+                spawn_local(async move {
+                    let v = invoke("list_maintenance_tasks", JsValue::NULL).await;
+                    if let Ok(list) = serde_wasm_bindgen::from_value::<Vec<MaintenanceTask>>(v) {
+                        *tasks_ref.borrow_mut() = list.clone();
+                        tasks.set(list);
+                    }
+                    let handler = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+                        let payload = js_sys::Reflect::get(&event, &JsValue::from_str("payload"))
+                            .unwrap_or(JsValue::NULL);
+                        if let Ok(task) = serde_wasm_bindgen::from_value::<MaintenanceTask>(payload)
+                        {
+                            if task.status != TaskStatus::Running {
+                                if let Some(cb) = &on_task_finished {
+                                    cb.emit(task.clone());
+                                }
+                            }
+                            upsert(&mut tasks_ref.borrow_mut(), task);
+                            tasks.set(tasks_ref.borrow().clone());
+                        }
+                    });
+                    let unlisten: js_sys::Function = listen("maintenance_progress", &handler)
+                        .await
+                        .unchecked_into();
+                    if alive.get() {
+                        *listener.borrow_mut() = Some((unlisten, handler));
+                    } else {
+                        let _ = unlisten.call0(&JsValue::NULL);
+                    }
+                });
+            }
+            move || {
+                alive.set(false);
+                if let Some((unlisten, _handler)) = listener.borrow_mut().take() {
+                    let _ = unlisten.call0(&JsValue::NULL);
+                }
+            }
+        });
+    }
+
+    let on_cancel = Callback::from(move |id: u32| {
+        spawn_local(async move {
+            let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "id": id })).unwrap();
+            let _ = invoke("cancel_maintenance_task", args).await;
+        });
+    });
+
+    html! {
+        <div id="maintenance-panel" class="maintenance-panel">
+            {
+                if tasks.is_empty() {
+                    html!{ <div id="maintenance-panel-empty" class="maintenance-empty">{"No maintenance tasks have run yet."}</div> }
+                } else {
+                    html!{
+                        <ul id="maintenance-task-list" class="maintenance-task-list">
+                            { for tasks.iter().map(|t| {
+                                let running = t.status == TaskStatus::Running;
+                                let percent = t.total.filter(|n| *n > 0).map(|n| (t.done * 100 / n).min(100));
+                                let id = t.id;
+                                let on_cancel = on_cancel.clone();
+                                html!{
+                                    <li id={format!("maintenance-task-{id}")} class={classes!("maintenance-task", format!("maintenance-task-{}", status_label(t.status).to_lowercase()))}>
+                                        <div class="maintenance-task-head">
+                                            <span class="maintenance-task-label">{&t.label}</span>
+                                            <span class="maintenance-task-status">
+                                                {
+                                                    match (running, percent) {
+                                                        (true, Some(p)) => format!("{p}%"),
+                                                        _ => status_label(t.status).to_string(),
+                                                    }
+                                                }
+                                            </span>
+                                            {
+                                                if running && t.cancelable {
+                                                    html!{ <button class="maintenance-task-cancel" onclick={Callback::from(move |_| on_cancel.emit(id))}>{"Cancel"}</button> }
+                                                } else { html!{} }
+                                            }
+                                        </div>
+                                        {
+                                            if running {
+                                                html!{ <progress max="100" value={percent.unwrap_or(0).to_string()}></progress> }
+                                            } else { html!{} }
+                                        }
+                                        {
+                                            if t.message.is_empty() { html!{} } else {
+                                                html!{ <div class="maintenance-task-message">{&t.message}</div> }
+                                            }
+                                        }
+                                    </li>
+                                }
+                            }) }
+                        </ul>
+                    }
+                }
+            }
+        </div>
+    }
+}
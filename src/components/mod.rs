@@ -1 +1,17 @@
+pub mod attempt_history;
+pub mod clipboard_prompt;
+pub mod collection_cover;
+pub mod collection_filter;
+pub mod crash_recovery_prompt;
+pub mod csv_import_prompt;
+pub mod import_history;
+pub mod item_thumbnail;
+pub mod library_switcher;
+pub mod maintenance_panel;
+pub mod platform_icon;
+pub mod profile_picker;
 pub mod sidebar;
+pub mod storage_badge;
+pub mod tag_chips;
+pub mod trash_panel;
+pub mod watch_panel;
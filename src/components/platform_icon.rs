@@ -0,0 +1,63 @@
+use crate::platforms;
+use crate::types::Platform;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PlatformIconProps {
+    pub platform: Platform,
+    /// Any link on the site; used to look up a favicon for `Platform::Other`.
+    #[prop_or_default]
+    pub link: String,
+}
+
+/// Brand icon for known platforms, the site's favicon for everything else.
+#[function_component(PlatformIcon)]
+pub fn platform_icon(props: &PlatformIconProps) -> Html {
+    let domain = if props.platform == Platform::Other {
+        clip_core::url::host(&props.link)
+    } else {
+        None
+    };
+    let favicon = use_state(|| {
+        domain
+            .as_deref()
+            .and_then(platforms::cached_favicon)
+            .flatten()
+    });
+
+    {
+        let favicon = favicon.clone();
+        use_effect_with(domain, move |domain| {
+            if let Some(domain) = domain.clone() {
+                match platforms::cached_favicon(&domain) {
+                    Some(icon) => favicon.set(icon),
+                    None => spawn_local(async move {
+                        let args =
+                            serde_wasm_bindgen::to_value(&serde_json::json!({ "domain": domain }))
+                                .unwrap();
+                        let v = invoke("get_platform_icon", args).await;
+                        let icon = serde_wasm_bindgen::from_value::<Option<String>>(v)
+                            .ok()
+                            .flatten();
+                        platforms::store_favicon(&domain, icon.clone());
+                        favicon.set(icon);
+                    }),
+                }
+            }
+        });
+    }
+
+    let src = match platforms::icon_for(props.platform.as_str()) {
+        "" => (*favicon).clone().unwrap_or_default(),
+        s => s.to_string(),
+    };
+    html! { <img class="brand-icon" {src} /> }
+}
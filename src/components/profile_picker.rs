@@ -0,0 +1,157 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+}
+
+#[derive(serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ProfileEntry {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ProfileIndex {
+    pub active: String,
+    pub profiles: Vec<ProfileEntry>,
+    pub ask_on_startup: bool,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ProfilePickerProps {
+    /// Keep the current profile and close the picker.
+    pub on_close: Callback<()>,
+}
+
+/// Modal listing the app's profiles. Picking another one restarts the app on it.
+#[function_component(ProfilePicker)]
+pub fn profile_picker(props: &ProfilePickerProps) -> Html {
+    let index = use_state(|| None::<ProfileIndex>);
+    let error = use_state(|| None::<String>);
+
+    let reload = {
+        let index = index.clone();
+        Callback::from(move |_: ()| {
+            let index = index.clone();
+            spawn_local(async move {
+                let v = invoke("list_profiles", JsValue::NULL).await;
+                if let Ok(list) = serde_wasm_bindgen::from_value::<ProfileIndex>(v) {
+                    index.set(Some(list));
+                }
+            });
+        })
+    };
+    {
+        let reload = reload.clone();
+        use_effect_with((), move |_| {
+            reload.emit(());
+            || ()
+        });
+    }
+
+    let Some(current) = (*index).clone() else {
+        return html! {};
+    };
+
+    let on_pick = {
+        let error = error.clone();
+        let on_close = props.on_close.clone();
+        let active = current.active.clone();
+        Callback::from(move |id: String| {
+            if id == active {
+                on_close.emit(());
+                return;
+            }
+            let error = error.clone();
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "id": id })).unwrap();
+                // On success the app restarts and this never resolves.
+                let v = invoke("switch_profile", args).await;
+                if let Some(msg) = v.as_string() {
+                    error.set(Some(msg));
+                }
+            });
+        })
+    };
+
+    let on_create = {
+        let error = error.clone();
+        let reload = reload.clone();
+        Callback::from(move |_| {
+            let Some(name) = web_sys::window()
+                .and_then(|w| w.prompt_with_message("Name of the new profile").ok())
+                .flatten()
+                .filter(|n| !n.trim().is_empty())
+            else {
+                return;
+            };
+            let error = error.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                let args =
+                    serde_wasm_bindgen::to_value(&serde_json::json!({ "name": name })).unwrap();
+                let v = invoke("create_profile", args).await;
+                match v.as_string() {
+                    Some(msg) => error.set(Some(msg)),
+                    None => reload.emit(()),
+                }
+            });
+        })
+    };
+
+    let on_ask_change = {
+        let index = index.clone();
+        Callback::from(move |e: Event| {
+            let enabled = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            if let Some(mut next) = (*index).clone() {
+                next.ask_on_startup = enabled;
+                index.set(Some(next));
+            }
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "enabled": enabled }))
+                    .unwrap();
+                let _ = invoke("set_profile_prompt", args).await;
+            });
+        })
+    };
+
+    html! {
+        <div id="profile-picker-backdrop" class="profile-picker-backdrop">
+            <div id="profile-picker" class="profile-picker">
+                <h2 id="profile-picker-title">{"Who's using Clip Downloader?"}</h2>
+                <div id="profile-picker-list" class="profile-picker-list">
+                    { for current.profiles.iter().map(|p| {
+                        let id = p.id.clone();
+                        let on_pick = on_pick.clone();
+                        let active = p.id == current.active;
+                        html!{
+                            <button
+                                id={format!("profile-picker-{}", p.id)}
+                                class={classes!("profile-picker-item", active.then_some("active"))}
+                                onclick={Callback::from(move |_| on_pick.emit(id.clone()))}
+                            >
+                                {&p.name}
+                            </button>
+                        }
+                    }) }
+                    <button id="profile-picker-new" class="profile-picker-item" onclick={on_create}>{"New profile…"}</button>
+                </div>
+                <label id="profile-picker-ask-label" class="profile-picker-ask">
+                    <input type="checkbox" id="profile-picker-ask-checkbox" checked={current.ask_on_startup} onchange={on_ask_change} />
+                    {"Ask at startup"}
+                </label>
+                {
+                    if let Some(msg) = (*error).clone() {
+                        html!{ <div id="profile-picker-error" class="profile-picker-error">{msg}</div> }
+                    } else { html!{} }
+                }
+            </div>
+        </div>
+    }
+}
@@ -1,12 +1,13 @@
-use crate::app::Page;
+use crate::components::library_switcher::LibrarySwitcher;
+use crate::components::storage_badge::StorageBadge;
 use crate::dom::assign_missing_descriptive_ids;
-use crate::log;
+use crate::router::{Page, Router};
 use yew::prelude::*;
 use yew_icons::{Icon, IconId};
 
 #[derive(Properties, PartialEq)]
 pub struct SidebarProps {
-    pub page: UseStateHandle<Page>,
+    pub router: Router,
 }
 
 #[function_component(Sidebar)]
@@ -16,31 +17,19 @@ pub fn sidebar(props: &SidebarProps) -> Html {
         || ()
     });
 
-    let set_page = |p: Page, page_handle: UseStateHandle<Page>| {
-        let label = match p {
-            Page::Home => "Home",
-            Page::Downloads => "Downloads",
-            Page::Library => "Library",
-            Page::Settings => "Settings",
-            Page::Extension => "Extension",
-            Page::Sponsor => "Sponsor",
-        }
-        .to_string();
-
-        Callback::from(move |_| {
-            log::info("navigate", serde_json::json!({ "to": label }));
-            page_handle.set(p);
-        })
-    };
+    let set_page = |p: Page| props.router.link(p);
 
     html! {
         <aside id="app-sidebar" class="sidebar">
-            <button id="sidebar-home-button" class="nav-btn" onclick={set_page(Page::Home, props.page.clone())} title="Home"><Icon icon_id={IconId::LucideHome} width={"28"} height={"28"} /></button>
-            <button id="sidebar-downloads-button" class="nav-btn" onclick={set_page(Page::Downloads, props.page.clone())} title="Downloads"><Icon icon_id={IconId::LucideDownload} width={"28"} height={"28"} /></button>
-            <button id="sidebar-library-button" class="nav-btn" onclick={set_page(Page::Library, props.page.clone())} title="Library"><Icon icon_id={IconId::LucideLibrary} width={"28"} height={"28"} /></button>
-            <button id="sidebar-settings-button" class="nav-btn" onclick={set_page(Page::Settings, props.page.clone())} title="Settings"><Icon icon_id={IconId::LucideSettings} width={"28"} height={"28"} /></button>
-            <button id="sidebar-extension-button" class="nav-btn" onclick={set_page(Page::Extension, props.page.clone())} title="Extension"><Icon icon_id={IconId::LucideListEnd} width={"28"} height={"28"} class="flipped-icon" /></button>
-            <button id="sidebar-sponsor-button" class="nav-btn" onclick={set_page(Page::Sponsor, props.page.clone())} title="Sponsor"><Icon icon_id={IconId::LucideHeart} width={"28"} height={"28"} /></button>
+            <button id="sidebar-home-button" class="nav-btn" onclick={set_page(Page::Home)} title="Home"><Icon icon_id={IconId::LucideHome} width={"28"} height={"28"} /></button>
+            <button id="sidebar-downloads-button" class="nav-btn" onclick={set_page(Page::Downloads)} title="Downloads"><Icon icon_id={IconId::LucideDownload} width={"28"} height={"28"} /></button>
+            <button id="sidebar-library-button" class="nav-btn" onclick={set_page(Page::Library)} title="Library"><Icon icon_id={IconId::LucideLibrary} width={"28"} height={"28"} /></button>
+            <button id="sidebar-stats-button" class="nav-btn" onclick={set_page(Page::Stats)} title="Stats"><Icon icon_id={IconId::LucideBarChart} width={"28"} height={"28"} /></button>
+            <button id="sidebar-settings-button" class="nav-btn" onclick={set_page(Page::Settings)} title="Settings"><Icon icon_id={IconId::LucideSettings} width={"28"} height={"28"} /></button>
+            <button id="sidebar-extension-button" class="nav-btn" onclick={set_page(Page::Extension)} title="Extension"><Icon icon_id={IconId::LucideListEnd} width={"28"} height={"28"} class="flipped-icon" /></button>
+            <button id="sidebar-sponsor-button" class="nav-btn" onclick={set_page(Page::Sponsor)} title="Sponsor"><Icon icon_id={IconId::LucideHeart} width={"28"} height={"28"} /></button>
+            <StorageBadge />
+            <LibrarySwitcher />
         </aside>
     }
 }
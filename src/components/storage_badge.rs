@@ -0,0 +1,66 @@
+use crate::pages::downloads::format_bytes;
+use crate::types::{StorageLevel, StorageStatus};
+use gloo_timers::callback::Interval;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+}
+
+/// How often the free space is re-read.
+const REFRESH_MS: u32 = 60_000;
+
+/// Sidebar dot for the download folder's drive: green, yellow when space runs
+/// low, red when nearly full or unreachable. Hover for the numbers.
+#[function_component(StorageBadge)]
+pub fn storage_badge() -> Html {
+    let status = use_state(|| None::<StorageStatus>);
+
+    {
+        let status = status.clone();
+        use_effect_with((), move |_| {
+            let refresh = move || {
+                let status = status.clone();
+                spawn_local(async move {
+                    let v = invoke("get_storage_status", JsValue::NULL).await;
+                    if let Ok(s) = serde_wasm_bindgen::from_value::<StorageStatus>(v) {
+                        status.set(Some(s));
+                    }
+                });
+            };
+            refresh();
+            let interval = Interval::new(REFRESH_MS, refresh);
+            move || drop(interval)
+        });
+    }
+
+    let Some(s) = (*status).clone() else {
+        return html! {};
+    };
+    let (class, summary) = match s.level {
+        StorageLevel::Ok => ("ok", format!("{} free", format_bytes(s.free_bytes))),
+        StorageLevel::Low => (
+            "low",
+            format!("Low space: {} free", format_bytes(s.free_bytes)),
+        ),
+        StorageLevel::Critical => (
+            "critical",
+            format!("Almost full: {} free", format_bytes(s.free_bytes)),
+        ),
+        StorageLevel::Unreachable => ("critical", "Download folder unreachable".to_string()),
+    };
+    let title = if s.total_bytes > 0 {
+        format!("{summary} of {}\n{}", format_bytes(s.total_bytes), s.path)
+    } else {
+        format!("{summary}\n{}", s.path)
+    };
+    html! {
+        <div id="sidebar-storage-badge" class={classes!("storage-badge", class)} title={title}>
+            <span class="storage-dot"></span>
+        </div>
+    }
+}
@@ -0,0 +1,91 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+}
+
+#[derive(Properties, PartialEq)]
+pub struct TagChipsProps {
+    pub link: String,
+    pub tags: Vec<String>,
+    /// Fired after a tag was added or removed (e.g. to reload tag counts).
+    #[prop_or_default]
+    pub on_change: Callback<()>,
+}
+
+/// Tags of one Library item: removable chips plus an input that adds one on Enter.
+#[function_component(TagChips)]
+pub fn tag_chips(props: &TagChipsProps) -> Html {
+    let tags = use_state(|| props.tags.clone());
+    {
+        let tags = tags.clone();
+        use_effect_with(props.tags.clone(), move |t| {
+            tags.set(t.clone());
+            || ()
+        });
+    }
+
+    let send = {
+        let tags = tags.clone();
+        let link = props.link.clone();
+        let on_change = props.on_change.clone();
+        move |cmd: &'static str, tag: String| {
+            let tags = tags.clone();
+            let on_change = on_change.clone();
+            let args =
+                serde_wasm_bindgen::to_value(&serde_json::json!({ "link": link, "tag": tag }))
+                    .unwrap();
+            spawn_local(async move {
+                let v = invoke(cmd, args).await;
+                if let Ok(updated) = serde_wasm_bindgen::from_value::<Vec<String>>(v) {
+                    tags.set(updated);
+                    on_change.emit(());
+                }
+            });
+        }
+    };
+
+    let on_keydown = {
+        let send = send.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() != "Enter" {
+                return;
+            }
+            e.prevent_default();
+            let input = e.target_unchecked_into::<web_sys::HtmlInputElement>();
+            let tag = input.value().trim().to_string();
+            if !tag.is_empty() {
+                input.set_value("");
+                send("add_tag_to_link", tag);
+            }
+        })
+    };
+
+    html! {
+        <span class="tag-chips" onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}>
+            {
+                for tags.iter().map(|tag| {
+                    let on_remove = {
+                        let send = send.clone();
+                        let tag = tag.clone();
+                        Callback::from(move |e: MouseEvent| {
+                            e.prevent_default();
+                            send("remove_tag", tag.clone());
+                        })
+                    };
+                    html! {
+                        <span class="tag-chip" key={tag.clone()}>
+                            { tag }
+                            <button class="tag-chip-remove" type_="button" title="Remove tag" onclick={on_remove}>{"×"}</button>
+                        </span>
+                    }
+                })
+            }
+            <input class="tag-input" type="text" placeholder="+ tag" title="Type a tag and press Enter" onkeydown={on_keydown} />
+        </span>
+    }
+}
@@ -0,0 +1,147 @@
+use crate::types::ClipRow;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+
+    #[wasm_bindgen(catch, js_namespace = ["window", "__TAURI__", "core"], js_name = invoke)]
+    async fn try_invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+fn row_label(row: &ClipRow) -> String {
+    row.title
+        .clone()
+        .unwrap_or_else(|| clip_core::url::item_label(row.platform, &row.link))
+}
+
+#[derive(Properties, PartialEq)]
+pub struct TrashPanelProps {
+    /// Called after rows come back, so the page can reload what it shows.
+    pub on_restored: Callback<()>,
+}
+
+/// "Trash" expander of the Library: rows deleted in Soft mode, each with
+/// Restore, plus Restore all and Empty trash. Loaded when opened.
+#[function_component(TrashPanel)]
+pub fn trash_panel(props: &TrashPanelProps) -> Html {
+    let rows = use_state(|| None::<Vec<ClipRow>>);
+    // outcome of the last action
+    let status = use_state(|| None::<String>);
+
+    let reload = {
+        let rows = rows.clone();
+        Callback::from(move |_: ()| {
+            let rows = rows.clone();
+            spawn_local(async move {
+                let v = invoke("list_trash", JsValue::NULL).await;
+                rows.set(Some(serde_wasm_bindgen::from_value(v).unwrap_or_default()));
+            });
+        })
+    };
+
+    let on_toggle = {
+        let reload = reload.clone();
+        Callback::from(move |e: Event| {
+            let open = e
+                .target_unchecked_into::<web_sys::Element>()
+                .has_attribute("open");
+            if open {
+                reload.emit(());
+            }
+        })
+    };
+
+    let restore = |ids: Vec<i64>| {
+        let reload = reload.clone();
+        let status = status.clone();
+        let on_restored = props.on_restored.clone();
+        Callback::from(move |_: MouseEvent| {
+            let (reload, status, on_restored) =
+                (reload.clone(), status.clone(), on_restored.clone());
+            let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "ids": ids })).unwrap();
+            spawn_local(async move {
+                match try_invoke("restore_from_trash", args).await {
+                    Ok(v) => {
+                        let n = serde_wasm_bindgen::from_value::<u64>(v).unwrap_or(0);
+                        status.set(Some(format!("Restored {n}")));
+                        on_restored.emit(());
+                    }
+                    Err(e) => status.set(Some(
+                        e.as_string().unwrap_or_else(|| "Restore failed".into()),
+                    )),
+                }
+                reload.emit(());
+            });
+        })
+    };
+
+    let on_empty = {
+        let reload = reload.clone();
+        let status = status.clone();
+        Callback::from(move |_: MouseEvent| {
+            let confirmed = web_sys::window()
+                .and_then(|w| {
+                    w.confirm_with_message(
+                        "Delete everything in the trash for good, downloaded files included?",
+                    )
+                    .ok()
+                })
+                .unwrap_or(false);
+            if !confirmed {
+                return;
+            }
+            let (reload, status) = (reload.clone(), status.clone());
+            spawn_local(async move {
+                status.set(Some(match try_invoke("empty_trash", JsValue::NULL).await {
+                    Ok(v) => {
+                        let n = serde_wasm_bindgen::from_value::<u64>(v).unwrap_or(0);
+                        format!("Deleted {n} for good")
+                    }
+                    Err(e) => e.as_string().unwrap_or_else(|| "Emptying failed".into()),
+                }));
+                reload.emit(());
+            });
+        })
+    };
+
+    let body = match &*rows {
+        None => html! { <p class="attempt-empty">{"Loading…"}</p> },
+        Some(rows) if rows.is_empty() => {
+            html! { <p class="attempt-empty">{"The trash is empty."}</p> }
+        }
+        Some(rows) => html! {
+            <>
+                <div class="trash-actions">
+                    <button id="library-trash-restore-all" type="button" onclick={restore(Vec::new())}>{"Restore all"}</button>
+                    <button id="library-trash-empty" type="button" onclick={on_empty}>{"Empty trash"}</button>
+                </div>
+                <ul class="rows">
+                    { for rows.iter().map(|row| html! {
+                        <li class="row-line" key={row.id}>
+                            <a class="link-text" href={row.link.clone()} target="_blank">
+                                { format!("{} | {} - {}", row.handle, row.content_type.as_str(), row_label(row)) }
+                            </a>
+                            <div class="row-actions">
+                                <button type="button" onclick={restore(vec![row.id])}>{"Restore"}</button>
+                            </div>
+                        </li>
+                    }) }
+                </ul>
+            </>
+        },
+    };
+
+    html! {
+        <details id="library-trash" class="trash-panel" ontoggle={on_toggle}>
+            <summary>{"Trash"}</summary>
+            if let Some(status) = (*status).clone() {
+                <span class="trash-status">{ status }</span>
+            }
+            { body }
+        </details>
+    }
+}
@@ -0,0 +1,207 @@
+use crate::platforms;
+use crate::types::WatchedCollection;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+
+    #[wasm_bindgen(catch, js_namespace = ["window", "__TAURI__", "core"], js_name = invoke)]
+    async fn try_invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+fn short_time(rfc3339: &str) -> String {
+    // "2024-05-01T12:34:56+00:00" -> "2024-05-01 12:34"
+    rfc3339.get(..16).unwrap_or(rfc3339).replace('T', " ")
+}
+
+/// "Checked 2024-05-01 12:34, 3 new" or the last error.
+fn last_check(watch: &WatchedCollection) -> String {
+    match (&watch.last_checked_at, &watch.last_error) {
+        (None, _) => "Not checked yet".into(),
+        (Some(at), Some(e)) => format!("Checked {}: {e}", short_time(at)),
+        (Some(at), None) => format!("Checked {}, {} new", short_time(at), watch.last_added),
+    }
+}
+
+/// Run `cmd` with `args`, then report `done` (or the error) and reload the list.
+fn run(
+    cmd: &'static str,
+    args: serde_json::Value,
+    done: impl FnOnce(JsValue) -> Option<String> + 'static,
+    status: UseStateHandle<Option<String>>,
+    reload: Callback<()>,
+) {
+    let args = serde_wasm_bindgen::to_value(&args).unwrap();
+    spawn_local(async move {
+        match try_invoke(cmd, args).await {
+            Ok(v) => {
+                if let Some(message) = done(v) {
+                    status.set(Some(message));
+                }
+            }
+            Err(e) => status.set(Some(
+                e.as_string().unwrap_or_else(|| format!("{cmd} failed")),
+            )),
+        }
+        reload.emit(());
+    });
+}
+
+fn watch_row(
+    watch: &WatchedCollection,
+    reload: &Callback<()>,
+    status: &UseStateHandle<Option<String>>,
+) -> Html {
+    let id = watch.id;
+    let update = move |interval_hours: u32, auto_queue: bool| serde_json::json!({ "id": id, "intervalHours": interval_hours, "autoQueue": auto_queue });
+
+    let on_interval = {
+        let (reload, status) = (reload.clone(), status.clone());
+        let auto_queue = watch.auto_queue;
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            let Ok(hours) = value.trim().parse::<u32>() else {
+                return;
+            };
+            run(
+                "update_watched_collection",
+                update(hours.max(1), auto_queue),
+                |_| None,
+                status.clone(),
+                reload.clone(),
+            );
+        })
+    };
+
+    let on_auto_queue = {
+        let (reload, status) = (reload.clone(), status.clone());
+        let interval_hours = watch.interval_hours;
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            run(
+                "update_watched_collection",
+                update(interval_hours, checked),
+                |_| None,
+                status.clone(),
+                reload.clone(),
+            );
+        })
+    };
+
+    let on_check_now = {
+        let (reload, status) = (reload.clone(), status.clone());
+        let handle = watch.handle.clone();
+        Callback::from(move |_: MouseEvent| {
+            status.set(Some(format!("Checking {handle}…")));
+            let handle = handle.clone();
+            run(
+                "sync_watched_collection",
+                serde_json::json!({ "id": id }),
+                move |v| {
+                    let n = serde_wasm_bindgen::from_value::<u64>(v).unwrap_or(0);
+                    Some(format!("{handle}: {n} new"))
+                },
+                status.clone(),
+                reload.clone(),
+            );
+        })
+    };
+
+    let on_unwatch = {
+        let (reload, status) = (reload.clone(), status.clone());
+        Callback::from(move |_: MouseEvent| {
+            run(
+                "unwatch_collection",
+                serde_json::json!({ "id": id }),
+                |_| None,
+                status.clone(),
+                reload.clone(),
+            );
+        })
+    };
+
+    html! {
+        <li class="row-line watch-row" key={watch.id}>
+            <img class="brand-icon" src={platforms::icon_for(watch.platform.as_str())} />
+            <a class="link-text" href={watch.url.clone()} target="_blank" title={last_check(watch)}>
+                { format!("{} | {}", watch.handle, watch.origin.as_str()) }
+            </a>
+            <span class="watch-last-check">{ last_check(watch) }</span>
+            <label class="watch-interval" title="Hours between checks">
+                {"Every"}
+                <input type="number" min="1" value={watch.interval_hours.to_string()} onchange={on_interval} />
+                {"h"}
+            </label>
+            <label title="Queue new items as soon as they are found">
+                <input type="checkbox" checked={watch.auto_queue} onchange={on_auto_queue} />
+                {"Auto-queue"}
+            </label>
+            <div class="row-actions">
+                <button type="button" onclick={on_check_now}>{"Check now"}</button>
+                <button type="button" onclick={on_unwatch}>{"Stop watching"}</button>
+            </div>
+        </li>
+    }
+}
+
+/// "Watched" expander of the Library: collections listed again on a timer,
+/// each with its interval, auto-queue, Check now and Stop watching.
+#[function_component(WatchPanel)]
+pub fn watch_panel() -> Html {
+    let watches = use_state(|| None::<Vec<WatchedCollection>>);
+    // outcome of the last action
+    let status = use_state(|| None::<String>);
+
+    let reload = {
+        let watches = watches.clone();
+        Callback::from(move |_: ()| {
+            let watches = watches.clone();
+            spawn_local(async move {
+                let v = invoke("list_watched_collections", JsValue::NULL).await;
+                watches.set(Some(serde_wasm_bindgen::from_value(v).unwrap_or_default()));
+            });
+        })
+    };
+
+    let on_toggle = {
+        let reload = reload.clone();
+        Callback::from(move |e: Event| {
+            let open = e
+                .target_unchecked_into::<web_sys::Element>()
+                .has_attribute("open");
+            if open {
+                reload.emit(());
+            }
+        })
+    };
+
+    let body = match &*watches {
+        None => html! { <p class="attempt-empty">{"Loading…"}</p> },
+        Some(list) if list.is_empty() => html! {
+            <p class="attempt-empty">{"Nothing watched yet. Enter a profile, channel or playlist link on Home and press Watch."}</p>
+        },
+        Some(list) => html! {
+            <ul class="rows">
+                { for list.iter().map(|watch| watch_row(watch, &reload, &status)) }
+            </ul>
+        },
+    };
+
+    html! {
+        <details id="library-watched" class="trash-panel" ontoggle={on_toggle}>
+            <summary>{"Watched"}</summary>
+            if let Some(status) = (*status).clone() {
+                <span class="trash-status">{ status }</span>
+            }
+            { body }
+        </details>
+    }
+}
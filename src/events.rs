@@ -0,0 +1,92 @@
+pub use clip_core::DownloadEvent;
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(catch, js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+#[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+extern "C" {
+    type Channel;
+    #[wasm_bindgen(constructor)]
+    fn new() -> Channel;
+    #[wasm_bindgen(method, setter)]
+    fn set_onmessage(this: &Channel, f: &Closure<dyn FnMut(JsValue)>);
+}
+
+/// A live `subscribe_download_events` channel. Dropping it detaches the
+/// subscription on the backend.
+pub struct DownloadEventSubscription {
+    id: Rc<Cell<Option<u32>>>,
+    dropped: Rc<Cell<bool>>,
+    _channel: Channel,
+    _handler: Closure<dyn FnMut(JsValue)>,
+}
+
+pub fn subscribe_download_events<F>(mut on_event: F) -> DownloadEventSubscription
+where
+    F: FnMut(DownloadEvent) + 'static,
+{
+    let handler = Closure::<dyn FnMut(JsValue)>::new(move |message: JsValue| {
+        match serde_wasm_bindgen::from_value::<DownloadEvent>(message) {
+            Ok(evt) => on_event(evt),
+            Err(e) => web_sys::console::warn_1(&format!("bad download event: {e}").into()),
+        }
+    });
+    let channel = Channel::new();
+    channel.set_onmessage(&handler);
+
+    let id = Rc::new(Cell::new(None));
+    let dropped = Rc::new(Cell::new(false));
+    {
+        let id = id.clone();
+        let dropped = dropped.clone();
+        // Pass the channel object itself so Tauri can serialize it into a channel reference.
+        let args = js_sys::Object::new();
+        let _ = js_sys::Reflect::set(&args, &"channel".into(), channel.as_ref());
+        spawn_local(async move {
+            match invoke("subscribe_download_events", args.into()).await {
+                Ok(v) => {
+                    if let Ok(sub_id) = serde_wasm_bindgen::from_value::<u32>(v) {
+                        if dropped.get() {
+                            unsubscribe(sub_id);
+                        } else {
+                            id.set(Some(sub_id));
+                        }
+                    }
+                }
+                Err(e) => crate::app::log_invoke_err("subscribe_download_events", e),
+            }
+        });
+    }
+
+    DownloadEventSubscription {
+        id,
+        dropped,
+        _channel: channel,
+        _handler: handler,
+    }
+}
+
+fn unsubscribe(id: u32) {
+    spawn_local(async move {
+        let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "id": id })).unwrap();
+        if let Err(e) = invoke("unsubscribe_download_events", args).await {
+            crate::app::log_invoke_err("unsubscribe_download_events", e);
+        }
+    });
+}
+
+impl Drop for DownloadEventSubscription {
+    fn drop(&mut self) {
+        self.dropped.set(true);
+        if let Some(id) = self.id.take() {
+            unsubscribe(id);
+        }
+    }
+}
@@ -1,6 +1,13 @@
-use crate::app::{DeleteItem, MoveItem};
+use crate::app::{DeleteItem, MoveItem, QueueMove, ReviewItem};
+use crate::components::attempt_history::AttemptHistory;
+use crate::components::collection_filter::CollectionFilterEditor;
+use crate::components::platform_icon::PlatformIcon;
 use crate::dom::assign_missing_descriptive_ids;
-use crate::types::{content_type_str, platform_str, ClipRow, ContentType, MediaKind, Platform};
+use crate::platforms;
+use crate::types::{
+    ClipRow, ContentType, DateRange, DownloadStatus, FailureKind, MediaKind, Platform,
+    PlatformPause, ScheduleStatus, SessionReport, StorageStatus, ToolAdvisory, VolumeStatus,
+};
 use wasm_bindgen::prelude::*;
 use yew::prelude::*;
 use yew_icons::{Icon, IconId};
@@ -10,6 +17,17 @@ extern "C" {
     #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
     async fn invoke(cmd: &str, args: JsValue) -> JsValue;
 }
+/// Quick speed caps offered on the Downloads page (kbit/s, 0 = unlimited).
+const RATE_CHOICES_KBPS: &[u32] = &[0, 1_000, 5_000, 20_000, 50_000];
+
+fn rate_label(kbps: u32) -> String {
+    match kbps {
+        0 => "Unlimited".into(),
+        k if k % 1000 == 0 => format!("{} Mbit/s", k / 1000),
+        k => format!("{k} kbit/s"),
+    }
+}
+
 #[allow(dead_code)]
 fn toggle_icon_for_row(row: &ClipRow) -> IconId {
     // If row carries explicit output_format, prefer showing music icon for audio
@@ -26,6 +44,7 @@ fn toggle_icon_for_row(row: &ClipRow) -> IconId {
 
 #[derive(Properties, PartialEq, Clone)]
 pub struct Props {
+    pub pending: Vec<ClipRow>,
     pub backlog: Vec<ClipRow>,
     pub queue: Vec<ClipRow>,
     pub issues: Vec<ClipRow>,
@@ -35,35 +54,69 @@ pub struct Props {
     pub on_toggle_pause: Callback<()>,
     pub on_delete: Callback<DeleteItem>,
     pub on_move_to_queue: Callback<MoveItem>,
+    /// Backlog rows published outside this window are hidden and not queued
+    pub queue_range: DateRange,
+    pub on_queue_range: Callback<DateRange>,
     pub on_move_to_backlog: Callback<crate::app::MoveBackItem>,
     pub on_retry_issue: Callback<i64>,
+    /// Stop one active download, keeping what it fetched so far
+    pub on_pause_download: Callback<i64>,
+    pub on_resume_download: Callback<i64>,
+    pub on_review_pending: Callback<ReviewItem>,
+    pub on_queue_new: Callback<()>,
+    pub on_reorder: Callback<QueueMove>,
+    pub session_report: Option<SessionReport>,
+    pub on_dismiss_report: Callback<i64>,
+    /// Download windows; the queue holds while outside them
+    pub schedule: Option<ScheduleStatus>,
+    /// The queue also holds while the download drive is unplugged
+    pub volume: VolumeStatus,
+    /// Platforms held back because most of their recent downloads failed
+    pub paused_platforms: Vec<PlatformPause>,
+    /// Known-bad extractor versions detected at startup
+    pub advisories: Vec<ToolAdvisory>,
+    /// Failed download to reveal with its details open, from an internal link
+    pub focus: Option<i64>,
 }
 
 #[derive(Clone, PartialEq)]
 pub struct ActiveDownload {
     pub row: ClipRow,
     pub progress: Option<String>,
+    /// 0.0..=1.0 once the tool reports a percentage
+    pub fraction: Option<f32>,
+    pub downloaded_bytes: u64,
+    pub total_bytes: Option<u64>,
+    pub eta_secs: Option<u64>,
     pub stage: String,
 }
 
 /* ───────────────────────── label helpers ───────────────────────── */
 
-fn url_after_domain(url: &str) -> String {
-    let no_scheme = url.split("//").nth(1).unwrap_or(url);
-    match no_scheme.find('/') {
-        Some(i) => no_scheme[i + 1..].to_string(),
-        None => String::new(),
-    }
+/// The media title once metadata is known, else a label derived from the link.
+fn item_label_for_row(row: &ClipRow) -> String {
+    row.title
+        .clone()
+        .unwrap_or_else(|| clip_core::url::item_label(row.platform, &row.link))
 }
 
-fn last_two_path_segments(url: &str) -> String {
-    let tail = url_after_domain(url);
-    let parts: Vec<&str> = tail.split('/').filter(|s| !s.is_empty()).collect();
-    match parts.len() {
-        0 => tail,
-        1 => parts[0].to_string(),
-        _ => format!("{}/{}", parts[parts.len() - 2], parts[parts.len() - 1]),
-    }
+/// "12:34" from the probed duration; empty when unknown.
+pub(crate) fn duration_hint(row: &ClipRow) -> String {
+    row.duration_secs
+        .map(|s| match s / 3600 {
+            0 => format!("{}:{:02}", s / 60, s % 60),
+            h => format!("{h}:{:02}:{:02}", s / 60 % 60, s % 60),
+        })
+        .unwrap_or_default()
+}
+
+/// Last path component of a destination folder, for the compact row badge.
+fn folder_name(dir: &str) -> &str {
+    dir.trim_end_matches(['/', '\\'])
+        .rsplit(['/', '\\'])
+        .next()
+        .filter(|name| !name.is_empty())
+        .unwrap_or(dir)
 }
 
 /// Collection display name: "{handle} | {type}"
@@ -73,86 +126,160 @@ fn collection_title(row: &ClipRow) -> String {
     } else {
         &row.handle
     };
-    let typ = content_type_str(&row.content_type);
+    let typ = row.content_type.as_str();
     format!("{handle} | {typ}")
 }
 
-fn item_label_for_row(row: &ClipRow) -> String {
-    let link = row.link.trim();
-    let platform = platform_str(&row.platform);
-    if platform == "instagram" {
-        let tail = url_after_domain(link);
-        let mut parts = tail.split('/').filter(|s| !s.is_empty());
-        let _maybe_user = parts.next().unwrap_or_default();
-        let b = parts.next().unwrap_or_default(); // "p" or "reel"
-        let c = parts.next().unwrap_or_default(); // id
-        if (b == "p" || b == "reel") && !c.is_empty() {
-            format!("{b}/{c}")
-        } else {
-            last_two_path_segments(link)
-        }
-    } else if platform == "tiktok" {
-        let tail = url_after_domain(link);
-        let pieces: Vec<&str> = tail.split('/').filter(|s| !s.is_empty()).collect();
-        if let Some(pos) = pieces.iter().position(|p| *p == "photo" || *p == "video") {
-            if pos + 1 < pieces.len() {
-                format!("{}/{}", pieces[pos], pieces[pos + 1])
-            } else {
-                last_two_path_segments(link)
-            }
-        } else {
-            last_two_path_segments(link)
-        }
+/* ───────────────────────── helpers ───────────────────────── */
+
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
     } else {
-        last_two_path_segments(link)
+        format!("{value:.1} {}", UNITS[unit])
     }
 }
 
-/* ───────────────────────── helpers ───────────────────────── */
+/// Let the backend ask for a folder and queue `ids` into it; the status events
+/// it sends bring the rows (and their destination) back into the lists.
+fn spawn_enqueue_to_folder(ids: Vec<i64>) {
+    wasm_bindgen_futures::spawn_local(async move {
+        let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "ids": ids })).unwrap();
+        let _ = invoke("enqueue_to_folder", args).await;
+    });
+}
+
+/// Queue a collection's failed rows again (only `kinds` when given); status
+/// events move them out of Issues.
+fn spawn_retry_failed(
+    platform: String,
+    handle: String,
+    origin: String,
+    kinds: Option<Vec<FailureKind>>,
+) {
+    wasm_bindgen_futures::spawn_local(async move {
+        let args = serde_wasm_bindgen::to_value(&serde_json::json!({
+            "platform": platform,
+            "handle": handle,
+            "origin": origin,
+            "kinds": kinds,
+        }))
+        .unwrap();
+        let _ = invoke("retry_failed_collection", args).await;
+    });
+}
 
-fn is_instagram_photo(link: &str) -> bool {
-    let tail = url_after_domain(link);
-    let mut parts = tail.split('/').filter(|s| !s.is_empty());
-    let _maybe_user = parts.next().unwrap_or_default();
-    let b = parts.next().unwrap_or_default(); // "p" or "reel"
-    b == "p"
+/// "≈ 3.2 GB (4 not sized yet) · 12.0 GB free" for the queue heading, and
+/// whether the known sizes alone already exceed the free space.
+fn queue_size_summary(rows: &[ClipRow], free_bytes: Option<u64>) -> (String, bool) {
+    let known: u64 = rows.iter().filter_map(|r| r.estimated_bytes).sum();
+    let unknown = rows.iter().filter(|r| r.estimated_bytes.is_none()).count();
+    let mut text = format!("≈ {}", format_bytes(known));
+    if unknown > 0 {
+        text.push_str(&format!(" ({unknown} not sized yet)"));
+    }
+    if let Some(free) = free_bytes {
+        text.push_str(&format!(" · {} free", format_bytes(free)));
+    }
+    (text, free_bytes.is_some_and(|free| known > free))
 }
 
-fn is_tiktok_photo(link: &str) -> bool {
-    let tail = url_after_domain(link);
-    let pieces: Vec<&str> = tail.split('/').filter(|s| !s.is_empty()).collect();
-    pieces.iter().any(|p| *p == "photo")
+fn format_elapsed(secs: u64) -> String {
+    match (secs / 3600, (secs % 3600) / 60, secs % 60) {
+        (0, 0, s) => format!("{s}s"),
+        (0, m, s) => format!("{m}m {s}s"),
+        (h, m, _) => format!("{h}h {m}m"),
+    }
 }
 
-fn icon_for_row(row: &ClipRow) -> IconId {
-    let plat = platform_str(&row.platform);
-    let link = row.link.trim();
-    // Pinterest: always show as Image icon (treat all pins as images in UI)
-    if plat == "pinterest" {
-        return IconId::LucideImage;
+/// "4.4 MB of 10.5 MB · ETA 12s" for an active download, when sizes are known.
+fn progress_detail(active: &ActiveDownload) -> Option<String> {
+    let total = active.total_bytes?;
+    let mut detail = format!(
+        "{} of {}",
+        format_bytes(active.downloaded_bytes),
+        format_bytes(total)
+    );
+    if let Some(eta) = active.eta_secs {
+        detail.push_str(&format!(" · ETA {}", format_elapsed(eta)));
     }
-    if (plat == "instagram" && is_instagram_photo(link))
-        || (plat == "tiktok" && is_tiktok_photo(link))
-    {
+    Some(detail)
+}
+
+fn advisories_card(advisories: &[ToolAdvisory]) -> Html {
+    html! {
+        <div id="downloads-tool-advisories" class="tool-advisories">
+            <strong>{"Extractor warnings"}</strong>
+            { for advisories.iter().map(|a| html!{
+                <div class="tool-advisory-line">
+                    { format!("{} (installed {} {}). Update it to fix {} downloads.", a.message, a.tool, a.version, platforms::label_for(&a.platform)) }
+                </div>
+            }) }
+        </div>
+    }
+}
+
+fn session_report_card(report: &SessionReport, on_dismiss: Callback<i64>) -> Html {
+    let id = report.id;
+    let on_close = Callback::from(move |_e: MouseEvent| on_dismiss.emit(id));
+    html! {
+        <div class="summary">
+            <div class="session-report">
+                <div class="session-report-header">
+                    <strong>{"Queue finished"}</strong>
+                    <button class="icon-btn" type_="button" title="Dismiss" onclick={on_close}>{"✕"}</button>
+                </div>
+                <div class="session-report-stats">
+                    <span>{ format!("{} downloaded", report.downloaded) }</span>
+                    <span>{ format!("{} failed", report.failed) }</span>
+                    <span>{ format_bytes(report.bytes) }</span>
+                    <span>{ format_elapsed(report.elapsed_secs) }</span>
+                </div>
+                {
+                    if report.failures.is_empty() { html!{} } else {
+                        html!{
+                            <div class="session-report-line">
+                                {"Failures: "}
+                                { report.failures.iter().map(|(k, n)| format!("{k} ({n})")).collect::<Vec<_>>().join(", ") }
+                            </div>
+                        }
+                    }
+                }
+                {
+                    if report.top_collections.is_empty() { html!{} } else {
+                        html!{
+                            <div class="session-report-line">
+                                {"Top collections: "}
+                                { report.top_collections.iter().map(|(k, n)| format!("{k} ({n})")).collect::<Vec<_>>().join(", ") }
+                            </div>
+                        }
+                    }
+                }
+            </div>
+        </div>
+    }
+}
+
+fn icon_for_row(row: &ClipRow) -> IconId {
+    let desc = platforms::info(row.platform);
+    // Image-only platforms (Pinterest) and image posts always show the Image icon
+    if desc.default_media == MediaKind::Image || desc.is_image_url(row.link.trim()) {
         IconId::LucideImage
     } else {
         match row.media {
-            MediaKind::Pictures => IconId::LucideImage,
+            MediaKind::Image => IconId::LucideImage,
             MediaKind::Video => IconId::LucideVideo,
         }
     }
 }
 
-fn platform_icon_src(p: &str) -> &'static str {
-    match p {
-        "instagram" => "public/instagram.webp",
-        "pinterest" => "public/pinterest.png",
-        "tiktok" => "public/tiktok.webp",
-        "youtube" => "public/youtube.webp",
-        _ => "",
-    }
-}
-
 /* ───────────────────────── component ───────────────────────── */
 #[function_component(DownloadsPage)]
 pub fn downloads_page(props: &Props) -> Html {
@@ -163,32 +290,158 @@ pub fn downloads_page(props: &Props) -> Html {
     let has_any_rows = !props.active.is_empty()
         || !props.queue.is_empty()
         || !props.backlog.is_empty()
-        || !props.issues.is_empty();
+        || !props.issues.is_empty()
+        || !props.pending.is_empty();
     let expanded_platforms = use_state(|| std::collections::HashSet::<String>::new());
     let expanded_collections = use_state(|| std::collections::HashSet::<String>::new());
     // Local overrides so icon flips instantly on click (DB persists separately)
     let output_overrides = use_state(|| std::collections::HashMap::<String, String>::new());
+    let rate_limit = use_state(|| 0u32);
+    // Full-text search: ids of matching rows, or None when the box is empty
+    let search = use_state(String::new);
+    let search_hits = use_state(|| None::<std::collections::HashSet<i64>>);
+    let search_seq = use_mut_ref(|| 0u32);
+    let free_bytes = use_state(|| None::<u64>);
+    {
+        let free_bytes = free_bytes.clone();
+        use_effect_with(props.queue.len(), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                let v = invoke("get_storage_status", JsValue::NULL).await;
+                if let Ok(s) = serde_wasm_bindgen::from_value::<StorageStatus>(v) {
+                    free_bytes.set((s.total_bytes > 0).then_some(s.free_bytes));
+                }
+            });
+            || ()
+        });
+    }
+    // browsers offered as a per-row cookie choice
+    let cookie_browsers = use_state(Vec::<String>::new);
+    {
+        let cookie_browsers = cookie_browsers.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                let v = invoke("list_cookie_browsers", JsValue::NULL).await;
+                if let Ok(list) = serde_wasm_bindgen::from_value::<Vec<String>>(v) {
+                    cookie_browsers.set(list);
+                }
+            });
+            || ()
+        });
+    }
+    {
+        let rate_limit = rate_limit.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                let v = invoke("get_bandwidth_limit", JsValue::NULL).await;
+                if let Some(kbps) = v.as_f64() {
+                    rate_limit.set(kbps as u32);
+                }
+            });
+            || ()
+        });
+    }
 
     let on_toggle_pause_click_header = {
         let cb = props.on_toggle_pause.clone();
         Callback::from(move |_e: MouseEvent| cb.emit(()))
     };
+    let on_queue_new_click = {
+        let cb = props.on_queue_new.clone();
+        Callback::from(move |_e: MouseEvent| cb.emit(()))
+    };
     let _on_toggle_pause_click_row = {
         let cb = props.on_toggle_pause.clone();
         Callback::from(move |_e: MouseEvent| cb.emit(()))
     };
 
+    let on_search_input = {
+        let search = search.clone();
+        let search_hits = search_hits.clone();
+        let search_seq = search_seq.clone();
+        Callback::from(move |e: InputEvent| {
+            let query = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            search.set(query.clone());
+            let seq = {
+                let mut s = search_seq.borrow_mut();
+                *s += 1;
+                *s
+            };
+            if query.trim().is_empty() {
+                search_hits.set(None);
+                return;
+            }
+            let search_hits = search_hits.clone();
+            let search_seq = search_seq.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let args =
+                    serde_wasm_bindgen::to_value(&serde_json::json!({ "query": query })).unwrap();
+                let v = invoke("search_downloads", args).await;
+                // a newer keystroke already sent its own search
+                if *search_seq.borrow() != seq {
+                    return;
+                }
+                let rows = serde_wasm_bindgen::from_value::<Vec<ClipRow>>(v).unwrap_or_default();
+                search_hits.set(Some(rows.into_iter().map(|r| r.id).collect()));
+            });
+        })
+    };
+    let searching = search_hits.is_some();
+    let matching = |rows: &[ClipRow]| -> Vec<ClipRow> {
+        match &*search_hits {
+            Some(ids) => rows
+                .iter()
+                .filter(|r| ids.contains(&r.id))
+                .cloned()
+                .collect(),
+            None => rows.to_vec(),
+        }
+    };
+    let queue_rows = matching(&props.queue);
+    let pending_rows = matching(&props.pending);
+    let backlog_rows: Vec<ClipRow> = matching(&props.backlog)
+        .into_iter()
+        .filter(|r| props.queue_range.contains(r.upload_date.as_deref()))
+        .collect();
+    let on_range_bound = |after: bool| {
+        let on_queue_range = props.on_queue_range.clone();
+        let range = props.queue_range.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            let value = (!value.is_empty()).then_some(value);
+            let mut range = range.clone();
+            if after {
+                range.after = value;
+            } else {
+                range.before = value;
+            }
+            on_queue_range.emit(range);
+        })
+    };
+    let issue_rows = matching(&props.issues);
+
     let render_section = {
         let expanded_platforms = expanded_platforms.clone();
         let expanded_collections = expanded_collections.clone();
         let on_delete_prop = props.on_delete.clone();
         let on_move_prop = props.on_move_to_queue.clone();
         let on_move_back_prop = props.on_move_to_backlog.clone();
+        let on_reorder_prop = props.on_reorder.clone();
+        let top_priority = props.queue.iter().map(|r| r.priority).max().unwrap_or(0);
         let output_overrides = output_overrides.clone();
+        let cookie_browsers = (*cookie_browsers).clone();
 
+        let free_bytes = *free_bytes;
         move |rows_in: Vec<ClipRow>, title: &str, enable_queue_action: bool| -> Html {
             use std::collections::{BTreeMap, HashSet};
 
+            // Projected size of the queue, so it can be trimmed before a run
+            let size_summary =
+                (!enable_queue_action).then(|| queue_size_summary(&rows_in, free_bytes));
+
             let section_id = title.to_lowercase(); // "backlog" or "queue"
 
             // platform -> (handle, type, Platform, ContentType) -> rows
@@ -204,8 +457,8 @@ pub fn downloads_page(props: &Props) -> Html {
                 if r.handle.trim().is_empty() {
                     r.handle = "Unknown".into();
                 }
-                let plat = platform_str(&r.platform).to_string();
-                let typ = content_type_str(&r.content_type).to_string();
+                let plat = r.platform.as_str().to_string();
+                let typ = r.content_type.as_str().to_string();
 
                 let dedup_key = format!(
                     "{}|{}|{}|{}",
@@ -227,12 +480,27 @@ pub fn downloads_page(props: &Props) -> Html {
 
             html! {
                 <>
-                    <h2 style="margin: 24px 0 8px 16px;">{ title }</h2>
+                    <h2 style="margin: 24px 0 8px 16px;">
+                        { title }
+                        {
+                            if let Some((text, over)) = size_summary.clone() {
+                                html!{ <span id="downloads-queue-size-estimate" class={classes!("queue-size-estimate", over.then_some("over"))} title="Sizes the sites report for the default format; actual files can differ">{ text }</span> }
+                            } else {
+                                html!{}
+                            }
+                        }
+                    </h2>
                     <div class="summary">
                         {
                             for map.into_iter().map(|(plat_label, mut col_map)| {
                                 for rows in col_map.values_mut() {
-                                    rows.sort_by(|a, b| item_label_for_row(a).cmp(&item_label_for_row(b)));
+                                    if enable_queue_action {
+                                        // By link, so rows don't move as prefetched titles arrive
+                                        rows.sort_by_cached_key(|r| clip_core::url::item_label(r.platform, &r.link));
+                                    } else {
+                                        // Queue rows in start order
+                                        rows.sort_by_key(|r| (std::cmp::Reverse(r.priority), r.id));
+                                    }
                                 }
 
                                 let collections_count = col_map.len();
@@ -240,7 +508,7 @@ pub fn downloads_page(props: &Props) -> Html {
 
                                 /* ---- platform open state (namespaced) ---- */
                                 let platform_key = format!("{}::{}", section_id, plat_label);
-                                let is_open = expanded_platforms.contains(&platform_key);
+                                let is_open = searching || expanded_platforms.contains(&platform_key);
 
                                 let on_platform_click = {
                                     let expanded_platforms = expanded_platforms.clone();
@@ -254,13 +522,7 @@ pub fn downloads_page(props: &Props) -> Html {
 
                                 let on_delete_platform = {
                                     let on_delete = on_delete_prop.clone();
-                                    let platform = match plat_label.as_str() {
-                                        "instagram" => Platform::Instagram,
-                                        "tiktok" => Platform::Tiktok,
-                                        "youtube" => Platform::Youtube,
-                                        "pinterest" => Platform::Pinterest,
-                                        _ => Platform::Tiktok,
-                                    };
+                                    let platform = Platform::from(plat_label.as_str());
                                     // Backend deletion honoring delete mode
                                     let platform_str_for_backend = plat_label.clone();
                                     Callback::from(move |e: MouseEvent| {
@@ -277,13 +539,7 @@ pub fn downloads_page(props: &Props) -> Html {
 
                                 let on_queue_platform = {
                                     let on_move = on_move_prop.clone();
-                                    let platform = match plat_label.as_str() {
-                                        "instagram" => Platform::Instagram,
-                                        "tiktok" => Platform::Tiktok,
-                                        "youtube" => Platform::Youtube,
-                                        "pinterest" => Platform::Pinterest,
-                                        _ => Platform::Tiktok,
-                                    };
+                                    let platform = Platform::from(plat_label.as_str());
                                     Callback::from(move |e: MouseEvent| {
                                         e.prevent_default();
                                         e.stop_propagation();
@@ -293,13 +549,7 @@ pub fn downloads_page(props: &Props) -> Html {
 
                                 let on_back_platform = {
                                     let on_move_back = on_move_back_prop.clone();
-                                    let platform = match plat_label.as_str() {
-                                        "instagram" => Platform::Instagram,
-                                        "tiktok" => Platform::Tiktok,
-                                        "youtube" => Platform::Youtube,
-                                        "pinterest" => Platform::Pinterest,
-                                        _ => Platform::Tiktok,
-                                    };
+                                    let platform = Platform::from(plat_label.as_str());
                                     Callback::from(move |e: MouseEvent| {
                                         e.prevent_default();
                                         e.stop_propagation();
@@ -314,7 +564,7 @@ pub fn downloads_page(props: &Props) -> Html {
                                                 for col_map.into_iter().map(|((handle, typ_str, plat, ctype), rows)| {
                                                     /* ---- collection open state (namespaced) ---- */
                                                     let col_key = format!("{}::{}::{}::{}", section_id, plat_label, handle, typ_str);
-                                                    let col_open = expanded_collections.contains(&col_key);
+                                                    let col_open = searching || expanded_collections.contains(&col_key);
 
                                                     let on_col_click = {
                                                         let expanded_collections = expanded_collections.clone();
@@ -363,30 +613,23 @@ pub fn downloads_page(props: &Props) -> Html {
                                                             e.stop_propagation();
                                                             if enable_queue_action {
                                                                 on_move.emit(MoveItem::Collection(
-                                                                    match plat_label_s.as_str() {
-                                                                        "instagram"         => Platform::Instagram,
-                                                                        "tiktok"            => Platform::Tiktok,
-                                                                        "youtube"           => Platform::Youtube,
-                                                                        "pinterest"         => Platform::Pinterest,
-                                                                        _                   => Platform::Tiktok,
-                                                                    },
+                                                                    Platform::from(plat_label_s.as_str()),
                                                                     handle_s.clone(),
-                                                                    match typ_s.as_str() {
-                                                                        "liked"             => ContentType::Liked,
-                                                                        "reposts"           => ContentType::Reposts,
-                                                                        "profile"           => ContentType::Profile,
-                                                                        "bookmarks"         => ContentType::Bookmarks,
-                                                                        "playlist"          => ContentType::Playlist,
-                                                                        "recommendation"    => ContentType::Recommendation,
-                                                                        "manual"            => ContentType::Manual,
-                                                                        "pinboard"          => ContentType::Pinboard,
-                                                                        _                   => ContentType::Other,
-                                                                    }
+                                                                    ContentType::from(typ_s.as_str())
                                                                 ));
                                                             }
                                                         })
                                                     };
 
+                                                    let on_queue_collection_to_folder = {
+                                                        let ids: Vec<i64> = rows.iter().map(|r| r.id).collect();
+                                                        Callback::from(move |e: MouseEvent| {
+                                                            e.prevent_default();
+                                                            e.stop_propagation();
+                                                            spawn_enqueue_to_folder(ids.clone());
+                                                        })
+                                                    };
+
                                                     let on_back_collection = {
                                                         let on_move_back = on_move_back_prop.clone();
                                                         let plat_label_s = plat_label.clone();
@@ -397,25 +640,9 @@ pub fn downloads_page(props: &Props) -> Html {
                                                             e.stop_propagation();
                                                             if !enable_queue_action {
                                                                 on_move_back.emit(crate::app::MoveBackItem::Collection(
-                                                                    match plat_label_s.as_str() {
-                                                                        "instagram"         => Platform::Instagram,
-                                                                        "tiktok"            => Platform::Tiktok,
-                                                                        "youtube"           => Platform::Youtube,
-                                                                        "pinterest"         => Platform::Pinterest,
-                                                                        _                   => Platform::Tiktok,
-                                                                    },
+                                                                    Platform::from(plat_label_s.as_str()),
                                                                     handle_s.clone(),
-                                                                    match typ_s.as_str() {
-                                                                        "liked"             => ContentType::Liked,
-                                                                        "reposts"           => ContentType::Reposts,
-                                                                        "profile"           => ContentType::Profile,
-                                                                        "bookmarks"         => ContentType::Bookmarks,
-                                                                        "playlist"          => ContentType::Playlist,
-                                                                        "recommendation"    => ContentType::Recommendation,
-                                                                        "manual"            => ContentType::Manual,
-                                                                        "pinboard"          => ContentType::Pinboard,
-                                                                        _                   => ContentType::Other,
-                                                                    }
+                                                                    ContentType::from(typ_s.as_str())
                                                                 ));
                                                             }
                                                         })
@@ -436,9 +663,14 @@ pub fn downloads_page(props: &Props) -> Html {
                                                                     {
                                                                         if enable_queue_action {
                                                                             html!{
-                                                                                <button class="icon-btn" type_="button" title="Queue" onclick={on_queue_collection.clone()}>
-                                                                                    <Icon icon_id={IconId::LucideDownload} width={"18"} height={"18"} />
-                                                                                </button>
+                                                                                <>
+                                                                                    <button class="icon-btn" type_="button" title="Queue into a folder…" onclick={on_queue_collection_to_folder}>
+                                                                                        <Icon icon_id={IconId::LucideFolder} width={"18"} height={"18"} />
+                                                                                    </button>
+                                                                                    <button class="icon-btn" type_="button" title="Queue" onclick={on_queue_collection.clone()}>
+                                                                                        <Icon icon_id={IconId::LucideDownload} width={"18"} height={"18"} />
+                                                                                    </button>
+                                                                                </>
                                                                             }
                                                                         } else {
                                                                             html!{}
@@ -451,9 +683,37 @@ pub fn downloads_page(props: &Props) -> Html {
                                                                 if col_open {
                                                                     html!{
                                                                         <div class="rows-card">
+                                                                            <CollectionFilterEditor platform={plat_label.clone()} handle={handle.clone()} origin={typ_str.clone()} />
                                                                             <ul class="rows">
                                                                                 {
-                                                                                    for rows.into_iter().map(|row| {
+                                                                                    for rows.iter().enumerate().map(|(index, row)| {
+                                                                                        let row = row.clone();
+                                                                                        let prev_id = index.checked_sub(1).and_then(|i| rows.get(i)).map(|r| r.id);
+                                                                                        let next_id = rows.get(index + 1).map(|r| r.id);
+                                                                                        let on_move_up = {
+                                                                                            let on_reorder = on_reorder_prop.clone();
+                                                                                            let id = row.id;
+                                                                                            Callback::from(move |e: MouseEvent| {
+                                                                                                e.prevent_default();
+                                                                                                e.stop_propagation();
+                                                                                                if e.shift_key() {
+                                                                                                    on_reorder.emit(QueueMove::Top(id, top_priority + 1));
+                                                                                                } else if let Some(anchor) = prev_id {
+                                                                                                    on_reorder.emit(QueueMove::Before(id, anchor));
+                                                                                                }
+                                                                                            })
+                                                                                        };
+                                                                                        let on_move_down = {
+                                                                                            let on_reorder = on_reorder_prop.clone();
+                                                                                            let id = row.id;
+                                                                                            Callback::from(move |e: MouseEvent| {
+                                                                                                e.prevent_default();
+                                                                                                e.stop_propagation();
+                                                                                                if let Some(anchor) = next_id {
+                                                                                                    on_reorder.emit(QueueMove::After(id, anchor));
+                                                                                                }
+                                                                                            })
+                                                                                        };
                                                                                         let on_delete_row = {
                                                                                             let on_delete = on_delete_prop.clone();
                                                                                             let link = row.link.clone();
@@ -478,6 +738,14 @@ pub fn downloads_page(props: &Props) -> Html {
                                                                                                 if enable_queue_action { on_move.emit(MoveItem::Row(link.clone())); }
                                                                                             })
                                                                                         };
+                                                                                        let on_queue_row_to_folder = {
+                                                                                            let id = row.id;
+                                                                                            Callback::from(move |e: MouseEvent| {
+                                                                                                e.prevent_default();
+                                                                                                e.stop_propagation();
+                                                                                                spawn_enqueue_to_folder(vec![id]);
+                                                                                            })
+                                                                                        };
                                                                                         // Determine current effective format (override -> row value)
                                                                                         let effective_fmt: Option<String> = (*output_overrides).get(&row.link).cloned().or_else(|| row.output_format.clone());
                                                                                         let row_icon = if effective_fmt.as_deref() == Some("audio") { IconId::LucideMusic } else { icon_for_row(&row) };
@@ -501,13 +769,113 @@ pub fn downloads_page(props: &Props) -> Html {
                                                                                                 });
                                                                                             })
                                                                                         };
+                                                                                        let is_playlist = enable_queue_action
+                                                                                            && clip_core::url::infer_origin(row.platform, &row.link) == Some(ContentType::Playlist);
+                                                                                        let on_expand_playlist = {
+                                                                                            let url = row.link.clone();
+                                                                                            Callback::from(move |_| {
+                                                                                                let url = url.clone();
+                                                                                                wasm_bindgen_futures::spawn_local(async move {
+                                                                                                    let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "url": url })).unwrap();
+                                                                                                    let _ = invoke("expand_playlist", args).await;
+                                                                                                });
+                                                                                            })
+                                                                                        };
                                                                                         html!{
                                                                                             <li class="row-line" key={row.link.clone()}>
+                                                                                                { if row.platform == Platform::Other { html!{ <PlatformIcon platform={row.platform} link={row.link.clone()} /> } } else { html!{} } }
                                                                                                 <span onclick={on_click_toggle.clone()}><Icon icon_id={row_icon} width={"16"} height={"16"} /></span>
-                                                                                                <a class="link-text" href={row.link.clone()} target="_blank">
+                                                                                                {
+                                                                                                    if effective_fmt.as_deref() == Some("audio") {
+                                                                                                        let link = row.link.clone();
+                                                                                                        let on_audio_format = Callback::from(move |e: Event| {
+                                                                                                            let value = e.target_unchecked_into::<web_sys::HtmlSelectElement>().value();
+                                                                                                            let link = link.clone();
+                                                                                                            wasm_bindgen_futures::spawn_local(async move {
+                                                                                                                let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "link": link, "format": value })).unwrap();
+                                                                                                                let _ = invoke("set_output_format", args).await;
+                                                                                                            });
+                                                                                                        });
+                                                                                                        let pinned = row.audio_format.clone().unwrap_or_default();
+                                                                                                        html!{
+                                                                                                            <select class="row-audio-format" title="Audio format for this item" onchange={on_audio_format}>
+                                                                                                                <option value="audio" selected={pinned.is_empty()}>{"Default"}</option>
+                                                                                                                { for ["mp3", "m4a", "opus", "flac"].iter().map(|f| html!{
+                                                                                                                    <option value={*f} selected={pinned == *f}>{ f.to_uppercase() }</option>
+                                                                                                                }) }
+                                                                                                            </select>
+                                                                                                        }
+                                                                                                    } else {
+                                                                                                        html!{}
+                                                                                                    }
+                                                                                                }
+                                                                                                if cookie_browsers.len() > 1 {
+                                                                                                    {{
+                                                                                                        let link = row.link.clone();
+                                                                                                        let on_cookie_browser = Callback::from(move |e: Event| {
+                                                                                                            let value = e.target_unchecked_into::<web_sys::HtmlSelectElement>().value();
+                                                                                                            let link = link.clone();
+                                                                                                            wasm_bindgen_futures::spawn_local(async move {
+                                                                                                                let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "link": link, "browser": value })).unwrap();
+                                                                                                                let _ = invoke("set_cookie_browser", args).await;
+                                                                                                            });
+                                                                                                        });
+                                                                                                        let pinned = row.cookie_browser.clone().unwrap_or_default();
+                                                                                                        html!{
+                                                                                                            <select class="row-cookie-browser" title="Browser whose cookies this item tries first" onchange={on_cookie_browser}>
+                                                                                                                <option value="" selected={pinned.is_empty()}>{"Cookies: default"}</option>
+                                                                                                                { for cookie_browsers.iter().map(|b| html!{
+                                                                                                                    <option value={b.clone()} selected={pinned == *b}>{ b.clone() }</option>
+                                                                                                                }) }
+                                                                                                            </select>
+                                                                                                        }
+                                                                                                    }}
+                                                                                                }
+                                                                                                <a class="link-text" href={row.link.clone()} target="_blank" title={duration_hint(&row)}>
                                                                                                     { item_label_for_row(&row) }
                                                                                                 </a>
+                                                                                                {
+                                                                                                    if let Some(bytes) = row.estimated_bytes {
+                                                                                                        html!{ <span class="row-size" title="Estimated size">{ format_bytes(bytes) }</span> }
+                                                                                                    } else {
+                                                                                                        html!{}
+                                                                                                    }
+                                                                                                }
+                                                                                                {
+                                                                                                    if let Some(dir) = row.destination_dir.as_deref().filter(|d| !d.is_empty()) {
+                                                                                                        html!{ <span class="dest-badge" title={format!("Saves to {dir}")}>{ folder_name(dir) }</span> }
+                                                                                                    } else {
+                                                                                                        html!{}
+                                                                                                    }
+                                                                                                }
                                                                                                 <div class="row-actions">
+                                                                                                    {
+                                                                                                        if is_playlist {
+                                                                                                            html!{
+                                                                                                                <button class="icon-btn" type_="button" title="Expand into one row per video" onclick={on_expand_playlist}>
+                                                                                                                    <Icon icon_id={IconId::LucideListPlus} width={"18"} height={"18"} />
+                                                                                                                </button>
+                                                                                                            }
+                                                                                                        } else {
+                                                                                                            html!{}
+                                                                                                        }
+                                                                                                    }
+                                                                                                    {
+                                                                                                        if !enable_queue_action {
+                                                                                                            html!{
+                                                                                                                <>
+                                                                                                                    <button class="icon-btn" type_="button" title="Move up (Shift: to the top of the queue)" onclick={on_move_up}>
+                                                                                                                        <Icon icon_id={IconId::LucideArrowUp} width={"18"} height={"18"} />
+                                                                                                                    </button>
+                                                                                                                    <button class="icon-btn" type_="button" title="Move down" disabled={next_id.is_none()} onclick={on_move_down}>
+                                                                                                                        <Icon icon_id={IconId::LucideArrowDown} width={"18"} height={"18"} />
+                                                                                                                    </button>
+                                                                                                                </>
+                                                                                                            }
+                                                                                                        } else {
+                                                                                                            html!{}
+                                                                                                        }
+                                                                                                    }
                                                                                                     <button class="icon-btn" type_="button" title="Delete" onclick={on_delete_row}>
                                                                                                         <Icon icon_id={IconId::LucideTrash2} width={"18"} height={"18"} />
                                                                                                     </button>
@@ -527,9 +895,14 @@ pub fn downloads_page(props: &Props) -> Html {
                                                                                                     {
                                                                                                         if enable_queue_action {
                                                                                                             html!{
-                                                                                                                <button class="icon-btn" type_="button" title="Queue" onclick={on_queue_row}>
-                                                                                                                    <Icon icon_id={IconId::LucideDownload} width={"18"} height={"18"} />
-                                                                                                                </button>
+                                                                                                                <>
+                                                                                                                    <button class="icon-btn" type_="button" title="Queue into a folder…" onclick={on_queue_row_to_folder}>
+                                                                                                                        <Icon icon_id={IconId::LucideFolder} width={"18"} height={"18"} />
+                                                                                                                    </button>
+                                                                                                                    <button class="icon-btn" type_="button" title="Queue" onclick={on_queue_row}>
+                                                                                                                        <Icon icon_id={IconId::LucideDownload} width={"18"} height={"18"} />
+                                                                                                                    </button>
+                                                                                                                </>
                                                                                                             }
                                                                                                         } else {
                                                                                                             html!{}
@@ -557,8 +930,8 @@ pub fn downloads_page(props: &Props) -> Html {
                                     <div class="platform-block" key={platform_key.clone()}>
                                         <div class="platform-item" onclick={on_platform_click}>
                                             <div class="item-left">
-                                                <img class="brand-icon" src={platform_icon_src(&plat_label)} />
-                                                <span class="item-title">{ plat_label.clone() }</span>
+                                                <img class="brand-icon" src={platforms::icon_for(&plat_label)} />
+                                                <span class="item-title">{ platforms::label_for(&plat_label) }</span>
                                             </div>
                                             <div class="item-right">
                                                 <span>{ format!("{} collections | {} items", collections_count, bookmarks_count) }</span>
@@ -588,7 +961,51 @@ pub fn downloads_page(props: &Props) -> Html {
         }
     };
 
+    // Reveal a linked failed row: open its platform and collection, then scroll to it.
+    {
+        let expanded_platforms = expanded_platforms.clone();
+        let expanded_collections = expanded_collections.clone();
+        let focus_keys = props
+            .focus
+            .and_then(|id| props.issues.iter().find(|r| r.id == id))
+            .map(|r| {
+                let plat = r.platform.as_str();
+                let handle = if r.handle.trim().is_empty() {
+                    "Unknown"
+                } else {
+                    r.handle.as_str()
+                };
+                (
+                    r.id,
+                    format!("issues::{plat}"),
+                    format!("issues::{plat}::{handle}::{}", r.content_type.as_str()),
+                )
+            });
+        use_effect_with(focus_keys, move |keys| {
+            if let Some((id, platform_key, col_key)) = keys.clone() {
+                let mut platforms = (*expanded_platforms).clone();
+                platforms.insert(platform_key);
+                expanded_platforms.set(platforms);
+                let mut collections = (*expanded_collections).clone();
+                collections.insert(col_key);
+                expanded_collections.set(collections);
+                wasm_bindgen_futures::spawn_local(async move {
+                    // Wait for the expanded rows to render.
+                    gloo_timers::future::TimeoutFuture::new(50).await;
+                    if let Some(el) = web_sys::window()
+                        .and_then(|w| w.document())
+                        .and_then(|d| d.get_element_by_id(&format!("issue-row-{id}")))
+                    {
+                        el.scroll_into_view();
+                    }
+                });
+            }
+            || ()
+        });
+    }
+
     let render_issues = {
+        let focus = props.focus;
         let expanded_platforms = expanded_platforms.clone();
         let expanded_collections = expanded_collections.clone();
         let on_delete_prop = props.on_delete.clone();
@@ -604,8 +1021,8 @@ pub fn downloads_page(props: &Props) -> Html {
                 if r.handle.trim().is_empty() {
                     r.handle = "Unknown".into();
                 }
-                let plat = platform_str(&r.platform).to_string();
-                let typ = content_type_str(&r.content_type).to_string();
+                let plat = r.platform.as_str().to_string();
+                let typ = r.content_type.as_str().to_string();
                 map.entry(plat)
                     .or_default()
                     .entry((r.handle.clone(), typ))
@@ -613,9 +1030,26 @@ pub fn downloads_page(props: &Props) -> Html {
                     .push(r);
             }
 
+            let issue_bulk = |cmd: &'static str| {
+                Callback::from(move |e: MouseEvent| {
+                    e.prevent_default();
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let _ = invoke(cmd, JsValue::NULL).await;
+                    });
+                })
+            };
+
             html! {
                 <>
-                    <h2 style="margin: 24px 0 8px 16px;">{"Issues"}</h2>
+                    <div style="display:flex; align-items:center; gap:8px; margin: 24px 0 8px 16px;">
+                        <h2 style="margin:0;">{"Issues"}</h2>
+                        <button id="downloads-issues-retry-all" class="icon-btn" type_="button" title="Retry all" onclick={issue_bulk("retry_all_errors")}>
+                            <Icon icon_id={IconId::LucideDownload} width={"18"} height={"18"} />
+                        </button>
+                        <button id="downloads-issues-clear" class="icon-btn" type_="button" title="Clear: remove these rows" onclick={issue_bulk("clear_errors")}>
+                            <Icon icon_id={IconId::LucideTrash2} width={"18"} height={"18"} />
+                        </button>
+                    </div>
                     <div class="summary">
                         {
                             for map.into_iter().map(|(plat_label, col_map)| {
@@ -636,13 +1070,7 @@ pub fn downloads_page(props: &Props) -> Html {
 
                                 let on_delete_platform = {
                                     let on_delete = on_delete_prop.clone();
-                                    let platform = match plat_label.as_str() {
-                                        "instagram" => Platform::Instagram,
-                                        "tiktok"    => Platform::Tiktok,
-                                        "youtube"   => Platform::Youtube,
-                                        "pinterest" => Platform::Pinterest,
-                                        _           => Platform::Tiktok,
-                                    };
+                                    let platform = Platform::from(plat_label.as_str());
                                     let plat_s = plat_label.clone();
                                     Callback::from(move |e: MouseEvent| {
                                         e.prevent_default();
@@ -679,24 +1107,8 @@ pub fn downloads_page(props: &Props) -> Html {
                                                         let plat_s = plat_label.clone();
                                                         let handle_s = handle.clone();
                                                         let typ_s = typ_str.clone();
-                                                        let platform = match plat_label.as_str() {
-                                                            "instagram" => Platform::Instagram,
-                                                            "tiktok"    => Platform::Tiktok,
-                                                            "youtube"   => Platform::Youtube,
-                                                            "pinterest" => Platform::Pinterest,
-                                                            _           => Platform::Tiktok,
-                                                        };
-                                                        let ctype = match typ_str.as_str() {
-                                                            "liked"          => ContentType::Liked,
-                                                            "reposts"        => ContentType::Reposts,
-                                                            "profile"        => ContentType::Profile,
-                                                            "bookmarks"      => ContentType::Bookmarks,
-                                                            "playlist"       => ContentType::Playlist,
-                                                            "recommendation" => ContentType::Recommendation,
-                                                            "manual"         => ContentType::Manual,
-                                                            "pinboard"       => ContentType::Pinboard,
-                                                            _                => ContentType::Other,
-                                                        };
+                                                        let platform = Platform::from(plat_label.as_str());
+                                                        let ctype = ContentType::from(typ_str.as_str());
                                                         Callback::from(move |e: MouseEvent| {
                                                             e.prevent_default();
                                                             e.stop_propagation();
@@ -716,6 +1128,19 @@ pub fn downloads_page(props: &Props) -> Html {
                                                         })
                                                     };
 
+                                                    let mut kind_counts: BTreeMap<FailureKind, usize> = BTreeMap::new();
+                                                    for r in &rows {
+                                                        *kind_counts.entry(FailureKind::classify(r.last_error.as_deref())).or_default() += 1;
+                                                    }
+                                                    let retry_col = |kinds: Option<Vec<FailureKind>>| {
+                                                        let (plat_s, handle_s, typ_s) = (plat_label.clone(), handle.clone(), typ_str.clone());
+                                                        Callback::from(move |e: MouseEvent| {
+                                                            e.prevent_default();
+                                                            e.stop_propagation();
+                                                            spawn_retry_failed(plat_s.clone(), handle_s.clone(), typ_s.clone(), kinds.clone());
+                                                        })
+                                                    };
+
                                                     html! {
                                                         <div class="collection-block" key={col_key.clone()}>
                                                             <div class="collection-item" onclick={on_col_click}>
@@ -723,7 +1148,15 @@ pub fn downloads_page(props: &Props) -> Html {
                                                                     <span class="item-title">{ format!("{} | {}", handle, typ_str) }</span>
                                                                 </div>
                                                                 <div class="item-right">
+                                                                    { for kind_counts.iter().map(|(kind, n)| html!{
+                                                                        <button class="failure-chip" type_="button" title={format!("Retry only {} failures", kind.label().to_lowercase())} onclick={retry_col(Some(vec![*kind]))}>
+                                                                            { format!("{} {}", kind.label(), n) }
+                                                                        </button>
+                                                                    }) }
                                                                     <span>{ format!("{} items", rows.len()) }</span>
+                                                                    <button class="icon-btn" type_="button" title="Retry all failed" onclick={retry_col(None)}>
+                                                                        <Icon icon_id={IconId::LucideDownload} width={"18"} height={"18"} />
+                                                                    </button>
                                                                     <button class="icon-btn" type_="button" title="Delete" onclick={on_delete_col}>
                                                                         <Icon icon_id={IconId::LucideTrash2} width={"18"} height={"18"} />
                                                                     </button>
@@ -737,6 +1170,7 @@ pub fn downloads_page(props: &Props) -> Html {
                                                                                 {
                                                                                     for rows.into_iter().map(|row| {
                                                                                         let issue_id = row.id;
+                                                                                        let retry_title = if row.status == DownloadStatus::Missing { "Repair: download again" } else { "Retry" };
                                                                                         let on_delete_row = {
                                                                                             let on_delete = on_delete_prop.clone();
                                                                                             let link = row.link.clone();
@@ -768,15 +1202,35 @@ pub fn downloads_page(props: &Props) -> Html {
                                                                                                 on_retry.emit(issue_id);
                                                                                             })
                                                                                         };
+                                                                                        let on_use_mirror = Callback::from(move |e: MouseEvent| {
+                                                                                            e.prevent_default();
+                                                                                            e.stop_propagation();
+                                                                                            wasm_bindgen_futures::spawn_local(async move {
+                                                                                                let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "id": issue_id })).unwrap();
+                                                                                                let _ = invoke("use_mirror", args).await;
+                                                                                            });
+                                                                                        });
                                                                                         html! {
-                                                                                            <li class="row-line issue-line" key={row.link.clone()}>
+                                                                                            <li id={format!("issue-row-{issue_id}")} class={classes!("row-line", "issue-line", (focus == Some(issue_id)).then_some("focused"))} key={row.link.clone()}>
                                                                                                 <div class="issue-copy">
                                                                                                     <div class="issue-title">
                                                                                                         <span class="link-text">{ item_label_for_row(&row) }</span>
                                                                                                     </div>
                                                                                                     <div class="issue-reason">
-                                                                                                        { row.last_error.clone().unwrap_or_else(|| "Download failed".into()) }
+                                                                                                        {
+                                                                                                            row.last_error.clone().unwrap_or_else(|| {
+                                                                                                                if row.status == DownloadStatus::Canceled { "Canceled".into() } else { "Download failed".into() }
+                                                                                                            })
+                                                                                                        }
                                                                                                     </div>
+                                                                                                    if let Some(mirror) = row.mirror_url.clone() {
+                                                                                                        <div class="issue-mirror">
+                                                                                                            <span>{ format!("Found on {}:", row.mirror_source.clone().unwrap_or_else(|| "another site".into())) }</span>
+                                                                                                            <a class="link-text" href={mirror.clone()} target="_blank">{ mirror }</a>
+                                                                                                            <button type="button" onclick={on_use_mirror}>{"Download from mirror"}</button>
+                                                                                                        </div>
+                                                                                                    }
+                                                                                                    <AttemptHistory download_id={issue_id} open={focus == Some(issue_id)} />
                                                                                                 </div>
                                                                                                 <div class="row-actions active-status issue-actions">
                                                                                                     <button class="icon-btn" type_="button" title="Delete" onclick={on_delete_row}>
@@ -785,7 +1239,7 @@ pub fn downloads_page(props: &Props) -> Html {
                                                                                                     <button class="icon-btn" type_="button" title="Move back to backlog" onclick={on_backlog_row}>
                                                                                                         <Icon icon_id={IconId::LucideRotateCcw} width={"18"} height={"18"} />
                                                                                                     </button>
-                                                                                                    <button class="icon-btn" type_="button" title="Retry" onclick={on_retry_row}>
+                                                                                                    <button class="icon-btn" type_="button" title={retry_title} onclick={on_retry_row}>
                                                                                                         <Icon icon_id={IconId::LucideDownload} width={"18"} height={"18"} />
                                                                                                     </button>
                                                                                                 </div>
@@ -810,8 +1264,8 @@ pub fn downloads_page(props: &Props) -> Html {
                                     <div class="platform-block" key={platform_key.clone()}>
                                         <div class="platform-item" onclick={on_platform_click}>
                                             <div class="item-left">
-                                                <img class="brand-icon" src={platform_icon_src(&plat_label)} />
-                                                <span class="item-title">{ plat_label.clone() }</span>
+                                                <img class="brand-icon" src={platforms::icon_for(&plat_label)} />
+                                                <span class="item-title">{ platforms::label_for(&plat_label) }</span>
                                             </div>
                                             <div class="item-right">
                                                 <span>{ format!("{} collections | {} items", col_count, total) }</span>
@@ -831,6 +1285,127 @@ pub fn downloads_page(props: &Props) -> Html {
         }
     };
 
+    let render_pending = {
+        let on_review_prop = props.on_review_pending.clone();
+
+        move |rows_in: Vec<ClipRow>| -> Html {
+            use std::collections::BTreeMap;
+
+            // One entry per collection; review is all-or-nothing per collection.
+            let mut map: BTreeMap<(String, String, String), (Platform, ContentType, Vec<ClipRow>)> =
+                BTreeMap::new();
+            for mut r in rows_in {
+                if r.handle.trim().is_empty() {
+                    r.handle = "Unknown".into();
+                }
+                let key = (
+                    r.platform.as_str().to_string(),
+                    r.handle.clone(),
+                    r.content_type.as_str().to_string(),
+                );
+                map.entry(key)
+                    .or_insert_with(|| (r.platform, r.content_type, Vec::new()))
+                    .2
+                    .push(r);
+            }
+
+            html! {
+                <>
+                    <h2 style="margin: 24px 0 8px 16px;">{"Pending review"}</h2>
+                    <div class="summary">
+                        {
+                            for map.into_iter().map(|((plat_label, handle, typ_str), (platform, ctype, rows))| {
+                                let col_key = format!("pending::{}::{}::{}", plat_label, handle, typ_str);
+                                let on_approve = {
+                                    let cb = on_review_prop.clone();
+                                    let handle = handle.clone();
+                                    Callback::from(move |e: MouseEvent| {
+                                        e.prevent_default();
+                                        e.stop_propagation();
+                                        cb.emit(ReviewItem::Approve(platform, handle.clone(), ctype));
+                                    })
+                                };
+                                let on_reject = {
+                                    let cb = on_review_prop.clone();
+                                    let handle = handle.clone();
+                                    Callback::from(move |e: MouseEvent| {
+                                        e.prevent_default();
+                                        e.stop_propagation();
+                                        cb.emit(ReviewItem::Reject(platform, handle.clone(), ctype));
+                                    })
+                                };
+                                let sample: Vec<ClipRow> = rows.iter().take(3).cloned().collect();
+
+                                html! {
+                                    <div class="collection-block" key={col_key}>
+                                        <div class="collection-item">
+                                            <div class="item-left">
+                                                <img class="brand-icon" src={platforms::icon_for(&plat_label)} />
+                                                <span class="item-title">{ format!("{} | {}", handle, typ_str) }</span>
+                                            </div>
+                                            <div class="item-right">
+                                                <span>{ format!("{} items", rows.len()) }</span>
+                                                <button type_="button" title="Add to backlog" onclick={on_approve}>{"Approve"}</button>
+                                                <button class="icon-btn" type_="button" title="Reject" onclick={on_reject}>
+                                                    <Icon icon_id={IconId::LucideTrash2} width={"18"} height={"18"} />
+                                                </button>
+                                            </div>
+                                        </div>
+                                        <div class="rows-card">
+                                            <ul class="rows">
+                                                {
+                                                    for sample.into_iter().map(|row| html! {
+                                                        <li class="row-line" key={row.link.clone()}>
+                                                            <span class="link-text" title={row.link.clone()}>{ row.link.clone() }</span>
+                                                        </li>
+                                                    })
+                                                }
+                                            </ul>
+                                        </div>
+                                    </div>
+                                }
+                            })
+                        }
+                    </div>
+                </>
+            }
+        }
+    };
+
+    let on_rate_change = {
+        let rate_limit = rate_limit.clone();
+        Callback::from(move |e: Event| {
+            let kbps: u32 = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value()
+                .parse()
+                .unwrap_or(0);
+            rate_limit.set(kbps);
+            wasm_bindgen_futures::spawn_local(async move {
+                let args =
+                    serde_wasm_bindgen::to_value(&serde_json::json!({ "kbps": kbps })).unwrap();
+                let _ = invoke("set_bandwidth_limit", args).await;
+            });
+        })
+    };
+    let rate_choices: Vec<u32> = {
+        let mut v = RATE_CHOICES_KBPS.to_vec();
+        if !v.contains(&*rate_limit) {
+            v.push(*rate_limit);
+            v.sort_unstable();
+        }
+        v
+    };
+
+    let schedule_hint = props
+        .schedule
+        .as_ref()
+        .filter(|s| s.scheduled && !s.open)
+        .map(|s| match &s.starts_at {
+            Some(at) => format!("Scheduled: starts at {at}"),
+            None => "Scheduled: outside download windows".to_string(),
+        });
+
     html! {
         <main id="downloads-page" class="container downloads">
             <div style="display:flex; align-items:center; gap:8px; margin: 24px 0 8px 16px;">
@@ -844,8 +1419,65 @@ pub fn downloads_page(props: &Props) -> Html {
                         }
                     }
                 </button>
+                <button class="icon-btn" type_="button" onclick={on_queue_new_click} title="Queue all new since last run">
+                    <Icon icon_id={IconId::LucideListEnd} width={"18"} height={"18"} />
+                </button>
+                if let Some(hint) = schedule_hint {
+                    <span id="downloads-schedule-hint" class="schedule-hint" title="Change download windows in Settings">{ hint }</span>
+                }
+                <input id="downloads-search-input" class="search-input" type="search" placeholder="Search creator, title or link" value={(*search).clone()} oninput={on_search_input} />
+                <span id="downloads-date-range" class="date-range" title="Only show and queue backlog items published in this window">
+                    <input type="date" id="downloads-date-after-input" value={props.queue_range.after.clone().unwrap_or_default()} onchange={on_range_bound(true)} />
+                    <span>{"–"}</span>
+                    <input type="date" id="downloads-date-before-input" value={props.queue_range.before.clone().unwrap_or_default()} onchange={on_range_bound(false)} />
+                </span>
+                <select id="downloads-rate-limit-select" class="rate-limit-select" title="Download speed limit; resets to the Settings value on restart" onchange={on_rate_change}>
+                    { for rate_choices.iter().map(|kbps| html!{
+                        <option value={kbps.to_string()} selected={*kbps == *rate_limit}>{ rate_label(*kbps) }</option>
+                    }) }
+                </select>
             </div>
 
+            if props.volume.offline {
+                <div id="downloads-volume-banner" class="volume-banner">
+                    <strong>{"Download drive disconnected"}</strong>
+                    { format!(" {} is not connected. The queue is paused and resumes on its own once the drive is back.", props.volume.mount_point.as_deref().unwrap_or("The download folder")) }
+                </div>
+            }
+
+            { for props.paused_platforms.iter().map(|p| {
+                let platform = p.platform.clone();
+                let on_resume = Callback::from(move |_| {
+                    let platform = platform.clone();
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "platform": platform })).unwrap();
+                        let _ = invoke("resume_platform", args).await;
+                    });
+                });
+                html! {
+                    <div class="volume-banner platform-pause-banner" key={p.platform.clone()}>
+                        <strong>{ format!("{} paused", platforms::label_for(&p.platform)) }</strong>
+                        { format!(" {} of the last {} downloads failed, mostly: {}. Other platforms keep going.", p.failures, p.window, p.dominant.label().to_lowercase()) }
+                        <button class="icon-btn" type_="button" onclick={on_resume}>{"Resume"}</button>
+                    </div>
+                }
+            }) }
+
+            if !props.advisories.is_empty() {
+                { advisories_card(&props.advisories) }
+            }
+
+            {
+                match &props.session_report {
+                    Some(report) => session_report_card(report, props.on_dismiss_report.clone()),
+                    None => html! {},
+                }
+            }
+
+            if searching && queue_rows.is_empty() && pending_rows.is_empty() && backlog_rows.is_empty() && issue_rows.is_empty() {
+                <div class="summary"><p class="search-empty">{"No matching downloads."}</p></div>
+            }
+
             {
                 if props.loading && !has_any_rows {
                     html! {
@@ -866,14 +1498,25 @@ pub fn downloads_page(props: &Props) -> Html {
                                 <ul class="rows">
                                     {
                                         for props.active.iter().map(|active| {
-                                            let plat_label = platform_str(&active.row.platform).to_string();
+                                            let id = active.row.id;
+                                            let is_paused = active.row.status == DownloadStatus::Paused;
+                                            let on_toggle = if is_paused {
+                                                let cb = props.on_resume_download.clone();
+                                                Callback::from(move |_: MouseEvent| cb.emit(id))
+                                            } else {
+                                                let cb = props.on_pause_download.clone();
+                                                Callback::from(move |_: MouseEvent| cb.emit(id))
+                                            };
                                             html! {
-                                                <li class="row-line">
-                                                    <img class="brand-icon" src={platform_icon_src(&plat_label)} />
+                                                <li class={classes!("row-line", is_paused.then_some("paused"))}>
+                                                    <PlatformIcon platform={active.row.platform} link={active.row.link.clone()} />
                                                     <span class="link-text">{ collection_title(&active.row) }</span>
                                                     <span class="link-text" style="opacity:0.9;">{" - "}{ item_label_for_row(&active.row) }</span>
                                                     <div class="row-actions active-status">
                                                         <span class="stage-text">{ &active.stage }</span>
+                                                        if let Some(fraction) = active.fraction {
+                                                            <progress class="download-progress" max="1" value={fraction.to_string()} />
+                                                        }
                                                         {
                                                             if let Some(progress) = &active.progress {
                                                                 html! { <span class="progress-text">{ progress }</span> }
@@ -881,6 +1524,12 @@ pub fn downloads_page(props: &Props) -> Html {
                                                                 html! {}
                                                             }
                                                         }
+                                                        if let Some(detail) = progress_detail(active) {
+                                                            <span class="progress-detail">{ detail }</span>
+                                                        }
+                                                        <button class="icon-btn" type="button" title={if is_paused { "Resume download" } else { "Pause download (keeps the partial file)" }} onclick={on_toggle}>
+                                                            <Icon icon_id={if is_paused { IconId::LucidePlay } else { IconId::LucidePause }} width={"16"} height={"16"} />
+                                                        </button>
                                                     </div>
                                                 </li>
                                             }
@@ -894,10 +1543,10 @@ pub fn downloads_page(props: &Props) -> Html {
             }
 
             {
-                if !props.queue.is_empty() {
+                if !queue_rows.is_empty() {
                     html!{
                         {
-                            render_section(props.queue.clone(), "Queue", false)
+                            render_section(queue_rows, "Queue", false)
                         }
                     }
                 } else {
@@ -906,16 +1555,24 @@ pub fn downloads_page(props: &Props) -> Html {
             }
 
             {
-                if !props.backlog.is_empty() {
-                    html! { render_section(props.backlog.clone(), "Backlog", true) }
+                if !pending_rows.is_empty() {
+                    render_pending(pending_rows)
+                } else {
+                    html! {}
+                }
+            }
+
+            {
+                if !backlog_rows.is_empty() {
+                    html! { render_section(backlog_rows, "Backlog", true) }
                 } else {
                     html! {}
                 }
             }
 
             {
-                if !props.issues.is_empty() {
-                    render_issues(props.issues.clone())
+                if !issue_rows.is_empty() {
+                    render_issues(issue_rows)
                 } else {
                     html! {}
                 }
@@ -1,4 +1,6 @@
+use crate::components::platform_icon::PlatformIcon;
 use crate::dom::assign_missing_descriptive_ids;
+use crate::types::PlatformCapabilities;
 use wasm_bindgen::prelude::*;
 use yew::prelude::*;
 
@@ -6,6 +8,16 @@ use yew::prelude::*;
 extern "C" {
     #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "shell"])]
     async fn open(url: &str);
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+}
+
+fn capability_cell(supported: bool) -> Html {
+    if supported {
+        html! { <td class="capability-yes">{"✓"}</td> }
+    } else {
+        html! { <td class="capability-no">{"—"}</td> }
+    }
 }
 
 #[function_component(ExtensionPage)]
@@ -14,6 +26,18 @@ pub fn extension_page() -> Html {
         assign_missing_descriptive_ids("extension-page");
         || ()
     });
+    let capabilities = use_state(Vec::<PlatformCapabilities>::new);
+    {
+        let capabilities = capabilities.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                let v = invoke("get_platform_capabilities", JsValue::NULL).await;
+                if let Ok(rows) = serde_wasm_bindgen::from_value::<Vec<PlatformCapabilities>>(v) {
+                    capabilities.set(rows);
+                }
+            });
+        });
+    }
     let on_chrome_download_click = Callback::from(|e: MouseEvent| {
         e.prevent_default();
         let url = "https://chromewebstore.google.com/detail/listr/dogifpgpdjhldninabaejghgojpdokmn";
@@ -62,6 +86,44 @@ pub fn extension_page() -> Html {
                     </div>
                 </div>
             </section>
+            <p id="extension-pairing-note">
+                { "To send links straight to the app, turn on \"Allow the browser extension to add links\" in Settings and paste the token shown there into the extension." }
+            </p>
+            <section id="extension-capabilities-section" class="capabilities">
+                <h2 id="extension-capabilities-heading">{ "What works where" }</h2>
+                <table id="extension-capabilities-table" class="capability-table">
+                    <thead>
+                        <tr>
+                            <th>{"Platform"}</th>
+                            <th>{"Video"}</th>
+                            <th>{"Images"}</th>
+                            <th>{"Stories"}</th>
+                            <th>{"Playlists"}</th>
+                            <th>{"Audio only"}</th>
+                            <th>{"Subtitles"}</th>
+                            <th>{"Notes"}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for capabilities.iter().map(|c| html!{
+                            <tr key={c.platform.as_str()}>
+                                <td class="capability-platform">
+                                    <PlatformIcon platform={c.platform} />
+                                    <span>{c.label.clone()}</span>
+                                    <small class="capability-tool">{c.tool.clone()}</small>
+                                </td>
+                                { capability_cell(c.video) }
+                                { capability_cell(c.images) }
+                                { capability_cell(c.stories) }
+                                { capability_cell(c.playlists) }
+                                { capability_cell(c.audio_only) }
+                                { capability_cell(c.subtitles) }
+                                <td class="capability-notes">{c.notes.join("; ")}</td>
+                            </tr>
+                        }) }
+                    </tbody>
+                </table>
+            </section>
         </div>
     }
 }
@@ -1,6 +1,11 @@
 use crate::log;
-use crate::types::DownloadStatus;
+use crate::types::{
+    ContentType, DateRange, DownloadStatus, ImportSummary, MaintenanceTask, TaskStatus,
+    UrlBatchReport, WatchedCollection,
+};
 use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::spawn_local;
@@ -10,7 +15,10 @@ use yew_hooks::prelude::*;
 use yew_icons::{Icon, IconId};
 
 use crate::app::log_invoke_err;
+use crate::components::import_history::ImportHistory;
 use crate::dom::assign_missing_descriptive_ids;
+use crate::events::{subscribe_download_events, DownloadEvent};
+use crate::platforms;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 struct DownloadResult {
@@ -28,17 +36,27 @@ struct LoadedSettings {
 extern "C" {
     #[wasm_bindgen(catch, js_namespace = ["window", "__TAURI__", "core"])]
     async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
-
     #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "event"])]
     async fn listen(event: &str, f: &Closure<dyn FnMut(JsValue)>) -> JsValue;
 }
 
+type Listener = (js_sys::Function, Closure<dyn FnMut(JsValue)>);
+
 #[derive(Properties, PartialEq, Clone)]
 pub struct Props {
     pub on_open_file: Callback<()>,
     pub on_csv_load: Callback<String>,
 }
 
+/// "YYYY-MM-DD" (UTC) of `days` ago, for the history range inputs.
+fn date_days_ago(days: f64) -> String {
+    let ms = js_sys::Date::now() - days * 86_400_000.0;
+    let iso: String = js_sys::Date::new(&JsValue::from_f64(ms))
+        .to_iso_string()
+        .into();
+    iso.chars().take(10).collect()
+}
+
 #[function_component(HomePage)]
 pub fn home_page(props: &Props) -> Html {
     println!("[FRONTEND] [pages/home.rs] [home_page component]");
@@ -52,10 +70,67 @@ pub fn home_page(props: &Props) -> Html {
     let is_downloading = use_state(|| false);
     let download_progress = use_state(|| String::from("Starting download..."));
     let active_download_id = use_state(|| None::<i64>);
-    let is_valid_url = name.contains("instagram.com")
-        || name.contains("tiktok.com")
-        || name.contains("youtube.com")
-        || name.contains("youtu.be");
+    // Maintenance task listing a profile/channel/playlist link, if one is running.
+    let expand_task = use_mut_ref(|| None::<u32>);
+    let is_valid_url = platforms::detect(&name).is_some();
+    let is_collection_input = platforms::detect(&name)
+        .is_some_and(|p| clip_core::url::is_collection_url(p.platform, &name));
+    // optional publish-date window for expanding profiles and playlists
+    let date_range = use_state(DateRange::default);
+
+    {
+        let download_results = download_results.clone();
+        let is_downloading = is_downloading.clone();
+        let download_progress = download_progress.clone();
+        let expand_task = expand_task.clone();
+        use_effect_with((), move |_| {
+            let alive = Rc::new(Cell::new(true));
+            let listener: Rc<RefCell<Option<Listener>>> = Rc::new(RefCell::new(None));
+            {
+                let alive = alive.clone();
+                let listener = listener.clone();
+                spawn_local(async move {
+                    let handler = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+                        let payload = js_sys::Reflect::get(&event, &JsValue::from_str("payload"))
+                            .unwrap_or(JsValue::NULL);
+                        let Ok(task) = serde_wasm_bindgen::from_value::<MaintenanceTask>(payload)
+                        else {
+                            return;
+                        };
+                        if *expand_task.borrow() != Some(task.id) {
+                            return;
+                        }
+                        if task.status == TaskStatus::Running {
+                            download_progress.set(task.message);
+                            return;
+                        }
+                        expand_task.borrow_mut().take();
+                        is_downloading.set(false);
+                        let mut results = (*download_results).clone();
+                        results.push(DownloadResult {
+                            success: task.status == TaskStatus::Completed,
+                            message: task.message,
+                        });
+                        download_results.set(results);
+                    });
+                    let unlisten: js_sys::Function = listen("maintenance_progress", &handler)
+                        .await
+                        .unchecked_into();
+                    if alive.get() {
+                        *listener.borrow_mut() = Some((unlisten, handler));
+                    } else {
+                        let _ = unlisten.call0(&JsValue::NULL);
+                    }
+                });
+            }
+            move || {
+                alive.set(false);
+                if let Some((unlisten, _handler)) = listener.borrow_mut().take() {
+                    let _ = unlisten.call0(&JsValue::NULL);
+                }
+            }
+        });
+    }
 
     {
         let download_results = download_results.clone();
@@ -67,80 +142,54 @@ pub fn home_page(props: &Props) -> Html {
             let is_downloading_clone = is_downloading.clone();
             let download_progress_clone = download_progress.clone();
             let active_download_id = active_download_id.clone();
-            spawn_local(async move {
-                #[derive(serde::Deserialize)]
-                #[serde(tag = "type")]
-                enum DownloadEventPayload {
-                    StatusChanged {
-                        id: i64,
-                        status: DownloadStatus,
-                    },
-                    Progress {
-                        id: i64,
-                        progress: f32,
-                        #[allow(dead_code)]
-                        downloaded_bytes: u64,
-                        #[allow(dead_code)]
-                        total_bytes: Option<u64>,
-                    },
-                    Message {
-                        id: i64,
-                        message: String,
-                    },
-                }
-                let closure = Closure::wrap(Box::new(move |event: JsValue| {
-                    let payload = js_sys::Reflect::get(&event, &JsValue::from_str("payload"))
-                        .unwrap_or(event.clone());
-                    if let Ok(evt) = serde_wasm_bindgen::from_value::<DownloadEventPayload>(payload)
-                    {
-                        if let Some(active_id) = *active_download_id {
-                            match evt {
-                                DownloadEventPayload::Progress { id, progress, .. } => {
-                                    if id == active_id {
-                                        download_progress_clone
-                                            .set(format!("{:.0}% complete", progress * 100.0));
-                                    }
-                                }
-                                DownloadEventPayload::Message { id, message } => {
-                                    if id == active_id {
-                                        download_progress_clone.set(message.clone());
+            let subscription = subscribe_download_events(move |evt| {
+                if let Some(active_id) = *active_download_id {
+                    match evt {
+                        DownloadEvent::Progress { id, progress, .. } => {
+                            if id == active_id {
+                                download_progress_clone
+                                    .set(format!("{:.0}% complete", progress * 100.0));
+                            }
+                        }
+                        DownloadEvent::Message { id, message, .. } => {
+                            if id == active_id {
+                                download_progress_clone.set(message.clone());
+                            }
+                        }
+                        DownloadEvent::StatusChanged { id, status } => {
+                            if id == active_id {
+                                match status {
+                                    DownloadStatus::Done => {
+                                        is_downloading_clone.set(false);
+                                        active_download_id.set(None);
+                                        let mut results = (*download_results).clone();
+                                        results.push(DownloadResult {
+                                            success: true,
+                                            message: "Saved download".into(),
+                                        });
+                                        download_results.set(results);
                                     }
-                                }
-                                DownloadEventPayload::StatusChanged { id, status } => {
-                                    if id == active_id {
-                                        match status {
-                                            DownloadStatus::Done => {
-                                                is_downloading_clone.set(false);
-                                                active_download_id.set(None);
-                                                let mut results = (*download_results).clone();
-                                                results.push(DownloadResult {
-                                                    success: true,
-                                                    message: "Saved download".into(),
-                                                });
-                                                download_results.set(results);
-                                            }
-                                            DownloadStatus::Error | DownloadStatus::Canceled => {
-                                                is_downloading_clone.set(false);
-                                                active_download_id.set(None);
-                                                let mut results = (*download_results).clone();
-                                                results.push(DownloadResult {
-                                                    success: false,
-                                                    message: format!("Download {:?}", status),
-                                                });
-                                                download_results.set(results);
-                                            }
-                                            _ => {}
-                                        }
+                                    DownloadStatus::Error | DownloadStatus::Canceled => {
+                                        is_downloading_clone.set(false);
+                                        active_download_id.set(None);
+                                        let mut results = (*download_results).clone();
+                                        results.push(DownloadResult {
+                                            success: false,
+                                            message: format!("Download {:?}", status),
+                                        });
+                                        download_results.set(results);
                                     }
+                                    _ => {}
                                 }
                             }
                         }
+                        DownloadEvent::BulkStatusChanged { .. }
+                        | DownloadEvent::MetadataUpdated { .. }
+                        | DownloadEvent::MirrorFound { .. } => {}
                     }
-                }) as Box<dyn FnMut(_)>);
-                let _ = listen("download_event", &closure).await;
-                closure.forget();
+                }
             });
-            || {}
+            move || drop(subscription)
         });
     }
 
@@ -169,6 +218,58 @@ pub fn home_page(props: &Props) -> Html {
             || {}
         });
     }
+    // watch the collection link for new items instead of listing it once
+    let on_watch = {
+        let greet_input_ref = greet_input_ref.clone();
+        let download_results = download_results.clone();
+        Callback::from(move |_: MouseEvent| {
+            let value = greet_input_ref
+                .cast::<web_sys::HtmlInputElement>()
+                .unwrap()
+                .value();
+            let download_results = download_results.clone();
+            spawn_local(async move {
+                let args =
+                    serde_wasm_bindgen::to_value(&serde_json::json!({ "url": value })).unwrap();
+                let result = match invoke("watch_collection", args).await {
+                    Ok(v) => match serde_wasm_bindgen::from_value::<WatchedCollection>(v) {
+                        Ok(w) => DownloadResult {
+                            success: true,
+                            message: format!(
+                                "Watching {} every {}h; new items go to the backlog (see Library → Watched)",
+                                w.handle, w.interval_hours
+                            ),
+                        },
+                        Err(e) => DownloadResult {
+                            success: false,
+                            message: e.to_string(),
+                        },
+                    },
+                    Err(e) => DownloadResult {
+                        success: false,
+                        message: e.as_string().unwrap_or_else(|| format!("{e:?}")),
+                    },
+                };
+                download_results.set(vec![result]);
+            });
+        })
+    };
+    let on_date_bound = |after: bool| {
+        let date_range = date_range.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            let value = (!value.is_empty()).then_some(value);
+            let mut range = (*date_range).clone();
+            if after {
+                range.after = value;
+            } else {
+                range.before = value;
+            }
+            date_range.set(range);
+        })
+    };
     let toggle_output_icon = {
         let output_icon_is_music = output_icon_is_music.clone();
         Callback::from(move |_| output_icon_is_music.set(!*output_icon_is_music))
@@ -194,6 +295,8 @@ pub fn home_page(props: &Props) -> Html {
         let download_progress = download_progress.clone();
         let current_output_state = current_output_state.clone();
         let active_download_id = active_download_id.clone();
+        let expand_task = expand_task.clone();
+        let date_range = date_range.clone();
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
             is_downloading.set(true);
@@ -205,22 +308,51 @@ pub fn home_page(props: &Props) -> Html {
                 .value();
             log::info("home_download_clicked", serde_json::json!({ "url": value }));
             web_sys::console::log_1(&format!("Form submitted with URL: {}", value).into());
+            let is_collection = platforms::detect(&value)
+                .is_some_and(|p| clip_core::url::is_collection_url(p.platform, &value));
+            if is_collection {
+                download_progress.set("Listing items...".to_string());
+                let expand_task = expand_task.clone();
+                let is_downloading = is_downloading.clone();
+                let download_results = download_results.clone();
+                let range = (*date_range).clone();
+                spawn_local(async move {
+                    let args = serde_wasm_bindgen::to_value(&serde_json::json!({
+                        "url": value,
+                        "dateRange": (!range.is_open()).then_some(range),
+                    }))
+                    .unwrap();
+                    // Playlists become rows named after the playlist's title
+                    let cmd = match platforms::detect(&value)
+                        .and_then(|p| clip_core::url::infer_origin(p.platform, &value))
+                    {
+                        Some(ContentType::Playlist) => "expand_playlist",
+                        _ => "expand_collection_url",
+                    };
+                    match invoke(cmd, args).await {
+                        Ok(val) => {
+                            if let Ok(id) = serde_wasm_bindgen::from_value::<u32>(val) {
+                                *expand_task.borrow_mut() = Some(id);
+                            }
+                        }
+                        Err(e) => {
+                            is_downloading.set(false);
+                            download_results.set(vec![DownloadResult {
+                                success: false,
+                                message: e.as_string().unwrap_or_else(|| format!("{e:?}")),
+                            }]);
+                        }
+                    }
+                });
+                return;
+            }
             let want_audio = *current_output_state;
-            // Encode Home flags directly into the URL so backend reliably honors them
-            let url_for_backend = {
-                let mut u = value.clone();
-                if want_audio {
-                    u.push_str("#__audio_only__");
-                }
-                // Home downloads should always target the flat destination (settings root)
-                u.push_str("#__flat__");
-                u
-            };
             wasm_bindgen_futures::spawn_local({
                 let active_download_id = active_download_id.clone();
                 async move {
                     let fmt = if want_audio { "audio" } else { "video" };
-                    let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "url": url_for_backend, "output_format": fmt, "flat_destination": true })).unwrap();
+                    // Home downloads always target the flat destination (settings root)
+                    let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "url": value, "outputFormat": fmt, "flatDestination": true })).unwrap();
                     match invoke("download_url", args).await {
                         Ok(val) => {
                             if let Ok(id) = serde_wasm_bindgen::from_value::<i64>(val) {
@@ -240,8 +372,16 @@ pub fn home_page(props: &Props) -> Html {
         let is_downloading = is_downloading.clone();
         let download_results = download_results.clone();
         let active_download_id = active_download_id.clone();
+        let expand_task = expand_task.clone();
         Callback::from(move |_| {
             log::warn("home_download_cancel", serde_json::json!({}));
+            if let Some(id) = expand_task.borrow_mut().take() {
+                spawn_local(async move {
+                    let args =
+                        serde_wasm_bindgen::to_value(&serde_json::json!({ "id": id })).unwrap();
+                    let _ = invoke("cancel_maintenance_task", args).await;
+                });
+            }
             is_downloading.set(false);
             download_results.set(vec![]);
             spawn_local({
@@ -258,6 +398,173 @@ pub fn home_page(props: &Props) -> Html {
         })
     };
 
+    let review_imports = use_state(crate::app::review_imports);
+    let on_review_imports_change = {
+        let review_imports = review_imports.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            crate::app::set_review_imports(checked);
+            review_imports.set(checked);
+        })
+    };
+
+    let on_import_takeout = {
+        let review_imports = review_imports.clone();
+        let download_results = download_results.clone();
+        Callback::from(move |_: MouseEvent| {
+            let args =
+                serde_wasm_bindgen::to_value(&serde_json::json!({ "review": *review_imports }))
+                    .unwrap();
+            let download_results = download_results.clone();
+            spawn_local(async move {
+                let result = match invoke("import_takeout", args).await {
+                    Ok(v) => {
+                        let s: ImportSummary =
+                            serde_wasm_bindgen::from_value(v).unwrap_or_default();
+                        DownloadResult {
+                            success: true,
+                            message: format!(
+                                "Imported {} links from the export ({} already known)",
+                                s.inserted,
+                                s.skipped + s.updated
+                            ),
+                        }
+                    }
+                    Err(e) => DownloadResult {
+                        success: false,
+                        message: e.as_string().unwrap_or_else(|| format!("{e:?}")),
+                    },
+                };
+                download_results.set(vec![result]);
+            });
+        })
+    };
+
+    // browser history range; visited items are proposed for review
+    let history_from = use_state(|| date_days_ago(30.0));
+    let history_to = use_state(|| date_days_ago(0.0));
+    let history_busy = use_state(|| false);
+    let on_history_from_change = {
+        let history_from = history_from.clone();
+        Callback::from(move |e: Event| {
+            history_from.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            );
+        })
+    };
+    let on_history_to_change = {
+        let history_to = history_to.clone();
+        Callback::from(move |e: Event| {
+            history_to.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            );
+        })
+    };
+    let on_import_history = {
+        let history_from = history_from.clone();
+        let history_to = history_to.clone();
+        let history_busy = history_busy.clone();
+        let download_results = download_results.clone();
+        Callback::from(move |_| {
+            history_busy.set(true);
+            let args = serde_wasm_bindgen::to_value(&serde_json::json!({
+                "from": *history_from,
+                "to": *history_to,
+            }))
+            .unwrap();
+            let history_busy = history_busy.clone();
+            let download_results = download_results.clone();
+            spawn_local(async move {
+                let result = match invoke("import_browser_history", args).await {
+                    Ok(v) => {
+                        let n = serde_wasm_bindgen::from_value::<u64>(v).unwrap_or(0);
+                        DownloadResult {
+                            success: true,
+                            message: format!(
+                                "{n} links from your browser history are waiting for review in Downloads"
+                            ),
+                        }
+                    }
+                    Err(e) => DownloadResult {
+                        success: false,
+                        message: e.as_string().unwrap_or_else(|| format!("{e:?}")),
+                    },
+                };
+                download_results.set(vec![result]);
+                history_busy.set(false);
+            });
+        })
+    };
+
+    // pasted list of links, one per line
+    let batch_input_ref = use_node_ref();
+    let batch_busy = use_state(|| false);
+    let on_batch = |queue: bool| {
+        let batch_input_ref = batch_input_ref.clone();
+        let batch_busy = batch_busy.clone();
+        let download_results = download_results.clone();
+        Callback::from(move |_: MouseEvent| {
+            let Some(input) = batch_input_ref.cast::<web_sys::HtmlTextAreaElement>() else {
+                return;
+            };
+            let text = input.value();
+            if text.trim().is_empty() {
+                return;
+            }
+            batch_busy.set(true);
+            let args = serde_wasm_bindgen::to_value(&serde_json::json!({
+                "text": text,
+                "queue": queue,
+            }))
+            .unwrap();
+            let batch_busy = batch_busy.clone();
+            let download_results = download_results.clone();
+            spawn_local(async move {
+                let results = match invoke("download_urls", args).await {
+                    Ok(v) => {
+                        let report: UrlBatchReport =
+                            serde_wasm_bindgen::from_value(v).unwrap_or_default();
+                        // keep only the lines that need fixing
+                        input.set_value(&report.invalid.join("\n"));
+                        let mut message = format!(
+                            "Added {}, {} already known",
+                            report.added.len(),
+                            report.known
+                        );
+                        if queue {
+                            message.push_str(&format!(", {} queued", report.queued));
+                        }
+                        let mut results = vec![DownloadResult {
+                            success: true,
+                            message,
+                        }];
+                        if !report.invalid.is_empty() {
+                            results.push(DownloadResult {
+                                success: false,
+                                message: format!(
+                                    "{} lines aren't links from a supported site: {}",
+                                    report.invalid.len(),
+                                    report.invalid.join(", ")
+                                ),
+                            });
+                        }
+                        results
+                    }
+                    Err(e) => vec![DownloadResult {
+                        success: false,
+                        message: e.as_string().unwrap_or_else(|| format!("{e:?}")),
+                    }],
+                };
+                download_results.set(results);
+                batch_busy.set(false);
+            });
+        })
+    };
+
     let open_click = {
         println!("[FRONTEND] [pages/home.rs] [open_click callback]");
         let on_open_file = props.on_open_file.clone();
@@ -339,6 +646,15 @@ pub fn home_page(props: &Props) -> Html {
                     }
                 } else { html!{} }}
             </form>
+            if is_collection_input && !*is_downloading {
+                <div id="home-date-range" class="row home-actions" title="Only add items published in this window; leave a side empty for no limit">
+                    <span id="home-date-range-label">{"Published"}</span>
+                    <input type="date" id="home-date-after-input" value={date_range.after.clone().unwrap_or_default()} onchange={on_date_bound(true)} />
+                    <span>{"to"}</span>
+                    <input type="date" id="home-date-before-input" value={date_range.before.clone().unwrap_or_default()} onchange={on_date_bound(false)} />
+                    <button id="home-watch-collection-button" type="button" title="Check this link for new items every day and add them to the backlog" onclick={on_watch}>{"Watch"}</button>
+                </div>
+            }
 
             { if *is_downloading {
                 html! {
@@ -365,7 +681,29 @@ pub fn home_page(props: &Props) -> Html {
             </div>
             <div id="home-secondary-actions" class="row home-actions">
                 <button id="home-import-list-button" type="button" onclick={open_click}>{"Import list"}</button>
+                <button id="home-import-takeout-button" type="button" title="YouTube Takeout watch history or playlist CSV, or a TikTok data export JSON" onclick={on_import_takeout}>{"Import takeout"}</button>
+                <label id="home-review-imports-label" for="home-review-imports-checkbox" style="display:flex; gap:6px; align-items:center;">
+                    <input type="checkbox" id="home-review-imports-checkbox" checked={*review_imports} onchange={on_review_imports_change} />
+                    {"Review before backlog"}
+                </label>
+            </div>
+            <div id="home-history-import" class="row home-actions" title="Supported videos and posts you visited, grouped by site for review">
+                <span id="home-history-import-label">{"Browser history"}</span>
+                <input type="date" id="home-history-from-input" value={(*history_from).clone()} onchange={on_history_from_change} />
+                <input type="date" id="home-history-to-input" value={(*history_to).clone()} onchange={on_history_to_change} />
+                <button id="home-import-history-button" type="button" disabled={*history_busy} onclick={on_import_history}>
+                    { if *history_busy { "Reading…" } else { "Propose links" } }
+                </button>
             </div>
+            <details id="home-batch-urls" class="batch-urls">
+                <summary>{"Paste several links"}</summary>
+                <textarea id="home-batch-urls-input" ref={batch_input_ref.clone()} rows="8" placeholder="One link per line" disabled={*batch_busy}></textarea>
+                <div id="home-batch-urls-actions" class="batch-urls-actions">
+                    <button id="home-batch-download-button" type="button" disabled={*batch_busy} onclick={on_batch(true)}>{"Download all"}</button>
+                    <button id="home-batch-backlog-button" type="button" disabled={*batch_busy} onclick={on_batch(false)}>{"Add to backlog"}</button>
+                </div>
+            </details>
+            <ImportHistory />
         </main>
     }
 }
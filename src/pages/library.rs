@@ -1,5 +1,15 @@
+use crate::components::collection_cover::CollectionCover;
+use crate::components::item_thumbnail::ItemThumbnail;
+use crate::components::tag_chips::TagChips;
+use crate::components::trash_panel::TrashPanel;
+use crate::components::watch_panel::WatchPanel;
 use crate::dom::assign_missing_descriptive_ids;
-use crate::types::{content_type_str, platform_str, ClipRow, MediaKind};
+use crate::pages::downloads::{duration_hint, format_bytes};
+use crate::platforms;
+use crate::types::{
+    ClipRow, LibraryPlatformNode, MediaKind, RowPage, RowSort, TagCount, VolumeStatus,
+};
+use std::collections::{HashMap, HashSet};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
@@ -13,143 +23,508 @@ extern "C" {
 
 /* ───────── helpers mirrored from downloads.rs for consistent look ───────── */
 
-fn url_after_domain(url: &str) -> String {
-    let no_scheme = url.split("//").nth(1).unwrap_or(url);
-    match no_scheme.find('/') {
-        Some(i) => no_scheme[i + 1..].to_string(),
-        None => String::new(),
-    }
-}
-fn last_two_path_segments(url: &str) -> String {
-    let tail = url_after_domain(url);
-    let parts: Vec<&str> = tail.split('/').filter(|s| !s.is_empty()).collect();
-    match parts.len() {
-        0 => tail,
-        1 => parts[0].to_string(),
-        _ => format!("{}/{}", parts[parts.len() - 2], parts[parts.len() - 1]),
-    }
-}
+/// The media title once metadata is known, else a label derived from the link.
 fn item_label_for_row(row: &ClipRow) -> String {
-    let link = row.link.trim();
-    let platform = platform_str(&row.platform);
-    if platform == "instagram" {
-        let tail = url_after_domain(link);
-        let mut parts = tail.split('/').filter(|s| !s.is_empty());
-        let _maybe_user = parts.next().unwrap_or_default();
-        let b = parts.next().unwrap_or_default();
-        let c = parts.next().unwrap_or_default();
-        if (b == "p" || b == "reel") && !c.is_empty() {
-            format!("{b}/{c}")
-        } else {
-            last_two_path_segments(link)
-        }
-    } else if platform == "tiktok" {
-        let tail = url_after_domain(link);
-        let pieces: Vec<&str> = tail.split('/').filter(|s| !s.is_empty()).collect();
-        if let Some(pos) = pieces.iter().position(|p| *p == "photo" || *p == "video") {
-            if pos + 1 < pieces.len() {
-                format!("{}/{}", pieces[pos], pieces[pos + 1])
-            } else {
-                last_two_path_segments(link)
-            }
-        } else {
-            last_two_path_segments(link)
-        }
-    } else {
-        last_two_path_segments(link)
-    }
+    row.title
+        .clone()
+        .unwrap_or_else(|| clip_core::url::item_label(row.platform, &row.link))
 }
-fn platform_icon_src(p: &str) -> &'static str {
-    match p {
-        "instagram" => "public/instagram.webp",
-        "pinterest" => "public/pinterest.png",
-        "tiktok" => "public/tiktok.webp",
-        "youtube" => "public/youtube.webp",
-        _ => "",
+
+/// "uploader · 2024-05-01 · 1200 views" for the link tooltip, with the
+/// description below; empty without metadata.
+fn metadata_hint(row: &ClipRow) -> String {
+    let views = row.view_count.map(|n| format!("{n} views"));
+    let line = [
+        row.uploader.as_deref(),
+        row.upload_date.as_deref(),
+        views.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" · ");
+    match row.description.as_deref().map(str::trim) {
+        Some(desc) if !desc.is_empty() => {
+            let desc: String = desc.chars().take(300).collect();
+            format!("{line}\n\n{desc}").trim_start().to_string()
+        }
+        _ => line,
     }
 }
+
 fn collection_title(row: &ClipRow) -> String {
     let handle = if row.handle.trim().is_empty() {
         "Unknown"
     } else {
         &row.handle
     };
-    let typ = content_type_str(&row.content_type);
+    let typ = row.content_type.as_str();
     format!("{handle} | {typ}")
 }
 
+/// Rows fetched per request when a collection is expanded.
+const COLLECTION_PAGE: usize = 200;
+
+/// Orders offered above the collections, with their button labels.
+const SORTS: [(RowSort, &str); 4] = [
+    (RowSort::Title, "Title"),
+    (RowSort::Newest, "Newest"),
+    (RowSort::Largest, "Size"),
+    (RowSort::Longest, "Length"),
+];
+
+/// "3:24 · 12.5 MB" once the download's length and size are known.
+fn size_hint(row: &ClipRow) -> String {
+    let duration = duration_hint(row);
+    let size = row.file_bytes.map(format_bytes);
+    [Some(duration).filter(|d| !d.is_empty()), size]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+fn collection_key(platform: &str, handle: &str, origin: &str) -> String {
+    format!("library::{platform}::{handle}::{origin}")
+}
+fn display_handle(handle: &str) -> &str {
+    if handle.trim().is_empty() {
+        "Unknown"
+    } else {
+        handle
+    }
+}
+
+/// Remove one item from the counts of a collection, pruning empty nodes.
+fn decrement_tree(tree: &mut Vec<LibraryPlatformNode>, platform: &str, handle: &str, origin: &str) {
+    if let Some(node) = tree.iter_mut().find(|n| n.platform == platform) {
+        if let Some(col) = node
+            .collections
+            .iter_mut()
+            .find(|c| c.handle == handle && c.origin == origin)
+        {
+            col.item_count = col.item_count.saturating_sub(1);
+            node.item_count = node.item_count.saturating_sub(1);
+        }
+        node.collections.retain(|c| c.item_count > 0);
+    }
+    tree.retain(|n| !n.collections.is_empty());
+}
+
+/// One search or tag hit: the same line as inside an expanded collection, minus delete.
+fn search_result_row(row: &ClipRow, offline: bool, on_tags_change: Callback<()>) -> Html {
+    let on_open_file = {
+        let link = row.link.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            e.stop_propagation();
+            let l = link.clone();
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "link": l })).unwrap();
+                let _ = invoke("open_file_for_link", args).await;
+            });
+        })
+    };
+    let on_open_folder = {
+        let link = row.link.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            e.stop_propagation();
+            let l = link.clone();
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "link": l })).unwrap();
+                let _ = invoke("open_folder_for_link", args).await;
+            });
+        })
+    };
+    html! {
+        <li class={classes!("row-line", offline.then_some("offline"))} key={row.link.clone()}>
+            <img class="brand-icon" src={platforms::icon_for(row.platform.as_str())} />
+            <a class="link-text" href={row.link.clone()} target="_blank" title={metadata_hint(row)}>
+                { collection_title(row) }{" - "}{ item_label_for_row(row) }
+            </a>
+            if offline {
+                <span class="offline-badge" title="The drive holding this file is not connected">{"Offline"}</span>
+            }
+            if row.subtitle_path.is_some() {
+                <span class="cc-badge" title="Subtitles downloaded">{"CC"}</span>
+            }
+            <TagChips link={row.link.clone()} tags={row.tags.clone()} on_change={on_tags_change} />
+            <div class="row-actions">
+                <button class="icon-btn" type_="button" title="Play" disabled={offline} onclick={on_open_file}>
+                    <Icon icon_id={IconId::LucidePlay} width={"18"} height={"18"} />
+                </button>
+                <button class="icon-btn" type_="button" title="Show in folder" disabled={offline} onclick={on_open_folder}>
+                    <Icon icon_id={IconId::LucideFolder} width={"18"} height={"18"} />
+                </button>
+            </div>
+        </li>
+    }
+}
+
 /* ───────────────────────── component ───────────────────────── */
 
+#[derive(Properties, PartialEq, Clone)]
+pub struct Props {
+    /// Items on an unplugged download drive show as offline rather than missing
+    pub volume: VolumeStatus,
+}
+
 #[function_component(LibraryPage)]
-pub fn library_page() -> Html {
+pub fn library_page(props: &Props) -> Html {
+    let offline = props.volume.offline;
     use_effect(|| {
         assign_missing_descriptive_ids("library-page");
         || ()
     });
-    let done_rows = use_state(|| Vec::<ClipRow>::new());
+    let tree = use_state(Vec::<LibraryPlatformNode>::new);
+
+    // rows of expanded collections, fetched lazily and keyed by collection_key()
+    let items = use_state(HashMap::<String, Vec<ClipRow>>::new);
+    let items_ref = use_mut_ref(HashMap::<String, Vec<ClipRow>>::new);
 
-    // load once
+    // expand/collapse state (namespaced with "library")
+    let expanded_platforms = use_state(|| HashSet::<String>::new());
+    let expanded_collections = use_state(|| HashSet::<String>::new());
+
+    // load the grouped tree once, and again when rows come back from the trash
+    let reload_tree = {
+        let tree = tree.clone();
+        let items = items.clone();
+        let items_ref = items_ref.clone();
+        let expanded_collections = expanded_collections.clone();
+        Callback::from(move |_: ()| {
+            let tree = tree.clone();
+            // cached collection rows may be missing what came back
+            items_ref.borrow_mut().clear();
+            items.set(HashMap::new());
+            expanded_collections.set(HashSet::new());
+            spawn_local(async move {
+                let v = invoke("list_library_tree", JsValue::NULL).await;
+                if let Ok(nodes) = serde_wasm_bindgen::from_value::<Vec<LibraryPlatformNode>>(v) {
+                    tree.set(nodes);
+                }
+            });
+        })
+    };
     {
-        let done_rows = done_rows.clone();
+        let reload_tree = reload_tree.clone();
         use_effect_with((), move |_| {
+            reload_tree.emit(());
+            || ()
+        });
+    }
+
+    // order of the rows inside expanded collections
+    let sort = use_state(|| RowSort::Title);
+
+    // next page of a collection's rows (key, platform, handle, origin),
+    // appended to what is already loaded
+    let load_page = {
+        let items = items.clone();
+        let items_ref = items_ref.clone();
+        let sort = *sort;
+        Callback::from(move |(k, p, h, o): (String, String, String, String)| {
+            let items = items.clone();
+            let items_ref = items_ref.clone();
+            let offset = items_ref.borrow().get(&k).map_or(0, Vec::len);
             spawn_local(async move {
-                let v = invoke("list_done", JsValue::NULL).await;
-                if let Ok(rows) = serde_wasm_bindgen::from_value::<Vec<ClipRow>>(v) {
-                    done_rows.set(rows);
+                let args = serde_wasm_bindgen::to_value(&serde_json::json!({
+                    "offset": offset,
+                    "limit": COLLECTION_PAGE,
+                    "sort": sort,
+                    "filter": { "platform": p, "handle": h, "origin": o },
+                }))
+                .unwrap();
+                let v = invoke("list_done_paged", args).await;
+                if let Ok(page) = serde_wasm_bindgen::from_value::<RowPage>(v) {
+                    let mut cache = items_ref.borrow_mut();
+                    let rows = cache.entry(k).or_default();
+                    for row in page.rows {
+                        if !rows.iter().any(|r| r.link == row.link) {
+                            rows.push(row);
+                        }
+                    }
+                    drop(cache);
+                    items.set(items_ref.borrow().clone());
                 }
             });
+        })
+    };
+
+    // a new order refetches the open collections from the start
+    {
+        let items = items.clone();
+        let items_ref = items_ref.clone();
+        let tree = tree.clone();
+        let expanded_collections = expanded_collections.clone();
+        let load_page = load_page.clone();
+        use_effect_with(*sort, move |_| {
+            items_ref.borrow_mut().clear();
+            items.set(HashMap::new());
+            for node in tree.iter() {
+                for col in &node.collections {
+                    let k = collection_key(&node.platform, &col.handle, &col.origin);
+                    if expanded_collections.contains(&k) {
+                        load_page.emit((
+                            k,
+                            node.platform.clone(),
+                            col.handle.clone(),
+                            col.origin.clone(),
+                        ));
+                    }
+                }
+            }
             || ()
         });
     }
+    let on_sort = |value: RowSort| {
+        let sort = sort.clone();
+        Callback::from(move |_: MouseEvent| sort.set(value))
+    };
 
-    // expand/collapse state (namespaced with "library")
-    let expanded_platforms = use_state(|| std::collections::HashSet::<String>::new());
-    let expanded_collections = use_state(|| std::collections::HashSet::<String>::new());
+    // card view: one cover card per collection instead of the platform tree
+    let card_view = use_state(|| false);
+    // grid view: the platform tree, with thumbnails instead of text rows
+    let grid_view = use_state(|| false);
+    let grid = *grid_view;
+    let on_show_list = {
+        let card_view = card_view.clone();
+        let grid_view = grid_view.clone();
+        Callback::from(move |_| {
+            card_view.set(false);
+            grid_view.set(false);
+        })
+    };
+    let on_show_grid = {
+        let card_view = card_view.clone();
+        let grid_view = grid_view.clone();
+        Callback::from(move |_| {
+            card_view.set(false);
+            grid_view.set(true);
+        })
+    };
+    let on_show_cards = {
+        let card_view = card_view.clone();
+        let grid_view = grid_view.clone();
+        Callback::from(move |_| {
+            card_view.set(true);
+            grid_view.set(false);
+        })
+    };
 
-    // group like /downloads: platform -> (handle,type) -> rows
-    use std::collections::{BTreeMap, HashSet};
-    let mut map: BTreeMap<String, BTreeMap<(String, String), Vec<ClipRow>>> = BTreeMap::new();
-    let mut seen = HashSet::<String>::new();
+    // tags in use, reloaded whenever a row's tags change
+    let all_tags = use_state(Vec::<TagCount>::new);
+    let tag_filter = use_state(|| None::<String>);
+    let reload_tags = {
+        let all_tags = all_tags.clone();
+        Callback::from(move |_: ()| {
+            let all_tags = all_tags.clone();
+            spawn_local(async move {
+                let v = invoke("list_tags", JsValue::NULL).await;
+                if let Ok(tags) = serde_wasm_bindgen::from_value::<Vec<TagCount>>(v) {
+                    all_tags.set(tags);
+                }
+            });
+        })
+    };
+    {
+        let reload_tags = reload_tags.clone();
+        use_effect_with((), move |_| {
+            reload_tags.emit(());
+            || ()
+        });
+    }
 
-    for mut r in (*done_rows).clone() {
-        if r.handle.trim().is_empty() {
-            r.handle = "Unknown".into();
-        }
-        let plat = platform_str(&r.platform).to_string();
-        let typ = content_type_str(&r.content_type).to_string();
-
-        let key = format!(
-            "{}|{}|{}|{}",
-            plat,
-            r.handle.to_lowercase().trim(),
-            typ,
-            r.link.trim()
-        );
-        if !seen.insert(key) {
-            continue;
-        }
+    // full-text search or a picked tag; while either is set the hits replace the tree
+    let search = use_state(String::new);
+    let search_hits = use_state(|| None::<Vec<ClipRow>>);
+    let search_seq = use_mut_ref(|| 0u32);
+    let on_tag_click = {
+        let search = search.clone();
+        let search_hits = search_hits.clone();
+        let search_seq = search_seq.clone();
+        let tag_filter = tag_filter.clone();
+        Callback::from(move |tag: String| {
+            search.set(String::new());
+            *search_seq.borrow_mut() += 1;
+            if tag_filter.as_deref() == Some(tag.as_str()) {
+                tag_filter.set(None);
+                search_hits.set(None);
+                return;
+            }
+            tag_filter.set(Some(tag.clone()));
+            let search_hits = search_hits.clone();
+            spawn_local(async move {
+                let args =
+                    serde_wasm_bindgen::to_value(&serde_json::json!({ "tag": tag })).unwrap();
+                let v = invoke("list_downloads_by_tag", args).await;
+                search_hits.set(Some(
+                    serde_wasm_bindgen::from_value::<Vec<ClipRow>>(v).unwrap_or_default(),
+                ));
+            });
+        })
+    };
+    let on_search_input = {
+        let search = search.clone();
+        let search_hits = search_hits.clone();
+        let search_seq = search_seq.clone();
+        let tag_filter = tag_filter.clone();
+        Callback::from(move |e: InputEvent| {
+            let query = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            search.set(query.clone());
+            tag_filter.set(None);
+            let seq = {
+                let mut s = search_seq.borrow_mut();
+                *s += 1;
+                *s
+            };
+            if query.trim().is_empty() {
+                search_hits.set(None);
+                return;
+            }
+            let search_hits = search_hits.clone();
+            let search_seq = search_seq.clone();
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&serde_json::json!({
+                    "query": query,
+                    "statusFilter": "done",
+                }))
+                .unwrap();
+                let v = invoke("search_downloads", args).await;
+                // a newer keystroke already sent its own search
+                if *search_seq.borrow() != seq {
+                    return;
+                }
+                let rows = serde_wasm_bindgen::from_value::<Vec<ClipRow>>(v).unwrap_or_default();
+                let mut seen = HashSet::new();
+                search_hits.set(Some(
+                    rows.into_iter()
+                        .filter(|r| seen.insert(r.link.clone()))
+                        .collect(),
+                ));
+            });
+        })
+    };
 
-        map.entry(plat)
-            .or_default()
-            .entry((r.handle.clone(), typ))
-            .or_default()
-            .push(r);
-    }
+    let results = match &*search_hits {
+        None => html! {},
+        Some(rows) if rows.is_empty() => html! {
+            <div class="summary"><p class="search-empty">{"No matching clips."}</p></div>
+        },
+        Some(rows) => html! {
+            <div class="summary">
+                <div class="rows-card no-indent">
+                    <ul class="rows">
+                        { for rows.iter().map(|row| search_result_row(row, offline, reload_tags.clone())) }
+                    </ul>
+                </div>
+            </div>
+        },
+    };
 
-    // sort each collection by label for stability
-    for col_map in map.values_mut() {
-        for rows in col_map.values_mut() {
-            rows.sort_by(|a, b| item_label_for_row(a).cmp(&item_label_for_row(b)));
+    let tag_bar = if all_tags.is_empty() {
+        html! {}
+    } else {
+        html! {
+            <div id="library-tag-bar" class="tag-bar">
+                {
+                    for all_tags.iter().map(|t| {
+                        let active = tag_filter.as_deref() == Some(t.name.as_str());
+                        let onclick = {
+                            let cb = on_tag_click.clone();
+                            let name = t.name.clone();
+                            Callback::from(move |_: MouseEvent| cb.emit(name.clone()))
+                        };
+                        html! {
+                            <button type_="button" class={classes!("tag-chip", active.then_some("active"))} key={t.name.clone()} {onclick}>
+                                { format!("{} ({})", t.name, t.count) }
+                            </button>
+                        }
+                    })
+                }
+            </div>
         }
-    }
+    };
+
+    let cards = if *card_view && search_hits.is_none() {
+        html! {
+            <div class="collection-cards">
+                {
+                    for (*tree).iter().flat_map(|node| {
+                        node.collections.iter().map(move |col| (node.platform.clone(), col.clone()))
+                    }).map(|(plat_label, col)| {
+                        let on_open_folder = {
+                            let (p, h, t) = (plat_label.clone(), col.handle.clone(), col.origin.clone());
+                            Callback::from(move |e: MouseEvent| {
+                                e.prevent_default();
+                                e.stop_propagation();
+                                let (p, h, t) = (p.clone(), h.clone(), t.clone());
+                                spawn_local(async move {
+                                    let args = serde_wasm_bindgen::to_value(&serde_json::json!({
+                                        "platform": p,
+                                        "handle": h,
+                                        "contentType": t,
+                                    })).unwrap();
+                                    let _ = invoke("open_collection_folder", args).await;
+                                });
+                            })
+                        };
+                        html!{
+                            <div class="collection-card" key={collection_key(&plat_label, &col.handle, &col.origin)}>
+                                <CollectionCover platform={plat_label.clone()} handle={col.handle.clone()} origin={col.origin.clone()} />
+                                <div class="collection-card-body">
+                                    <img class="brand-icon" src={platforms::icon_for(&plat_label)} />
+                                    <span class="item-title">{ format!("{} | {}", display_handle(&col.handle), col.origin) }</span>
+                                </div>
+                                <div class="collection-card-footer">
+                                    <span>{ format!("{} items", col.item_count) }</span>
+                                    <button class="icon-btn" type_="button" title="Show in folder" onclick={on_open_folder}>
+                                        <Icon icon_id={IconId::LucideFolder} width={"18"} height={"18"} />
+                                    </button>
+                                </div>
+                            </div>
+                        }
+                    })
+                }
+            </div>
+        }
+    } else {
+        html! {}
+    };
 
     html! {
         <main id="library-page" class="container downloads library">
-            <h1>{"Library"}</h1>
-            <div class="summary">
+            <div class="library-header">
+                <h1>{"Library"}</h1>
+                <input id="library-search-input" class="search-input" type="search" placeholder="Search creator, title or link" value={(*search).clone()} oninput={on_search_input} />
+                <div class="library-view-toggle">
+                    <button type_="button" class={classes!((!*card_view && !grid).then_some("active"))} onclick={on_show_list}>{"List"}</button>
+                    <button type_="button" class={classes!((!*card_view && grid).then_some("active"))} onclick={on_show_grid}>{"Grid"}</button>
+                    <button type_="button" class={classes!((*card_view).then_some("active"))} onclick={on_show_cards}>{"Cards"}</button>
+                </div>
+                <div id="library-sort" class="library-view-toggle" title="Order of the items in open collections" hidden={*card_view}>
+                    { for SORTS.into_iter().map(|(value, label)| html! {
+                        <button type_="button" class={classes!((*sort == value).then_some("active"))} onclick={on_sort(value)}>{ label }</button>
+                    }) }
+                </div>
+            </div>
+            if offline {
+                <div id="library-volume-banner" class="volume-banner">
+                    <strong>{"Library drive disconnected"}</strong>
+                    { format!(" Connect {} to play or reveal these items. Nothing has been removed.", props.volume.mount_point.as_deref().unwrap_or("the drive")) }
+                </div>
+            }
+            { tag_bar }
+            { results }
+            { cards }
+            <div class="summary" hidden={*card_view || search_hits.is_some()}>
                 {
-                    for map.into_iter().map(|(plat_label, col_map)| {
+                    for (*tree).clone().into_iter().map(|node| {
                         let section_id = "library";
+                        let plat_label = node.platform.clone();
                         let platform_key = format!("{}::{}", section_id, plat_label);
                         let is_open = expanded_platforms.contains(&platform_key);
 
@@ -163,25 +538,24 @@ pub fn library_page() -> Html {
                             })
                         };
 
-                        let collections_count = col_map.len();
-                        let items_count: usize = col_map.values().map(|v| v.len()).sum();
+                        let collections_count = node.collections.len();
+                        let items_count = node.item_count;
 
-                        // Gather links under this platform for actions
-                        let platform_links: Vec<String> = col_map.values().flat_map(|rs| rs.iter().map(|r| r.link.clone())).collect();
                         let on_platform_delete = {
-                            let done_rows = done_rows.clone();
-                            let links = platform_links.clone();
+                            let tree = tree.clone();
+                            let items = items.clone();
+                            let items_ref = items_ref.clone();
                             let plat_for_backend = plat_label.clone();
                             Callback::from(move |e: MouseEvent| {
                                 e.prevent_default();
                                 e.stop_propagation();
                                 // optimistic UI update
-                                let filtered: Vec<ClipRow> = (*done_rows)
-                                    .clone()
-                                    .into_iter()
-                                    .filter(|r| !links.contains(&r.link))
-                                    .collect();
-                                done_rows.set(filtered);
+                                let mut nodes = (*tree).clone();
+                                nodes.retain(|n| n.platform != plat_for_backend);
+                                tree.set(nodes);
+                                let prefix = format!("library::{}::", plat_for_backend);
+                                items_ref.borrow_mut().retain(|k, _| !k.starts_with(&prefix));
+                                items.set(items_ref.borrow().clone());
                                 // backend delete honoring delete mode (clone so handler stays Fn)
                                 let p = plat_for_backend.clone();
                                 spawn_local(async move {
@@ -208,44 +582,64 @@ pub fn library_page() -> Html {
                             html!{
                                 <div>
                                     {
-                                        for col_map.into_iter().map(|((handle, typ_str), rows)| {
-                                            let col_key = format!("{}::{}::{}::{}", section_id, plat_label, handle, typ_str);
+                                        for node.collections.into_iter().map(|col| {
+                                            let handle = col.handle.clone();
+                                            let origin = col.origin.clone();
+                                            let col_key = collection_key(&plat_label, &handle, &origin);
                                             let col_open = expanded_collections.contains(&col_key);
                                             let on_col_click = {
+                                                let load_page = load_page.clone();
                                                 let expanded_collections = expanded_collections.clone();
+                                                let items_ref = items_ref.clone();
                                                 let k = col_key.clone();
+                                                let (p, h, o) = (plat_label.clone(), handle.clone(), origin.clone());
                                                 Callback::from(move |_| {
                                                     let mut set = (*expanded_collections).clone();
-                                                    if !set.insert(k.clone()) { set.remove(&k); }
+                                                    if !set.insert(k.clone()) {
+                                                        set.remove(&k);
+                                                    } else if !items_ref.borrow().contains_key(&k) {
+                                                        // first expansion: fetch this collection's first page
+                                                        load_page.emit((k.clone(), p.clone(), h.clone(), o.clone()));
+                                                    }
                                                     expanded_collections.set(set);
                                                 })
                                             };
                                             // Per-collection actions (folder + delete)
-                                            let links_for_collection: Vec<String> = rows.iter().map(|r| r.link.clone()).collect();
                                             let on_delete_collection = {
-                                                let done_rows = done_rows.clone();
-                                                let links = links_for_collection.clone();
+                                                let tree = tree.clone();
+                                                let items = items.clone();
+                                                let items_ref = items_ref.clone();
+                                                let k = col_key.clone();
                                                 let plat_for_backend = plat_label.clone();
                                                 let handle_for_backend = handle.clone();
-                                                let typ_for_backend = typ_str.clone();
+                                                let origin_for_backend = origin.clone();
                                                 Callback::from(move |e: MouseEvent| {
                                                     e.prevent_default();
                                                     e.stop_propagation();
-                                                    let filtered: Vec<ClipRow> = (*done_rows)
-                                                        .clone()
-                                                        .into_iter()
-                                                        .filter(|r| !links.contains(&r.link))
-                                                        .collect();
-                                                    done_rows.set(filtered);
+                                                    let mut nodes = (*tree).clone();
+                                                    if let Some(n) = nodes.iter_mut().find(|n| n.platform == plat_for_backend) {
+                                                        let removed: u64 = n
+                                                            .collections
+                                                            .iter()
+                                                            .filter(|c| c.handle == handle_for_backend && c.origin == origin_for_backend)
+                                                            .map(|c| c.item_count)
+                                                            .sum();
+                                                        n.item_count = n.item_count.saturating_sub(removed);
+                                                        n.collections.retain(|c| !(c.handle == handle_for_backend && c.origin == origin_for_backend));
+                                                    }
+                                                    nodes.retain(|n| !n.collections.is_empty());
+                                                    tree.set(nodes);
+                                                    items_ref.borrow_mut().remove(&k);
+                                                    items.set(items_ref.borrow().clone());
                                                     // backend delete honoring delete mode
                                                     let p = plat_for_backend.clone();
                                                     let h = handle_for_backend.clone();
-                                                    let t = typ_for_backend.clone();
+                                                    let o = origin_for_backend.clone();
                                                     spawn_local(async move {
                                                         let args = serde_wasm_bindgen::to_value(&serde_json::json!({
                                                             "platform": p,
                                                             "handle": h,
-                                                            "origin": t,
+                                                            "origin": o,
                                                         })).unwrap();
                                                         let _ = invoke("delete_rows_by_collection", args).await;
                                                     });
@@ -255,13 +649,13 @@ pub fn library_page() -> Html {
                                             let on_open_collection_folder = {
                                                 let plat = plat_label.clone();
                                                 let handle = handle.clone();
-                                                let typ = typ_str.clone();
+                                                let origin = origin.clone();
                                                 Callback::from(move |e: MouseEvent| {
                                                     e.prevent_default();
                                                     e.stop_propagation();
                                                     let p = plat.clone();
                                                     let h = handle.clone();
-                                                    let t = typ.clone();
+                                                    let t = origin.clone();
                                                     spawn_local(async move {
                                                         let args = serde_wasm_bindgen::to_value(&serde_json::json!({
                                                             "platform": p,
@@ -273,14 +667,68 @@ pub fn library_page() -> Html {
                                                 })
                                             };
 
+                                            let rows = items.get(&col_key).cloned();
+
+                                            // Re-read titles etc. for the loaded rows that have none
+                                            let on_refresh_metadata = {
+                                                let ids: Vec<i64> = rows
+                                                    .iter()
+                                                    .flatten()
+                                                    .filter(|r| r.title.is_none() && r.uploader.is_none() && r.upload_date.is_none())
+                                                    .map(|r| r.id)
+                                                    .collect();
+                                                Callback::from(move |e: MouseEvent| {
+                                                    e.prevent_default();
+                                                    e.stop_propagation();
+                                                    let ids = ids.clone();
+                                                    spawn_local(async move {
+                                                        let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "ids": ids })).unwrap();
+                                                        let _ = invoke("refresh_metadata", args).await;
+                                                    });
+                                                })
+                                            };
+                                            let on_export_zip = {
+                                                let (p, h, o) = (plat_label.clone(), handle.clone(), origin.clone());
+                                                Callback::from(move |e: MouseEvent| {
+                                                    e.prevent_default();
+                                                    e.stop_propagation();
+                                                    let args = serde_wasm_bindgen::to_value(&serde_json::json!({
+                                                        "platform": p,
+                                                        "handle": h,
+                                                        "contentType": o,
+                                                    })).unwrap();
+                                                    spawn_local(async move {
+                                                        let _ = invoke("export_collection_zip", args).await;
+                                                    });
+                                                })
+                                            };
+                                            let remaining = (col.item_count as usize)
+                                                .saturating_sub(rows.as_ref().map_or(0, Vec::len));
+                                            let show_more = col_open && rows.is_some() && remaining > 0;
+                                            let on_load_more = {
+                                                let load_page = load_page.clone();
+                                                let page = (col_key.clone(), plat_label.clone(), handle.clone(), origin.clone());
+                                                Callback::from(move |_: MouseEvent| load_page.emit(page.clone()))
+                                            };
+                                            let can_refresh_metadata = col_open
+                                                && rows.iter().flatten().any(|r| r.title.is_none() && r.uploader.is_none() && r.upload_date.is_none());
+
                                             html!{
                                                 <div class="collection-block" key={col_key.clone()}>
                                                     <div class="collection-item" onclick={on_col_click}>
                                                         <div class="item-left">
-                                                            <span class="item-title">{ format!("{} | {}", handle, typ_str) }</span>
+                                                            <span class="item-title">{ format!("{} | {}", display_handle(&handle), origin) }</span>
                                                         </div>
                                                         <div class="item-right">
-                                                            <span>{ format!("{} items", rows.len()) }</span>
+                                                            <span>{ format!("{} items", col.item_count) }</span>
+                                                            if can_refresh_metadata {
+                                                                <button class="icon-btn" type_="button" title="Refresh missing titles, uploaders and dates (see Settings → Maintenance)" onclick={on_refresh_metadata}>
+                                                                    <Icon icon_id={IconId::LucideRotateCcw} width={"18"} height={"18"} />
+                                                                </button>
+                                                            }
+                                                            <button class="icon-btn" type_="button" title="Download as ZIP (progress in Settings → Maintenance)" onclick={on_export_zip}>
+                                                                <Icon icon_id={IconId::LucideArchive} width={"18"} height={"18"} />
+                                                            </button>
                                                             <button class="icon-btn" type_="button" title="Show in folder" onclick={on_open_collection_folder}>
                                                                 <Icon icon_id={IconId::LucideFolder} width={"18"} height={"18"} />
                                                             </button>
@@ -290,26 +738,63 @@ pub fn library_page() -> Html {
                                                         </div>
                                                     </div>
                                                     {
-                                                        if col_open {
-                                                            html!{
+                                                        match (col_open, rows) {
+                                                            (false, _) => html!{},
+                                                            (true, None) => html!{
+                                                                <div class="rows-card"><span class="stage-text">{"Loading…"}</span></div>
+                                                            },
+                                                            (true, Some(rows)) if grid => html!{
+                                                                <div class="rows-card">
+                                                                    <div class="thumb-grid">
+                                                                        {
+                                                                            for rows.into_iter().map(|row| {
+                                                                                let on_open_file = {
+                                                                                    let link = row.link.clone();
+                                                                                    Callback::from(move |e: MouseEvent| {
+                                                                                        e.prevent_default();
+                                                                                        let l = link.clone();
+                                                                                        spawn_local(async move {
+                                                                                            let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "link": l })).unwrap();
+                                                                                            let _ = invoke("open_file_for_link", args).await;
+                                                                                        });
+                                                                                    })
+                                                                                };
+                                                                                html!{
+                                                                                    <button type_="button" class={classes!("thumb-tile", offline.then_some("offline"))} key={row.link.clone()} title={metadata_hint(&row)} disabled={offline} onclick={on_open_file}>
+                                                                                        <ItemThumbnail id={row.id} cached={row.thumbnail_path.is_some()} media={row.media} />
+                                                                                        <span class="thumb-tile-label">{ item_label_for_row(&row) }</span>
+                                                                                    </button>
+                                                                                }
+                                                                            })
+                                                                        }
+                                                                    </div>
+                                                                </div>
+                                                            },
+                                                            (true, Some(rows)) => html!{
                                                                 <div class="rows-card">
                                                                     <ul class="rows">
                                                                         {
                                                                             for rows.into_iter().map(|row| {
                                                                                 // Delete callback: optimistic UI update + backend delete
                                                                                 let on_delete_row = {
-                                                                                    let done_rows = done_rows.clone();
+                                                                                    let tree = tree.clone();
+                                                                                    let items = items.clone();
+                                                                                    let items_ref = items_ref.clone();
+                                                                                    let k = col_key.clone();
+                                                                                    let (p, h, o) = (plat_label.clone(), handle.clone(), origin.clone());
                                                                                     let link = row.link.clone();
                                                                                     Callback::from(move |e: MouseEvent| {
                                                                                         e.prevent_default();
                                                                                         e.stop_propagation();
 
                                                                                         // Optimistic UI removal
-                                                                                        let filtered: Vec<ClipRow> = (*done_rows).clone()
-                                                                                            .into_iter()
-                                                                                            .filter(|r| r.link != link)
-                                                                                            .collect();
-                                                                                        done_rows.set(filtered);
+                                                                                        if let Some(rows) = items_ref.borrow_mut().get_mut(&k) {
+                                                                                            rows.retain(|r| r.link != link);
+                                                                                        }
+                                                                                        items.set(items_ref.borrow().clone());
+                                                                                        let mut nodes = (*tree).clone();
+                                                                                        decrement_tree(&mut nodes, &p, &h, &o);
+                                                                                        tree.set(nodes);
 
                                                                                         // Backend delete honoring delete mode
                                                                                         let link_for_backend = link.clone();
@@ -320,6 +805,33 @@ pub fn library_page() -> Html {
                                                                                     })
                                                                                 };
 
+                                                                                // Download again, replacing the file; the row leaves the library until it finishes
+                                                                                let on_redownload = {
+                                                                                    let tree = tree.clone();
+                                                                                    let items = items.clone();
+                                                                                    let items_ref = items_ref.clone();
+                                                                                    let k = col_key.clone();
+                                                                                    let (p, h, o) = (plat_label.clone(), handle.clone(), origin.clone());
+                                                                                    let link = row.link.clone();
+                                                                                    Callback::from(move |e: MouseEvent| {
+                                                                                        e.prevent_default();
+                                                                                        e.stop_propagation();
+                                                                                        if let Some(rows) = items_ref.borrow_mut().get_mut(&k) {
+                                                                                            rows.retain(|r| r.link != link);
+                                                                                        }
+                                                                                        items.set(items_ref.borrow().clone());
+                                                                                        let mut nodes = (*tree).clone();
+                                                                                        decrement_tree(&mut nodes, &p, &h, &o);
+                                                                                        tree.set(nodes);
+
+                                                                                        let l = link.clone();
+                                                                                        spawn_local(async move {
+                                                                                            let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "link": l, "overwrite": true })).unwrap();
+                                                                                            let _ = invoke("requeue_link", args).await;
+                                                                                        });
+                                                                                    })
+                                                                                };
+
                                                                                 // Open file with default app
                                                                                 let on_open_file = {
                                                                                     let link = row.link.clone();
@@ -349,23 +861,34 @@ pub fn library_page() -> Html {
                                                                                 };
 
                                                                                 html!{
-                                                                                    <li class="row-line" key={row.link.clone()}>
+                                                                                    <li class={classes!("row-line", offline.then_some("offline"))} key={row.link.clone()}>
                                                                                         {
                                                                                             match row.media {
-                                                                                                MediaKind::Pictures => html!{ <Icon icon_id={IconId::LucideImage} width={"16"} height={"16"} /> },
+                                                                                                MediaKind::Image => html!{ <Icon icon_id={IconId::LucideImage} width={"16"} height={"16"} /> },
                                                                                                 MediaKind::Video    => html!{ <Icon icon_id={IconId::LucideVideo} width={"16"} height={"16"} /> },
                                                                                             }
                                                                                         }
-                                                                                        <a class="link-text" href={row.link.clone()} target="_blank">
+                                                                                        <a class="link-text" href={row.link.clone()} target="_blank" title={metadata_hint(&row)}>
                                                                                             { collection_title(&row) }{" - "}{ item_label_for_row(&row) }
                                                                                         </a>
+                                                                                        if offline {
+                                                                                            <span class="offline-badge" title="The drive holding this file is not connected">{"Offline"}</span>
+                                                                                        }
+                                                                                        if row.subtitle_path.is_some() {
+                                                                                            <span class="cc-badge" title="Subtitles downloaded">{"CC"}</span>
+                                                                                        }
+                                                                                        <span class="row-size">{ size_hint(&row) }</span>
+                                                                                        <TagChips link={row.link.clone()} tags={row.tags.clone()} on_change={reload_tags.clone()} />
                                                                                         <div class="row-actions">
-                                                                                            <button class="icon-btn" type_="button" title="Play" onclick={on_open_file}>
+                                                                                            <button class="icon-btn" type_="button" title="Play" disabled={offline} onclick={on_open_file}>
                                                                                                 <Icon icon_id={IconId::LucidePlay} width={"18"} height={"18"} />
                                                                                             </button>
-                                                                                            <button class="icon-btn" type_="button" title="Show in folder" onclick={on_open_folder}>
+                                                                                            <button class="icon-btn" type_="button" title="Show in folder" disabled={offline} onclick={on_open_folder}>
                                                                                                 <Icon icon_id={IconId::LucideFolder} width={"18"} height={"18"} />
                                                                                             </button>
+                                                                                            <button class="icon-btn" type_="button" title="Download again, replacing the file" onclick={on_redownload}>
+                                                                                                <Icon icon_id={IconId::LucideRotateCcw} width={"18"} height={"18"} />
+                                                                                            </button>
                                                                                             <button class="icon-btn" type_="button" title="Delete" onclick={on_delete_row}>
                                                                                                 <Icon icon_id={IconId::LucideTrash2} width={"18"} height={"18"} />
                                                                                             </button>
@@ -376,8 +899,13 @@ pub fn library_page() -> Html {
                                                                         }
                                                                     </ul>
                                                                 </div>
-                                                            }
-                                                        } else { html!{} }
+                                                            },
+                                                        }
+                                                    }
+                                                    if show_more {
+                                                        <button class="load-more" type_="button" onclick={on_load_more}>
+                                                            { format!("Load more ({remaining} left)") }
+                                                        </button>
                                                     }
                                                 </div>
                                             }
@@ -391,8 +919,8 @@ pub fn library_page() -> Html {
                             <div class="platform-block" key={platform_key.clone()}>
                                 <div class="platform-item" onclick={on_platform_click}>
                                     <div class="item-left">
-                                        <img class="brand-icon" src={platform_icon_src(&plat_label)} />
-                                        <span class="item-title">{ plat_label.clone() }</span>
+                                        <img class="brand-icon" src={platforms::icon_for(&plat_label)} />
+                                        <span class="item-title">{ platforms::label_for(&plat_label) }</span>
                                     </div>
                                     <div class="item-right">
                                         <span>{ format!("{} collections | {} items", collections_count, items_count) }</span>
@@ -410,6 +938,8 @@ pub fn library_page() -> Html {
                     })
                 }
             </div>
+            <WatchPanel />
+            <TrashPanel on_restored={reload_tree} />
         </main>
     }
 }
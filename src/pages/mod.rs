@@ -4,3 +4,4 @@ pub mod home;
 pub mod library;
 pub mod settings;
 pub mod sponsor;
+pub mod stats;
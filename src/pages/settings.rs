@@ -1,4 +1,8 @@
+use crate::components::maintenance_panel::MaintenancePanel;
+use crate::components::profile_picker::ProfilePicker;
 use crate::dom::assign_missing_descriptive_ids;
+use crate::router::Page;
+use crate::types::{MaintenanceTask, ToolReport};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
@@ -39,14 +43,219 @@ pub struct Settings {
     pub cooldown_secs: u32,
     #[serde(default)]
     pub retry_on_queue_empty: bool,
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    #[serde(default = "default_retry_delay_secs")]
+    pub retry_delay_secs: u32,
+    #[serde(default)]
+    pub max_download_rate_kbps: u32,
+    #[serde(default = "default_progress_updates_per_sec")]
+    pub progress_updates_per_sec: u8,
+    #[serde(default)]
+    pub event_verbosity: EventVerbosity,
+    #[serde(default = "default_collection_folder_template")]
+    pub collection_folder_template: String,
+    #[serde(default = "default_folder_template")]
+    pub folder_template: String,
+    #[serde(default = "default_true")]
+    pub hard_delete_to_trash: bool,
+    #[serde(default)]
+    pub queue_new_on_startup: bool,
+    #[serde(default)]
+    pub check_links_on_import: bool,
+    #[serde(default)]
+    pub find_mirrors: bool,
+    #[serde(default)]
+    pub notifications_enabled: bool,
+    #[serde(default)]
+    pub close_to_tray: bool,
+    #[serde(default)]
+    pub hash_downloads: bool,
+    #[serde(default = "default_true")]
+    pub notify_on_done: bool,
+    #[serde(default = "default_true")]
+    pub notify_on_error: bool,
+    #[serde(default = "default_true")]
+    pub notify_on_session_end: bool,
+    #[serde(default)]
+    pub download_windows: Vec<DownloadWindow>,
+    #[serde(default)]
+    pub digest_mode: DigestMode,
+    #[serde(default)]
+    pub smtp: SmtpConfig,
+    #[serde(default)]
+    pub redact_logs: bool,
+    #[serde(default)]
+    pub http_headers: Vec<HeaderRule>,
+    #[serde(default)]
+    pub geo_bypass_country: std::collections::BTreeMap<String, String>,
+    #[serde(default)]
+    pub proxy_url: String,
+    #[serde(default)]
+    pub per_platform_proxy: std::collections::BTreeMap<String, String>,
+    #[serde(default)]
+    pub preferred_cookie_browser: Option<String>,
+    #[serde(default)]
+    pub platform_overrides: std::collections::BTreeMap<String, PlatformOverride>,
+    #[serde(default)]
+    pub filename_filesystem: FilesystemKind,
+    #[serde(default)]
+    pub max_filename_len: u16,
+    #[serde(default)]
+    pub read_only: bool,
+    #[serde(default)]
+    pub trim_to_link_timestamp: bool,
+    #[serde(default)]
+    pub tool_env: Vec<EnvVar>,
+    #[serde(default)]
+    pub clipboard_watch: ClipboardWatch,
+    #[serde(default)]
+    pub on_import_duplicate: ImportDuplicate,
+    #[serde(default)]
+    pub http_api: HttpApiConfig,
+    #[serde(default)]
+    pub prefetch_metadata: bool,
+    #[serde(default)]
+    pub download_subtitles: bool,
+    #[serde(default = "default_subtitle_langs")]
+    pub subtitle_langs: String,
+    #[serde(default)]
+    pub embed_subs: bool,
+    #[serde(default)]
+    pub collection_index: CollectionIndex,
+    #[serde(default)]
+    pub write_info_json: bool,
+    #[serde(default)]
+    pub max_resolution: u32,
+    #[serde(default)]
+    pub preferred_codec: VideoCodec,
+    #[serde(default)]
+    pub audio_format: AudioFormat,
+    #[serde(default = "default_audio_quality")]
+    pub audio_quality: String,
+    #[serde(default)]
+    pub startup_page: Page,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub enum FilesystemKind {
+    #[default]
+    Auto,
+    Fat,
+    ExFat,
+    Ntfs,
+    Apfs,
+    HfsPlus,
+    Ext4,
+    Other,
+}
+
+const FILESYSTEM_CHOICES: &[(FilesystemKind, &str, &str)] = &[
+    (FilesystemKind::Auto, "Auto", "Auto-detect"),
+    (FilesystemKind::Fat, "Fat", "FAT32"),
+    (FilesystemKind::ExFat, "ExFat", "exFAT"),
+    (FilesystemKind::Ntfs, "Ntfs", "NTFS"),
+    (FilesystemKind::Apfs, "Apfs", "APFS"),
+    (FilesystemKind::HfsPlus, "HfsPlus", "HFS+"),
+    (FilesystemKind::Ext4, "Ext4", "ext4 / Linux"),
+];
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct HeaderRule {
+    pub platform: String,
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct PlatformOverride {
+    #[serde(default)]
+    pub output: Option<DefaultOutput>,
+    #[serde(default)]
+    pub max_height: Option<u32>,
+    #[serde(default)]
+    pub max_parallel: Option<u8>,
+    #[serde(default)]
+    pub subfolder: Option<String>,
+    #[serde(default)]
+    pub cookie_browser: Option<String>,
+    #[serde(default)]
+    pub cookie_file: Option<String>,
+}
+
+/// Video heights offered as a per-platform quality cap.
+const MAX_HEIGHTS: &[u32] = &[2160, 1440, 1080, 720, 480, 360];
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct EnvVar {
+    pub name: String,
+    pub value: String,
+}
+
+/// Local "HH:MM" range in which queued downloads may start; `days` 0 = Monday, empty = every day.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct DownloadWindow {
+    pub start: String,
+    pub end: String,
+    #[serde(default)]
+    pub days: Vec<u8>,
 }
 
+const WEEKDAY_LABELS: &[&str] = &["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Platform choices for per-platform settings; "all" (headers only) applies to every link.
+const RULE_PLATFORMS: &[(&str, &str)] = &[
+    ("all", "All platforms"),
+    ("tiktok", "TikTok"),
+    ("instagram", "Instagram"),
+    ("youtube", "YouTube"),
+    ("pinterest", "Pinterest"),
+    ("twitter", "X (Twitter)"),
+    ("reddit", "Reddit"),
+    ("other", "Other"),
+];
+
 fn default_true() -> bool {
     true
 }
 fn default_parallel_downloads() -> u8 {
     3
 }
+fn default_progress_updates_per_sec() -> u8 {
+    4
+}
+fn default_max_retries() -> u32 {
+    2
+}
+fn default_retry_delay_secs() -> u32 {
+    30
+}
+fn default_subtitle_langs() -> String {
+    "en.*".into()
+}
+fn default_collection_folder_template() -> String {
+    "{origin} - {handle}".to_string()
+}
+fn default_folder_template() -> String {
+    "{platform}/{collection}".to_string()
+}
+fn default_audio_quality() -> String {
+    "0".to_string()
+}
+
+/// yt-dlp `--audio-quality` choices: VBR best, then fixed bitrates.
+const AUDIO_QUALITIES: &[(&str, &str)] = &[
+    ("0", "Best"),
+    ("320K", "320 kbit/s"),
+    ("192K", "192 kbit/s"),
+    ("128K", "128 kbit/s"),
+];
+
+#[derive(serde::Deserialize, Clone, Debug, PartialEq)]
+struct CollectionFolderPreview {
+    current: String,
+    preview: String,
+}
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
 pub enum DeleteMode {
@@ -66,10 +275,168 @@ impl Default for DefaultOutput {
     }
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub enum EventVerbosity {
+    ErrorsOnly,
+    Milestones,
+    Verbose,
+}
+
+impl Default for EventVerbosity {
+    fn default() -> Self {
+        EventVerbosity::Milestones
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Default)]
+pub enum DigestMode {
+    #[default]
+    Off,
+    LocalHtml,
+    Email,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub enum VideoCodec {
+    #[default]
+    Any,
+    H264,
+    Vp9,
+    Av1,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    #[default]
+    Mp3,
+    M4a,
+    Opus,
+    Flac,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub enum CollectionIndex {
+    #[default]
+    Off,
+    Csv,
+    Readme,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub enum ClipboardWatch {
+    #[default]
+    Off,
+    Ask,
+    Backlog,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub enum ImportDuplicate {
+    #[default]
+    Skip,
+    UpdateCollection,
+    InsertAnyway,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    pub from: String,
+    pub to: String,
+    pub starttls: bool,
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        Self {
+            host: String::new(),
+            port: 587,
+            username: String::new(),
+            password: String::new(),
+            from: String::new(),
+            to: String::new(),
+            starttls: true,
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct HttpApiConfig {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String,
+}
+
+const DEFAULT_HTTP_API_PORT: u16 = 47821;
+
+impl Default for HttpApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_HTTP_API_PORT,
+            token: String::new(),
+        }
+    }
+}
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
     async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+
+    #[wasm_bindgen(catch, js_namespace = ["window", "__TAURI__", "core"], js_name = invoke)]
+    async fn try_invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+/// Change handler for one field of a platform's overrides; the entry is
+/// dropped again once nothing in it is set.
+fn platform_override_change(
+    settings: &UseStateHandle<Settings>,
+    platform: &str,
+    apply: fn(&mut PlatformOverride, String),
+) -> Callback<Event> {
+    let settings = settings.clone();
+    let platform = platform.to_string();
+    Callback::from(move |e: Event| {
+        let value = e
+            .target_dyn_into::<web_sys::HtmlInputElement>()
+            .map(|i| i.value())
+            .or_else(|| {
+                e.target_dyn_into::<web_sys::HtmlSelectElement>()
+                    .map(|s| s.value())
+            })
+            .unwrap_or_default();
+        update_platform_override(&settings, &platform, |ov| {
+            apply(ov, value.trim().to_string())
+        });
+    })
+}
+
+/// Edit one platform's overrides, dropping the entry once nothing is set.
+fn update_platform_override(
+    settings: &UseStateHandle<Settings>,
+    platform: &str,
+    apply: impl FnOnce(&mut PlatformOverride),
+) {
+    let mut s = (**settings).clone();
+    let mut ov = s
+        .platform_overrides
+        .get(platform)
+        .cloned()
+        .unwrap_or_default();
+    apply(&mut ov);
+    if ov == PlatformOverride::default() {
+        s.platform_overrides.remove(platform);
+    } else {
+        s.platform_overrides.insert(platform.to_string(), ov);
+    }
+    settings.set(s);
 }
 
 #[function_component(SettingsPage)]
@@ -80,11 +447,40 @@ pub fn settings_page() -> Html {
     });
     let settings = use_state(Settings::default);
     let libs = use_state(|| None::<SidecarCheck>);
+    let tool_report = use_state(|| None::<ToolReport>);
+    let folder_previews = use_state(Vec::<CollectionFolderPreview>::new);
+    let folder_preview_refresh = use_state(|| 0u32);
+    let folder_rename_status = use_state(|| None::<String>);
+    let digest_status = use_state(|| None::<String>);
+    let proxy_status = use_state(|| None::<String>);
+    // outcome of the last cookies.txt import
+    let cookie_status = use_state(|| None::<String>);
+    let config_status = use_state(|| None::<String>);
+    let backup_status = use_state(|| None::<String>);
+    let stats_export_status = use_state(|| None::<String>);
+    let dedupe_status = use_state(|| None::<String>);
+    let verify_status = use_state(|| None::<String>);
+    let show_profiles = use_state(|| false);
+    let detected_filesystem = use_state(|| None::<FilesystemKind>);
+    {
+        let detected_filesystem = detected_filesystem.clone();
+        use_effect_with(settings.download_directory.clone(), move |_| {
+            spawn_local(async move {
+                let v = invoke("detect_download_filesystem", JsValue::NULL).await;
+                detected_filesystem.set(serde_wasm_bindgen::from_value(v).ok());
+            });
+            || ()
+        });
+    }
+    // read-only as last saved; while it is on, saving only unlocks
+    let saved_read_only = use_state(|| false);
     let settings_clone = settings.clone();
+    let saved_read_only_clone = saved_read_only.clone();
     use_effect_with((), move |_| {
         spawn_local(async move {
             let loaded_settings = invoke("load_settings", JsValue::NULL).await;
-            if let Ok(s) = serde_wasm_bindgen::from_value(loaded_settings) {
+            if let Ok(s) = serde_wasm_bindgen::from_value::<Settings>(loaded_settings) {
+                saved_read_only_clone.set(s.read_only);
                 settings_clone.set(s);
             }
         });
@@ -151,6 +547,18 @@ pub fn settings_page() -> Html {
         })
     };
 
+    let on_redact_logs_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.redact_logs = checked;
+            settings.set(s);
+        })
+    };
+
     let on_download_automatically_change = {
         let settings = settings.clone();
         Callback::from(move |e: Event| {
@@ -175,126 +583,1038 @@ pub fn settings_page() -> Html {
         })
     };
 
-    let on_parallel_downloads_change = {
+    let on_startup_page_change = {
         let settings = settings.clone();
-        Callback::from(move |e: web_sys::InputEvent| {
+        Callback::from(move |e: Event| {
             let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.startup_page = Page::ALL
+                .into_iter()
+                .find(|p| p.label() == value)
+                .unwrap_or_default();
+            settings.set(s);
+        })
+    };
+
+    let on_queue_new_on_startup_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
                 .target_unchecked_into::<web_sys::HtmlInputElement>()
-                .value_as_number() as u8;
+                .checked();
             let mut s = (*settings).clone();
-            s.parallel_downloads = value.max(1);
+            s.queue_new_on_startup = checked;
             settings.set(s);
         })
     };
 
-    let on_use_system_binaries_change = {
+    let on_check_links_change = {
         let settings = settings.clone();
         Callback::from(move |e: Event| {
             let checked = e
                 .target_unchecked_into::<web_sys::HtmlInputElement>()
                 .checked();
             let mut s = (*settings).clone();
-            s.use_system_binaries = checked;
+            s.check_links_on_import = checked;
             settings.set(s);
         })
     };
 
-    let on_cooldown_change = {
+    let on_find_mirrors_change = {
         let settings = settings.clone();
-        Callback::from(move |e: web_sys::InputEvent| {
-            let value = e
+        Callback::from(move |e: Event| {
+            let checked = e
                 .target_unchecked_into::<web_sys::HtmlInputElement>()
-                .value_as_number() as u32;
+                .checked();
             let mut s = (*settings).clone();
-            s.cooldown_secs = value;
+            s.find_mirrors = checked;
             settings.set(s);
         })
     };
 
-    let on_retry_on_queue_empty_change = {
+    let on_close_to_tray_change = {
         let settings = settings.clone();
         Callback::from(move |e: Event| {
             let checked = e
                 .target_unchecked_into::<web_sys::HtmlInputElement>()
                 .checked();
             let mut s = (*settings).clone();
-            s.retry_on_queue_empty = checked;
+            s.close_to_tray = checked;
             settings.set(s);
         })
     };
 
-    let on_delete_mode_change = {
+    let on_hash_downloads_change = {
         let settings = settings.clone();
         Callback::from(move |e: Event| {
-            let value = e
-                .target_unchecked_into::<web_sys::HtmlSelectElement>()
-                .value();
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
             let mut s = (*settings).clone();
-            s.delete_mode = if value == "hard" {
-                DeleteMode::Hard
-            } else {
-                DeleteMode::Soft
-            };
+            s.hash_downloads = checked;
             settings.set(s);
         })
     };
 
-    let on_save = {
+    let on_notifications_change = {
         let settings = settings.clone();
-        Callback::from(move |_| {
-            let settings_to_save = (*settings).clone();
-            spawn_local(async move {
-                let args = serde_wasm_bindgen::to_value(
-                    &serde_json::json!({ "settings": settings_to_save }),
-                )
-                .unwrap();
-                let result = invoke("save_settings", args).await;
-                if result.is_null() {
-                    web_sys::console::log_1(&"Settings saved successfully.".into());
-                } else {
-                    web_sys::console::error_1(&"Failed to save settings:".into());
-                    web_sys::console::error_1(&result);
-                }
-            });
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.notifications_enabled = checked;
+            settings.set(s);
         })
     };
 
-    let on_default_output_change = {
+    let on_notify_done_change = {
         let settings = settings.clone();
         Callback::from(move |e: Event| {
-            let value = e
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.notify_on_done = checked;
+            settings.set(s);
+        })
+    };
+
+    let on_notify_error_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.notify_on_error = checked;
+            settings.set(s);
+        })
+    };
+
+    let on_notify_session_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
                 .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.notify_on_session_end = checked;
+            settings.set(s);
+        })
+    };
+
+    let on_clipboard_watch_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
                 .value();
             let mut s = (*settings).clone();
-            s.default_output = if value == "audio" {
-                DefaultOutput::Audio
-            } else {
-                DefaultOutput::Video
+            s.clipboard_watch = match value.as_str() {
+                "Ask" => ClipboardWatch::Ask,
+                "Backlog" => ClipboardWatch::Backlog,
+                _ => ClipboardWatch::Off,
             };
             settings.set(s);
         })
     };
 
-    let on_check_tools = {
-        let libs = libs.clone();
-        Callback::from(move |_| {
-            // Clone outside the async move so the outer callback implements Fn
-            let libs_set = libs.clone();
-            spawn_local(async move {
-                let v = invoke("check_sidecar_tools", JsValue::NULL).await;
-                if let Ok(res) = serde_wasm_bindgen::from_value::<SidecarCheck>(v) {
-                    libs_set.set(Some(res));
-                }
-            });
+    let on_import_duplicate_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.on_import_duplicate = match value.as_str() {
+                "UpdateCollection" => ImportDuplicate::UpdateCollection,
+                "InsertAnyway" => ImportDuplicate::InsertAnyway,
+                _ => ImportDuplicate::Skip,
+            };
+            settings.set(s);
         })
     };
 
-    html! {
-        <main id="settings-page" class="container">
-            <h1 id="settings-page-heading">{"Settings"}</h1>
-            <div id="settings-form" class="settings-form">
-                <div id="settings-download-directory-group" class="form-group">
-                    <label id="settings-download-directory-label" for="settings-download-directory-input">{"Default Download Directory"}</label>
+    let on_prefetch_metadata_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.prefetch_metadata = checked;
+            settings.set(s);
+        })
+    };
+
+    let on_http_api_enabled_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.http_api.enabled = checked;
+            settings.set(s);
+        })
+    };
+
+    let on_http_api_port_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: web_sys::InputEvent| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.http_api.port = value.trim().parse().unwrap_or(DEFAULT_HTTP_API_PORT);
+            settings.set(s);
+        })
+    };
+
+    // Saved immediately by the backend, which also restarts the server with it.
+    let on_regenerate_http_api_token = {
+        let settings = settings.clone();
+        Callback::from(move |_| {
+            let settings = settings.clone();
+            spawn_local(async move {
+                match try_invoke("regenerate_http_api_token", JsValue::NULL).await {
+                    Ok(v) => {
+                        if let Some(token) = v.as_string() {
+                            let mut s = (*settings).clone();
+                            s.http_api.token = token;
+                            settings.set(s);
+                        }
+                    }
+                    Err(e) => web_sys::console::error_1(&e),
+                }
+            });
+        })
+    };
+
+    let on_digest_mode_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.digest_mode = match value.as_str() {
+                "LocalHtml" => DigestMode::LocalHtml,
+                "Email" => DigestMode::Email,
+                _ => DigestMode::Off,
+            };
+            settings.set(s);
+        })
+    };
+
+    // One text/number field of the SMTP config; `apply` writes the value into it.
+    let smtp_input = |apply: fn(&mut SmtpConfig, String)| {
+        let settings = settings.clone();
+        Callback::from(move |e: web_sys::InputEvent| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            let mut s = (*settings).clone();
+            apply(&mut s.smtp, value);
+            settings.set(s);
+        })
+    };
+    let on_smtp_host_change = smtp_input(|c, v| c.host = v.trim().to_string());
+    let on_smtp_port_change = smtp_input(|c, v| c.port = v.trim().parse().unwrap_or(587));
+    let on_smtp_username_change = smtp_input(|c, v| c.username = v.trim().to_string());
+    let on_smtp_password_change = smtp_input(|c, v| c.password = v);
+    let on_smtp_from_change = smtp_input(|c, v| c.from = v.trim().to_string());
+    let on_smtp_to_change = smtp_input(|c, v| c.to = v.trim().to_string());
+
+    let on_smtp_starttls_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.smtp.starttls = checked;
+            settings.set(s);
+        })
+    };
+
+    let on_send_digest_now = {
+        let digest_status = digest_status.clone();
+        Callback::from(move |_| {
+            let digest_status = digest_status.clone();
+            digest_status.set(Some("Sending…".to_string()));
+            spawn_local(async move {
+                let v = invoke("send_digest_now", JsValue::NULL).await;
+                let status = if v.is_null() || v.is_undefined() {
+                    "Nothing new since the last digest".to_string()
+                } else {
+                    match v.as_string() {
+                        Some(target) => format!("Digest sent to {target}"),
+                        None => {
+                            web_sys::console::error_1(&v);
+                            "Failed to send digest".to_string()
+                        }
+                    }
+                };
+                digest_status.set(Some(status));
+            });
+        })
+    };
+
+    let on_proxy_url_change = {
+        let settings = settings.clone();
+        let proxy_status = proxy_status.clone();
+        Callback::from(move |e: web_sys::InputEvent| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.proxy_url = value;
+            settings.set(s);
+            proxy_status.set(None);
+        })
+    };
+
+    // browsers (and profiles) whose cookies can be used
+    let cookie_browsers = use_state(Vec::<String>::new);
+    {
+        let cookie_browsers = cookie_browsers.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                let v = invoke("list_cookie_browsers", JsValue::NULL).await;
+                if let Ok(list) = serde_wasm_bindgen::from_value::<Vec<String>>(v) {
+                    cookie_browsers.set(list);
+                }
+            });
+            || ()
+        });
+    }
+    let on_cookie_browser_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.preferred_cookie_browser = (!value.is_empty()).then_some(value);
+            settings.set(s);
+        })
+    };
+
+    let on_test_proxy = {
+        let settings = settings.clone();
+        let proxy_status = proxy_status.clone();
+        Callback::from(move |_| {
+            let proxy_status = proxy_status.clone();
+            let proxy_url = settings.proxy_url.clone();
+            proxy_status.set(Some("Testing…".to_string()));
+            spawn_local(async move {
+                let args =
+                    serde_wasm_bindgen::to_value(&serde_json::json!({ "proxyUrl": proxy_url }))
+                        .unwrap();
+                let status = match try_invoke("test_proxy", args).await {
+                    Ok(v) => format!("Works ({:.0} ms)", v.as_f64().unwrap_or_default()),
+                    Err(e) => format!(
+                        "Failed: {}",
+                        e.as_string().unwrap_or_else(|| "no answer".into())
+                    ),
+                };
+                proxy_status.set(Some(status));
+            });
+        })
+    };
+
+    let on_parallel_downloads_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: web_sys::InputEvent| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value_as_number() as u8;
+            let mut s = (*settings).clone();
+            s.parallel_downloads = value.max(1);
+            settings.set(s);
+        })
+    };
+
+    let on_use_system_binaries_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.use_system_binaries = checked;
+            settings.set(s);
+        })
+    };
+
+    let on_cooldown_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: web_sys::InputEvent| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value_as_number() as u32;
+            let mut s = (*settings).clone();
+            s.cooldown_secs = value;
+            settings.set(s);
+        })
+    };
+
+    let on_retry_on_queue_empty_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.retry_on_queue_empty = checked;
+            settings.set(s);
+        })
+    };
+
+    let on_max_retries_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: web_sys::InputEvent| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value_as_number() as u32;
+            let mut s = (*settings).clone();
+            s.max_retries = value;
+            settings.set(s);
+        })
+    };
+
+    let on_retry_delay_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: web_sys::InputEvent| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value_as_number() as u32;
+            let mut s = (*settings).clone();
+            s.retry_delay_secs = value;
+            settings.set(s);
+        })
+    };
+
+    let on_max_rate_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: web_sys::InputEvent| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value_as_number();
+            let mut s = (*settings).clone();
+            s.max_download_rate_kbps = if value.is_finite() { value as u32 } else { 0 };
+            settings.set(s);
+        })
+    };
+
+    let on_progress_updates_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: web_sys::InputEvent| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value_as_number();
+            let mut s = (*settings).clone();
+            s.progress_updates_per_sec = if value.is_nan() {
+                0
+            } else {
+                value.clamp(0.0, 60.0) as u8
+            };
+            settings.set(s);
+        })
+    };
+
+    let on_event_verbosity_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.event_verbosity = match value.as_str() {
+                "ErrorsOnly" => EventVerbosity::ErrorsOnly,
+                "Verbose" => EventVerbosity::Verbose,
+                _ => EventVerbosity::Milestones,
+            };
+            settings.set(s);
+        })
+    };
+
+    {
+        let folder_previews = folder_previews.clone();
+        use_effect_with(
+            (
+                settings.collection_folder_template.clone(),
+                settings.folder_template.clone(),
+                *folder_preview_refresh,
+            ),
+            move |(template, folder_template, _)| {
+                let args = serde_wasm_bindgen::to_value(&serde_json::json!({
+                    "template": template,
+                    "folderTemplate": folder_template,
+                }))
+                .unwrap();
+                spawn_local(async move {
+                    let v = invoke("preview_collection_folders", args).await;
+                    if let Ok(rows) =
+                        serde_wasm_bindgen::from_value::<Vec<CollectionFolderPreview>>(v)
+                    {
+                        folder_previews.set(rows);
+                    }
+                });
+            },
+        );
+    }
+
+    let on_collection_folder_template_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: web_sys::InputEvent| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.collection_folder_template = value;
+            settings.set(s);
+        })
+    };
+
+    let on_folder_template_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: web_sys::InputEvent| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.folder_template = value;
+            settings.set(s);
+        })
+    };
+
+    let on_rename_collection_folders = {
+        let settings = settings.clone();
+        let folder_rename_status = folder_rename_status.clone();
+        Callback::from(move |_| {
+            let args = serde_wasm_bindgen::to_value(&serde_json::json!({
+                "template": settings.collection_folder_template,
+                "folderTemplate": settings.folder_template,
+            }))
+            .unwrap();
+            let folder_rename_status = folder_rename_status.clone();
+            spawn_local(async move {
+                let v = invoke("apply_collection_folder_template", args).await;
+                if v.as_f64().is_some() {
+                    folder_rename_status.set(Some(
+                        "Renaming in the background (see Maintenance)".to_string(),
+                    ));
+                } else {
+                    web_sys::console::error_1(&"Failed to rename collection folders:".into());
+                    web_sys::console::error_1(&v);
+                    folder_rename_status.set(v.as_string());
+                }
+            });
+        })
+    };
+
+    // Current labels change once a rename finishes; refresh the preview list then.
+    let on_maintenance_task_finished = {
+        let folder_preview_refresh = folder_preview_refresh.clone();
+        let folder_rename_status = folder_rename_status.clone();
+        let verify_status = verify_status.clone();
+        Callback::from(move |task: MaintenanceTask| {
+            if task.kind == "rename_collection_folders" {
+                folder_rename_status.set(Some(task.message));
+                folder_preview_refresh.set(*folder_preview_refresh + 1);
+            } else if task.kind == "verify_library" {
+                verify_status.set(Some(task.message));
+            }
+        })
+    };
+
+    let on_delete_mode_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.delete_mode = if value == "Hard" {
+                DeleteMode::Hard
+            } else {
+                DeleteMode::Soft
+            };
+            settings.set(s);
+        })
+    };
+
+    let on_trim_timestamp_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.trim_to_link_timestamp = checked;
+            settings.set(s);
+        })
+    };
+
+    let on_download_subtitles_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.download_subtitles = checked;
+            settings.set(s);
+        })
+    };
+
+    let on_subtitle_langs_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: web_sys::InputEvent| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.subtitle_langs = value;
+            settings.set(s);
+        })
+    };
+
+    let on_embed_subs_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.embed_subs = checked;
+            settings.set(s);
+        })
+    };
+
+    let on_write_info_json_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.write_info_json = checked;
+            settings.set(s);
+        })
+    };
+
+    let on_max_resolution_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.max_resolution = value.parse().unwrap_or(0);
+            settings.set(s);
+        })
+    };
+
+    let on_preferred_codec_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.preferred_codec = match value.as_str() {
+                "H264" => VideoCodec::H264,
+                "Vp9" => VideoCodec::Vp9,
+                "Av1" => VideoCodec::Av1,
+                _ => VideoCodec::Any,
+            };
+            settings.set(s);
+        })
+    };
+
+    let on_audio_format_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.audio_format = match value.as_str() {
+                "m4a" => AudioFormat::M4a,
+                "opus" => AudioFormat::Opus,
+                "flac" => AudioFormat::Flac,
+                _ => AudioFormat::Mp3,
+            };
+            settings.set(s);
+        })
+    };
+
+    let on_audio_quality_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.audio_quality = value;
+            settings.set(s);
+        })
+    };
+
+    let on_collection_index_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.collection_index = match value.as_str() {
+                "Csv" => CollectionIndex::Csv,
+                "Readme" => CollectionIndex::Readme,
+                _ => CollectionIndex::Off,
+            };
+            settings.set(s);
+        })
+    };
+
+    let on_read_only_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.read_only = checked;
+            settings.set(s);
+        })
+    };
+
+    let on_hard_delete_to_trash_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let checked = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .checked();
+            let mut s = (*settings).clone();
+            s.hard_delete_to_trash = checked;
+            settings.set(s);
+        })
+    };
+
+    let on_save = {
+        let settings = settings.clone();
+        let saved_read_only = saved_read_only.clone();
+        Callback::from(move |_| {
+            let settings_to_save = (*settings).clone();
+            let settings = settings.clone();
+            let was_read_only = *saved_read_only;
+            let saved_read_only = saved_read_only.clone();
+            spawn_local(async move {
+                // The backend creates the API token on save; show it.
+                let token_pending =
+                    settings_to_save.http_api.enabled && settings_to_save.http_api.token.is_empty();
+                let args = serde_wasm_bindgen::to_value(
+                    &serde_json::json!({ "settings": settings_to_save }),
+                )
+                .unwrap();
+                let result = invoke("save_settings", args).await;
+                if result.is_null() {
+                    web_sys::console::log_1(&"Settings saved successfully.".into());
+                    saved_read_only.set(settings_to_save.read_only && !was_read_only);
+                    // Unlocking a read-only library saves nothing but the lock.
+                    if token_pending || was_read_only {
+                        let saved = invoke("load_settings", JsValue::NULL).await;
+                        if let Ok(saved) = serde_wasm_bindgen::from_value::<Settings>(saved) {
+                            settings.set(saved);
+                        }
+                    }
+                } else {
+                    web_sys::console::error_1(&"Failed to save settings:".into());
+                    web_sys::console::error_1(&result);
+                }
+            });
+        })
+    };
+
+    let on_default_output_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.default_output = if value == "audio" {
+                DefaultOutput::Audio
+            } else {
+                DefaultOutput::Video
+            };
+            settings.set(s);
+        })
+    };
+
+    let on_filename_filesystem_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            let mut s = (*settings).clone();
+            s.filename_filesystem = FILESYSTEM_CHOICES
+                .iter()
+                .find(|(_, id, _)| *id == value)
+                .map_or(FilesystemKind::Auto, |(kind, _, _)| *kind);
+            settings.set(s);
+        })
+    };
+
+    let on_max_filename_len_change = {
+        let settings = settings.clone();
+        Callback::from(move |e: web_sys::InputEvent| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value_as_number();
+            let mut s = (*settings).clone();
+            s.max_filename_len = if value.is_finite() {
+                value.clamp(0.0, 255.0) as u16
+            } else {
+                0
+            };
+            settings.set(s);
+        })
+    };
+
+    let on_add_header = {
+        let settings = settings.clone();
+        Callback::from(move |_| {
+            let mut s = (*settings).clone();
+            s.http_headers.push(HeaderRule {
+                platform: "all".into(),
+                name: String::new(),
+                value: String::new(),
+            });
+            settings.set(s);
+        })
+    };
+
+    let on_add_env_var = {
+        let settings = settings.clone();
+        Callback::from(move |_| {
+            let mut s = (*settings).clone();
+            s.tool_env.push(EnvVar {
+                name: String::new(),
+                value: String::new(),
+            });
+            settings.set(s);
+        })
+    };
+
+    let on_add_window = {
+        let settings = settings.clone();
+        Callback::from(move |_| {
+            let mut s = (*settings).clone();
+            s.download_windows.push(DownloadWindow {
+                start: "01:00".into(),
+                end: "07:00".into(),
+                days: Vec::new(),
+            });
+            settings.set(s);
+        })
+    };
+
+    let on_export_config = {
+        let config_status = config_status.clone();
+        Callback::from(move |_| {
+            let config_status = config_status.clone();
+            spawn_local(async move {
+                let v = invoke("export_config", JsValue::NULL).await;
+                if let Some(path) = v.as_string() {
+                    config_status.set(Some(format!("Exported to {path}")));
+                } else if !v.is_null() && !v.is_undefined() {
+                    web_sys::console::error_1(&v);
+                    config_status.set(Some("Export failed".to_string()));
+                }
+            });
+        })
+    };
+
+    let on_import_config = {
+        let settings = settings.clone();
+        let config_status = config_status.clone();
+        Callback::from(move |_| {
+            let settings = settings.clone();
+            let config_status = config_status.clone();
+            spawn_local(async move {
+                let v = invoke("import_config", JsValue::NULL).await;
+                if v.is_null() || v.is_undefined() {
+                    return;
+                }
+                match serde_wasm_bindgen::from_value::<Settings>(v.clone()) {
+                    Ok(imported) => {
+                        settings.set(imported);
+                        config_status.set(Some("Configuration imported".to_string()));
+                    }
+                    Err(_) => {
+                        web_sys::console::error_1(&v);
+                        config_status.set(Some(
+                            v.as_string().unwrap_or_else(|| "Import failed".into()),
+                        ));
+                    }
+                }
+            });
+        })
+    };
+
+    let export_stats = |format: &'static str| {
+        let stats_export_status = stats_export_status.clone();
+        Callback::from(move |_| {
+            let stats_export_status = stats_export_status.clone();
+            spawn_local(async move {
+                let args =
+                    serde_wasm_bindgen::to_value(&serde_json::json!({ "format": format })).unwrap();
+                match try_invoke("export_stats", args).await {
+                    Ok(v) => {
+                        if let Ok(Some(files)) =
+                            serde_wasm_bindgen::from_value::<Option<Vec<String>>>(v)
+                        {
+                            stats_export_status.set(Some(format!("Exported {}", files.join(", "))));
+                        }
+                    }
+                    Err(e) => stats_export_status.set(Some(
+                        e.as_string().unwrap_or_else(|| "Export failed".into()),
+                    )),
+                }
+            });
+        })
+    };
+    let on_export_stats_csv = export_stats("Csv");
+    let on_export_stats_json = export_stats("Json");
+
+    let on_backup_database = {
+        let backup_status = backup_status.clone();
+        Callback::from(move |_| {
+            let backup_status = backup_status.clone();
+            spawn_local(async move {
+                match try_invoke("backup_database", JsValue::NULL).await {
+                    Ok(v) => {
+                        if let Some(path) = v.as_string() {
+                            backup_status.set(Some(format!("Backup saved to {path}")));
+                        }
+                    }
+                    Err(e) => backup_status.set(Some(
+                        e.as_string().unwrap_or_else(|| "Backup failed".into()),
+                    )),
+                }
+            });
+        })
+    };
+
+    let on_restore_database = {
+        let backup_status = backup_status.clone();
+        Callback::from(move |_| {
+            let backup_status = backup_status.clone();
+            spawn_local(async move {
+                // On success the app restarts on the restored data.
+                if let Err(e) = try_invoke("restore_database", JsValue::NULL).await {
+                    backup_status.set(Some(
+                        e.as_string().unwrap_or_else(|| "Restore failed".into()),
+                    ));
+                }
+            });
+        })
+    };
+
+    let on_refresh_metadata = Callback::from(move |_| {
+        spawn_local(async move {
+            let args =
+                serde_wasm_bindgen::to_value(&serde_json::json!({ "ids": Vec::<i64>::new() }))
+                    .unwrap();
+            let _ = invoke("refresh_metadata", args).await;
+        });
+    });
+
+    let on_dedupe = {
+        let dedupe_status = dedupe_status.clone();
+        Callback::from(move |_| {
+            let dedupe_status = dedupe_status.clone();
+            spawn_local(async move {
+                dedupe_status.set(Some(
+                    match try_invoke("dedupe_downloads", JsValue::NULL).await {
+                        Ok(v) => {
+                            let n = serde_wasm_bindgen::from_value::<u64>(v).unwrap_or(0);
+                            format!("Removed {n} duplicate rows")
+                        }
+                        Err(e) => e.as_string().unwrap_or_else(|| "Dedupe failed".into()),
+                    },
+                ));
+            });
+        })
+    };
+
+    let on_verify_library = {
+        let verify_status = verify_status.clone();
+        Callback::from(move |_| {
+            let verify_status = verify_status.clone();
+            spawn_local(async move {
+                if let Err(e) = try_invoke("verify_library", JsValue::NULL).await {
+                    verify_status.set(Some(e.as_string().unwrap_or_else(|| "Check failed".into())));
+                }
+            });
+        })
+    };
+
+    let on_repair_missing = {
+        let verify_status = verify_status.clone();
+        Callback::from(move |_| {
+            let verify_status = verify_status.clone();
+            spawn_local(async move {
+                verify_status.set(Some(
+                    match try_invoke("repair_missing", JsValue::NULL).await {
+                        Ok(v) => {
+                            let ids =
+                                serde_wasm_bindgen::from_value::<Vec<i64>>(v).unwrap_or_default();
+                            format!("Queued {} missing files", ids.len())
+                        }
+                        Err(e) => e.as_string().unwrap_or_else(|| "Repair failed".into()),
+                    },
+                ));
+            });
+        })
+    };
+
+    let on_check_tools = {
+        let libs = libs.clone();
+        let tool_report = tool_report.clone();
+        Callback::from(move |_| {
+            // Clone outside the async move so the outer callback implements Fn
+            let libs_set = libs.clone();
+            let tool_report = tool_report.clone();
+            spawn_local(async move {
+                let v = invoke("check_sidecar_tools", JsValue::NULL).await;
+                if let Ok(res) = serde_wasm_bindgen::from_value::<SidecarCheck>(v) {
+                    libs_set.set(Some(res));
+                }
+                let args =
+                    serde_wasm_bindgen::to_value(&serde_json::json!({ "refresh": true })).unwrap();
+                let v = invoke("tool_report", args).await;
+                if let Ok(report) = serde_wasm_bindgen::from_value::<ToolReport>(v) {
+                    tool_report.set(Some(report));
+                }
+            });
+        })
+    };
+
+    html! {
+        <main id="settings-page" class="container">
+            <h1 id="settings-page-heading">{"Settings"}</h1>
+            <div id="settings-form" class="settings-form">
+                <div id="settings-download-directory-group" class="form-group">
+                    <label id="settings-download-directory-label" for="settings-download-directory-input">{"Default Download Directory"}</label>
                     <div id="settings-download-directory-controls" class="input-group">
                         <input type="text" id="settings-download-directory-input" readonly=true value={settings.download_directory.clone()} />
                         <button id="settings-select-directory-button" onclick={on_directory_pick}>{"Select"}</button>
@@ -317,7 +1637,7 @@ pub fn settings_page() -> Html {
                     <label id="settings-delete-mode-label" for="settings-delete-mode-select">{"Delete behavior"}</label>
                     <select id="settings-delete-mode-select" onchange={on_delete_mode_change}>
                         <option id="settings-delete-mode-soft-option" value="Soft" selected={settings.delete_mode == DeleteMode::Soft}>
-                            {"Soft delete (remove from library only)"}
+                            {"Soft delete (move to the Library trash; files go when it is emptied)"}
                         </option>
                         <option id="settings-delete-mode-hard-option" value="Hard" selected={settings.delete_mode == DeleteMode::Hard}>
                             {"Hard delete (remove files from disk)"}
@@ -325,6 +1645,22 @@ pub fn settings_page() -> Html {
                     </select>
                 </div>
 
+                {
+                    if settings.delete_mode == DeleteMode::Hard {
+                        html!{
+                            <div id="settings-hard-delete-trash-group" class="form-group row">
+                                <label id="settings-hard-delete-trash-label" for="settings-hard-delete-trash-checkbox">{"Move deleted files to trash"}</label>
+                                <input type="checkbox" id="settings-hard-delete-trash-checkbox" checked={settings.hard_delete_to_trash} onchange={on_hard_delete_to_trash_change} />
+                            </div>
+                        }
+                    } else { html!{} }
+                }
+
+                <div id="settings-read-only-group" class="form-group row">
+                    <label id="settings-read-only-label" for="settings-read-only-checkbox" title="Blocks deleting, moving and overwriting files; other settings stay as saved until this is turned off">{"Read-only library"}</label>
+                    <input type="checkbox" id="settings-read-only-checkbox" checked={settings.read_only} onchange={on_read_only_change} />
+                </div>
+
                 <div id="settings-default-output-group" class="form-group row">
                     <label id="settings-default-output-label">{"Default output"}</label>
                     <div id="settings-default-output-options" style="display:flex; gap: 16px; align-items:center;">
@@ -339,11 +1675,103 @@ pub fn settings_page() -> Html {
                     </div>
                 </div>
 
+                <div id="settings-max-resolution-group" class="form-group row">
+                    <label id="settings-max-resolution-label" for="settings-max-resolution-select">{"Video quality"}</label>
+                    <select id="settings-max-resolution-select" title="Lower caps save disk space; per-platform overrides win" onchange={on_max_resolution_change}>
+                        <option id="settings-max-resolution-best-option" value="0" selected={settings.max_resolution == 0}>{"Best available"}</option>
+                        { for [2160u32, 1080, 720, 480].iter().map(|h| html!{
+                            <option value={h.to_string()} selected={settings.max_resolution == *h}>{format!("Up to {h}p")}</option>
+                        }) }
+                    </select>
+                </div>
+
+                <div id="settings-preferred-codec-group" class="form-group row">
+                    <label id="settings-preferred-codec-label" for="settings-preferred-codec-select">{"Preferred video codec"}</label>
+                    <select id="settings-preferred-codec-select" onchange={on_preferred_codec_change}>
+                        <option id="settings-preferred-codec-any-option" value="Any" selected={settings.preferred_codec == VideoCodec::Any}>{"Any"}</option>
+                        <option id="settings-preferred-codec-h264-option" value="H264" selected={settings.preferred_codec == VideoCodec::H264}>{"H.264 (most compatible)"}</option>
+                        <option id="settings-preferred-codec-vp9-option" value="Vp9" selected={settings.preferred_codec == VideoCodec::Vp9}>{"VP9"}</option>
+                        <option id="settings-preferred-codec-av1-option" value="Av1" selected={settings.preferred_codec == VideoCodec::Av1}>{"AV1 (smallest files)"}</option>
+                    </select>
+                </div>
+
+                <div id="settings-audio-format-group" class="form-group row">
+                    <label id="settings-audio-format-label" for="settings-audio-format-select">{"Audio format"}</label>
+                    <select id="settings-audio-format-select" title="Used for audio-only downloads unless a row picks its own" onchange={on_audio_format_change}>
+                        <option id="settings-audio-format-mp3-option" value="mp3" selected={settings.audio_format == AudioFormat::Mp3}>{"MP3"}</option>
+                        <option id="settings-audio-format-m4a-option" value="m4a" selected={settings.audio_format == AudioFormat::M4a}>{"M4A (AAC)"}</option>
+                        <option id="settings-audio-format-opus-option" value="opus" selected={settings.audio_format == AudioFormat::Opus}>{"Opus"}</option>
+                        <option id="settings-audio-format-flac-option" value="flac" selected={settings.audio_format == AudioFormat::Flac}>{"FLAC (lossless)"}</option>
+                    </select>
+                </div>
+
+                <div id="settings-audio-quality-group" class="form-group row">
+                    <label id="settings-audio-quality-label" for="settings-audio-quality-select">{"Audio quality"}</label>
+                    <select id="settings-audio-quality-select" disabled={settings.audio_format == AudioFormat::Flac} onchange={on_audio_quality_change}>
+                        { for AUDIO_QUALITIES.iter().map(|(value, label)| html!{
+                            <option value={*value} selected={settings.audio_quality == *value}>{*label}</option>
+                        }) }
+                    </select>
+                </div>
+
+                <div id="settings-trim-timestamp-group" class="form-group row">
+                    <label id="settings-trim-timestamp-label" for="settings-trim-timestamp-checkbox" title="Links saved with ?t=120 download from 2:00 onwards">{"Start videos at the link's timestamp"}</label>
+                    <input type="checkbox" id="settings-trim-timestamp-checkbox" checked={settings.trim_to_link_timestamp} onchange={on_trim_timestamp_change} />
+                </div>
+
+                <div id="settings-download-subtitles-group" class="form-group row">
+                    <label id="settings-download-subtitles-label" for="settings-download-subtitles-checkbox">{"Download subtitles with videos"}</label>
+                    <input type="checkbox" id="settings-download-subtitles-checkbox" checked={settings.download_subtitles} onchange={on_download_subtitles_change} />
+                </div>
+                {
+                    if settings.download_subtitles {
+                        html!{
+                            <>
+                                <div id="settings-subtitle-langs-group" class="form-group row">
+                                    <label id="settings-subtitle-langs-label" for="settings-subtitle-langs-input" title="yt-dlp language patterns, comma separated (\"all\" for every language)">{"Subtitle languages"}</label>
+                                    <input type="text" id="settings-subtitle-langs-input" placeholder="en.*" value={settings.subtitle_langs.clone()} oninput={on_subtitle_langs_change} />
+                                </div>
+                                <div id="settings-embed-subs-group" class="form-group row">
+                                    <label id="settings-embed-subs-label" for="settings-embed-subs-checkbox">{"Embed subtitles into the video file"}</label>
+                                    <input type="checkbox" id="settings-embed-subs-checkbox" checked={settings.embed_subs} onchange={on_embed_subs_change} />
+                                </div>
+                            </>
+                        }
+                    } else {
+                        html!{}
+                    }
+                }
+
+                <div id="settings-write-info-json-group" class="form-group row">
+                    <label id="settings-write-info-json-label" for="settings-write-info-json-checkbox" title="Title, description, date, duration and views are also shown in the Library">{"Save a metadata .json next to each download"}</label>
+                    <input type="checkbox" id="settings-write-info-json-checkbox" checked={settings.write_info_json} onchange={on_write_info_json_change} />
+                </div>
+
+                <div id="settings-collection-index-group" class="form-group row">
+                    <label id="settings-collection-index-label" for="settings-collection-index-select" title="Lists each file with its source link and download date, rewritten after every finished download">{"Collection folder index"}</label>
+                    <select id="settings-collection-index-select" onchange={on_collection_index_change}>
+                        <option id="settings-collection-index-off-option" value="Off" selected={settings.collection_index == CollectionIndex::Off}>{"None"}</option>
+                        <option id="settings-collection-index-csv-option" value="Csv" selected={settings.collection_index == CollectionIndex::Csv}>{"index.csv"}</option>
+                        <option id="settings-collection-index-readme-option" value="Readme" selected={settings.collection_index == CollectionIndex::Readme}>{"README.md"}</option>
+                    </select>
+                </div>
+
                 <div id="settings-debug-logs-group" class="form-group row">
                     <label id="settings-debug-logs-label" for="settings-debug-logs-checkbox">{"Activate debug logs"}</label>
                     <input type="checkbox" id="settings-debug-logs-checkbox" checked={settings.debug_logs} onchange={on_debug_logs_change} />
                 </div>
 
+                {
+                    if settings.debug_logs {
+                        html!{
+                            <div id="settings-redact-logs-group" class="form-group row">
+                                <label id="settings-redact-logs-label" for="settings-redact-logs-checkbox">{"Hide links and handles in log files"}</label>
+                                <input type="checkbox" id="settings-redact-logs-checkbox" checked={settings.redact_logs} onchange={on_redact_logs_change} />
+                            </div>
+                        }
+                    } else { html!{} }
+                }
+
                 <div id="settings-download-automatically-group" class="form-group row">
                     <label id="settings-download-automatically-label" for="settings-download-automatically-checkbox">{"Download automatically"}</label>
                     <input type="checkbox" id="settings-download-automatically-checkbox" checked={settings.download_automatically} onchange={on_download_automatically_change} />
@@ -354,6 +1782,112 @@ pub fn settings_page() -> Html {
                     <input type="checkbox" id="settings-keep-downloading-checkbox" checked={settings.keep_downloading_on_other_pages} onchange={on_keep_downloading_change} />
                 </div>
 
+                <div id="settings-startup-page-group" class="form-group row">
+                    <label id="settings-startup-page-label" for="settings-startup-page-select">{"Open on launch"}</label>
+                    <select id="settings-startup-page-select" onchange={on_startup_page_change}>
+                        { for [Page::Home, Page::Downloads, Page::Library, Page::Settings, Page::Extension].into_iter().map(|p| html!{
+                            <option value={p.label()} selected={settings.startup_page == p}>{ p.label() }</option>
+                        }) }
+                    </select>
+                </div>
+
+                <div id="settings-queue-new-on-startup-group" class="form-group row">
+                    <label id="settings-queue-new-on-startup-label" for="settings-queue-new-on-startup-checkbox">{"Queue new backlog items on startup"}</label>
+                    <input type="checkbox" id="settings-queue-new-on-startup-checkbox" checked={settings.queue_new_on_startup} onchange={on_queue_new_on_startup_change} />
+                </div>
+
+                <div id="settings-prefetch-metadata-group" class="form-group row">
+                    <label id="settings-prefetch-metadata-label" for="settings-prefetch-metadata-checkbox">{"Look up titles and sizes for waiting items in the background"}</label>
+                    <input type="checkbox" id="settings-prefetch-metadata-checkbox" title="Probes one link at a time while nothing is downloading, pausing between requests to each site" checked={settings.prefetch_metadata} onchange={on_prefetch_metadata_change} />
+                </div>
+
+                <div id="settings-clipboard-watch-group" class="form-group row">
+                    <label id="settings-clipboard-watch-label" for="settings-clipboard-watch-select">{"Copied links"}</label>
+                    <select id="settings-clipboard-watch-select" title="Watch the clipboard for links from supported sites" onchange={on_clipboard_watch_change}>
+                        <option id="settings-clipboard-watch-off-option" value="Off" selected={settings.clipboard_watch == ClipboardWatch::Off}>{"Ignore"}</option>
+                        <option id="settings-clipboard-watch-ask-option" value="Ask" selected={settings.clipboard_watch == ClipboardWatch::Ask}>{"Ask to queue"}</option>
+                        <option id="settings-clipboard-watch-backlog-option" value="Backlog" selected={settings.clipboard_watch == ClipboardWatch::Backlog}>{"Add to backlog"}</option>
+                    </select>
+                </div>
+
+                <div id="settings-http-api-group" class="form-group row">
+                    <label id="settings-http-api-label" for="settings-http-api-checkbox">{"Allow the browser extension to add links (local HTTP API)"}</label>
+                    <input type="checkbox" id="settings-http-api-checkbox" checked={settings.http_api.enabled} onchange={on_http_api_enabled_change} />
+                </div>
+                {
+                    if settings.http_api.enabled {
+                        html!{
+                            <div id="settings-http-api-details" class="form-group">
+                                <div class="form-group row">
+                                    <label id="settings-http-api-port-label" for="settings-http-api-port-input">{"Port (127.0.0.1 only)"}</label>
+                                    <input type="number" id="settings-http-api-port-input" min="1024" max="65535" value={settings.http_api.port.to_string()} oninput={on_http_api_port_change} />
+                                </div>
+                                <div class="form-group row">
+                                    <label id="settings-http-api-token-label" for="settings-http-api-token-input">{"Token (paste into the extension)"}</label>
+                                    <input type="text" id="settings-http-api-token-input" readonly={true} placeholder="Created when you save" value={settings.http_api.token.clone()} />
+                                    <button id="settings-http-api-token-button" type="button" title="The current token stops working" onclick={on_regenerate_http_api_token}>{"New token"}</button>
+                                </div>
+                            </div>
+                        }
+                    } else { html!{} }
+                }
+
+                <div id="settings-import-duplicate-group" class="form-group row">
+                    <label id="settings-import-duplicate-label" for="settings-import-duplicate-select">{"Imported links that are already known"}</label>
+                    <select id="settings-import-duplicate-select" onchange={on_import_duplicate_change}>
+                        <option id="settings-import-duplicate-skip-option" value="Skip" selected={settings.on_import_duplicate == ImportDuplicate::Skip}>{"Skip them"}</option>
+                        <option id="settings-import-duplicate-update-option" value="UpdateCollection" title="Rows already downloaded stay where they are" selected={settings.on_import_duplicate == ImportDuplicate::UpdateCollection}>{"Move them to the imported collection"}</option>
+                        <option id="settings-import-duplicate-insert-option" value="InsertAnyway" selected={settings.on_import_duplicate == ImportDuplicate::InsertAnyway}>{"Add them again"}</option>
+                    </select>
+                </div>
+
+                <div id="settings-check-links-group" class="form-group row">
+                    <label id="settings-check-links-label" for="settings-check-links-checkbox">{"Check links while importing (skip dead ones)"}</label>
+                    <input type="checkbox" id="settings-check-links-checkbox" checked={settings.check_links_on_import} onchange={on_check_links_change} />
+                </div>
+
+                <div id="settings-find-mirrors-group" class="form-group row">
+                    <label id="settings-find-mirrors-label" for="settings-find-mirrors-checkbox" title="Checks the Wayback Machine and searches YouTube for the same title">{"Look for removed videos elsewhere"}</label>
+                    <input type="checkbox" id="settings-find-mirrors-checkbox" checked={settings.find_mirrors} onchange={on_find_mirrors_change} />
+                </div>
+
+                <div id="settings-close-to-tray-group" class="form-group row">
+                    <label id="settings-close-to-tray-label" for="settings-close-to-tray-checkbox" title="Downloads keep running; click the tray icon to bring the window back">{"Closing the window keeps the app in the tray"}</label>
+                    <input type="checkbox" id="settings-close-to-tray-checkbox" checked={settings.close_to_tray} onchange={on_close_to_tray_change} />
+                </div>
+
+                <div id="settings-hash-downloads-group" class="form-group row">
+                    <label id="settings-hash-downloads-label" for="settings-hash-downloads-checkbox" title="Lets “Verify files” notice a file that was replaced or corrupted; hashing large videos takes a moment">{"Store a checksum of each download"}</label>
+                    <input type="checkbox" id="settings-hash-downloads-checkbox" checked={settings.hash_downloads} onchange={on_hash_downloads_change} />
+                </div>
+
+                <div id="settings-notifications-group" class="form-group row">
+                    <label id="settings-notifications-label" for="settings-notifications-checkbox">{"Desktop notifications"}</label>
+                    <input type="checkbox" id="settings-notifications-checkbox" checked={settings.notifications_enabled} onchange={on_notifications_change} />
+                </div>
+                {
+                    if settings.notifications_enabled {
+                        html!{
+                            <>
+                                <div id="settings-notify-done-group" class="form-group row">
+                                    <label id="settings-notify-done-label" for="settings-notify-done-checkbox">{"Notify when a download finishes"}</label>
+                                    <input type="checkbox" id="settings-notify-done-checkbox" checked={settings.notify_on_done} onchange={on_notify_done_change} />
+                                </div>
+                                <div id="settings-notify-error-group" class="form-group row">
+                                    <label id="settings-notify-error-label" for="settings-notify-error-checkbox">{"Notify when a download fails"}</label>
+                                    <input type="checkbox" id="settings-notify-error-checkbox" checked={settings.notify_on_error} onchange={on_notify_error_change} />
+                                </div>
+                                <div id="settings-notify-session-group" class="form-group row">
+                                    <label id="settings-notify-session-label" for="settings-notify-session-checkbox" title="Only after a session of more than one download">{"Notify when the queue finishes"}</label>
+                                    <input type="checkbox" id="settings-notify-session-checkbox" checked={settings.notify_on_session_end} onchange={on_notify_session_change} />
+                                </div>
+                            </>
+                        }
+                    } else {
+                        html!{}
+                    }
+                }
+
                 <div id="settings-parallel-downloads-group" class="form-group row">
                     <label id="settings-parallel-downloads-label" for="settings-parallel-downloads-input">{"Parallel downloads"}</label>
                     <input type="number" id="settings-parallel-downloads-input" min="1" value={settings.parallel_downloads.to_string()} oninput={on_parallel_downloads_change} />
@@ -364,11 +1898,549 @@ pub fn settings_page() -> Html {
                     <input type="number" id="settings-cooldown-input" min="0" value={settings.cooldown_secs.to_string()} oninput={on_cooldown_change} />
                 </div>
 
+                <div id="settings-max-retries-group" class="form-group row">
+                    <label id="settings-max-retries-label" for="settings-max-retries-input" title="Only network errors, server errors and rate limits are retried">{"Automatic retries per download"}</label>
+                    <input type="number" id="settings-max-retries-input" min="0" max="10" value={settings.max_retries.to_string()} oninput={on_max_retries_change} />
+                </div>
+
+                <div id="settings-retry-delay-group" class="form-group row">
+                    <label id="settings-retry-delay-label" for="settings-retry-delay-input">{"First retry delay (seconds, doubles each time)"}</label>
+                    <input type="number" id="settings-retry-delay-input" min="0" value={settings.retry_delay_secs.to_string()} oninput={on_retry_delay_change} />
+                </div>
+
+                <div id="settings-max-rate-group" class="form-group row">
+                    <label id="settings-max-rate-label" for="settings-max-rate-input">{"Max download rate (kbit/s, 0 = unlimited)"}</label>
+                    <input type="number" id="settings-max-rate-input" min="0" step="500" value={settings.max_download_rate_kbps.to_string()} oninput={on_max_rate_change} />
+                </div>
+
                 <div id="settings-retry-on-empty-group" class="form-group row">
                     <label id="settings-retry-on-empty-label" for="settings-retry-on-empty-checkbox">{"Retry failed downloads when queue empties"}</label>
                     <input type="checkbox" id="settings-retry-on-empty-checkbox" checked={settings.retry_on_queue_empty} onchange={on_retry_on_queue_empty_change} />
                 </div>
 
+                <div id="settings-download-windows-group" class="form-group">
+                    <label id="settings-download-windows-label">{"Download windows (queued items only start inside them)"}</label>
+                    { for settings.download_windows.iter().enumerate().map(|(idx, window)| {
+                        let on_start = {
+                            let settings = settings.clone();
+                            Callback::from(move |e: Event| {
+                                let value = e.target_unchecked_into::<web_sys::HtmlInputElement>().value();
+                                let mut s = (*settings).clone();
+                                s.download_windows[idx].start = value;
+                                settings.set(s);
+                            })
+                        };
+                        let on_end = {
+                            let settings = settings.clone();
+                            Callback::from(move |e: Event| {
+                                let value = e.target_unchecked_into::<web_sys::HtmlInputElement>().value();
+                                let mut s = (*settings).clone();
+                                s.download_windows[idx].end = value;
+                                settings.set(s);
+                            })
+                        };
+                        let on_remove = {
+                            let settings = settings.clone();
+                            Callback::from(move |_| {
+                                let mut s = (*settings).clone();
+                                s.download_windows.remove(idx);
+                                settings.set(s);
+                            })
+                        };
+                        html!{
+                            <div id={format!("settings-download-window-row-{idx}")} class="header-rule-row download-window-row">
+                                <input type="time" value={window.start.clone()} onchange={on_start} />
+                                <span>{"to"}</span>
+                                <input type="time" value={window.end.clone()} onchange={on_end} />
+                                { for WEEKDAY_LABELS.iter().enumerate().map(|(day, label)| {
+                                    let day = day as u8;
+                                    let on_day = {
+                                        let settings = settings.clone();
+                                        Callback::from(move |e: Event| {
+                                            let checked = e.target_unchecked_into::<web_sys::HtmlInputElement>().checked();
+                                            let mut s = (*settings).clone();
+                                            let days = &mut s.download_windows[idx].days;
+                                            days.retain(|d| *d != day);
+                                            if checked {
+                                                days.push(day);
+                                                days.sort_unstable();
+                                            }
+                                            settings.set(s);
+                                        })
+                                    };
+                                    html!{
+                                        <label class="weekday-toggle">
+                                            <input type="checkbox" checked={window.days.contains(&day)} onchange={on_day} />
+                                            {*label}
+                                        </label>
+                                    }
+                                }) }
+                                <button class="icon-btn" title="Remove" onclick={on_remove}>
+                                    <Icon icon_id={IconId::LucideTrash2} width={"18"} height={"18"} />
+                                </button>
+                            </div>
+                        }
+                    }) }
+                    <small class="download-window-hint">{"No days ticked means every day. An end before the start runs past midnight."}</small>
+                    <button id="settings-download-window-add-button" onclick={on_add_window}>{"Add window"}</button>
+                </div>
+
+                <div id="settings-progress-updates-group" class="form-group row">
+                    <label id="settings-progress-updates-label" for="settings-progress-updates-input">{"Progress updates per second (0 = unlimited)"}</label>
+                    <input type="number" id="settings-progress-updates-input" min="0" max="60" value={settings.progress_updates_per_sec.to_string()} oninput={on_progress_updates_change} />
+                </div>
+
+                <div id="settings-event-verbosity-group" class="form-group row">
+                    <label id="settings-event-verbosity-label" for="settings-event-verbosity-select">{"Download messages"}</label>
+                    <select id="settings-event-verbosity-select" onchange={on_event_verbosity_change}>
+                        <option id="settings-event-verbosity-errors-option" value="ErrorsOnly" selected={settings.event_verbosity == EventVerbosity::ErrorsOnly}>{"Errors only"}</option>
+                        <option id="settings-event-verbosity-milestones-option" value="Milestones" selected={settings.event_verbosity == EventVerbosity::Milestones}>{"Milestones"}</option>
+                        <option id="settings-event-verbosity-verbose-option" value="Verbose" selected={settings.event_verbosity == EventVerbosity::Verbose}>{"Verbose (all tool output)"}</option>
+                    </select>
+                </div>
+
+                <div id="settings-collection-folder-group" class="form-group">
+                    <label id="settings-folder-layout-label" for="settings-folder-layout-input">{"Folder layout ({platform}, {handle}, {origin}, {collection}; \"/\" between folders, empty = flat)"}</label>
+                    <input type="text" id="settings-folder-layout-input" value={settings.folder_template.clone()} oninput={on_folder_template_change} />
+                    <label id="settings-collection-folder-label" for="settings-collection-folder-input">{"Collection folder name ({origin}, {handle}, {platform})"}</label>
+                    <div id="settings-collection-folder-controls" class="input-group">
+                        <input type="text" id="settings-collection-folder-input" value={settings.collection_folder_template.clone()} oninput={on_collection_folder_template_change} />
+                        <button id="settings-collection-folder-rename-button" onclick={on_rename_collection_folders}>{"Move existing folders"}</button>
+                    </div>
+                    <ul id="settings-collection-folder-preview-list" class="collection-folder-preview">
+                        { for folder_previews.iter().map(|p| html!{
+                            <li>
+                                <span class="collection-folder-preview-current">{p.current.clone()}</span>
+                                {" → "}
+                                <span class="collection-folder-preview-new">{p.preview.clone()}</span>
+                            </li>
+                        }) }
+                    </ul>
+                    {
+                        if let Some(status) = (*folder_rename_status).clone() {
+                            html!{ <div id="settings-collection-folder-status">{status}</div> }
+                        } else { html!{} }
+                    }
+                </div>
+
+                <div id="settings-digest-group" class="form-group row">
+                    <label id="settings-digest-label" for="settings-digest-select">{"Daily digest"}</label>
+                    <div id="settings-digest-controls" style="display:flex; gap: 12px; align-items:center;">
+                        <select id="settings-digest-select" onchange={on_digest_mode_change}>
+                            <option id="settings-digest-off-option" value="Off" selected={settings.digest_mode == DigestMode::Off}>{"Off"}</option>
+                            <option id="settings-digest-local-option" value="LocalHtml" selected={settings.digest_mode == DigestMode::LocalHtml}>{"Local HTML report"}</option>
+                            <option id="settings-digest-email-option" value="Email" selected={settings.digest_mode == DigestMode::Email}>{"Email"}</option>
+                        </select>
+                        {
+                            if settings.digest_mode != DigestMode::Off {
+                                html!{ <button id="settings-digest-send-button" title="Uses the saved settings" onclick={on_send_digest_now}>{"Send now"}</button> }
+                            } else { html!{} }
+                        }
+                        {
+                            if let Some(status) = (*digest_status).clone() {
+                                html!{ <span id="settings-digest-status">{status}</span> }
+                            } else { html!{} }
+                        }
+                    </div>
+                </div>
+
+                {
+                    if settings.digest_mode == DigestMode::Email {
+                        html!{
+                            <div id="settings-smtp-group" class="form-group">
+                                <div class="form-group row">
+                                    <label id="settings-smtp-host-label" for="settings-smtp-host-input">{"SMTP server"}</label>
+                                    <input type="text" id="settings-smtp-host-input" placeholder="smtp.example.com" value={settings.smtp.host.clone()} oninput={on_smtp_host_change} />
+                                </div>
+                                <div class="form-group row">
+                                    <label id="settings-smtp-port-label" for="settings-smtp-port-input">{"Port"}</label>
+                                    <input type="number" id="settings-smtp-port-input" min="1" max="65535" value={settings.smtp.port.to_string()} oninput={on_smtp_port_change} />
+                                </div>
+                                <div class="form-group row">
+                                    <label id="settings-smtp-starttls-label" for="settings-smtp-starttls-checkbox">{"Use STARTTLS (otherwise implicit TLS)"}</label>
+                                    <input type="checkbox" id="settings-smtp-starttls-checkbox" checked={settings.smtp.starttls} onchange={on_smtp_starttls_change} />
+                                </div>
+                                <div class="form-group row">
+                                    <label id="settings-smtp-username-label" for="settings-smtp-username-input">{"Username"}</label>
+                                    <input type="text" id="settings-smtp-username-input" value={settings.smtp.username.clone()} oninput={on_smtp_username_change} />
+                                </div>
+                                <div class="form-group row">
+                                    <label id="settings-smtp-password-label" for="settings-smtp-password-input">{"Password"}</label>
+                                    <input type="password" id="settings-smtp-password-input" value={settings.smtp.password.clone()} oninput={on_smtp_password_change} />
+                                </div>
+                                <div class="form-group row">
+                                    <label id="settings-smtp-from-label" for="settings-smtp-from-input">{"From (defaults to username)"}</label>
+                                    <input type="text" id="settings-smtp-from-input" value={settings.smtp.from.clone()} oninput={on_smtp_from_change} />
+                                </div>
+                                <div class="form-group row">
+                                    <label id="settings-smtp-to-label" for="settings-smtp-to-input">{"Send to"}</label>
+                                    <input type="text" id="settings-smtp-to-input" value={settings.smtp.to.clone()} oninput={on_smtp_to_change} />
+                                </div>
+                            </div>
+                        }
+                    } else { html!{} }
+                }
+
+                <details id="settings-advanced-section" class="settings-advanced">
+                    <summary id="settings-advanced-summary">{"Advanced"}</summary>
+
+                    <div id="settings-http-headers-group" class="form-group">
+                        <label id="settings-http-headers-label">{"Custom HTTP headers (e.g. Referer)"}</label>
+                        { for settings.http_headers.iter().enumerate().map(|(idx, rule)| {
+                            let on_platform = {
+                                let settings = settings.clone();
+                                Callback::from(move |e: Event| {
+                                    let value = e.target_unchecked_into::<web_sys::HtmlSelectElement>().value();
+                                    let mut s = (*settings).clone();
+                                    s.http_headers[idx].platform = value;
+                                    settings.set(s);
+                                })
+                            };
+                            let on_name = {
+                                let settings = settings.clone();
+                                Callback::from(move |e: web_sys::InputEvent| {
+                                    let value = e.target_unchecked_into::<web_sys::HtmlInputElement>().value();
+                                    let mut s = (*settings).clone();
+                                    s.http_headers[idx].name = value;
+                                    settings.set(s);
+                                })
+                            };
+                            let on_value = {
+                                let settings = settings.clone();
+                                Callback::from(move |e: web_sys::InputEvent| {
+                                    let value = e.target_unchecked_into::<web_sys::HtmlInputElement>().value();
+                                    let mut s = (*settings).clone();
+                                    s.http_headers[idx].value = value;
+                                    settings.set(s);
+                                })
+                            };
+                            let on_remove = {
+                                let settings = settings.clone();
+                                Callback::from(move |_| {
+                                    let mut s = (*settings).clone();
+                                    s.http_headers.remove(idx);
+                                    settings.set(s);
+                                })
+                            };
+                            html!{
+                                <div id={format!("settings-http-header-row-{idx}")} class="header-rule-row">
+                                    <select onchange={on_platform}>
+                                        { for RULE_PLATFORMS.iter().map(|(id, label)| html!{
+                                            <option value={*id} selected={rule.platform == *id}>{*label}</option>
+                                        }) }
+                                    </select>
+                                    <input type="text" placeholder="Header" value={rule.name.clone()} oninput={on_name} />
+                                    <input type="text" placeholder="Value" value={rule.value.clone()} oninput={on_value} />
+                                    <button class="icon-btn" title="Remove" onclick={on_remove}>
+                                        <Icon icon_id={IconId::LucideTrash2} width={"18"} height={"18"} />
+                                    </button>
+                                </div>
+                            }
+                        }) }
+                        <button id="settings-http-header-add-button" onclick={on_add_header}>{"Add header"}</button>
+                    </div>
+
+                    <div id="settings-tool-env-group" class="form-group">
+                        <label id="settings-tool-env-label" title="Set on every yt-dlp and gallery-dl run; a PATH entry is added in front of the normal PATH">{"Tool environment (e.g. HTTPS_PROXY, LANG, PATH)"}</label>
+                        { for settings.tool_env.iter().enumerate().map(|(idx, var)| {
+                            let on_name = {
+                                let settings = settings.clone();
+                                Callback::from(move |e: web_sys::InputEvent| {
+                                    let value = e.target_unchecked_into::<web_sys::HtmlInputElement>().value();
+                                    let mut s = (*settings).clone();
+                                    s.tool_env[idx].name = value;
+                                    settings.set(s);
+                                })
+                            };
+                            let on_value = {
+                                let settings = settings.clone();
+                                Callback::from(move |e: web_sys::InputEvent| {
+                                    let value = e.target_unchecked_into::<web_sys::HtmlInputElement>().value();
+                                    let mut s = (*settings).clone();
+                                    s.tool_env[idx].value = value;
+                                    settings.set(s);
+                                })
+                            };
+                            let on_remove = {
+                                let settings = settings.clone();
+                                Callback::from(move |_| {
+                                    let mut s = (*settings).clone();
+                                    s.tool_env.remove(idx);
+                                    settings.set(s);
+                                })
+                            };
+                            html!{
+                                <div id={format!("settings-tool-env-row-{idx}")} class="header-rule-row">
+                                    <input type="text" placeholder="Variable" value={var.name.clone()} oninput={on_name} />
+                                    <input type="text" placeholder="Value" value={var.value.clone()} oninput={on_value} />
+                                    <button class="icon-btn" title="Remove" onclick={on_remove}>
+                                        <Icon icon_id={IconId::LucideTrash2} width={"18"} height={"18"} />
+                                    </button>
+                                </div>
+                            }
+                        }) }
+                        <button id="settings-tool-env-add-button" onclick={on_add_env_var}>{"Add variable"}</button>
+                    </div>
+
+                    <div id="settings-filename-filesystem-group" class="form-group row">
+                        <label id="settings-filename-filesystem-label" for="settings-filename-filesystem-select">{"Filename rules"}</label>
+                        <select id="settings-filename-filesystem-select" onchange={on_filename_filesystem_change}>
+                            { for FILESYSTEM_CHOICES.iter().map(|(kind, id, label)| {
+                                let label = match (*kind, *detected_filesystem) {
+                                    (FilesystemKind::Auto, Some(found)) => {
+                                        let name = FILESYSTEM_CHOICES
+                                            .iter()
+                                            .find(|(k, _, _)| *k == found)
+                                            .map_or("unknown", |(_, _, l)| *l);
+                                        format!("{label} ({name})")
+                                    }
+                                    _ => label.to_string(),
+                                };
+                                html!{ <option value={*id} selected={settings.filename_filesystem == *kind}>{label}</option> }
+                            }) }
+                        </select>
+                    </div>
+
+                    <div id="settings-max-filename-len-group" class="form-group row">
+                        <label id="settings-max-filename-len-label" for="settings-max-filename-len-input">{"Max filename length (0 = filesystem limit)"}</label>
+                        <input type="number" id="settings-max-filename-len-input" min="0" max="255" value={settings.max_filename_len.to_string()} oninput={on_max_filename_len_change} />
+                    </div>
+
+                    <div id="settings-geo-bypass-group" class="form-group">
+                        <label id="settings-geo-bypass-label">{"Geo-bypass country (two-letter code, videos only)"}</label>
+                        { for RULE_PLATFORMS.iter().filter(|(id, _)| *id != "all").map(|(id, label)| {
+                            let on_input = {
+                                let settings = settings.clone();
+                                let platform = id.to_string();
+                                Callback::from(move |e: web_sys::InputEvent| {
+                                    let value = e.target_unchecked_into::<web_sys::HtmlInputElement>().value();
+                                    let mut s = (*settings).clone();
+                                    let code = value.trim().to_uppercase();
+                                    if code.is_empty() {
+                                        s.geo_bypass_country.remove(&platform);
+                                    } else {
+                                        s.geo_bypass_country.insert(platform.clone(), code);
+                                    }
+                                    settings.set(s);
+                                })
+                            };
+                            let current = settings.geo_bypass_country.get(*id).cloned().unwrap_or_default();
+                            html!{
+                                <div id={format!("settings-geo-bypass-{id}-row")} class="form-group row">
+                                    <label for={format!("settings-geo-bypass-{id}-input")}>{*label}</label>
+                                    <input type="text" id={format!("settings-geo-bypass-{id}-input")} maxlength="2" size="3" placeholder="US" value={current} oninput={on_input} />
+                                </div>
+                            }
+                        }) }
+                    </div>
+
+                    <div id="settings-proxy-group" class="form-group row">
+                        <label id="settings-proxy-label" for="settings-proxy-input" title="http://, https:// or socks5:// with optional user:pass@">{"Proxy"}</label>
+                        <div id="settings-proxy-controls" style="display:flex; gap: 12px; align-items:center;">
+                            <input type="text" id="settings-proxy-input" placeholder="socks5://127.0.0.1:1080" value={settings.proxy_url.clone()} oninput={on_proxy_url_change} />
+                            <button id="settings-proxy-test-button" disabled={settings.proxy_url.trim().is_empty()} onclick={on_test_proxy}>{"Test proxy"}</button>
+                            {
+                                if let Some(status) = (*proxy_status).clone() {
+                                    html!{ <span id="settings-proxy-status">{status}</span> }
+                                } else { html!{} }
+                            }
+                        </div>
+                    </div>
+
+                    <div id="settings-platform-proxy-group" class="form-group">
+                        <label id="settings-platform-proxy-label">{"Per-platform proxy (blank = proxy above)"}</label>
+                        { for RULE_PLATFORMS.iter().filter(|(id, _)| *id != "all").map(|(id, label)| {
+                            let on_input = {
+                                let settings = settings.clone();
+                                let platform = id.to_string();
+                                Callback::from(move |e: web_sys::InputEvent| {
+                                    let value = e.target_unchecked_into::<web_sys::HtmlInputElement>().value();
+                                    let mut s = (*settings).clone();
+                                    if value.trim().is_empty() {
+                                        s.per_platform_proxy.remove(&platform);
+                                    } else {
+                                        s.per_platform_proxy.insert(platform.clone(), value);
+                                    }
+                                    settings.set(s);
+                                })
+                            };
+                            let current = settings.per_platform_proxy.get(*id).cloned().unwrap_or_default();
+                            html!{
+                                <div id={format!("settings-platform-proxy-{id}-row")} class="form-group row">
+                                    <label for={format!("settings-platform-proxy-{id}-input")}>{*label}</label>
+                                    <input type="text" id={format!("settings-platform-proxy-{id}-input")} value={current} oninput={on_input} />
+                                </div>
+                            }
+                        }) }
+                    </div>
+
+                    <div id="settings-cookie-browser-group" class="form-group row">
+                        <label id="settings-cookie-browser-label" for="settings-cookie-browser-select" title="Cookies tried first; the others are only tried if it fails">{"Cookie browser"}</label>
+                        <select id="settings-cookie-browser-select" onchange={on_cookie_browser_change}>
+                            <option value="" selected={settings.preferred_cookie_browser.is_none()}>{"Try each in turn"}</option>
+                            { for cookie_browsers.iter().map(|b| html!{
+                                <option value={b.clone()} selected={settings.preferred_cookie_browser.as_deref() == Some(b.as_str())}>{ b.clone() }</option>
+                            }) }
+                        </select>
+                    </div>
+
+                    <div id="settings-platform-overrides-group" class="form-group">
+                        <label id="settings-platform-overrides-label">{"Per-platform overrides (blank = global setting)"}</label>
+                        { for RULE_PLATFORMS.iter().filter(|(id, _)| *id != "all").map(|(id, label)| {
+                            let ov = settings.platform_overrides.get(*id).cloned().unwrap_or_default();
+                            let on_output = platform_override_change(&settings, id, |ov, v| {
+                                ov.output = match v.as_str() {
+                                    "Audio" => Some(DefaultOutput::Audio),
+                                    "Video" => Some(DefaultOutput::Video),
+                                    _ => None,
+                                };
+                            });
+                            let on_height = platform_override_change(&settings, id, |ov, v| ov.max_height = v.parse().ok());
+                            let on_parallel = platform_override_change(&settings, id, |ov, v| {
+                                ov.max_parallel = v.parse().ok().filter(|n| *n > 0);
+                            });
+                            let on_subfolder = platform_override_change(&settings, id, |ov, v| {
+                                ov.subfolder = (!v.is_empty()).then_some(v);
+                            });
+                            let on_cookie_browser = platform_override_change(&settings, id, |ov, v| {
+                                ov.cookie_browser = (!v.is_empty()).then(|| v.to_lowercase());
+                            });
+                            let on_cookie_file = {
+                                let settings = settings.clone();
+                                let cookie_status = cookie_status.clone();
+                                let platform = id.to_string();
+                                Callback::from(move |_: MouseEvent| {
+                                    let settings = settings.clone();
+                                    let cookie_status = cookie_status.clone();
+                                    let platform = platform.clone();
+                                    spawn_local(async move {
+                                        let args = serde_wasm_bindgen::to_value(&serde_json::json!({ "platform": platform })).unwrap();
+                                        match try_invoke("import_cookie_file", args).await {
+                                            Ok(v) => {
+                                                if let Some(path) = v.as_string() {
+                                                    update_platform_override(&settings, &platform, |ov| ov.cookie_file = Some(path));
+                                                    cookie_status.set(Some(format!("Cookie file set for {platform}; save to use it")));
+                                                }
+                                            }
+                                            Err(e) => cookie_status.set(Some(format!(
+                                                "Cookie file not imported: {}",
+                                                e.as_string().unwrap_or_else(|| "unknown error".into())
+                                            ))),
+                                        }
+                                    });
+                                })
+                            };
+                            let on_clear_cookie_file = {
+                                let settings = settings.clone();
+                                let platform = id.to_string();
+                                Callback::from(move |_: MouseEvent| {
+                                    update_platform_override(&settings, &platform, |ov| ov.cookie_file = None);
+                                })
+                            };
+                            html!{
+                                <div id={format!("settings-platform-override-{id}-row")} class="form-group row platform-override-row">
+                                    <label>{*label}</label>
+                                    <select id={format!("settings-platform-override-{id}-output-select")} title="Output" onchange={on_output}>
+                                        <option value="" selected={ov.output.is_none()}>{"Default output"}</option>
+                                        <option value="Video" selected={ov.output == Some(DefaultOutput::Video)}>{"Video"}</option>
+                                        <option value="Audio" selected={ov.output == Some(DefaultOutput::Audio)}>{"Audio"}</option>
+                                    </select>
+                                    <select id={format!("settings-platform-override-{id}-height-select")} title="Quality cap" onchange={on_height}>
+                                        <option value="" selected={ov.max_height.is_none()}>{"Best quality"}</option>
+                                        { for MAX_HEIGHTS.iter().map(|h| html!{
+                                            <option value={h.to_string()} selected={ov.max_height == Some(*h)}>{format!("Up to {h}p")}</option>
+                                        }) }
+                                    </select>
+                                    <input type="number" id={format!("settings-platform-override-{id}-parallel-input")} title="Downloads at once" min="0" max="16" placeholder="Parallel" value={ov.max_parallel.map(|n| n.to_string()).unwrap_or_default()} onchange={on_parallel} />
+                                    <input type="text" id={format!("settings-platform-override-{id}-subfolder-input")} title="Folder under the download folder" placeholder="Subfolder" value={ov.subfolder.clone().unwrap_or_default()} onchange={on_subfolder} />
+                                    <input type="text" id={format!("settings-platform-override-{id}-cookie-input")} title="Browser whose cookies are tried first" placeholder="Cookie browser" value={ov.cookie_browser.clone().unwrap_or_default()} onchange={on_cookie_browser} />
+                                    if let Some(file) = ov.cookie_file.clone() {
+                                        <span id={format!("settings-platform-override-{id}-cookie-file")} class="cookie-file" title={file}>{"cookies.txt"}</span>
+                                        <button id={format!("settings-platform-override-{id}-cookie-file-clear-button")} title="Use browser cookies again" onclick={on_clear_cookie_file}>{"×"}</button>
+                                    } else {
+                                        <button id={format!("settings-platform-override-{id}-cookie-file-button")} title="Netscape cookies.txt used before browser cookies, for when the browser's cookie database is locked" onclick={on_cookie_file}>{"Cookies.txt…"}</button>
+                                    }
+                                </div>
+                            }
+                        }) }
+                        if let Some(status) = (*cookie_status).clone() {
+                            <span id="settings-cookie-file-status">{ status }</span>
+                        }
+                    </div>
+                </details>
+
+                <div id="settings-profiles-group" class="form-group row">
+                    <label id="settings-profiles-label">{"Profiles"}</label>
+                    <button id="settings-profiles-button" title="Separate backlogs, history and settings for each person" onclick={{
+                        let show_profiles = show_profiles.clone();
+                        Callback::from(move |_| show_profiles.set(true))
+                    }}>{"Switch profile…"}</button>
+                    {
+                        if *show_profiles {
+                            let show_profiles = show_profiles.clone();
+                            html!{ <ProfilePicker on_close={Callback::from(move |_| show_profiles.set(false))} /> }
+                        } else { html!{} }
+                    }
+                </div>
+
+                <div id="settings-config-bundle-group" class="form-group row">
+                    <label id="settings-config-bundle-label">{"Configuration"}</label>
+                    <div id="settings-config-bundle-controls" style="display:flex; gap: 12px; align-items:center;">
+                        <button id="settings-export-config-button" title="Settings only, no media; passwords are left out" onclick={on_export_config}>{"Export…"}</button>
+                        <button id="settings-import-config-button" onclick={on_import_config}>{"Import…"}</button>
+                        {
+                            if let Some(status) = (*config_status).clone() {
+                                html!{ <span id="settings-config-bundle-status">{status}</span> }
+                            } else { html!{} }
+                        }
+                    </div>
+                </div>
+
+                <div id="settings-backup-group" class="form-group row">
+                    <label id="settings-backup-label">{"Backup"}</label>
+                    <div id="settings-backup-controls" style="display:flex; gap: 12px; align-items:center;">
+                        <button id="settings-backup-button" title="Database and settings as a zip in a folder you choose" onclick={on_backup_database}>{"Back up…"}</button>
+                        <button id="settings-restore-button" title="Replaces the library database and settings, then restarts" onclick={on_restore_database}>{"Restore…"}</button>
+                        {
+                            if let Some(status) = (*backup_status).clone() {
+                                html!{ <span id="settings-backup-status">{status}</span> }
+                            } else { html!{} }
+                        }
+                    </div>
+                </div>
+
+                <div id="settings-stats-export-group" class="form-group row">
+                    <label id="settings-stats-export-label">{"Statistics"}</label>
+                    <div id="settings-stats-export-controls" style="display:flex; gap: 12px; align-items:center;">
+                        <button id="settings-stats-export-csv-button" title="Counts per platform, status and month, plus every download session" onclick={on_export_stats_csv}>{"Export CSV…"}</button>
+                        <button id="settings-stats-export-json-button" title="The same data as a single JSON file" onclick={on_export_stats_json}>{"Export JSON…"}</button>
+                        {
+                            if let Some(status) = (*stats_export_status).clone() {
+                                html!{ <span id="settings-stats-export-status">{status}</span> }
+                            } else { html!{} }
+                        }
+                    </div>
+                </div>
+
+                <div id="settings-maintenance-group" class="form-group">
+                    <label id="settings-maintenance-label">{"Maintenance"}</label>
+                    <button id="settings-refresh-metadata-button" title="Look up title, uploader and upload date for downloads that have none; media is not downloaded again" onclick={on_refresh_metadata}>{"Refresh missing metadata"}</button>
+                    <button id="settings-verify-library-button" title="Check that every downloaded file is still on disk; missing ones are listed with the failed downloads" onclick={on_verify_library}>{"Verify files"}</button>
+                    <button id="settings-repair-missing-button" title="Download every missing file again" onclick={on_repair_missing}>{"Repair missing"}</button>
+                    {
+                        if let Some(status) = (*verify_status).clone() {
+                            html!{ <span id="settings-verify-library-status">{status}</span> }
+                        } else { html!{} }
+                    }
+                    <button id="settings-dedupe-button" title="Keep one row per link, preferring a finished download; the others are deleted as set above" onclick={on_dedupe}>{"Remove duplicate rows"}</button>
+                    {
+                        if let Some(status) = (*dedupe_status).clone() {
+                            html!{ <span id="settings-dedupe-status">{status}</span> }
+                        } else { html!{} }
+                    }
+                    <MaintenancePanel on_task_finished={on_maintenance_task_finished} />
+                </div>
+
                 <div id="settings-local-libraries-group" class="form-group row">
                     <label id="settings-local-libraries-label">{"Check for local libraries"}</label>
                     <div id="settings-local-libraries-controls" style="display:flex; gap: 12px; align-items:center;">
@@ -381,9 +2453,11 @@ pub fn settings_page() -> Html {
                                     <div id="settings-tool-status-summary" style="display:flex; gap: 16px; align-items:center; font-weight: 600;">
                                         <span id="settings-yt-dlp-status" style={format!("display:inline-flex; gap:6px; align-items:center; color:{};", if stats.yt_dlp { ok_color } else { bad_color })}>
                                             { if stats.yt_dlp { "✓" } else { "✗" } }{" yt-dlp"}
+                                            { (*tool_report).as_ref().and_then(|r| r.yt_dlp.clone()).map(|v| format!(" {v}")).unwrap_or_default() }
                                         </span>
                                         <span id="settings-gallery-dl-status" style={format!("display:inline-flex; gap:6px; align-items:center; color:{};", if stats.gallery_dl { ok_color } else { bad_color })}>
                                             { if stats.gallery_dl { "✓" } else { "✗" } }{" gallery-dl"}
+                                            { (*tool_report).as_ref().and_then(|r| r.gallery_dl.clone()).map(|v| format!(" {v}")).unwrap_or_default() }
                                         </span>
                                         <span id="settings-ffmpeg-status" style={format!("display:inline-flex; gap:6px; align-items:center; color:{};", if stats.ffmpeg { ok_color } else { bad_color })}>
                                             { if stats.ffmpeg { "✓" } else { "✗" } }{" ffmpeg"}
@@ -395,6 +2469,14 @@ pub fn settings_page() -> Html {
                     </div>
                 </div>
 
+                if let Some(report) = (*tool_report).clone().filter(|r| !r.advisories.is_empty()) {
+                    <ul id="settings-tool-advisories" class="tool-advisory-list">
+                        { for report.advisories.iter().map(|a| html!{
+                            <li>{ format!("{} (installed {} {})", a.message, a.tool, a.version) }</li>
+                        }) }
+                    </ul>
+                }
+
                 {
                     if let Some(stats) = (*libs).clone() {
                         if stats.yt_dlp && stats.gallery_dl && stats.ffmpeg {
@@ -436,6 +2518,52 @@ impl Default for Settings {
             use_system_binaries: false,
             cooldown_secs: 0,
             retry_on_queue_empty: false,
+            max_retries: default_max_retries(),
+            retry_delay_secs: default_retry_delay_secs(),
+            max_download_rate_kbps: 0,
+            progress_updates_per_sec: 4,
+            event_verbosity: EventVerbosity::Milestones,
+            collection_folder_template: default_collection_folder_template(),
+            folder_template: default_folder_template(),
+            hard_delete_to_trash: true,
+            queue_new_on_startup: false,
+            check_links_on_import: false,
+            find_mirrors: false,
+            notifications_enabled: false,
+            close_to_tray: false,
+            hash_downloads: false,
+            notify_on_done: true,
+            notify_on_error: true,
+            notify_on_session_end: true,
+            download_windows: Vec::new(),
+            digest_mode: DigestMode::Off,
+            smtp: SmtpConfig::default(),
+            redact_logs: false,
+            http_headers: Vec::new(),
+            geo_bypass_country: Default::default(),
+            proxy_url: String::new(),
+            per_platform_proxy: Default::default(),
+            preferred_cookie_browser: None,
+            platform_overrides: Default::default(),
+            filename_filesystem: FilesystemKind::Auto,
+            max_filename_len: 0,
+            read_only: false,
+            trim_to_link_timestamp: false,
+            tool_env: Vec::new(),
+            clipboard_watch: ClipboardWatch::Off,
+            on_import_duplicate: ImportDuplicate::Skip,
+            http_api: HttpApiConfig::default(),
+            prefetch_metadata: false,
+            download_subtitles: false,
+            subtitle_langs: default_subtitle_langs(),
+            embed_subs: false,
+            collection_index: CollectionIndex::Off,
+            write_info_json: false,
+            max_resolution: 0,
+            preferred_codec: VideoCodec::Any,
+            audio_format: AudioFormat::Mp3,
+            audio_quality: default_audio_quality(),
+            startup_page: Page::Home,
         }
     }
 }
@@ -0,0 +1,131 @@
+use crate::dom::assign_missing_descriptive_ids;
+use crate::pages::downloads::format_bytes;
+use crate::platforms;
+use crate::types::LibraryStats;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(catch, js_namespace = ["window", "__TAURI__", "core"])]
+    async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+/// "1h 05m", "3m 20s" or "12s".
+fn format_secs(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// Horizontal bars, one per (label, count), scaled to the largest count.
+fn bar_list(id: &str, title: &str, counts: &[(String, u64)], label: fn(&str) -> String) -> Html {
+    let max = counts.iter().map(|(_, n)| *n).max().unwrap_or(0).max(1);
+    html! {
+        <section id={id.to_string()} class="stats-card">
+            <h2>{ title }</h2>
+            if counts.is_empty() {
+                <p class="attempt-empty">{"Nothing downloaded yet."}</p>
+            }
+            <ul class="stats-bars">
+                { for counts.iter().map(|(key, n)| html! {
+                    <li key={key.clone()}>
+                        <span class="stats-bar-label">{ label(key) }</span>
+                        <span class="stats-bar-track">
+                            <span class="stats-bar" style={format!("width: {:.1}%", *n as f64 * 100.0 / max as f64)}></span>
+                        </span>
+                        <span class="stats-bar-count">{ n }</span>
+                    </li>
+                }) }
+            </ul>
+        </section>
+    }
+}
+
+/// What has been downloaded so far: totals by platform, origin and media
+/// kind, space used, the last 90 days and the average time per download.
+#[function_component(StatsPage)]
+pub fn stats_page() -> Html {
+    use_effect(|| {
+        assign_missing_descriptive_ids("stats-page");
+        || ()
+    });
+    let stats = use_state(|| None::<Result<LibraryStats, String>>);
+    {
+        let stats = stats.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                stats.set(Some(match invoke("library_stats", JsValue::NULL).await {
+                    Ok(v) => serde_wasm_bindgen::from_value(v).map_err(|e| e.to_string()),
+                    Err(e) => Err(e.as_string().unwrap_or_else(|| "Stats unavailable".into())),
+                }));
+            });
+            || ()
+        });
+    }
+
+    let body = match &*stats {
+        None => html! { <p class="attempt-empty">{"Counting…"}</p> },
+        Some(Err(e)) => html! { <p class="message-error">{ e }</p> },
+        Some(Ok(s)) => {
+            let day_max = s.per_day.iter().map(|(_, n)| *n).max().unwrap_or(0).max(1);
+            let last_90: u64 = s.per_day.iter().map(|(_, n)| n).sum();
+            html! {
+                <>
+                    <div id="stats-totals" class="stats-totals">
+                        <div class="stats-total">
+                            <span class="stats-total-value">{ s.total }</span>
+                            <span class="stats-total-label">{"items downloaded"}</span>
+                        </div>
+                        <div class="stats-total">
+                            <span class="stats-total-value">{ format_bytes(s.bytes_on_disk) }</span>
+                            <span class="stats-total-label">{"on disk"}</span>
+                        </div>
+                        <div class="stats-total">
+                            <span class="stats-total-value">{ last_90 }</span>
+                            <span class="stats-total-label">{"in the last 90 days"}</span>
+                        </div>
+                        <div class="stats-total">
+                            <span class="stats-total-value">
+                                { s.avg_download_secs.map(format_secs).unwrap_or_else(|| "—".into()) }
+                            </span>
+                            <span class="stats-total-label">{"per download"}</span>
+                        </div>
+                    </div>
+                    <section id="stats-per-day" class="stats-card">
+                        <h2>{"Downloads per day"}</h2>
+                        <div class="stats-days">
+                            { for s.per_day.iter().map(|(day, n)| html! {
+                                <span
+                                    class="stats-day"
+                                    title={format!("{day}: {n}")}
+                                    style={format!("height: {:.1}%", *n as f64 * 100.0 / day_max as f64)}
+                                ></span>
+                            }) }
+                        </div>
+                        <div class="stats-days-axis">
+                            <span>{ s.per_day.first().map(|(d, _)| d.clone()).unwrap_or_default() }</span>
+                            <span>{ s.per_day.last().map(|(d, _)| d.clone()).unwrap_or_default() }</span>
+                        </div>
+                    </section>
+                    <div class="stats-grid">
+                        { bar_list("stats-by-platform", "By platform", &s.by_platform, platforms::label_for) }
+                        { bar_list("stats-by-origin", "By origin", &s.by_origin, str::to_string) }
+                        { bar_list("stats-by-media", "By media", &s.by_media, str::to_string) }
+                    </div>
+                </>
+            }
+        }
+    };
+
+    html! {
+        <main id="stats-page" class="container stats-page">
+            <h1 id="stats-page-heading">{"Stats"}</h1>
+            { body }
+        </main>
+    }
+}
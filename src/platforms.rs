@@ -0,0 +1,29 @@
+//! Platform detection, labels and icons come from `clip_core::platform`; this
+//! module looks them up by backend token and caches site favicons.
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+pub use clip_core::platform::{by_id, detect, info};
+
+/// Brand icon for a backend token; empty for unknown platforms.
+pub fn icon_for(id: &str) -> &'static str {
+    by_id(id).map_or("", |p| p.icon)
+}
+
+/// Display label for a backend token, falling back to the token itself.
+pub fn label_for(id: &str) -> String {
+    by_id(id).map_or_else(|| id.to_string(), |p| p.label.to_string())
+}
+
+thread_local! {
+    /// Favicons already asked of the backend, by domain (`None` = site has none).
+    static FAVICONS: RefCell<HashMap<String, Option<String>>> = RefCell::new(HashMap::new());
+}
+
+pub fn cached_favicon(domain: &str) -> Option<Option<String>> {
+    FAVICONS.with(|m| m.borrow().get(domain).cloned())
+}
+
+pub fn store_favicon(domain: &str, icon: Option<String>) {
+    FAVICONS.with(|m| m.borrow_mut().insert(domain.to_string(), icon));
+}
@@ -0,0 +1,117 @@
+//! Which page is showing, plus internal links to pages and items on them.
+//!
+//! Links are short paths: `library`, `downloads`, or `downloads/42` to reveal
+//! the details of download 42. The backend sends them in `navigate` events
+//! and as the `--open <path>` launch argument.
+use crate::log;
+use yew::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum Page {
+    #[default]
+    Home,
+    Downloads,
+    Library,
+    Stats,
+    Settings,
+    Extension,
+    Sponsor,
+}
+
+impl Page {
+    pub const ALL: [Page; 7] = [
+        Page::Home,
+        Page::Downloads,
+        Page::Library,
+        Page::Stats,
+        Page::Settings,
+        Page::Extension,
+        Page::Sponsor,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Page::Home => "Home",
+            Page::Downloads => "Downloads",
+            Page::Library => "Library",
+            Page::Stats => "Stats",
+            Page::Settings => "Settings",
+            Page::Extension => "Extension",
+            Page::Sponsor => "Sponsor",
+        }
+    }
+
+    /// First segment of a link path ("library").
+    fn slug(self) -> String {
+        self.label().to_lowercase()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Route {
+    pub page: Page,
+    /// Download row to reveal on the page
+    pub item: Option<i64>,
+}
+
+impl Route {
+    /// `None` for paths naming no page; an unreadable item id is dropped.
+    pub fn parse(path: &str) -> Option<Self> {
+        let path = path.trim().trim_matches('/').to_lowercase();
+        let (slug, item) = match path.split_once('/') {
+            Some((slug, item)) => (slug, item.parse().ok()),
+            None => (path.as_str(), None),
+        };
+        let page = Page::ALL.into_iter().find(|p| p.slug() == slug)?;
+        Some(Route { page, item })
+    }
+}
+
+impl From<Page> for Route {
+    fn from(page: Page) -> Self {
+        Route { page, item: None }
+    }
+}
+
+/// Handle for reading and changing the route, cheap to clone into callbacks.
+#[derive(Clone, PartialEq)]
+pub struct Router(UseStateHandle<Route>);
+
+impl Router {
+    pub fn route(&self) -> Route {
+        *self.0
+    }
+
+    pub fn page(&self) -> Page {
+        self.0.page
+    }
+
+    pub fn go(&self, route: impl Into<Route>) {
+        let route = route.into();
+        log::info(
+            "navigate",
+            serde_json::json!({ "to": route.page.label(), "item": route.item }),
+        );
+        self.0.set(route);
+    }
+
+    /// Follow a link path; unknown paths are logged and ignored.
+    pub fn open(&self, path: &str) {
+        match Route::parse(path) {
+            Some(route) => self.go(route),
+            None => log::warn("navigate_unknown", serde_json::json!({ "path": path })),
+        }
+    }
+
+    /// Click handler going to `route`.
+    pub fn link(&self, route: impl Into<Route>) -> Callback<MouseEvent> {
+        let router = self.clone();
+        let route = route.into();
+        Callback::from(move |_| router.go(route))
+    }
+}
+
+#[hook]
+pub fn use_router(initial: Page) -> Router {
+    Router(use_state(|| Route::from(initial)))
+}